@@ -0,0 +1,31 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::resp::Protocol;
+
+static NEXT_CLIENT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The state of a single client connection.
+pub struct Client {
+    /// Unique for the lifetime of the server, like Redis' `CLIENT ID`
+    pub id: u64,
+    /// Set with `HELLO ... SETNAME`
+    pub name: Option<String>,
+    /// The protocol replies are encoded in, negotiated with `HELLO`
+    pub protocol: Protocol,
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed),
+            name: None,
+            protocol: Protocol::default(),
+        }
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,93 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    config::Config,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `APPEND key value`
+pub struct AppendCommand {
+    pub key: Key,
+    pub value: Bytes,
+}
+
+impl CommandTrait for AppendCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let value = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::Append(Self { key, value }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store.upsert(
+            &self.key,
+            || Value::Str(Bytes::new()),
+            |value| {
+                let len = value.as_str()?.len() + self.value.len();
+                check_string_len(len, &store.config)?;
+                value.str_mut(|buf| buf.extend_from_slice(&self.value))?;
+                Ok::<_, String>(len)
+            },
+        )?;
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+/// Like Redis, strings can't grow past `proto-max-bulk-len`, as they couldn't be read back
+pub fn check_string_len(len: usize, config: &Config) -> Result<(), String> {
+    match len > config.proto_max_bulk_len {
+        true => Err("string exceeds maximum allowed size (proto-max-bulk-len)".to_string()),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::Store;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_append_command() {
+        let store = Store::new();
+        store.set(Key::from("int"), Value::Int(12));
+
+        let append = |key: &'static str, value: &'static str| AppendCommand {
+            key: Key::from(key),
+            value: Bytes::from(value),
+        };
+        let mut client = Client::new();
+        for (key, value, expected) in [("log", "a", 1), ("log", "bc", 3), ("int", "3", 3)] {
+            assert_eq!(
+                append(key, value).execute(store.clone(), &mut client).await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+        assert_eq!(store.get(&Key::from("log")), Some(Value::Str("abc".into())));
+        assert_eq!(store.get(&Key::from("int")), Some(Value::Str("123".into())));
+
+        store.upsert(
+            &Key::from("list"),
+            || Value::List(["a"].into_iter().collect()),
+            |_| (),
+        );
+        assert!(append("list", "a")
+            .execute(store.clone(), &mut client)
+            .await
+            .is_err());
+    }
+}
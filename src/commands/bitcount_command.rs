@@ -0,0 +1,133 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::bitmap,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `BITCOUNT key [start end [BYTE | BIT]]`
+pub struct BitCountCommand {
+    pub key: Key,
+    /// The whole string if `None`
+    pub range: Option<BitRange>,
+}
+
+/// A range of a string, in bytes unless `bit`, as taken by `BITCOUNT` and `BITPOS`
+pub struct BitRange {
+    pub start: i64,
+    /// The end of the string if `None`
+    pub end: Option<i64>,
+    pub bit: bool,
+}
+
+impl BitRange {
+    /// The bits the range covers in a string of `len` bytes, inclusive
+    pub fn resolve(&self, len: usize) -> Option<(usize, usize)> {
+        bitmap::resolve_bit_range(self.start, self.end, len, self.bit)
+    }
+}
+
+/// Parses `BYTE` or `BIT`, returning whether the range is in bits
+pub fn parse_bit_mode(part: &[u8]) -> Result<bool, String> {
+    match lowercase(part).as_str() {
+        "byte" => Ok(false),
+        "bit" => Ok(true),
+        _ => Err("syntax error".to_string()),
+    }
+}
+
+impl CommandTrait for BitCountCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        let range = match parts.next() {
+            None => None,
+            Some(start) => {
+                let end = parts.next().ok_or("syntax error")?;
+                Some(BitRange {
+                    start: parse_int(&start)?,
+                    end: Some(parse_int(&end)?),
+                    bit: parts
+                        .next()
+                        .map_or(Ok(false), |mode| parse_bit_mode(&mode))?,
+                })
+            }
+        };
+
+        if parts.next().is_some() {
+            return Err("syntax error".to_string());
+        }
+
+        Ok(CommandWrapper::BitCount(Self { key, range }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let whole = BitRange {
+            start: 0,
+            end: None,
+            bit: false,
+        };
+        let range = self.range.as_ref().unwrap_or(&whole);
+        let count = store
+            .read(&self.key, |value| {
+                let bytes = value.as_str()?;
+                Ok::<_, String>(match range.resolve(bytes.len()) {
+                    Some((start, end)) => bitmap::count(&bytes, start, end),
+                    None => 0,
+                })
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(count as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn bitcount(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match BitCountCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::BitCount(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a BitCount command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bitcount_command() {
+        let store = Store::new();
+        store.set(Key::from("key"), Value::Str(Bytes::from("foobar")));
+
+        for (input, expected) in [
+            ("key", 26),
+            ("key 0 0", 4),
+            ("key 1 1", 6),
+            ("key 1 1 byte", 6),
+            ("key 5 30 bit", 17),
+            ("key -2 -1", 7),
+            ("missing", 0),
+        ] {
+            assert_eq!(
+                bitcount(&store, input).await,
+                Ok(Frame::Integer(expected)),
+                "{input}"
+            );
+        }
+        assert!(bitcount(&store, "key 0").await.is_err());
+        assert!(bitcount(&store, "key 0 1 nibble").await.is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::bitmap::{FieldType, Overflow},
+};
+
+use super::{
+    setbit_command::{check_bit_offset, OFFSET_OUT_OF_RANGE},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `BITFIELD` and `BITFIELD_RO` both access integers of any width in a string
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BitFieldVariant {
+    /// `BITFIELD key [GET encoding offset | [OVERFLOW <WRAP | SAT | FAIL>] <SET encoding offset value | INCRBY encoding offset increment> ...]`
+    BitField,
+    /// `BITFIELD_RO key [GET encoding offset ...]`
+    BitFieldRo,
+}
+
+pub struct BitFieldCommand {
+    pub key: Key,
+    pub ops: Vec<BitFieldOp>,
+}
+
+/// The offsets are in bits, and not checked against `proto-max-bulk-len` until executed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BitFieldOp {
+    Get {
+        field: FieldType,
+        offset: i64,
+    },
+    /// Replies with the previous value
+    Set {
+        field: FieldType,
+        offset: i64,
+        value: i64,
+    },
+    /// Replies with the new value
+    IncrBy {
+        field: FieldType,
+        offset: i64,
+        increment: i64,
+    },
+    /// Applies to the `SET` and `INCRBY` that follow it
+    Overflow(Overflow),
+}
+
+impl BitFieldOp {
+    fn is_write(&self) -> bool {
+        matches!(self, BitFieldOp::Set { .. } | BitFieldOp::IncrBy { .. })
+    }
+}
+
+impl BitFieldCommand {
+    pub fn from_parts_as(
+        variant: BitFieldVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        let mut ops = Vec::new();
+        while let Some(op) = parts.next() {
+            let op = lowercase(&op);
+            if variant == BitFieldVariant::BitFieldRo && op != "get" {
+                return Err("BITFIELD_RO only supports the GET subcommand".to_string());
+            }
+            let mut next = || parts.next().ok_or("syntax error".to_string());
+            ops.push(match op.as_str() {
+                "get" => {
+                    let field = FieldType::parse(&next()?)?;
+                    let offset = parse_offset(&next()?, field)?;
+                    BitFieldOp::Get { field, offset }
+                }
+                "set" => {
+                    let field = FieldType::parse(&next()?)?;
+                    let offset = parse_offset(&next()?, field)?;
+                    let value = parse_int(&next()?)?;
+                    BitFieldOp::Set {
+                        field,
+                        offset,
+                        value,
+                    }
+                }
+                "incrby" => {
+                    let field = FieldType::parse(&next()?)?;
+                    let offset = parse_offset(&next()?, field)?;
+                    let increment = parse_int(&next()?)?;
+                    BitFieldOp::IncrBy {
+                        field,
+                        offset,
+                        increment,
+                    }
+                }
+                "overflow" => BitFieldOp::Overflow(match lowercase(&next()?).as_str() {
+                    "wrap" => Overflow::Wrap,
+                    "sat" => Overflow::Sat,
+                    "fail" => Overflow::Fail,
+                    _ => return Err("Invalid OVERFLOW type specified".to_string()),
+                }),
+                _ => return Err("syntax error".to_string()),
+            });
+        }
+
+        Ok(CommandWrapper::BitField(Self { key, ops }))
+    }
+}
+
+/// Parses an offset in bits, or in multiples of the field width if prefixed with `#`
+fn parse_offset(part: &[u8], field: FieldType) -> Result<i64, String> {
+    match part.strip_prefix(b"#") {
+        Some(index) => parse_int(index)
+            .ok()
+            .and_then(|index| index.checked_mul(field.bits as i64)),
+        None => parse_int(part).ok(),
+    }
+    .ok_or(OFFSET_OUT_OF_RANGE.to_string())
+}
+
+impl CommandTrait for BitFieldCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(BitFieldVariant::BitField, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        for op in &self.ops {
+            if let BitFieldOp::Get { offset, .. }
+            | BitFieldOp::Set { offset, .. }
+            | BitFieldOp::IncrBy { offset, .. } = op
+            {
+                check_bit_offset(*offset, &store.config)?;
+            }
+        }
+
+        // Reading alone doesn't create the key
+        if !self.ops.iter().any(BitFieldOp::is_write) {
+            let bytes = store
+                .read(&self.key, Value::as_str)
+                .unwrap_or(Ok(Bytes::new()))?;
+            let replies = self.ops.iter().filter_map(|op| match *op {
+                BitFieldOp::Get { field, offset } => {
+                    Some(Frame::Integer(field.get(&bytes, offset as usize)))
+                }
+                _ => None,
+            });
+            return Ok(Frame::Array(replies.collect()));
+        }
+
+        let replies = store.upsert(
+            &self.key,
+            || Value::Str(Bytes::new()),
+            |value| {
+                value.str_mut(|buf| {
+                    let mut overflow = Overflow::default();
+                    let mut replies = Vec::new();
+                    for op in &self.ops {
+                        let (field, offset, value) = match *op {
+                            BitFieldOp::Overflow(to) => {
+                                overflow = to;
+                                continue;
+                            }
+                            BitFieldOp::Get { field, offset } => {
+                                replies.push(Frame::Integer(field.get(buf, offset as usize)));
+                                continue;
+                            }
+                            BitFieldOp::Set {
+                                field,
+                                offset,
+                                value,
+                            } => {
+                                // Like Redis, values for unsigned fields are taken as unsigned
+                                let value = match field.signed {
+                                    true => value as i128,
+                                    false => value as u64 as i128,
+                                };
+                                (field, offset as usize, value)
+                            }
+                            BitFieldOp::IncrBy {
+                                field,
+                                offset,
+                                increment,
+                            } => {
+                                let current = field.get(buf, offset as usize);
+                                (field, offset as usize, current as i128 + increment as i128)
+                            }
+                        };
+
+                        let previous = field.get(buf, offset);
+                        let Some(value) = field.fit(value, overflow) else {
+                            replies.push(Frame::Null);
+                            continue;
+                        };
+                        field.set(buf, offset, value);
+                        replies.push(Frame::Integer(match op {
+                            BitFieldOp::Set { .. } => previous,
+                            _ => value,
+                        }));
+                    }
+                    replies
+                })
+            },
+        )?;
+        Ok(Frame::Array(replies))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store},
+    };
+
+    use super::*;
+
+    async fn bitfield(
+        store: &ConcurrentStore,
+        variant: BitFieldVariant,
+        input: &str,
+    ) -> Result<Frame, String> {
+        match BitFieldCommand::from_parts_as(variant, inline_parts(input))? {
+            CommandWrapper::BitField(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a BitField command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bitfield_command() {
+        let store = Store::new();
+        let integers = |values: &[Option<i64>]| {
+            Ok(Frame::Array(
+                values
+                    .iter()
+                    .map(|value| value.map_or(Frame::Null, Frame::Integer))
+                    .collect(),
+            ))
+        };
+
+        for (input, expected) in [
+            ("key get u8 0", &[Some(0)][..]),
+            (
+                "key set i8 #1 -2 get u8 8 get u4 #3",
+                &[Some(0), Some(254), Some(14)],
+            ),
+            ("key incrby u2 100 1 get u2 100", &[Some(1), Some(1)]),
+            ("key set u8 0 -1 incrby u8 0 1", &[Some(0), Some(0)]),
+            ("key overflow sat incrby i8 8 -200", &[Some(-128)]),
+            (
+                "key overflow fail incrby u8 0 256 get u8 0",
+                &[None, Some(0)],
+            ),
+        ] {
+            assert_eq!(
+                bitfield(&store, BitFieldVariant::BitField, input).await,
+                integers(expected),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            bitfield(&store, BitFieldVariant::BitFieldRo, "key get i8 8").await,
+            integers(&[Some(-128)])
+        );
+        assert_eq!(
+            bitfield(&store, BitFieldVariant::BitField, "missing get i64 0").await,
+            integers(&[Some(0)])
+        );
+        assert_eq!(store.get(&Key::from("missing")), None);
+
+        for (variant, input, expected) in [
+            (
+                BitFieldVariant::BitFieldRo,
+                "key set u8 0 1",
+                "BITFIELD_RO only supports the GET subcommand",
+            ),
+            (
+                BitFieldVariant::BitField,
+                "key overflow nope",
+                "Invalid OVERFLOW type specified",
+            ),
+            (
+                BitFieldVariant::BitField,
+                "key get u8 -1",
+                OFFSET_OUT_OF_RANGE,
+            ),
+            (BitFieldVariant::BitField, "key get u8", "syntax error"),
+        ] {
+            assert_eq!(
+                bitfield(&store, variant, input).await,
+                Err(expected.to_string()),
+                "{input}"
+            );
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `BITOP <AND | OR | XOR | NOT | DIFF | DIFF1 | ANDOR | ONE> destkey key [key ...]`
+pub struct BitOpCommand {
+    pub op: BitOp,
+    pub destination: Key,
+    pub keys: Vec<Key>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BitOp {
+    And,
+    Or,
+    Xor,
+    /// Takes a single key
+    Not,
+    /// The bits set in the first key but in none of the others
+    Diff,
+    /// The bits set in any of the other keys but not in the first
+    Diff1,
+    /// The bits set in the first key and in any of the others
+    AndOr,
+    /// The bits set in exactly one of the keys
+    One,
+}
+
+impl BitOp {
+    fn name(&self) -> &'static str {
+        match self {
+            BitOp::And => "AND",
+            BitOp::Or => "OR",
+            BitOp::Xor => "XOR",
+            BitOp::Not => "NOT",
+            BitOp::Diff => "DIFF",
+            BitOp::Diff1 => "DIFF1",
+            BitOp::AndOr => "ANDOR",
+            BitOp::One => "ONE",
+        }
+    }
+
+    /// Combines the strings byte by byte, the shorter ones padded with zeros
+    pub fn apply(&self, strings: &[Bytes]) -> Vec<u8> {
+        let len = strings.iter().map(Bytes::len).max().unwrap_or(0);
+        let byte = |string: &Bytes, i: usize| string.get(i).copied().unwrap_or(0);
+        (0..len)
+            .map(|i| {
+                let mut bytes = strings.iter().map(|string| byte(string, i));
+                let first = bytes.next().unwrap_or(0);
+                match self {
+                    BitOp::And => bytes.fold(first, |acc, b| acc & b),
+                    BitOp::Or => bytes.fold(first, |acc, b| acc | b),
+                    BitOp::Xor => bytes.fold(first, |acc, b| acc ^ b),
+                    BitOp::Not => !first,
+                    BitOp::Diff => first & !bytes.fold(0, |acc, b| acc | b),
+                    BitOp::Diff1 => !first & bytes.fold(0, |acc, b| acc | b),
+                    BitOp::AndOr => first & bytes.fold(0, |acc, b| acc | b),
+                    BitOp::One => {
+                        // The bits seen once so far, and those seen more than once
+                        let (once, _) = bytes.fold((first, 0), |(once, more), b| {
+                            let more = more | (once & b);
+                            ((once ^ b) & !more, more)
+                        });
+                        once
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+impl CommandTrait for BitOpCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let op = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let op = match lowercase(&op).as_str() {
+            "and" => BitOp::And,
+            "or" => BitOp::Or,
+            "xor" => BitOp::Xor,
+            "not" => BitOp::Not,
+            "diff" => BitOp::Diff,
+            "diff1" => BitOp::Diff1,
+            "andor" => BitOp::AndOr,
+            "one" => BitOp::One,
+            _ => return Err("syntax error".to_string()),
+        };
+        let destination = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let keys: Vec<Key> = parts.collect();
+
+        match op {
+            _ if keys.is_empty() => return Err(ParseError::MissingKeys.to_string()),
+            BitOp::Not if keys.len() > 1 => {
+                return Err("BITOP NOT must be called with a single source key.".to_string())
+            }
+            BitOp::Diff | BitOp::Diff1 | BitOp::AndOr if keys.len() < 2 => {
+                return Err(format!(
+                    "BITOP {} must be called with at least two source keys.",
+                    op.name()
+                ))
+            }
+            _ => {}
+        }
+
+        Ok(CommandWrapper::BitOp(Self {
+            op,
+            destination,
+            keys,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store.atomically(|| {
+            // Missing keys are taken as empty strings
+            let strings = self
+                .keys
+                .iter()
+                .map(|key| store.read(key, Value::as_str).unwrap_or(Ok(Bytes::new())))
+                .collect::<Result<Vec<_>, _>>()?;
+            let result = self.op.apply(&strings);
+            let len = result.len();
+            // Like Redis, an empty result deletes the destination
+            match result.is_empty() {
+                true => _ = store.del(&self.destination),
+                false => store.set(self.destination.clone(), Value::Str(Bytes::from(result))),
+            }
+            Ok::<_, String>(len)
+        })?;
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        resp::Protocol,
+        store::{ConcurrentStore, Store},
+    };
+
+    use super::*;
+
+    async fn bitop(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match BitOpCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::BitOp(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a BitOp command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bitop_command() {
+        let store = Store::new();
+        store.set(
+            Key::from("a"),
+            Value::Str(Bytes::from_static(&[0b1100, 0xff])),
+        );
+        store.set(Key::from("b"), Value::Str(Bytes::from_static(&[0b1010])));
+        store.set(Key::from("c"), Value::Str(Bytes::from_static(&[0b0110])));
+
+        for (input, expected) in [
+            ("and dst a b", &[0b1000, 0x00][..]),
+            ("or dst a b", &[0b1110, 0xff]),
+            ("xor dst a b", &[0b0110, 0xff]),
+            ("not dst b", &[!0b1010]),
+            ("diff dst a b", &[0b0100, 0xff]),
+            ("diff1 dst a b c", &[0b0010, 0x00]),
+            ("andor dst a b c", &[0b1100, 0x00]),
+            ("one dst a b c", &[0b0000, 0xff]),
+        ] {
+            assert_eq!(
+                bitop(&store, input).await,
+                Ok(Frame::Integer(expected.len() as i64)),
+                "{input}"
+            );
+            assert_eq!(
+                store.get(&Key::from("dst")),
+                Some(Value::Str(Bytes::copy_from_slice(expected))),
+                "{input}"
+            );
+        }
+
+        assert_eq!(bitop(&store, "or dst missing").await, Ok(Frame::Integer(0)));
+        assert_eq!(store.get(&Key::from("dst")), None);
+
+        // The errors start with the command, which isn't an error code
+        let error = |reply: Result<Frame, String>| {
+            Frame::error(reply.unwrap_err()).to_bytes(Protocol::Resp2)
+        };
+        assert_eq!(
+            error(bitop(&store, "not dst a b").await),
+            b"-ERR BITOP NOT must be called with a single source key.\r\n"
+        );
+        assert_eq!(
+            error(bitop(&store, "andor dst a").await),
+            b"-ERR BITOP ANDOR must be called with at least two source keys.\r\n"
+        );
+    }
+}
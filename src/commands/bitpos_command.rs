@@ -0,0 +1,134 @@
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::bitmap,
+};
+
+use super::{
+    bitcount_command::{parse_bit_mode, BitRange},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `BITPOS key bit [start [end [BYTE | BIT]]]`
+pub struct BitPosCommand {
+    pub key: Key,
+    pub bit: bool,
+    pub range: BitRange,
+}
+
+impl CommandTrait for BitPosCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let bit = match &parts.next().ok_or(ParseError::MissingValue.to_string())?[..] {
+            b"0" => false,
+            b"1" => true,
+            _ => return Err("The bit argument must be 1 or 0.".to_string()),
+        };
+
+        let mut range = BitRange {
+            start: 0,
+            end: None,
+            bit: false,
+        };
+        if let Some(start) = parts.next() {
+            range.start = parse_int(&start)?;
+        }
+        if let Some(end) = parts.next() {
+            range.end = Some(parse_int(&end)?);
+        }
+        if let Some(mode) = parts.next() {
+            range.bit = parse_bit_mode(&mode)?;
+        }
+
+        if parts.next().is_some() {
+            return Err("syntax error".to_string());
+        }
+
+        Ok(CommandWrapper::BitPos(Self { key, bit, range }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let position = store
+            .read(&self.key, |value| {
+                let bytes = value.as_str()?;
+                let Some((start, end)) = self.range.resolve(bytes.len()) else {
+                    return Ok(-1);
+                };
+                Ok::<_, String>(match bitmap::position(&bytes, self.bit, start, end) {
+                    Some(position) => position as i64,
+                    // Like Redis, the string is taken as padded with zeros past its end,
+                    // unless the range has an explicit end
+                    None if !self.bit && self.range.end.is_none() => end as i64 + 1,
+                    None => -1,
+                })
+            })
+            .transpose()?;
+        // A missing key is an empty string, with nothing but zeros
+        Ok(Frame::Integer(position.unwrap_or(if self.bit {
+            -1
+        } else {
+            0
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn bitpos(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match BitPosCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::BitPos(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a BitPos command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bitpos_command() {
+        let store = Store::new();
+        store.set(
+            Key::from("key"),
+            Value::Str(Bytes::from_static(&[0xff, 0xf0, 0x00])),
+        );
+        store.set(
+            Key::from("ones"),
+            Value::Str(Bytes::from_static(&[0xff, 0xff])),
+        );
+
+        for (input, expected) in [
+            ("key 0", 12),
+            ("key 1 2", -1),
+            ("key 1 1", 8),
+            ("key 0 2 -1", 16),
+            ("key 1 7 15 bit", 7),
+            ("key 0 7 15 bit", 12),
+            ("ones 0", 16),
+            ("ones 0 0 -1", -1),
+            ("missing 0", 0),
+            ("missing 1", -1),
+        ] {
+            assert_eq!(
+                bitpos(&store, input).await,
+                Ok(Frame::Integer(expected)),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            bitpos(&store, "key 2").await,
+            Err("The bit argument must be 1 or 0.".to_string())
+        );
+    }
+}
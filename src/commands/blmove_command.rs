@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_list_end, parse_timeout, ParseError},
+    resp::Frame,
+    store::Key,
+    types::list::ListEnd,
+};
+
+use super::{lmove_command::lmove, CommandParts, CommandTrait, CommandWrapper};
+
+/// `BRPOPLPUSH` is `BLMOVE` with the ends fixed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BLMoveVariant {
+    /// `BLMOVE source destination <LEFT | RIGHT> <LEFT | RIGHT> timeout`
+    BLMove,
+    /// `BRPOPLPUSH source destination timeout`
+    BRPopLPush,
+}
+
+pub struct BLMoveCommand {
+    pub source: Key,
+    pub destination: Key,
+    /// The end of the source to pop from
+    pub from: ListEnd,
+    /// The end of the destination to push to
+    pub to: ListEnd,
+    /// Blocks forever if `None`
+    pub timeout: Option<Duration>,
+}
+
+impl BLMoveCommand {
+    pub fn from_parts_as(
+        variant: BLMoveVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let source = parts.next().ok_or(ParseError::MissingKeys.to_string())?;
+        let destination = parts.next().ok_or(ParseError::MissingKeys.to_string())?;
+        let mut next = || parts.next().ok_or(ParseError::MissingValue.to_string());
+        let (from, to) = match variant {
+            BLMoveVariant::BLMove => (parse_list_end(&next()?)?, parse_list_end(&next()?)?),
+            BLMoveVariant::BRPopLPush => (ListEnd::Right, ListEnd::Left),
+        };
+        let timeout = parse_timeout(&next()?)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::BLMove(Self {
+            source,
+            destination,
+            from,
+            to,
+            timeout,
+        }))
+    }
+}
+
+impl CommandTrait for BLMoveCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(BLMoveVariant::BLMove, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let Self {
+            source,
+            destination,
+            from,
+            to,
+            timeout,
+        } = self;
+        let serve = Box::new(move |store: &_, source: &Key| {
+            let element = lmove(store, source, &destination, from, to).transpose()?;
+            Some(element.map(Frame::bulk))
+        });
+
+        store
+            .block_on(
+                vec![source],
+                timeout,
+                |value| value.as_list().is_ok(),
+                serve,
+            )
+            .await
+            .unwrap_or(Ok(Frame::Null))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+        types::list::List,
+    };
+
+    use super::*;
+
+    async fn run(
+        store: &ConcurrentStore,
+        variant: BLMoveVariant,
+        input: &str,
+    ) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match BLMoveCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::BLMove(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a BLMove command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blmove_waits_for_the_source() {
+        let store = Store::new();
+        let client = {
+            let store = store.clone();
+            tokio::spawn(async move {
+                run(
+                    &store,
+                    BLMoveVariant::BLMove,
+                    "blmove source destination right left 0",
+                )
+                .await
+            })
+        };
+        while store.blocked_clients.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        store.upsert(
+            &Key::from("source"),
+            || Value::List(List::new()),
+            |value| value.as_list_mut().unwrap().push_back(b"a"),
+        );
+        store.serve_blocked_clients();
+        assert_eq!(client.await.unwrap(), Ok(Frame::bulk("a")));
+        assert_eq!(store.get(&Key::from("source")), None);
+        assert_eq!(
+            store.get(&Key::from("destination")),
+            Some(Value::List(["a"].into_iter().collect()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_brpoplpush_times_out() {
+        let store = Store::new();
+        assert_eq!(
+            run(
+                &store,
+                BLMoveVariant::BRPopLPush,
+                "brpoplpush source destination 0.01"
+            )
+            .await,
+            Ok(Frame::Null)
+        );
+        assert!(store.blocked_clients.is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_timeout, ParseError},
+    resp::Frame,
+    store::Key,
+    types::list::ListEnd,
+};
+
+use super::{
+    lmpop_command::{lmpop, lmpop_frame, parse_lmpop_parts},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `BLMPOP timeout numkeys key [key ...] <LEFT | RIGHT> [COUNT count]`
+pub struct BLMPopCommand {
+    pub keys: Vec<Key>,
+    pub end: ListEnd,
+    pub count: usize,
+    /// Blocks forever if `None`
+    pub timeout: Option<Duration>,
+}
+
+impl CommandTrait for BLMPopCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let timeout = parse_timeout(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let (keys, end, count) = parse_lmpop_parts(parts)?;
+
+        Ok(CommandWrapper::BLMPop(Self {
+            keys,
+            end,
+            count,
+            timeout,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let (end, count) = (self.end, self.count);
+        let serve = Box::new(move |store: &_, key: &Key| {
+            let popped = lmpop(store, std::slice::from_ref(key), end, count).transpose()?;
+            Some(popped.map(|popped| lmpop_frame(Some(popped))))
+        });
+
+        store
+            .block_on(
+                self.keys,
+                self.timeout,
+                |value| value.as_list().is_ok(),
+                serve,
+            )
+            .await
+            .unwrap_or(Ok(Frame::NullArray))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn blmpop(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match BLMPopCommand::from_parts(parts)? {
+            CommandWrapper::BLMPop(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a BLMPop command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blmpop_command() {
+        let store = Store::new();
+        let list = Value::List(["a", "b", "c"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        assert_eq!(
+            blmpop(&store, "blmpop 0 2 missing list left count 2").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("list"),
+                Frame::Array(vec![Frame::bulk("a"), Frame::bulk("b")])
+            ]))
+        );
+        assert_eq!(
+            blmpop(&store, "blmpop 0.01 1 missing right").await,
+            Ok(Frame::NullArray)
+        );
+        assert_eq!(
+            blmpop(&store, "blmpop 0 0 list right").await,
+            Err("numkeys should be greater than 0".to_string())
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_timeout, ParseError},
+    resp::Frame,
+    store::Key,
+    types::list::ListEnd,
+};
+
+use super::{lmpop_command::lmpop, CommandParts, CommandTrait, CommandWrapper};
+
+/// `BLPOP` and `BRPOP` only differ in the end of the lists popped from
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BLPopVariant {
+    /// `BLPOP key [key ...] timeout`
+    BLPop,
+    /// `BRPOP key [key ...] timeout`
+    BRPop,
+}
+
+pub struct BLPopCommand {
+    pub keys: Vec<Key>,
+    /// Blocks forever if `None`
+    pub timeout: Option<Duration>,
+    pub variant: BLPopVariant,
+}
+
+impl BLPopCommand {
+    pub fn from_parts_as(
+        variant: BLPopVariant,
+        parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let mut keys: Vec<Key> = parts.collect();
+        let timeout = keys.pop().ok_or(ParseError::MissingKeys.to_string())?;
+        if keys.is_empty() {
+            return Err(ParseError::MissingKeys.to_string());
+        }
+        let timeout = parse_timeout(&timeout)?;
+
+        Ok(CommandWrapper::BLPop(Self {
+            keys,
+            timeout,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for BLPopCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(BLPopVariant::BLPop, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let end = match self.variant {
+            BLPopVariant::BLPop => ListEnd::Left,
+            BLPopVariant::BRPop => ListEnd::Right,
+        };
+        let serve = Box::new(move |store: &_, key: &Key| {
+            let popped = lmpop(store, std::slice::from_ref(key), end, 1).transpose()?;
+            Some(popped.map(|(key, elements)| {
+                let mut reply = vec![Frame::bulk(key)];
+                reply.extend(elements.into_iter().map(Frame::bulk));
+                Frame::Array(reply)
+            }))
+        });
+
+        store
+            .block_on(
+                self.keys,
+                self.timeout,
+                |value| value.as_list().is_ok(),
+                serve,
+            )
+            .await
+            .unwrap_or(Ok(Frame::NullArray))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn run(
+        store: &ConcurrentStore,
+        variant: BLPopVariant,
+        input: &str,
+    ) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match BLPopCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::BLPop(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a BLPop command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blpop_pops_right_away() {
+        let store = Store::new();
+        let list = Value::List(["a", "b"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        assert_eq!(
+            run(&store, BLPopVariant::BRPop, "brpop missing list 0").await,
+            Ok(Frame::Array(vec![Frame::bulk("list"), Frame::bulk("b")]))
+        );
+        assert_eq!(
+            run(&store, BLPopVariant::BLPop, "blpop list 0.01").await,
+            Ok(Frame::Array(vec![Frame::bulk("list"), Frame::bulk("a")]))
+        );
+        assert_eq!(
+            run(&store, BLPopVariant::BLPop, "blpop list 0.01").await,
+            Ok(Frame::NullArray)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blpop_invalid_timeout() {
+        let store = Store::new();
+        assert_eq!(
+            run(&store, BLPopVariant::BLPop, "blpop list -1").await,
+            Err("timeout is negative".to_string())
+        );
+        assert_eq!(
+            run(&store, BLPopVariant::BLPop, "blpop list abc").await,
+            Err("timeout is not a float or out of range".to_string())
+        );
+        assert_eq!(
+            run(&store, BLPopVariant::BLPop, "blpop 0").await,
+            Err(ParseError::MissingKeys.to_string())
+        );
+    }
+}
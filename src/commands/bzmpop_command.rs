@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_timeout, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{
+    zmpop_command::{parse_zmpop_parts, zmpop, zmpop_frame},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `BZMPOP timeout numkeys key [key ...] <MIN | MAX> [COUNT count]`
+pub struct BZMPopCommand {
+    pub keys: Vec<Key>,
+    /// Whether the members with the highest scores are popped
+    pub max: bool,
+    pub count: usize,
+    /// Blocks forever if `None`
+    pub timeout: Option<Duration>,
+}
+
+impl CommandTrait for BZMPopCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let timeout = parse_timeout(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let (keys, max, count) = parse_zmpop_parts(parts)?;
+
+        Ok(CommandWrapper::BZMPop(Self {
+            keys,
+            max,
+            count,
+            timeout,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let (max, count) = (self.max, self.count);
+        let serve = Box::new(move |store: &_, key: &Key| {
+            let popped = zmpop(store, std::slice::from_ref(key), max, count).transpose()?;
+            Some(popped.map(|popped| zmpop_frame(Some(popped))))
+        });
+
+        store
+            .block_on(
+                self.keys,
+                self.timeout,
+                |value| value.as_zset().is_ok(),
+                serve,
+            )
+            .await
+            .unwrap_or(Ok(Frame::NullArray))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn bzmpop(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match BZMPopCommand::from_parts(parts)? {
+            CommandWrapper::BZMPop(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a BZMPop command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bzmpop_command() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0), ("c", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("z"), || zset, |_| ());
+
+        assert_eq!(
+            bzmpop(&store, "bzmpop 0 2 missing z min count 2").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("z"),
+                Frame::Array(vec![
+                    Frame::Array(vec![Frame::bulk("a"), Frame::Double(1.0)]),
+                    Frame::Array(vec![Frame::bulk("b"), Frame::Double(2.0)])
+                ])
+            ]))
+        );
+        assert_eq!(
+            bzmpop(&store, "bzmpop 0.01 1 missing max").await,
+            Ok(Frame::NullArray)
+        );
+        assert_eq!(
+            bzmpop(&store, "bzmpop 0 0 z max").await,
+            Err("numkeys should be greater than 0".to_string())
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use std::time::Duration;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_timeout, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{zmpop_command::zmpop, CommandParts, CommandTrait, CommandWrapper};
+
+/// `BZPOPMIN` and `BZPOPMAX` only differ in which end of the sorted sets they pop from
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BZPopMinVariant {
+    /// `BZPOPMIN key [key ...] timeout`
+    BZPopMin,
+    /// `BZPOPMAX key [key ...] timeout`
+    BZPopMax,
+}
+
+pub struct BZPopMinCommand {
+    pub keys: Vec<Key>,
+    /// Blocks forever if `None`
+    pub timeout: Option<Duration>,
+    pub variant: BZPopMinVariant,
+}
+
+impl BZPopMinCommand {
+    pub fn from_parts_as(
+        variant: BZPopMinVariant,
+        parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let mut keys: Vec<Key> = parts.collect();
+        let timeout = keys.pop().ok_or(ParseError::MissingKeys.to_string())?;
+        if keys.is_empty() {
+            return Err(ParseError::MissingKeys.to_string());
+        }
+        let timeout = parse_timeout(&timeout)?;
+
+        Ok(CommandWrapper::BZPopMin(Self {
+            keys,
+            timeout,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for BZPopMinCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(BZPopMinVariant::BZPopMin, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let max = self.variant == BZPopMinVariant::BZPopMax;
+        let serve = Box::new(move |store: &_, key: &Key| {
+            let popped = zmpop(store, std::slice::from_ref(key), max, 1).transpose()?;
+            Some(popped.map(|(key, members)| {
+                let mut reply = vec![Frame::bulk(key)];
+                for (member, score) in members {
+                    reply.extend([Frame::bulk(member), Frame::Double(score)]);
+                }
+                Frame::Array(reply)
+            }))
+        });
+
+        store
+            .block_on(
+                self.keys,
+                self.timeout,
+                |value| value.as_zset().is_ok(),
+                serve,
+            )
+            .await
+            .unwrap_or(Ok(Frame::NullArray))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        commands::blpop_command::{BLPopCommand, BLPopVariant},
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+        types::zset::ZSet,
+    };
+
+    use super::*;
+
+    async fn run(
+        store: &ConcurrentStore,
+        variant: BZPopMinVariant,
+        input: &str,
+    ) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match BZPopMinCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::BZPopMin(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a BZPopMin command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bzpopmin_pops_right_away() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0)].into_iter().collect());
+        store.upsert(&Key::from("z"), || zset, |_| ());
+
+        assert_eq!(
+            run(&store, BZPopMinVariant::BZPopMax, "bzpopmax missing z 0").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("z"),
+                Frame::bulk("b"),
+                Frame::Double(2.0)
+            ]))
+        );
+        assert_eq!(
+            run(&store, BZPopMinVariant::BZPopMin, "bzpopmin z 0.01").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("z"),
+                Frame::bulk("a"),
+                Frame::Double(1.0)
+            ]))
+        );
+        assert_eq!(
+            run(&store, BZPopMinVariant::BZPopMin, "bzpopmin z 0.01").await,
+            Ok(Frame::NullArray)
+        );
+        assert!(store.blocked_clients.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bzpopmin_waits_for_any_of_the_keys() {
+        let store = Store::new();
+        let client = {
+            let store = store.clone();
+            tokio::spawn(async move {
+                run(&store, BZPopMinVariant::BZPopMin, "bzpopmin first second 0").await
+            })
+        };
+        while store.blocked_clients.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        store.upsert(
+            &Key::from("second"),
+            || Value::ZSet(ZSet::new()),
+            |value| value.as_zset_mut().unwrap().insert("a".into(), 1.0),
+        );
+        store.serve_blocked_clients();
+        assert_eq!(
+            client.await.unwrap(),
+            Ok(Frame::Array(vec![
+                Frame::bulk("second"),
+                Frame::bulk("a"),
+                Frame::Double(1.0)
+            ]))
+        );
+        assert_eq!(store.get(&Key::from("second")), None);
+        assert!(store.blocked_clients.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bzpopmin_is_served_behind_a_list_client() {
+        let store = Store::new();
+        let list_client = {
+            let store = store.clone();
+            tokio::spawn(async move {
+                let Ok(CommandWrapper::BLPop(cmd)) =
+                    BLPopCommand::from_parts_as(BLPopVariant::BLPop, inline_parts("k 0.2"))
+                else {
+                    panic!("Expected a BLPop command");
+                };
+                cmd.execute(store, &mut Client::new()).await
+            })
+        };
+        while store.blocked_clients.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let zset_client = {
+            let store = store.clone();
+            tokio::spawn(
+                async move { run(&store, BZPopMinVariant::BZPopMin, "bzpopmin k 0").await },
+            )
+        };
+        while store.blocked_clients.len() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        // The list client is first in line, but can't be served from a sorted set
+        store.upsert(
+            &Key::from("k"),
+            || Value::ZSet(ZSet::new()),
+            |value| value.as_zset_mut().unwrap().insert("a".into(), 1.0),
+        );
+        store.serve_blocked_clients();
+        let reply = tokio::time::timeout(Duration::from_secs(1), zset_client).await;
+        assert_eq!(
+            reply.expect("the BZPOPMIN client wasn't served").unwrap(),
+            Ok(Frame::Array(vec![
+                Frame::bulk("k"),
+                Frame::bulk("a"),
+                Frame::Double(1.0)
+            ]))
+        );
+        assert_eq!(list_client.await.unwrap(), Ok(Frame::NullArray));
+        assert!(store.blocked_clients.is_empty());
+    }
+}
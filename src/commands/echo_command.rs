@@ -0,0 +1,60 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+pub struct EchoCommand {
+    pub message: Bytes,
+}
+
+impl CommandTrait for EchoCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let message = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::Echo(Self { message }))
+    }
+
+    async fn execute(
+        self,
+        _store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        Ok(Frame::bulk(self.message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::utils::inline_parts;
+
+    use super::*;
+
+    #[test]
+    fn test_echo_command_from_input() {
+        let input = "echo hello".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match EchoCommand::from_parts(parts).unwrap() {
+            CommandWrapper::Echo(cmd) => {
+                assert_eq!(cmd.message, "hello");
+            }
+            _ => panic!("Expected an Echo command"),
+        };
+    }
+
+    #[test]
+    fn test_echo_command_from_input_missing_message() {
+        let input = "echo".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match EchoCommand::from_parts(parts) {
+            Err(e) => assert_eq!(e, ParseError::MissingValue.to_string()),
+            _ => panic!("Expected an error"),
+        };
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::{ExpireCondition, Key},
+    utils::current_epoch_millis,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands of the `EXPIRE` family only differ in how the time is given
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ExpireVariant {
+    /// `EXPIRE key seconds`
+    Expire,
+    /// `PEXPIRE key milliseconds`
+    PExpire,
+    /// `EXPIREAT key unix-time-seconds`
+    ExpireAt,
+    /// `PEXPIREAT key unix-time-milliseconds`
+    PExpireAt,
+}
+
+impl ExpireVariant {
+    fn name(&self) -> &'static str {
+        match self {
+            ExpireVariant::Expire => "expire",
+            ExpireVariant::PExpire => "pexpire",
+            ExpireVariant::ExpireAt => "expireat",
+            ExpireVariant::PExpireAt => "pexpireat",
+        }
+    }
+}
+
+pub struct ExpireCommand {
+    pub key: Key,
+    pub variant: ExpireVariant,
+    /// Seconds or milliseconds, relative to now or to UNIX epoch, depending on the variant
+    pub time: i64,
+    pub condition: ExpireCondition,
+}
+
+impl ExpireCommand {
+    pub fn from_parts_as(
+        variant: ExpireVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let time = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+        let mut condition = ExpireCondition::default();
+        for part in parts {
+            match lowercase(&part).as_str() {
+                "nx" => condition.nx = true,
+                "xx" => condition.xx = true,
+                "gt" => condition.gt = true,
+                "lt" => condition.lt = true,
+                _ => {
+                    return Err(ParseError::InvalidCommandOptions(&format!(
+                        "Unsupported option {}",
+                        String::from_utf8_lossy(&part)
+                    ))
+                    .to_string())
+                }
+            }
+        }
+
+        if condition.nx && (condition.xx || condition.gt || condition.lt) {
+            return Err(ParseError::InvalidCommandOptions(
+                "NX and XX, GT or LT options at the same time are not compatible",
+            )
+            .to_string());
+        }
+        if condition.gt && condition.lt {
+            return Err(ParseError::InvalidCommandOptions(
+                "GT and LT options at the same time are not compatible",
+            )
+            .to_string());
+        }
+
+        Ok(CommandWrapper::Expire(Self {
+            key,
+            variant,
+            time,
+            condition,
+        }))
+    }
+
+    /// Resolves the time to milliseconds since UNIX epoch, `None` if it overflows
+    fn expires_at(&self, now: u128) -> Option<i64> {
+        let millis = match self.variant {
+            ExpireVariant::Expire | ExpireVariant::ExpireAt => self.time.checked_mul(1000)?,
+            ExpireVariant::PExpire | ExpireVariant::PExpireAt => self.time,
+        };
+        match self.variant {
+            ExpireVariant::Expire | ExpireVariant::PExpire => millis.checked_add(now as i64),
+            ExpireVariant::ExpireAt | ExpireVariant::PExpireAt => Some(millis),
+        }
+    }
+}
+
+impl CommandTrait for ExpireCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(ExpireVariant::Expire, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let expires_at = self.expires_at(current_epoch_millis()).ok_or(format!(
+            "invalid expire time in '{}' command",
+            self.variant.name()
+        ))?;
+
+        // Times before UNIX epoch are in the past all the same, and delete the key
+        let expires_at = expires_at.max(0) as u128;
+        Ok(Frame::Integer(
+            store.expire(&self.key, expires_at, self.condition) as i64,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::utils::inline_parts;
+
+    use super::*;
+
+    #[test]
+    fn test_expire_command_from_input() {
+        let input = "pexpire key 1500 xx gt".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match ExpireCommand::from_parts_as(ExpireVariant::PExpire, parts).unwrap() {
+            CommandWrapper::Expire(cmd) => {
+                assert_eq!(cmd.key, "key");
+                assert_eq!(cmd.variant, ExpireVariant::PExpire);
+                assert_eq!(cmd.time, 1500);
+                assert_eq!(
+                    cmd.condition,
+                    ExpireCondition {
+                        xx: true,
+                        gt: true,
+                        ..Default::default()
+                    }
+                );
+                assert_eq!(cmd.expires_at(1000), Some(2500));
+            }
+            _ => panic!("Expected an Expire command"),
+        };
+    }
+
+    #[test]
+    fn test_expire_command_from_input_incompatible_options() {
+        for input in ["expire key 10 nx xx", "expire key 10 gt lt"] {
+            let mut parts = inline_parts(input);
+            parts.next(); // Skip the command
+            assert!(ExpireCommand::from_parts(parts).is_err());
+        }
+    }
+
+    #[test]
+    fn test_expire_command_from_input_not_an_integer() {
+        let input = "expire key soon".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match ExpireCommand::from_parts(parts) {
+            Err(e) => assert_eq!(e, ParseError::NotAnInteger.to_string()),
+            _ => panic!("Expected an error"),
+        };
+    }
+
+    #[test]
+    fn test_expire_command_overflowing_time() {
+        let cmd = ExpireCommand {
+            key: Key::from("key"),
+            variant: ExpireVariant::Expire,
+            time: i64::MAX / 100,
+            condition: ExpireCondition::default(),
+        };
+        assert_eq!(cmd.expires_at(0), None);
+    }
+}
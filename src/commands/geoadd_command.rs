@@ -0,0 +1,157 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_float, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::{
+        geohash::{self, Point},
+        zset::ZSet,
+    },
+};
+
+use super::{
+    zadd_command::{add_member, Added, ZAddOptions},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `GEOADD key [NX | XX] [CH] longitude latitude member [longitude latitude member ...]`
+pub struct GeoAddCommand {
+    pub key: Key,
+    /// Only `NX`, `XX` and `CH` apply
+    pub options: ZAddOptions,
+    pub points: Vec<(Point, Bytes)>,
+}
+
+/// Parses a longitude and a latitude, which must be within what can be indexed.
+pub fn parse_point(longitude: &[u8], latitude: &[u8]) -> Result<Point, String> {
+    let point = (parse_float(longitude)?, parse_float(latitude)?);
+    geohash::check_point(point)?;
+    Ok(point)
+}
+
+impl CommandTrait for GeoAddCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        let mut parts = parts.peekable();
+        let mut options = ZAddOptions::default();
+        while let Some(part) = parts.peek() {
+            match lowercase(part).as_str() {
+                "nx" => options.nx = true,
+                "xx" => options.xx = true,
+                "ch" => options.ch = true,
+                _ => break,
+            }
+            parts.next();
+        }
+
+        if options.nx && options.xx {
+            return Err("XX and NX options at the same time are not compatible".to_string());
+        }
+        let rest: Vec<Bytes> = parts.collect();
+        if rest.is_empty() || !rest.len().is_multiple_of(3) {
+            return Err(
+                "syntax error. Try GEOADD key [x1] [y1] [name1] [x2] [y2] [name2] ... ".to_string(),
+            );
+        }
+
+        let points = rest
+            .chunks(3)
+            .map(|point| Ok((parse_point(&point[0], &point[1])?, point[2].clone())))
+            .collect::<Result<_, String>>()?;
+
+        Ok(CommandWrapper::GeoAdd(Self {
+            key,
+            options,
+            points,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let added = store.upsert(
+            &self.key,
+            || Value::ZSet(ZSet::new()),
+            |value| {
+                let zset = value.as_zset_mut()?;
+                self.points
+                    .iter()
+                    .map(|(point, member)| {
+                        let score = geohash::encode(*point) as f64;
+                        add_member(zset, member, score, self.options)
+                    })
+                    .collect::<Result<Vec<_>, String>>()
+            },
+        )?;
+
+        let counted = added
+            .iter()
+            .filter(|added| match added {
+                Added::New(_) => true,
+                Added::Updated(_) => self.options.ch,
+                Added::Unchanged(_) | Added::Skipped => false,
+            })
+            .count();
+        Ok(Frame::Integer(counted as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        resp::Protocol,
+        store::{ConcurrentStore, Store},
+    };
+
+    use super::*;
+
+    async fn geoadd(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match GeoAddCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::GeoAdd(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a GeoAdd command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_geoadd_command() {
+        let store = Store::new();
+        for (input, expected) in [
+            ("sicily 13.361389 38.115556 Palermo", 1),
+            (
+                "sicily 15.087269 37.502669 Catania 13.361389 38.115556 Palermo",
+                1,
+            ),
+            ("sicily xx ch 15 37 Catania 0 0 Nowhere", 1),
+            ("sicily nx 0 0 Catania", 0),
+        ] {
+            assert_eq!(
+                geoadd(&store, input).await,
+                Ok(Frame::Integer(expected)),
+                "{input}"
+            );
+        }
+        let score = store
+            .read(&Key::from("sicily"), |value| {
+                value.as_zset().unwrap().score(b"Palermo")
+            })
+            .flatten();
+        assert_eq!(score, Some(3479099956230698.0));
+
+        assert_eq!(
+            geoadd(&store, "sicily 0 90 Pole").await,
+            Err("invalid longitude,latitude pair 0.000000,90.000000".to_string())
+        );
+        assert_eq!(
+            Frame::error(geoadd(&store, "sicily xx nx 0 0 a").await.unwrap_err())
+                .to_bytes(Protocol::Resp2),
+            b"-ERR XX and NX options at the same time are not compatible\r\n"
+        );
+        assert!(geoadd(&store, "sicily 0 0").await.is_err());
+    }
+}
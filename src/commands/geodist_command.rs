@@ -0,0 +1,123 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::Frame,
+    store::Key,
+    types::geohash,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `GEODIST key member1 member2 [M | KM | FT | MI]`
+pub struct GeoDistCommand {
+    pub key: Key,
+    pub from: Bytes,
+    pub to: Bytes,
+    /// Meters per unit
+    pub unit: f64,
+}
+
+/// Parses a unit of distance, into meters per unit.
+pub fn parse_unit(part: &[u8]) -> Result<f64, String> {
+    match lowercase(part).as_str() {
+        "m" => Ok(1.0),
+        "km" => Ok(1000.0),
+        "ft" => Ok(0.3048),
+        "mi" => Ok(1609.34),
+        _ => Err("unsupported unit provided. please use M, KM, FT, MI".to_string()),
+    }
+}
+
+/// Distances are replied with four decimals, like Redis does
+pub fn distance_frame(distance: f64) -> Frame {
+    Frame::bulk(format!("{distance:.4}"))
+}
+
+impl CommandTrait for GeoDistCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let from = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let to = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let unit = parts.next().map_or(Ok(1.0), |unit| parse_unit(&unit))?;
+
+        if parts.next().is_some() {
+            return Err("syntax error".to_string());
+        }
+
+        Ok(CommandWrapper::GeoDist(Self {
+            key,
+            from,
+            to,
+            unit,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let distance = store
+            .read(&self.key, |value| {
+                let zset = value.as_zset()?;
+                let point = |member: &Bytes| {
+                    zset.score(member)
+                        .map(|score| geohash::decode(score as u64))
+                };
+                Ok::<_, String>(match (point(&self.from), point(&self.to)) {
+                    (Some(from), Some(to)) => Some(geohash::distance(from, to)),
+                    _ => None,
+                })
+            })
+            .transpose()?
+            .flatten();
+        Ok(distance.map_or(Frame::Null, |distance| distance_frame(distance / self.unit)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn geodist(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match GeoDistCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::GeoDist(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a GeoDist command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_geodist_command() {
+        let store = Store::new();
+        let zset = [
+            ("Palermo", geohash::encode((13.361389, 38.115556)) as f64),
+            ("Catania", geohash::encode((15.087269, 37.502669)) as f64),
+        ];
+        store.upsert(
+            &Key::from("sicily"),
+            || Value::ZSet(zset.into_iter().collect()),
+            |_| (),
+        );
+
+        for (input, expected) in [
+            ("sicily Palermo Catania", Frame::bulk("166274.1516")),
+            ("sicily Palermo Catania KM", Frame::bulk("166.2742")),
+            ("sicily Palermo Catania mi", Frame::bulk("103.3182")),
+            ("sicily Palermo Nowhere", Frame::Null),
+            ("missing Palermo Catania", Frame::Null),
+        ] {
+            assert_eq!(geodist(&store, input).await, Ok(expected), "{input}");
+        }
+        assert_eq!(
+            geodist(&store, "sicily Palermo Catania yd").await,
+            Err("unsupported unit provided. please use M, KM, FT, MI".to_string())
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key, types::geohash};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `GEOHASH key [member [member ...]]`
+pub struct GeoHashCommand {
+    pub key: Key,
+    pub members: Vec<Bytes>,
+}
+
+impl CommandTrait for GeoHashCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let members = parts.collect();
+        Ok(CommandWrapper::GeoHash(Self { key, members }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let scores = store
+            .read(&self.key, |value| {
+                let zset = value.as_zset()?;
+                Ok::<_, String>(
+                    self.members
+                        .iter()
+                        .map(|member| zset.score(member))
+                        .collect(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_else(|| vec![None; self.members.len()]);
+        Ok(Frame::Array(
+            scores
+                .into_iter()
+                .map(|score| {
+                    score.map_or(Frame::Null, |score| {
+                        Frame::bulk(geohash::to_standard(score as u64))
+                    })
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_geohash_command() {
+        let store = Store::new();
+        let zset = [
+            ("Palermo", geohash::encode((13.361389, 38.115556)) as f64),
+            ("Catania", geohash::encode((15.087269, 37.502669)) as f64),
+        ];
+        store.upsert(
+            &Key::from("sicily"),
+            || Value::ZSet(zset.into_iter().collect()),
+            |_| (),
+        );
+
+        let geohash = |input: &str| match GeoHashCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::GeoHash(cmd)) => cmd,
+            _ => panic!("Expected a GeoHash command"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            geohash("sicily Palermo Nowhere Catania")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("sqc8b49rny0"),
+                Frame::Null,
+                Frame::bulk("sqdtr74hyu0"),
+            ]))
+        );
+        assert_eq!(
+            geohash("missing a")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Array(vec![Frame::Null]))
+        );
+    }
+}
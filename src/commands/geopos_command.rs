@@ -0,0 +1,100 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::Key,
+    types::geohash::{self, Point},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `GEOPOS key [member [member ...]]`
+pub struct GeoPosCommand {
+    pub key: Key,
+    pub members: Vec<Bytes>,
+}
+
+/// A point as replied, longitude first
+pub fn point_frame((longitude, latitude): Point) -> Frame {
+    Frame::Array(vec![Frame::Double(longitude), Frame::Double(latitude)])
+}
+
+impl CommandTrait for GeoPosCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let members = parts.collect();
+        Ok(CommandWrapper::GeoPos(Self { key, members }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let scores = store
+            .read(&self.key, |value| {
+                let zset = value.as_zset()?;
+                Ok::<_, String>(
+                    self.members
+                        .iter()
+                        .map(|member| zset.score(member))
+                        .collect(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_else(|| vec![None; self.members.len()]);
+        Ok(Frame::Array(
+            scores
+                .into_iter()
+                .map(|score| {
+                    score.map_or(Frame::NullArray, |score| {
+                        point_frame(geohash::decode(score as u64))
+                    })
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_geopos_command() {
+        let store = Store::new();
+        let score = geohash::encode((13.361389, 38.115556)) as f64;
+        store.upsert(
+            &Key::from("sicily"),
+            || Value::ZSet([("Palermo", score)].into_iter().collect()),
+            |_| (),
+        );
+
+        let geopos = |input: &str| match GeoPosCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::GeoPos(cmd)) => cmd,
+            _ => panic!("Expected a GeoPos command"),
+        };
+        let mut client = Client::new();
+        let Ok(Frame::Array(points)) = geopos("sicily Palermo Nowhere")
+            .execute(store.clone(), &mut client)
+            .await
+        else {
+            panic!("Expected an array");
+        };
+        let Frame::Array(palermo) = &points[0] else {
+            panic!("Expected a point");
+        };
+        let [Frame::Double(longitude), Frame::Double(latitude)] = palermo[..] else {
+            panic!("Expected the coordinates");
+        };
+        assert!((longitude - 13.361389).abs() < 1e-5 && (latitude - 38.115556).abs() < 1e-5);
+        assert_eq!(points[1], Frame::NullArray);
+    }
+}
@@ -0,0 +1,552 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_float, parse_int, ParseError},
+    resp::Frame,
+    store::{ConcurrentStore, Key, Value},
+    types::{
+        geohash::{self, Point, Shape},
+        zset::{ScoreBound, ZSet},
+    },
+};
+
+use super::{
+    geoadd_command::parse_point,
+    geodist_command::{distance_frame, parse_unit},
+    geopos_command::point_frame,
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `GEOSEARCH`, `GEOSEARCHSTORE` and the deprecated `GEORADIUS` family all search
+/// a geospatial index for the members within a shape
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GeoSearchVariant {
+    /// `GEOSEARCH key <FROMMEMBER member | FROMLONLAT longitude latitude> <BYRADIUS radius <M | KM | FT | MI> | BYBOX width height <M | KM | FT | MI>> [ASC | DESC] [COUNT count [ANY]] [WITHCOORD] [WITHDIST] [WITHHASH]`
+    GeoSearch,
+    /// `GEOSEARCHSTORE destination source <FROMMEMBER member | FROMLONLAT longitude latitude> <BYRADIUS radius <M | KM | FT | MI> | BYBOX width height <M | KM | FT | MI>> [ASC | DESC] [COUNT count [ANY]] [STOREDIST]`
+    GeoSearchStore,
+    /// `GEORADIUS key longitude latitude radius <M | KM | FT | MI> [WITHCOORD] [WITHDIST] [WITHHASH] [COUNT count [ANY]] [ASC | DESC] [STORE key | STOREDIST key]`
+    GeoRadius,
+    /// `GEORADIUS_RO key longitude latitude radius <M | KM | FT | MI> [WITHCOORD] [WITHDIST] [WITHHASH] [COUNT count [ANY]] [ASC | DESC]`
+    GeoRadiusRo,
+    /// `GEORADIUSBYMEMBER key member radius <M | KM | FT | MI> [WITHCOORD] [WITHDIST] [WITHHASH] [COUNT count [ANY]] [ASC | DESC] [STORE key | STOREDIST key]`
+    GeoRadiusByMember,
+    /// `GEORADIUSBYMEMBER_RO key member radius <M | KM | FT | MI> [WITHCOORD] [WITHDIST] [WITHHASH] [COUNT count [ANY]] [ASC | DESC]`
+    GeoRadiusByMemberRo,
+}
+
+impl GeoSearchVariant {
+    fn name(&self) -> &'static str {
+        match self {
+            GeoSearchVariant::GeoSearch => "geosearch",
+            GeoSearchVariant::GeoSearchStore => "geosearchstore",
+            GeoSearchVariant::GeoRadius => "georadius",
+            GeoSearchVariant::GeoRadiusRo => "georadius_ro",
+            GeoSearchVariant::GeoRadiusByMember => "georadiusbymember",
+            GeoSearchVariant::GeoRadiusByMemberRo => "georadiusbymember_ro",
+        }
+    }
+
+    fn can_store(&self) -> bool {
+        matches!(
+            self,
+            GeoSearchVariant::GeoRadius | GeoSearchVariant::GeoRadiusByMember
+        )
+    }
+}
+
+pub struct GeoSearchCommand {
+    pub key: Key,
+    pub center: Center,
+    pub shape: Shape,
+    /// Meters per unit, which distances are replied in
+    pub unit: f64,
+    /// Nearest first if `Some(false)`, farthest first if `Some(true)`
+    pub descending: Option<bool>,
+    pub count: Option<usize>,
+    /// Reply with the first members found, rather than the nearest
+    pub any: bool,
+    pub with_coord: bool,
+    pub with_dist: bool,
+    pub with_hash: bool,
+    /// Stores the members in a sorted set, rather than replying with them
+    pub store: Option<GeoStore>,
+}
+
+/// Where a search is centered
+#[derive(Debug, PartialEq, Clone)]
+pub enum Center {
+    Member(Bytes),
+    Point(Point),
+}
+
+/// Where the members found are stored
+#[derive(Debug, PartialEq, Clone)]
+pub struct GeoStore {
+    pub destination: Key,
+    /// Scores the members by their distance, in the unit of the search, rather
+    /// than by their geohash
+    pub dist: bool,
+}
+
+/// A member found by a search
+struct Found {
+    member: Bytes,
+    score: u64,
+    /// In meters
+    distance: f64,
+    point: Point,
+}
+
+/// Parses a distance, which must not be negative, and its unit, into meters.
+fn parse_distance(part: &[u8], unit: f64, error: &str) -> Result<f64, String> {
+    parse_float(part)
+        .map(|distance| distance * unit)
+        .map_err(|_| error.to_string())
+}
+
+impl GeoSearchCommand {
+    pub fn from_parts_as(
+        variant: GeoSearchVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let next = |parts: &mut CommandParts| parts.next().ok_or("syntax error".to_string());
+        let searches = matches!(
+            variant,
+            GeoSearchVariant::GeoSearch | GeoSearchVariant::GeoSearchStore
+        );
+
+        let mut store = None;
+        if variant == GeoSearchVariant::GeoSearchStore {
+            let destination = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+            store = Some(GeoStore {
+                destination,
+                dist: false,
+            });
+        }
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        // The `GEORADIUS` family takes its center and radius first
+        let mut center = None;
+        let mut shape = None;
+        let mut unit = 1.0;
+        if !searches {
+            center = Some(match variant {
+                GeoSearchVariant::GeoRadius | GeoSearchVariant::GeoRadiusRo => {
+                    Center::Point(parse_point(&next(&mut parts)?, &next(&mut parts)?)?)
+                }
+                _ => Center::Member(next(&mut parts)?),
+            });
+            let radius = next(&mut parts)?;
+            unit = parse_unit(&next(&mut parts)?)?;
+            shape = Some(Shape::Radius(parse_distance(
+                &radius,
+                unit,
+                "need numeric radius",
+            )?));
+        }
+
+        let mut cmd = Self {
+            key,
+            center: Center::Point((0.0, 0.0)),
+            shape: Shape::Radius(0.0),
+            unit,
+            descending: None,
+            count: None,
+            any: false,
+            with_coord: false,
+            with_dist: false,
+            with_hash: false,
+            store,
+        };
+        while let Some(option) = parts.next() {
+            match lowercase(&option).as_str() {
+                "withdist" => cmd.with_dist = true,
+                "withhash" => cmd.with_hash = true,
+                "withcoord" => cmd.with_coord = true,
+                "any" => cmd.any = true,
+                "asc" => cmd.descending = Some(false),
+                "desc" => cmd.descending = Some(true),
+                "count" => {
+                    let count = parse_int(&next(&mut parts)?)?;
+                    if count <= 0 {
+                        return Err("COUNT must be > 0".to_string());
+                    }
+                    cmd.count = Some(count as usize);
+                }
+                option @ ("store" | "storedist") if variant.can_store() => {
+                    cmd.store = Some(GeoStore {
+                        destination: next(&mut parts)?,
+                        dist: option == "storedist",
+                    });
+                }
+                "storedist" if variant == GeoSearchVariant::GeoSearchStore => {
+                    if let Some(store) = &mut cmd.store {
+                        store.dist = true;
+                    }
+                }
+                "frommember" if searches && !matches!(center, Some(Center::Point(_))) => {
+                    center = Some(Center::Member(next(&mut parts)?));
+                }
+                "fromlonlat" if searches && !matches!(center, Some(Center::Member(_))) => {
+                    center = Some(Center::Point(parse_point(
+                        &next(&mut parts)?,
+                        &next(&mut parts)?,
+                    )?));
+                }
+                "byradius" if searches && !matches!(shape, Some(Shape::Box { .. })) => {
+                    let radius = next(&mut parts)?;
+                    cmd.unit = parse_unit(&next(&mut parts)?)?;
+                    shape = Some(Shape::Radius(parse_distance(
+                        &radius,
+                        cmd.unit,
+                        "need numeric radius",
+                    )?));
+                }
+                "bybox" if searches && !matches!(shape, Some(Shape::Radius(_))) => {
+                    let width = next(&mut parts)?;
+                    let height = next(&mut parts)?;
+                    cmd.unit = parse_unit(&next(&mut parts)?)?;
+                    shape = Some(Shape::Box {
+                        width: parse_distance(&width, cmd.unit, "need numeric width")?,
+                        height: parse_distance(&height, cmd.unit, "need numeric height")?,
+                    });
+                }
+                _ => return Err("syntax error".to_string()),
+            }
+        }
+
+        if cmd.store.is_some() && (cmd.with_dist || cmd.with_hash || cmd.with_coord) {
+            return Err(format!(
+                "{} is not compatible with WITHDIST, WITHHASH and WITHCOORD options",
+                match variant {
+                    GeoSearchVariant::GeoSearchStore => "GEOSEARCHSTORE",
+                    _ => "STORE option in GEORADIUS",
+                }
+            ));
+        }
+        cmd.center = center.ok_or(format!(
+            "exactly one of FROMMEMBER or FROMLONLAT can be specified for {}",
+            variant.name()
+        ))?;
+        cmd.shape = shape.ok_or(format!(
+            "exactly one of BYRADIUS and BYBOX can be specified for {}",
+            variant.name()
+        ))?;
+        match cmd.shape {
+            Shape::Radius(radius) if radius < 0.0 => {
+                return Err("radius cannot be negative".to_string())
+            }
+            Shape::Box { width, height } if width < 0.0 || height < 0.0 => {
+                return Err("height or width cannot be negative".to_string())
+            }
+            _ => {}
+        }
+        if cmd.any && cmd.count.is_none() {
+            return Err("the ANY argument requires COUNT argument".to_string());
+        }
+        // The nearest members are only found by sorting them all
+        if cmd.count.is_some() && cmd.descending.is_none() && !cmd.any {
+            cmd.descending = Some(false);
+        }
+
+        Ok(CommandWrapper::GeoSearch(cmd))
+    }
+
+    /// The members within the shape, or `None` if the key doesn't exist.
+    fn search(&self, store: &ConcurrentStore) -> Result<Option<Vec<Found>>, String> {
+        let found = store.read(&self.key, |value| {
+            let zset = value.as_zset()?;
+            let center = match &self.center {
+                Center::Point(point) => *point,
+                Center::Member(member) => zset
+                    .score(member)
+                    .map(|score| geohash::decode(score as u64))
+                    .ok_or("could not decode requested zset member")?,
+            };
+
+            // With `ANY`, the search stops at the first members found
+            let limit = self.count.filter(|_| self.any).unwrap_or(usize::MAX);
+            let mut found = Vec::new();
+            for (min, max) in self.shape.score_ranges(center) {
+                let ranks = zset.score_ranks(
+                    ScoreBound {
+                        score: min as f64,
+                        exclusive: false,
+                    },
+                    ScoreBound {
+                        score: max as f64,
+                        exclusive: true,
+                    },
+                );
+                for (member, score) in zset.iter_from(ranks.start, false).take(ranks.len()) {
+                    if found.len() >= limit {
+                        break;
+                    }
+                    let score = score as u64;
+                    let point = geohash::decode(score);
+                    if let Some(distance) = self.shape.distance_if_within(center, point) {
+                        found.push(Found {
+                            member: member.clone(),
+                            score,
+                            distance,
+                            point,
+                        });
+                    }
+                }
+            }
+
+            if let Some(descending) = self.descending {
+                found.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+                if descending {
+                    found.reverse();
+                }
+            }
+            if let Some(count) = self.count {
+                found.truncate(count);
+            }
+            Ok::<_, String>(found)
+        });
+        found.transpose()
+    }
+}
+
+impl CommandTrait for GeoSearchCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(GeoSearchVariant::GeoSearch, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let Some(target) = &self.store else {
+            let found = self.search(&store)?.unwrap_or_default();
+            let with_options = self.with_dist || self.with_hash || self.with_coord;
+            return Ok(Frame::Array(
+                found
+                    .into_iter()
+                    .map(|found| {
+                        if !with_options {
+                            return Frame::bulk(found.member);
+                        }
+                        let mut frames = vec![Frame::bulk(found.member)];
+                        if self.with_dist {
+                            frames.push(distance_frame(found.distance / self.unit));
+                        }
+                        if self.with_hash {
+                            frames.push(Frame::Integer(found.score as i64));
+                        }
+                        if self.with_coord {
+                            frames.push(point_frame(found.point));
+                        }
+                        Frame::Array(frames)
+                    })
+                    .collect(),
+            ));
+        };
+
+        let len = store.atomically(|| {
+            let found = self.search(&store)?.unwrap_or_default();
+            let len = found.len();
+            // Like Redis, finding nothing deletes the destination
+            if found.is_empty() {
+                store.del(&target.destination);
+                return Ok::<_, String>(0);
+            }
+            let mut zset = ZSet::new();
+            for found in found {
+                let score = match target.dist {
+                    true => found.distance / self.unit,
+                    false => found.score as f64,
+                };
+                zset.insert(found.member, score);
+            }
+            store.set(target.destination.clone(), Value::ZSet(zset));
+            Ok(len)
+        })?;
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, resp::Protocol, store::Store};
+
+    use super::*;
+
+    async fn geosearch(
+        store: &ConcurrentStore,
+        variant: GeoSearchVariant,
+        input: &str,
+    ) -> Result<Frame, String> {
+        match GeoSearchCommand::from_parts_as(variant, inline_parts(input))? {
+            CommandWrapper::GeoSearch(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a GeoSearch command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_geosearch_command() {
+        let store = Store::new();
+        let zset: ZSet = [
+            ("Palermo", (13.361389, 38.115556)),
+            ("Catania", (15.087269, 37.502669)),
+            ("edge1", (12.758489, 38.788135)),
+            ("edge2", (17.241510, 38.788135)),
+        ]
+        .into_iter()
+        .map(|(member, point)| (member, geohash::encode(point) as f64))
+        .collect();
+        store.upsert(&Key::from("sicily"), || Value::ZSet(zset), |_| ());
+
+        let members = |members: &[&'static str]| {
+            Ok(Frame::Array(
+                members.iter().map(|member| Frame::bulk(*member)).collect(),
+            ))
+        };
+        for (variant, input, expected) in [
+            (
+                GeoSearchVariant::GeoSearch,
+                "sicily fromlonlat 15 37 byradius 200 km asc",
+                &["Catania", "Palermo"][..],
+            ),
+            (
+                GeoSearchVariant::GeoSearch,
+                "sicily fromlonlat 15 37 bybox 400 400 km desc",
+                &["edge1", "edge2", "Palermo", "Catania"],
+            ),
+            (
+                GeoSearchVariant::GeoSearch,
+                "sicily frommember Palermo byradius 100 km",
+                &["Palermo", "edge1"],
+            ),
+            (
+                GeoSearchVariant::GeoRadiusRo,
+                "sicily 15 37 200 km count 1",
+                &["Catania"],
+            ),
+            (
+                GeoSearchVariant::GeoRadiusByMember,
+                "sicily Catania 200 km desc count 2",
+                &["Palermo", "Catania"],
+            ),
+            (
+                GeoSearchVariant::GeoSearch,
+                "missing fromlonlat 15 37 byradius 200 km",
+                &[],
+            ),
+        ] {
+            let mut found = geosearch(&store, variant, input).await;
+            let mut expected = members(expected);
+            // Without an order, members come in no particular one
+            let ordered = ["asc", "desc", "count"].iter().any(|o| input.contains(o));
+            for members in [&mut found, &mut expected] {
+                if let (Ok(Frame::Array(members)), false) = (members, ordered) {
+                    members.sort_by_key(|member| format!("{member:?}"));
+                }
+            }
+            assert_eq!(found, expected, "{input}");
+        }
+
+        assert_eq!(
+            geosearch(
+                &store,
+                GeoSearchVariant::GeoSearch,
+                "sicily fromlonlat 15 37 byradius 200 km withdist withhash withcoord asc count 1"
+            )
+            .await,
+            Ok(Frame::Array(vec![Frame::Array(vec![
+                Frame::bulk("Catania"),
+                Frame::bulk("56.4413"),
+                Frame::Integer(3479447370796909),
+                point_frame(geohash::decode(3479447370796909)),
+            ])]))
+        );
+
+        assert_eq!(
+            geosearch(
+                &store,
+                GeoSearchVariant::GeoSearchStore,
+                "dst sicily fromlonlat 15 37 byradius 200 km storedist"
+            )
+            .await,
+            Ok(Frame::Integer(2))
+        );
+        let distance = store
+            .read(&Key::from("dst"), |value| {
+                value.as_zset().unwrap().score(b"Catania")
+            })
+            .flatten();
+        assert!(distance.is_some_and(|distance| (distance - 56.4413).abs() < 1e-4));
+        assert_eq!(
+            geosearch(
+                &store,
+                GeoSearchVariant::GeoRadius,
+                "sicily 0 0 1 m store dst"
+            )
+            .await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(store.get(&Key::from("dst")), None);
+
+        for (variant, input, expected) in [
+            (
+                GeoSearchVariant::GeoSearch,
+                "sicily byradius 1 km",
+                "exactly one of FROMMEMBER or FROMLONLAT can be specified for geosearch",
+            ),
+            (
+                GeoSearchVariant::GeoSearch,
+                "sicily frommember Palermo",
+                "exactly one of BYRADIUS and BYBOX can be specified for geosearch",
+            ),
+            (
+                GeoSearchVariant::GeoSearch,
+                "sicily frommember Nowhere byradius 1 km",
+                "could not decode requested zset member",
+            ),
+            (
+                GeoSearchVariant::GeoSearch,
+                "sicily frommember Palermo byradius 1 km any",
+                "the ANY argument requires COUNT argument",
+            ),
+            (
+                GeoSearchVariant::GeoSearchStore,
+                "dst sicily frommember Palermo byradius 1 km withdist",
+                "GEOSEARCHSTORE is not compatible with WITHDIST, WITHHASH and WITHCOORD options",
+            ),
+            (
+                GeoSearchVariant::GeoRadiusRo,
+                "sicily 15 37 200 km store dst",
+                "syntax error",
+            ),
+            (
+                GeoSearchVariant::GeoRadius,
+                "sicily 15 37 -1 km",
+                "radius cannot be negative",
+            ),
+            (
+                GeoSearchVariant::GeoRadius,
+                "sicily 15 37 200 km count 0",
+                "COUNT must be > 0",
+            ),
+            (
+                GeoSearchVariant::GeoRadius,
+                "sicily 15 37 200 km withdist store dst",
+                "STORE option in GEORADIUS is not compatible with WITHDIST, WITHHASH and WITHCOORD options",
+            ),
+        ] {
+            // None of the errors start with an error code, even the ones starting
+            // with a command or an option
+            let error = geosearch(&store, variant, input).await.unwrap_err();
+            assert_eq!(
+                Frame::error(error).to_bytes(Protocol::Resp2),
+                format!("-ERR {expected}\r\n").into_bytes(),
+                "{input}"
+            );
+        }
+    }
+}
@@ -1,41 +1,40 @@
-use std::str::SplitWhitespace;
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
 
-use crate::parser::utils::ParseError;
-
-use super::{CommandTrait, CommandWrapper};
+use super::{CommandParts, CommandTrait, CommandWrapper};
 
 pub struct GetCommand {
-    pub key: String,
+    pub key: Key,
 }
 
 impl CommandTrait for GetCommand {
-    fn from_parts(mut parts: SplitWhitespace<'_>) -> Result<CommandWrapper, String> {
-        let key = parts
-            .next()
-            .ok_or(ParseError::MissingKey.to_string())?
-            .to_string();
-
-        Ok(CommandWrapper::Get(Self {
-            key: key.to_string(),
-        }))
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        Ok(CommandWrapper::Get(Self { key }))
     }
 
-    async fn execute(self, store: crate::store::ConcurrentStore) -> Result<String, String> {
-        match store.get(&self.key) {
-            Some(response) => Ok(response),
-            None => Ok("Key not found".to_string()),
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        match store.read(&self.key, |value| value.as_str()) {
+            Some(value) => Ok(Frame::bulk(value?)),
+            None => Ok(Frame::Null),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::parser::utils::inline_parts;
+
     use super::*;
 
     #[test]
     fn test_get_command_from_input() {
         let input = "get key".to_string();
-        let mut parts = input.split_whitespace();
+        let mut parts = inline_parts(&input);
         parts.next(); // Skip the command
         match GetCommand::from_parts(parts).unwrap() {
             CommandWrapper::Get(cmd) => {
@@ -48,7 +47,7 @@ mod tests {
     #[test]
     fn test_get_command_from_input_missing_key() {
         let input = "get".to_string();
-        let mut parts = input.split_whitespace();
+        let mut parts = inline_parts(&input);
         parts.next(); // Skip the command
         match GetCommand::from_parts(parts) {
             Err(e) => assert_eq!(e, ParseError::MissingKey.to_string()),
@@ -0,0 +1,89 @@
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::bitmap,
+};
+
+use super::{
+    setbit_command::{check_bit_offset, OFFSET_OUT_OF_RANGE},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `GETBIT key offset`
+pub struct GetBitCommand {
+    pub key: Key,
+    pub offset: i64,
+}
+
+impl CommandTrait for GetBitCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let offset = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let offset = parse_int(&offset).map_err(|_| OFFSET_OUT_OF_RANGE)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::GetBit(Self { key, offset }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let offset = check_bit_offset(self.offset, &store.config)?;
+        let bit = store
+            .read(&self.key, |value| {
+                value.as_str().map(|bytes| bitmap::get_bit(&bytes, offset))
+            })
+            .transpose()?
+            .unwrap_or(false);
+        Ok(Frame::Integer(bit as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_getbit_command() {
+        let store = Store::new();
+        store.set(
+            Key::from("key"),
+            Value::Str(Bytes::from_static(&[0x01, 0x40])),
+        );
+
+        let getbit = |key: &'static str, offset| GetBitCommand {
+            key: Key::from(key),
+            offset,
+        };
+        let mut client = Client::new();
+        for (key, offset, expected) in [
+            ("key", 7, 1),
+            ("key", 8, 0),
+            ("key", 9, 1),
+            ("key", 100, 0),
+            ("missing", 0, 0),
+        ] {
+            assert_eq!(
+                getbit(key, offset)
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+        assert!(getbit("key", -1)
+            .execute(store.clone(), &mut client)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `GETDEL key`
+pub struct GetDelCommand {
+    pub key: Key,
+}
+
+impl CommandTrait for GetDelCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::GetDel(Self { key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let value = store.atomically(|| {
+            // Only strings are deleted, other types are left alone with an error
+            let value = store.read(&self.key, |value| value.as_str()).transpose()?;
+            if value.is_some() {
+                store.del(&self.key);
+            }
+            Ok::<_, String>(value)
+        })?;
+        Ok(value.map_or(Frame::Null, Frame::bulk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_getdel_command() {
+        let store = Store::new();
+        store.set(Key::from("key"), Value::Str(Bytes::from("value")));
+        store.upsert(
+            &Key::from("list"),
+            || Value::List(["a"].into_iter().collect()),
+            |_| (),
+        );
+
+        let getdel = |key: &'static str| GetDelCommand {
+            key: Key::from(key),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            getdel("key").execute(store.clone(), &mut client).await,
+            Ok(Frame::bulk("value"))
+        );
+        assert_eq!(
+            getdel("key").execute(store.clone(), &mut client).await,
+            Ok(Frame::Null)
+        );
+        assert!(getdel("list")
+            .execute(store.clone(), &mut client)
+            .await
+            .is_err());
+        assert!(store.get(&Key::from("list")).is_some());
+    }
+}
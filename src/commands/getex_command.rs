@@ -0,0 +1,141 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::{ExpireCondition, Key},
+    utils::current_epoch_millis,
+};
+
+use super::{set_command::SetExpiry, CommandParts, CommandTrait, CommandWrapper};
+
+/// `GETEX key [EX seconds | PX milliseconds | EXAT unix-time-seconds | PXAT unix-time-milliseconds | PERSIST]`
+pub struct GetExCommand {
+    pub key: Key,
+    /// Sets the time to live of the key, or removes it with `PERSIST` if `None`.
+    /// The key is left as it is without either.
+    pub expiry: Option<Option<SetExpiry>>,
+}
+
+impl CommandTrait for GetExCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        let expiry = match parts.next() {
+            None => None,
+            Some(option) => {
+                let option = lowercase(&option);
+                let mut time = || parse_int(&parts.next().ok_or("syntax error")?);
+                Some(match option.as_str() {
+                    "ex" => Some(SetExpiry::Ex(time()?)),
+                    "px" => Some(SetExpiry::Px(time()?)),
+                    "exat" => Some(SetExpiry::ExAt(time()?)),
+                    "pxat" => Some(SetExpiry::PxAt(time()?)),
+                    "persist" => None,
+                    _ => return Err("syntax error".to_string()),
+                })
+            }
+        };
+
+        // Only one of the options can be given
+        if parts.next().is_some() {
+            return Err("syntax error".to_string());
+        }
+
+        Ok(CommandWrapper::GetEx(Self { key, expiry }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let expires_at = match &self.expiry {
+            Some(Some(expiry)) => expiry.expires_at(current_epoch_millis(), "getex")?,
+            _ => None,
+        };
+
+        let value = store.atomically(|| {
+            let value = store.read(&self.key, |value| value.as_str()).transpose()?;
+            if value.is_some() {
+                match (self.expiry.is_some(), expires_at) {
+                    // A time in the past deletes the key, like `EXPIRE` does
+                    (_, Some(at)) => {
+                        store.expire(&self.key, at, ExpireCondition::default());
+                    }
+                    (true, None) => {
+                        store.persist(&self.key);
+                    }
+                    (false, None) => {}
+                }
+            }
+            Ok::<_, String>(value)
+        })?;
+        Ok(value.map_or(Frame::Null, Frame::bulk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_getex_command() {
+        let store = Store::new();
+        store.set(Key::from("key"), Value::Str(Bytes::from("value")));
+        let key = Key::from("key");
+
+        let getex = |input: &str| match GetExCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::GetEx(cmd)) => Ok(cmd),
+            Ok(_) => panic!("Expected a GetEx command"),
+            Err(e) => Err(e),
+        };
+        let mut client = Client::new();
+        for (input, expires) in [("key ex 100", true), ("key", true), ("key persist", false)] {
+            assert_eq!(
+                getex(input)
+                    .unwrap()
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Ok(Frame::bulk("value"))
+            );
+            assert_eq!(
+                store.expires_at(&key).unwrap().is_some(),
+                expires,
+                "{input}"
+            );
+        }
+
+        assert_eq!(
+            getex("key pxat 1")
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::bulk("value"))
+        );
+        assert_eq!(store.get(&key), None);
+        assert_eq!(
+            getex("missing ex 10")
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Null)
+        );
+
+        assert_eq!(
+            getex("key ex 0")
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await,
+            Err("invalid expire time in 'getex' command".to_string())
+        );
+        assert!(getex("key ex 10 persist").is_err());
+        assert!(getex("key nope").is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    utils::resolve_range,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `GETRANGE key start end`
+pub struct GetRangeCommand {
+    pub key: Key,
+    pub start: i64,
+    /// Inclusive
+    pub end: i64,
+}
+
+impl CommandTrait for GetRangeCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let start = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let end = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::GetRange(Self { key, start, end }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let range = store
+            .read(&self.key, |value| {
+                let bytes = value.as_str()?;
+                Ok::<_, String>(match resolve_range(self.start, self.end, bytes.len()) {
+                    Some((start, end)) => bytes.slice(start..end),
+                    None => Bytes::new(),
+                })
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Frame::bulk(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_getrange_command() {
+        let store = Store::new();
+        store.set(
+            Key::from("key"),
+            Value::Str(Bytes::from("This is a string")),
+        );
+        store.set(Key::from("int"), Value::Int(-123));
+
+        let getrange = |key: &'static str, start, end| GetRangeCommand {
+            key: Key::from(key),
+            start,
+            end,
+        };
+        let mut client = Client::new();
+        for (key, start, end, expected) in [
+            ("key", 0, 3, "This"),
+            ("key", -3, -1, "ing"),
+            ("key", 0, -1, "This is a string"),
+            ("key", 10, 100, "string"),
+            ("key", 5, 2, ""),
+            ("int", 1, 2, "12"),
+            ("missing", 0, -1, ""),
+        ] {
+            assert_eq!(
+                getrange(key, start, end)
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Ok(Frame::bulk(expected))
+            );
+        }
+    }
+}
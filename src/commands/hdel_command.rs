@@ -0,0 +1,68 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `HDEL key field [field ...]`
+pub struct HDelCommand {
+    pub key: Key,
+    pub fields: Vec<Bytes>,
+}
+
+impl CommandTrait for HDelCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let fields: Vec<Bytes> = parts.collect();
+
+        if fields.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+
+        Ok(CommandWrapper::HDel(Self { key, fields }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let removed = store
+            .update(&self.key, |value| {
+                let hash = value.as_hash_mut()?;
+                Ok::<_, String>(self.fields.iter().filter(|f| hash.remove(f)).count())
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(removed as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hdel_deletes_the_emptied_hash() {
+        let store = Store::new();
+        let hash = Value::Hash([("a", "1"), ("b", "2")].into_iter().collect());
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        let hdel = |fields: &[&'static str]| HDelCommand {
+            key: Key::from("hash"),
+            fields: fields.iter().map(|f| Bytes::from(*f)).collect(),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            hdel(&["a", "c"]).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            hdel(&["b"]).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(store.get(&Key::from("hash")), None);
+    }
+}
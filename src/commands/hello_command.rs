@@ -0,0 +1,166 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::{Frame, Protocol},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+pub struct HelloCommand {
+    /// Switch the connection to this protocol, or keep the current one
+    pub protocol: Option<Protocol>,
+    /// `AUTH username password`
+    pub auth: Option<(String, String)>,
+    /// `SETNAME clientname`
+    pub client_name: Option<String>,
+}
+
+impl CommandTrait for HelloCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let protocol = match parts.next() {
+            None => None,
+            Some(version) => match String::from_utf8_lossy(&version).parse::<i64>() {
+                Ok(2) => Some(Protocol::Resp2),
+                Ok(3) => Some(Protocol::Resp3),
+                Ok(_) => return Err("NOPROTO unsupported protocol version".to_string()),
+                Err(_) => {
+                    return Err(ParseError::InvalidCommandOptionValue(
+                        "Protocol version is not an integer or out of range",
+                    )
+                    .to_string())
+                }
+            },
+        };
+
+        let mut auth = None;
+        let mut client_name = None;
+        while let Some(option) = parts.next() {
+            match lowercase(&option).as_str() {
+                "auth" => {
+                    let username = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+                    let password = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+                    auth = Some((
+                        String::from_utf8_lossy(&username).to_string(),
+                        String::from_utf8_lossy(&password).to_string(),
+                    ));
+                }
+                "setname" => {
+                    let name = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+                    if name.iter().any(|b| !(b'!'..=b'~').contains(b)) {
+                        return Err(ParseError::InvalidCommandOptionValue(
+                            "Client names cannot contain spaces, newlines or special characters.",
+                        )
+                        .to_string());
+                    }
+                    client_name = Some(String::from_utf8_lossy(&name).to_string());
+                }
+                _ => {
+                    return Err(
+                        ParseError::InvalidCommandOptions("Syntax error in HELLO option")
+                            .to_string(),
+                    )
+                }
+            }
+        }
+
+        Ok(CommandWrapper::Hello(Self {
+            protocol,
+            auth,
+            client_name,
+        }))
+    }
+
+    async fn execute(
+        self,
+        _store: crate::store::ConcurrentStore,
+        client: &mut Client,
+    ) -> Result<Frame, String> {
+        // There is no ACL yet, so only the passwordless default user exists
+        if let Some((username, _)) = &self.auth {
+            if username != "default" {
+                return Err(
+                    "WRONGPASS invalid username-password pair or user is disabled.".to_string(),
+                );
+            }
+        }
+
+        if let Some(name) = self.client_name {
+            client.name = Some(name);
+        }
+        if let Some(protocol) = self.protocol {
+            client.protocol = protocol;
+        }
+
+        Ok(Frame::Map(vec![
+            (Frame::bulk("server"), Frame::bulk("kiwi")),
+            (
+                Frame::bulk("version"),
+                Frame::bulk(env!("CARGO_PKG_VERSION")),
+            ),
+            (
+                Frame::bulk("proto"),
+                Frame::Integer(client.protocol.version()),
+            ),
+            (Frame::bulk("id"), Frame::Integer(client.id as i64)),
+            (Frame::bulk("mode"), Frame::bulk("standalone")),
+            (Frame::bulk("role"), Frame::bulk("master")),
+            (Frame::bulk("modules"), Frame::Array(vec![])),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[test]
+    fn test_hello_command_from_input() {
+        let input = "hello 3 auth default secret setname worker-1".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match HelloCommand::from_parts(parts).unwrap() {
+            CommandWrapper::Hello(cmd) => {
+                assert_eq!(cmd.protocol, Some(Protocol::Resp3));
+                assert_eq!(
+                    cmd.auth,
+                    Some(("default".to_string(), "secret".to_string()))
+                );
+                assert_eq!(cmd.client_name, Some("worker-1".to_string()));
+            }
+            _ => panic!("Expected a Hello command"),
+        };
+    }
+
+    #[test]
+    fn test_hello_command_from_input_unsupported_protocol() {
+        let input = "hello 4".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match HelloCommand::from_parts(parts) {
+            Err(e) => assert_eq!(e, "NOPROTO unsupported protocol version"),
+            _ => panic!("Expected an error"),
+        };
+    }
+
+    #[tokio::test]
+    async fn test_hello_command_switches_protocol() {
+        let mut client = Client::new();
+        let cmd = HelloCommand {
+            protocol: Some(Protocol::Resp3),
+            auth: None,
+            client_name: None,
+        };
+
+        let reply = cmd.execute(Store::new(), &mut client).await.unwrap();
+
+        assert_eq!(client.protocol, Protocol::Resp3);
+        match reply {
+            Frame::Map(pairs) => {
+                assert!(pairs.contains(&(Frame::bulk("proto"), Frame::Integer(3))))
+            }
+            _ => panic!("Expected a map reply"),
+        }
+    }
+}
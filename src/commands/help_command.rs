@@ -1,39 +1,86 @@
-use std::str::SplitWhitespace;
+use crate::{client::Client, resp::Frame};
 
-use super::{CommandTrait, CommandWrapper};
+use super::{CommandParts, CommandTrait, CommandWrapper};
 
 pub struct HelpCommand;
 
 impl CommandTrait for HelpCommand {
-    fn from_parts(_parts: SplitWhitespace<'_>) -> Result<CommandWrapper, String> {
+    fn from_parts(_parts: CommandParts) -> Result<CommandWrapper, String> {
         Ok(CommandWrapper::Help(Self))
     }
 
-    async fn execute(self, _store: crate::store::ConcurrentStore) -> Result<String, String> {
-        Ok(HELP_MESSAGE.to_string())
+    async fn execute(
+        self,
+        _store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        Ok(Frame::Array(
+            HELP_MESSAGE
+                .lines()
+                .map(|line| Frame::Simple(line.to_string()))
+                .collect(),
+        ))
     }
 }
 
+/// Lists every command, which the test below keeps up to date with the parser
 const HELP_MESSAGE: &str = "\
-Commands:
-  set <key> <value>        - Set a key-value pair
-  get <key>                - Get the value associated with a key
-  del <key>                - Delete a key-value pair
-  exit                     - Exit the shell
-  help                     - Show this help message";
+Commands, grouped by the type they work on:
+  Connection:   ping echo hello help
+  Server:       info object
+  Keys:         del touch expire pexpire expireat pexpireat ttl pttl expiretime
+                pexpiretime persist
+  Strings:      set setnx setex psetex getset get getdel getex mget mset msetnx
+                incr decr incrby decrby incrbyfloat append getrange setrange
+                strlen lcs
+  Bitmaps:      setbit getbit bitcount bitpos bitop bitfield bitfield_ro
+  HyperLogLogs: pfadd pfcount pfmerge pfdebug pfselftest
+  Lists:        lpush rpush lpushx rpushx lpop rpop lrange lindex llen lset
+                linsert lrem ltrim lpos lmove rpoplpush lmpop blpop brpop
+                blmove brpoplpush blmpop
+  Hashes:       hset hmset hsetnx hget hexists hstrlen hmget hdel hgetall hkeys
+                hvals hlen hincrby hincrbyfloat hscan hrandfield hexpire
+                hpexpire hexpireat hpexpireat httl hpttl hexpiretime
+                hpexpiretime hpersist
+  Sets:         sadd srem smembers scard sismember smismember spop srandmember
+                smove sscan sinter sunion sdiff sinterstore sunionstore
+                sdiffstore sintercard
+  Sorted sets:  zadd zincrby zrem zcard zscore zmscore zrank zrevrank zcount
+                zlexcount zrange zrevrange zrangebyscore zrevrangebyscore
+                zrangebylex zrevrangebylex zremrangebyrank zremrangebyscore
+                zremrangebylex zpopmin zpopmax zrandmember zunion zinter zdiff
+                zunionstore zinterstore zdiffstore zintercard zrangestore zmpop
+                bzpopmin bzpopmax bzmpop
+  Streams:      xadd xrange xrevrange xlen xdel xtrim xgroup xread xreadgroup
+                xack xpending xclaim xautoclaim xinfo
+  Geospatial:   geoadd geodist geohash geopos geosearch geosearchstore
+                georadius georadius_ro georadiusbymember georadiusbymember_ro
+
+Arguments are like Redis', see https://redis.io/docs/latest/commands/
+  exit          Exit the shell";
 
 #[cfg(test)]
 mod tests {
+    use crate::parser::{utils::inline_parts, Parser};
+
     use super::*;
 
     #[test]
     fn test_help_command_from_input() {
         let input = "help".to_string();
-        let mut parts = input.split_whitespace();
+        let mut parts = inline_parts(&input);
         parts.next(); // Skip the command
         match HelpCommand::from_parts(parts).unwrap() {
             CommandWrapper::Help(_cmd) => (),
             _ => panic!("Expected a Help command"),
         };
     }
+
+    #[test]
+    fn test_help_lists_every_command() {
+        let listed: Vec<&str> = HELP_MESSAGE.split_whitespace().collect();
+        for name in Parser::command_names() {
+            assert!(listed.contains(&name), "{name} isn't listed by HELP");
+        }
+    }
 }
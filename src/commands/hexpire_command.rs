@@ -0,0 +1,288 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::{ExpireCondition, Key},
+    types::hash::Hash,
+    utils::current_epoch_millis,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The latest time a field can expire at, in milliseconds since UNIX epoch, like Redis
+const MAX_EXPIRES_AT: i64 = (1 << 48) - 1;
+
+/// The commands of the `HEXPIRE` family only differ in how the time is given
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HExpireVariant {
+    /// `HEXPIRE key seconds [NX | XX | GT | LT] FIELDS numfields field [field ...]`
+    HExpire,
+    /// `HPEXPIRE key milliseconds [NX | XX | GT | LT] FIELDS numfields field [field ...]`
+    HPExpire,
+    /// `HEXPIREAT key unix-time-seconds [NX | XX | GT | LT] FIELDS numfields field [field ...]`
+    HExpireAt,
+    /// `HPEXPIREAT key unix-time-milliseconds [NX | XX | GT | LT] FIELDS numfields field [field ...]`
+    HPExpireAt,
+}
+
+pub struct HExpireCommand {
+    pub key: Key,
+    pub variant: HExpireVariant,
+    /// Seconds or milliseconds, relative to now or to UNIX epoch, depending on the variant
+    pub time: i64,
+    pub condition: ExpireCondition,
+    pub fields: Vec<Bytes>,
+}
+
+impl HExpireCommand {
+    pub fn from_parts_as(
+        variant: HExpireVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let time = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+        // Only one condition can be given, unlike `EXPIRE`
+        let mut parts = parts.peekable();
+        let mut condition = ExpireCondition::default();
+        if let Some(part) = parts.next_if(|part| lowercase(part) != "fields") {
+            match lowercase(&part).as_str() {
+                "nx" => condition.nx = true,
+                "xx" => condition.xx = true,
+                "gt" => condition.gt = true,
+                "lt" => condition.lt = true,
+                _ => {
+                    return Err(ParseError::InvalidCommandOptions(&format!(
+                        "Unsupported option {}",
+                        String::from_utf8_lossy(&part)
+                    ))
+                    .to_string())
+                }
+            }
+        }
+        let fields = parse_fields(parts)?;
+
+        Ok(CommandWrapper::HExpire(Self {
+            key,
+            variant,
+            time,
+            condition,
+            fields,
+        }))
+    }
+
+    /// Resolves the time to milliseconds since UNIX epoch, `None` if it's out of range
+    fn expires_at(&self, now: u128) -> Option<u128> {
+        if self.time < 0 {
+            return None;
+        }
+        let millis = match self.variant {
+            HExpireVariant::HExpire | HExpireVariant::HExpireAt => self.time.checked_mul(1000)?,
+            HExpireVariant::HPExpire | HExpireVariant::HPExpireAt => self.time,
+        };
+        let expires_at = match self.variant {
+            HExpireVariant::HExpire | HExpireVariant::HPExpire => millis.checked_add(now as i64)?,
+            HExpireVariant::HExpireAt | HExpireVariant::HPExpireAt => millis,
+        };
+        (expires_at <= MAX_EXPIRES_AT).then_some(expires_at as u128)
+    }
+}
+
+/// Parses the `FIELDS numfields field [field ...]` that ends the commands on the
+/// time to live of hash fields.
+pub fn parse_fields(mut parts: impl Iterator<Item = Bytes>) -> Result<Vec<Bytes>, String> {
+    if parts.next().is_none_or(|part| lowercase(&part) != "fields") {
+        return Err(
+            "Mandatory argument FIELDS is missing or not at the right position".to_string(),
+        );
+    }
+    let count = parts
+        .next()
+        .ok_or(ParseError::MissingValue.to_string())
+        .and_then(|part| parse_int(&part))
+        .ok()
+        .filter(|count| *count > 0)
+        .ok_or("Parameter `numFields` should be greater than 0")?;
+
+    let fields: Vec<Bytes> = parts.collect();
+    if fields.len() as i64 != count {
+        return Err("The `numfields` parameter must match the number of arguments".to_string());
+    }
+    Ok(fields)
+}
+
+/// Sets the expiry of a field, replying like `HEXPIRE` does for each field
+fn expire_field(
+    hash: &mut Hash,
+    field: &[u8],
+    expires_at: u128,
+    condition: ExpireCondition,
+    now: u128,
+) -> i64 {
+    let Some(current) = hash.field_expires_at(field) else {
+        return -2;
+    };
+    if !condition.allows(current, expires_at) {
+        return 0;
+    }
+    if expires_at <= now {
+        hash.remove(field);
+        return 2;
+    }
+    hash.set_field_expires_at(field, Some(expires_at));
+    1
+}
+
+impl CommandTrait for HExpireCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(HExpireVariant::HExpire, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let now = current_epoch_millis();
+        let expires_at = self.expires_at(now).ok_or(format!(
+            "invalid expire time, must be >= 0 && <= {MAX_EXPIRES_AT}"
+        ))?;
+
+        let replies = store
+            .update(&self.key, |value| {
+                let hash = value.as_hash_mut()?;
+                Ok::<_, String>(
+                    self.fields
+                        .iter()
+                        .map(|field| {
+                            Frame::Integer(expire_field(
+                                hash,
+                                field,
+                                expires_at,
+                                self.condition,
+                                now,
+                            ))
+                        })
+                        .collect(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_else(|| vec![Frame::Integer(-2); self.fields.len()]);
+        Ok(Frame::Array(replies))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    async fn run(store: &crate::store::ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        let variant = match parts.next().unwrap().as_ref() {
+            b"hexpire" => HExpireVariant::HExpire,
+            b"hpexpire" => HExpireVariant::HPExpire,
+            b"hexpireat" => HExpireVariant::HExpireAt,
+            _ => HExpireVariant::HPExpireAt,
+        };
+        match HExpireCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::HExpire(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected HExpire command"),
+        }
+    }
+
+    fn integers(replies: &[i64]) -> Result<Frame, String> {
+        Ok(Frame::Array(
+            replies.iter().copied().map(Frame::Integer).collect(),
+        ))
+    }
+
+    #[test]
+    fn test_hexpire_command_from_input() {
+        let mut parts = inline_parts("hpexpire key 1500 xx fields 2 a b");
+        parts.next(); // Skip the command
+        match HExpireCommand::from_parts_as(HExpireVariant::HPExpire, parts).unwrap() {
+            CommandWrapper::HExpire(cmd) => {
+                assert_eq!(cmd.key, "key");
+                assert_eq!(cmd.time, 1500);
+                assert!(cmd.condition.xx);
+                assert_eq!(cmd.fields, vec![Bytes::from("a"), Bytes::from("b")]);
+                assert_eq!(cmd.expires_at(1000), Some(2500));
+            }
+            _ => panic!("Expected HExpire command"),
+        }
+
+        let error = |input: &str| {
+            let mut parts = inline_parts(input);
+            parts.next();
+            HExpireCommand::from_parts(parts).err().unwrap()
+        };
+        assert_eq!(
+            error("hexpire key 10 nx xx fields 1 a"),
+            "Mandatory argument FIELDS is missing or not at the right position"
+        );
+        assert_eq!(
+            error("hexpire key 10 fields 0"),
+            "Parameter `numFields` should be greater than 0"
+        );
+        assert_eq!(
+            error("hexpire key 10 fields 2 a"),
+            "The `numfields` parameter must match the number of arguments"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hexpire_command() {
+        let store = Store::new();
+        let hash: Hash = [("a", "1"), ("b", "2"), ("c", "3")].into_iter().collect();
+        store.upsert(&Key::from("hash"), || Value::Hash(hash), |_| ());
+
+        assert_eq!(
+            run(&store, "hexpire hash 100 fields 3 a b missing").await,
+            integers(&[1, 1, -2])
+        );
+        assert_eq!(
+            run(&store, "hexpire hash 200 nx fields 2 a c").await,
+            integers(&[0, 1])
+        );
+        assert_eq!(
+            run(&store, "hpexpire hash 50000 gt fields 2 a b").await,
+            integers(&[0, 0])
+        );
+        assert_eq!(
+            run(&store, "hpexpire hash 50000 lt fields 1 a").await,
+            integers(&[1])
+        );
+        assert_eq!(
+            run(&store, "hexpire missing 100 fields 2 a b").await,
+            integers(&[-2, -2])
+        );
+        assert_eq!(
+            run(&store, "hexpireat hash 281474976711 fields 1 a").await,
+            Err("invalid expire time, must be >= 0 && <= 281474976710655".to_string())
+        );
+
+        // A time in the past deletes the fields, and the key with its last field
+        assert_eq!(
+            run(&store, "hexpire hash 0 fields 2 a b").await,
+            integers(&[2, 2])
+        );
+        assert_eq!(
+            run(&store, "hpexpireat hash 1 fields 1 c").await,
+            integers(&[2])
+        );
+        assert_eq!(store.get(&Key::from("hash")), None);
+
+        store.set(Key::from("string"), Value::Str(Bytes::from("value")));
+        assert!(run(&store, "hexpire string 100 fields 1 a")
+            .await
+            .unwrap_err()
+            .starts_with("WRONGTYPE"));
+    }
+}
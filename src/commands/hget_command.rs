@@ -0,0 +1,101 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands looking up a single hash field only differ in their reply
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HGetVariant {
+    /// `HGET key field` -- The value of the field
+    HGet,
+    /// `HEXISTS key field` -- Whether the field exists
+    HExists,
+    /// `HSTRLEN key field` -- The length of the value of the field
+    HStrLen,
+}
+
+pub struct HGetCommand {
+    pub key: Key,
+    pub field: Bytes,
+    pub variant: HGetVariant,
+}
+
+impl HGetCommand {
+    pub fn from_parts_as(
+        variant: HGetVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let field = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::HGet(Self {
+            key,
+            field,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for HGetCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(HGetVariant::HGet, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let value = store
+            .read(&self.key, |value| {
+                let hash = value.as_hash()?;
+                Ok::<_, String>(hash.get(&self.field).map(Bytes::copy_from_slice))
+            })
+            .transpose()?
+            .flatten();
+
+        Ok(match self.variant {
+            HGetVariant::HGet => value.map_or(Frame::Null, Frame::bulk),
+            HGetVariant::HExists => Frame::Integer(value.is_some() as i64),
+            HGetVariant::HStrLen => Frame::Integer(value.map_or(0, |value| value.len() as i64)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hget_variants() {
+        let store = Store::new();
+        let hash = Value::Hash([("field", "value")].into_iter().collect());
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        let hget = |field: &'static str, variant| HGetCommand {
+            key: Key::from("hash"),
+            field: Bytes::from(field),
+            variant,
+        };
+        let mut client = Client::new();
+        let cases = [
+            ("field", HGetVariant::HGet, Frame::bulk("value")),
+            ("missing", HGetVariant::HGet, Frame::Null),
+            ("field", HGetVariant::HExists, Frame::Integer(1)),
+            ("missing", HGetVariant::HExists, Frame::Integer(0)),
+            ("field", HGetVariant::HStrLen, Frame::Integer(5)),
+            ("missing", HGetVariant::HStrLen, Frame::Integer(0)),
+        ];
+        for (field, variant, expected) in cases {
+            let reply = hget(field, variant).execute(store.clone(), &mut client);
+            assert_eq!(reply.await, Ok(expected));
+        }
+    }
+}
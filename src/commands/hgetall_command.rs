@@ -0,0 +1,114 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands listing a whole hash only differ in what's listed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HGetAllVariant {
+    /// `HGETALL key` -- The fields and their values
+    HGetAll,
+    /// `HKEYS key` -- The fields
+    HKeys,
+    /// `HVALS key` -- The values
+    HVals,
+}
+
+pub struct HGetAllCommand {
+    pub key: Key,
+    pub variant: HGetAllVariant,
+}
+
+impl HGetAllCommand {
+    pub fn from_parts_as(
+        variant: HGetAllVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::HGetAll(Self { key, variant }))
+    }
+}
+
+impl CommandTrait for HGetAllCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(HGetAllVariant::HGetAll, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let bulk = |bytes: &[u8]| Frame::bulk(Bytes::copy_from_slice(bytes));
+        let reply = store
+            .read(&self.key, |value| {
+                let hash = value.as_hash()?;
+                Ok::<_, String>(match self.variant {
+                    HGetAllVariant::HGetAll => Frame::Map(
+                        hash.iter()
+                            .map(|(field, value)| (bulk(field), bulk(value)))
+                            .collect(),
+                    ),
+                    HGetAllVariant::HKeys => {
+                        Frame::Array(hash.iter().map(|(field, _)| bulk(field)).collect())
+                    }
+                    HGetAllVariant::HVals => {
+                        Frame::Array(hash.iter().map(|(_, value)| bulk(value)).collect())
+                    }
+                })
+            })
+            .transpose()?;
+
+        Ok(reply.unwrap_or(match self.variant {
+            HGetAllVariant::HGetAll => Frame::Map(vec![]),
+            HGetAllVariant::HKeys | HGetAllVariant::HVals => Frame::Array(vec![]),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hgetall_variants() {
+        let store = Store::new();
+        let hash = Value::Hash([("a", "1"), ("b", "2")].into_iter().collect());
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        let hgetall = |key: &'static str, variant| HGetAllCommand {
+            key: Key::from(key),
+            variant,
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            hgetall("hash", HGetAllVariant::HGetAll)
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Map(vec![
+                (Frame::bulk("a"), Frame::bulk("1")),
+                (Frame::bulk("b"), Frame::bulk("2"))
+            ]))
+        );
+        assert_eq!(
+            hgetall("hash", HGetAllVariant::HVals)
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Array(vec![Frame::bulk("1"), Frame::bulk("2")]))
+        );
+        assert_eq!(
+            hgetall("missing", HGetAllVariant::HKeys)
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Array(vec![]))
+        );
+    }
+}
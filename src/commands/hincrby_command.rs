@@ -0,0 +1,108 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::hash::Hash,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `HINCRBY key field increment`
+pub struct HIncrByCommand {
+    pub key: Key,
+    pub field: Bytes,
+    pub increment: i64,
+}
+
+impl CommandTrait for HIncrByCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let field = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let increment = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::HIncrBy(Self {
+            key,
+            field,
+            increment,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let limits = store.config.hash_limits();
+        let value = store.upsert(
+            &self.key,
+            || Value::Hash(Hash::new()),
+            |value| {
+                let hash = value.as_hash_mut()?;
+                let current = match hash.get(&self.field) {
+                    Some(current) => {
+                        parse_int(current).map_err(|_| "hash value is not an integer")?
+                    }
+                    None => 0,
+                };
+                let value = current
+                    .checked_add(self.increment)
+                    .ok_or("increment or decrement would overflow")?;
+                hash.insert_keeping_ttl(&self.field, value.to_string().as_bytes(), limits);
+                Ok::<_, String>(value)
+            },
+        )?;
+        Ok(Frame::Integer(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::Store;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hincrby_command() {
+        let store = Store::new();
+        let hash = Value::Hash(
+            [("text", "abc"), ("max", "9223372036854775807")]
+                .into_iter()
+                .collect(),
+        );
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        let hincrby = |field: &'static str, increment| HIncrByCommand {
+            key: Key::from("hash"),
+            field: Bytes::from(field),
+            increment,
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            hincrby("counter", 5)
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(5))
+        );
+        assert_eq!(
+            hincrby("counter", -7)
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(-2))
+        );
+        assert_eq!(
+            hincrby("text", 1).execute(store.clone(), &mut client).await,
+            Err("hash value is not an integer".to_string())
+        );
+        assert_eq!(
+            hincrby("max", 1).execute(store.clone(), &mut client).await,
+            Err("increment or decrement would overflow".to_string())
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_finite_float, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::hash::Hash,
+    utils::format_human_float,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `HINCRBYFLOAT key field increment`
+pub struct HIncrByFloatCommand {
+    pub key: Key,
+    pub field: Bytes,
+    pub increment: f64,
+}
+
+impl CommandTrait for HIncrByFloatCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let field = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let increment =
+            parse_finite_float(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::HIncrByFloat(Self {
+            key,
+            field,
+            increment,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let limits = store.config.hash_limits();
+        let value = store.upsert(
+            &self.key,
+            || Value::Hash(Hash::new()),
+            |value| {
+                let hash = value.as_hash_mut()?;
+                let current = match hash.get(&self.field) {
+                    Some(current) => {
+                        parse_finite_float(current).map_err(|_| "hash value is not a float")?
+                    }
+                    None => 0.0,
+                };
+                let value = current + self.increment;
+                if !value.is_finite() {
+                    return Err("increment would produce NaN or Infinity".to_string());
+                }
+                // Like Redis, stored and replied with without an exponent
+                let value = Bytes::from(format_human_float(value));
+                hash.insert_keeping_ttl(&self.field, &value, limits);
+                Ok(value)
+            },
+        )?;
+        Ok(Frame::bulk(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hincrbyfloat_command() {
+        let store = Store::new();
+        let hash = Value::Hash(
+            [
+                ("value", "10.50"),
+                ("text", "abc"),
+                ("tenth", "0.2"),
+                ("inf", "inf"),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        let hincrbyfloat = |field: &'static str, increment| HIncrByFloatCommand {
+            key: Key::from("hash"),
+            field: Bytes::from(field),
+            increment,
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            hincrbyfloat("value", 0.1)
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::bulk("10.6"))
+        );
+        assert_eq!(
+            hincrbyfloat("new", 5.0e3)
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::bulk("5000"))
+        );
+        assert_eq!(
+            hincrbyfloat("tenth", 0.1)
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::bulk("0.3"))
+        );
+        for field in ["text", "inf"] {
+            assert_eq!(
+                hincrbyfloat(field, 1.0)
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Err("hash value is not a float".to_string())
+            );
+        }
+        assert_eq!(
+            HIncrByFloatCommand::from_parts(inline_parts("hash value 1e400")).err(),
+            Some("value is not a valid float".to_string())
+        );
+        assert_eq!(
+            hincrbyfloat("value", f64::INFINITY)
+                .execute(store.clone(), &mut client)
+                .await,
+            Err("increment would produce NaN or Infinity".to_string())
+        );
+    }
+}
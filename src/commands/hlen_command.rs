@@ -0,0 +1,59 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `HLEN key`
+pub struct HLenCommand {
+    pub key: Key,
+}
+
+impl CommandTrait for HLenCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::HLen(Self { key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store
+            .read(&self.key, |value| value.as_hash().map(|hash| hash.len()))
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hlen_command() {
+        let store = Store::new();
+        let hash = Value::Hash([("a", "1"), ("b", "2")].into_iter().collect());
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        let hlen = |key: &'static str| HLenCommand {
+            key: Key::from(key),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            hlen("hash").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            hlen("missing").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(0))
+        );
+    }
+}
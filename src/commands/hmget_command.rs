@@ -0,0 +1,79 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `HMGET key field [field ...]`
+pub struct HMGetCommand {
+    pub key: Key,
+    pub fields: Vec<Bytes>,
+}
+
+impl CommandTrait for HMGetCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let fields: Vec<Bytes> = parts.collect();
+
+        if fields.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+
+        Ok(CommandWrapper::HMGet(Self { key, fields }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let values = store
+            .read(&self.key, |value| {
+                let hash = value.as_hash()?;
+                Ok::<_, String>(
+                    self.fields
+                        .iter()
+                        .map(|field| {
+                            hash.get(field)
+                                .map_or(Frame::Null, |v| Frame::bulk(Bytes::copy_from_slice(v)))
+                        })
+                        .collect(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_else(|| vec![Frame::Null; self.fields.len()]);
+        Ok(Frame::Array(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hmget_command() {
+        let store = Store::new();
+        let hash = Value::Hash([("a", "1"), ("b", "2")].into_iter().collect());
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        let hmget = |key: &'static str| HMGetCommand {
+            key: Key::from(key),
+            fields: vec![Bytes::from("b"), Bytes::from("c"), Bytes::from("a")],
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            hmget("hash").execute(store.clone(), &mut client).await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("2"),
+                Frame::Null,
+                Frame::bulk("1")
+            ]))
+        );
+        assert_eq!(
+            hmget("missing").execute(store.clone(), &mut client).await,
+            Ok(Frame::Array(vec![Frame::Null; 3]))
+        );
+    }
+}
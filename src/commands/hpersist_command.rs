@@ -0,0 +1,98 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{hexpire_command::parse_fields, CommandParts, CommandTrait, CommandWrapper};
+
+/// `HPERSIST key FIELDS numfields field [field ...]`
+pub struct HPersistCommand {
+    pub key: Key,
+    pub fields: Vec<Bytes>,
+}
+
+impl CommandTrait for HPersistCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let fields = parse_fields(parts)?;
+
+        Ok(CommandWrapper::HPersist(Self { key, fields }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let replies = store
+            .update(&self.key, |value| {
+                let hash = value.as_hash_mut()?;
+                Ok::<_, String>(
+                    self.fields
+                        .iter()
+                        .map(|field| {
+                            let reply = match hash.field_expires_at(field) {
+                                None => -2,
+                                Some(None) => -1,
+                                Some(Some(_)) => {
+                                    hash.set_field_expires_at(field, None);
+                                    1
+                                }
+                            };
+                            Frame::Integer(reply)
+                        })
+                        .collect(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_else(|| vec![Frame::Integer(-2); self.fields.len()]);
+        Ok(Frame::Array(replies))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+        types::hash::Hash,
+        utils::current_epoch_millis,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hpersist_command() {
+        let store = Store::new();
+        let mut hash: Hash = [("a", "1"), ("b", "2")].into_iter().collect();
+        hash.set_field_expires_at(b"a", Some(current_epoch_millis() + 10_000));
+        store.upsert(&Key::from("hash"), || Value::Hash(hash), |_| ());
+
+        let hpersist = |input: &str| match HPersistCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::HPersist(cmd)) => cmd,
+            _ => panic!("Expected HPersist command"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            hpersist("hash fields 3 a b missing")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Array(vec![
+                Frame::Integer(1),
+                Frame::Integer(-1),
+                Frame::Integer(-2)
+            ]))
+        );
+        assert_eq!(
+            store.read(&Key::from("hash"), |value| {
+                value.as_hash().unwrap().next_field_expiry()
+            }),
+            Some(None)
+        );
+        assert_eq!(
+            hpersist("missing fields 1 a")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Array(vec![Frame::Integer(-2)]))
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use bytes::Bytes;
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::{Frame, Protocol},
+    store::Key,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `HRANDFIELD key [count [WITHVALUES]]`
+pub struct HRandFieldCommand {
+    pub key: Key,
+    /// Reply with this many distinct fields, or with repetitions if negative,
+    /// instead of a single field
+    pub count: Option<i64>,
+    pub with_values: bool,
+}
+
+impl CommandTrait for HRandFieldCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let count = parts.next().map(|count| parse_int(&count)).transpose()?;
+        let with_values = match parts.next() {
+            Some(option) if lowercase(&option) == "withvalues" => true,
+            Some(option) => {
+                return Err(ParseError::InvalidCommandOptions(&format!(
+                    "Unsupported option {}",
+                    String::from_utf8_lossy(&option)
+                ))
+                .to_string())
+            }
+            None => false,
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+        // Like Redis, the count is capped so the reply fits in memory
+        if count.is_some_and(|count| count.unsigned_abs() > (i64::MAX / 2) as u64) {
+            return Err("value is out of range".to_string());
+        }
+
+        Ok(CommandWrapper::HRandField(Self {
+            key,
+            count,
+            with_values,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        client: &mut Client,
+    ) -> Result<Frame, String> {
+        let bulk = |bytes: &[u8]| Frame::bulk(Bytes::copy_from_slice(bytes));
+        let picked = store
+            .read(&self.key, |value| {
+                let hash = value.as_hash()?;
+                let mut rng = rand::thread_rng();
+                let pairs: Vec<(&[u8], &[u8])> = match self.count {
+                    None => hash.iter().choose(&mut rng).into_iter().collect(),
+                    Some(count) if count >= 0 => {
+                        let mut pairs = hash.iter().choose_multiple(&mut rng, count as usize);
+                        // `choose_multiple` keeps the order of the hash when it can
+                        rand::seq::SliceRandom::shuffle(&mut pairs[..], &mut rng);
+                        pairs
+                    }
+                    // Fields may repeat, so they're picked one at a time
+                    Some(count) => {
+                        let all: Vec<_> = hash.iter().collect();
+                        (0..count.unsigned_abs())
+                            .map(|_| all[rng.gen_range(0..all.len())])
+                            .collect()
+                    }
+                };
+                Ok::<_, String>(
+                    pairs
+                        .into_iter()
+                        .map(|(field, value)| (bulk(field), bulk(value)))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .transpose()?;
+
+        let Some(count) = self.count else {
+            return Ok(picked
+                .and_then(|mut pairs| pairs.pop())
+                .map_or(Frame::Null, |(field, _)| field));
+        };
+        let pairs = picked.unwrap_or_default();
+        debug_assert!(pairs.len() <= count.unsigned_abs() as usize);
+
+        Ok(Frame::Array(match self.with_values {
+            false => pairs.into_iter().map(|(field, _)| field).collect(),
+            // RESP3 pairs up fields and values, RESP2 replies with them in a row
+            true if client.protocol == Protocol::Resp3 => pairs
+                .into_iter()
+                .map(|(field, value)| Frame::Array(vec![field, value]))
+                .collect(),
+            true => pairs
+                .into_iter()
+                .flat_map(|(field, value)| [field, value])
+                .collect(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn hrandfield(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match HRandFieldCommand::from_parts(parts)? {
+            CommandWrapper::HRandField(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an HRandField command"),
+        }
+    }
+
+    fn len(frame: Frame) -> usize {
+        match frame {
+            Frame::Array(frames) => frames.len(),
+            _ => panic!("Expected an array"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hrandfield_command() {
+        let store = Store::new();
+        let hash = Value::Hash([("a", "1"), ("b", "2"), ("c", "3")].into_iter().collect());
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        let field = hrandfield(&store, "hrandfield hash").await.unwrap();
+        assert!(["a", "b", "c"].iter().any(|f| field == Frame::bulk(*f)));
+
+        assert_eq!(
+            len(hrandfield(&store, "hrandfield hash 2").await.unwrap()),
+            2
+        );
+        assert_eq!(
+            len(hrandfield(&store, "hrandfield hash 5").await.unwrap()),
+            3
+        );
+        assert_eq!(
+            len(hrandfield(&store, "hrandfield hash -5").await.unwrap()),
+            5
+        );
+        assert_eq!(
+            len(hrandfield(&store, "hrandfield hash 2 withvalues")
+                .await
+                .unwrap()),
+            4
+        );
+        assert_eq!(
+            hrandfield(&store, "hrandfield missing").await,
+            Ok(Frame::Null)
+        );
+        assert_eq!(
+            hrandfield(&store, "hrandfield missing 2").await,
+            Ok(Frame::Array(vec![]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hrandfield_distinct_fields() {
+        let store = Store::new();
+        let hash = Value::Hash((0..50).map(|i| (i.to_string(), "value")).collect());
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        let Frame::Array(fields) = hrandfield(&store, "hrandfield hash 30").await.unwrap() else {
+            panic!("Expected an array");
+        };
+        let mut fields: Vec<_> = fields.into_iter().map(|f| format!("{f:?}")).collect();
+        fields.sort();
+        fields.dedup();
+        assert_eq!(fields.len(), 30);
+    }
+}
@@ -0,0 +1,248 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::scan::{cursor_hash, scan},
+    utils::glob_match,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `HSCAN key cursor [MATCH pattern] [COUNT count] [NOVALUES]`
+pub struct HScanCommand {
+    pub key: Key,
+    pub cursor: u64,
+    pub options: ScanOptions,
+}
+
+/// The options shared by the `SCAN` family of commands
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScanOptions {
+    /// Only reply with the elements matching this glob-style pattern
+    pub pattern: Option<Bytes>,
+    /// Roughly how many elements to go through per call
+    pub count: usize,
+    /// Leave out the values of hash fields
+    pub no_values: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            pattern: None,
+            count: 10,
+            no_values: false,
+        }
+    }
+}
+
+impl ScanOptions {
+    /// Parses the options after the cursor, `NOVALUES` only being allowed for hashes.
+    pub fn from_parts(parts: CommandParts, allow_no_values: bool) -> Result<Self, String> {
+        let mut options = ScanOptions::default();
+        let mut parts = parts;
+        while let Some(option) = parts.next() {
+            match lowercase(&option).as_str() {
+                "match" => {
+                    options.pattern =
+                        Some(parts.next().ok_or(ParseError::MissingValue.to_string())?)
+                }
+                "count" => {
+                    let count =
+                        parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+                    options.count = usize::try_from(count)
+                        .ok()
+                        .filter(|count| *count > 0)
+                        .ok_or(
+                            ParseError::InvalidCommandOptionValue("COUNT must be positive")
+                                .to_string(),
+                        )?;
+                }
+                "novalues" if allow_no_values => options.no_values = true,
+                _ => {
+                    return Err(ParseError::InvalidCommandOptions(&format!(
+                        "Unsupported option {}",
+                        String::from_utf8_lossy(&option)
+                    ))
+                    .to_string())
+                }
+            }
+        }
+        Ok(options)
+    }
+
+    pub fn matches(&self, element: &[u8]) -> bool {
+        self.pattern
+            .as_ref()
+            .is_none_or(|pattern| glob_match(pattern, element))
+    }
+}
+
+/// Parses the cursor of the `SCAN` family of commands
+pub fn parse_cursor(part: &[u8]) -> Result<u64, String> {
+    std::str::from_utf8(part)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or("invalid cursor".to_string())
+}
+
+/// The `[cursor, [element ...]]` reply of the `SCAN` family of commands
+pub fn scan_frame(cursor: u64, elements: Vec<Frame>) -> Frame {
+    Frame::Array(vec![
+        Frame::bulk(cursor.to_string()),
+        Frame::Array(elements),
+    ])
+}
+
+impl CommandTrait for HScanCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let cursor = parse_cursor(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let options = ScanOptions::from_parts(parts, true)?;
+
+        Ok(CommandWrapper::HScan(Self {
+            key,
+            cursor,
+            options,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let options = &self.options;
+        let scanned = store
+            .read(&self.key, |value| {
+                let hash = value.as_hash()?;
+                // Like Redis, small hashes are scanned in one go
+                let (cursor, pairs) = if hash.is_listpack() {
+                    (0, hash.iter().collect())
+                } else {
+                    let pairs = hash.iter().map(|pair| (cursor_hash(pair.0), pair));
+                    scan(pairs, self.cursor, options.count)
+                };
+
+                let mut elements = Vec::new();
+                for (field, value) in pairs.into_iter().filter(|(f, _)| options.matches(f)) {
+                    elements.push(Frame::bulk(Bytes::copy_from_slice(field)));
+                    if !options.no_values {
+                        elements.push(Frame::bulk(Bytes::copy_from_slice(value)));
+                    }
+                }
+                Ok::<_, String>((cursor, elements))
+            })
+            .transpose()?;
+
+        let (cursor, elements) = scanned.unwrap_or_default();
+        Ok(scan_frame(cursor, elements))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        resp::Protocol,
+        store::{ConcurrentStore, Store, Value},
+        types::hash::Hash,
+    };
+
+    use super::*;
+
+    async fn hscan(store: &ConcurrentStore, input: &str) -> Result<(u64, Vec<Frame>), String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        let CommandWrapper::HScan(cmd) = HScanCommand::from_parts(parts)? else {
+            panic!("Expected an HScan command");
+        };
+        match cmd.execute(store.clone(), &mut Client::new()).await? {
+            Frame::Array(mut reply) => {
+                let Some(Frame::Array(elements)) = reply.pop() else {
+                    panic!("Expected the elements");
+                };
+                let Some(Frame::Bulk(cursor)) = reply.pop() else {
+                    panic!("Expected the cursor");
+                };
+                Ok((
+                    std::str::from_utf8(&cursor).unwrap().parse().unwrap(),
+                    elements,
+                ))
+            }
+            _ => panic!("Expected an array"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hscan_small_hash_in_one_go() {
+        let store = Store::new();
+        let hash = Value::Hash([("a", "1"), ("b", "2"), ("ab", "3")].into_iter().collect());
+        store.upsert(&Key::from("hash"), || hash, |_| ());
+
+        assert_eq!(
+            hscan(&store, "hscan hash 0 match a* count 1").await,
+            Ok((
+                0,
+                vec![
+                    Frame::bulk("a"),
+                    Frame::bulk("1"),
+                    Frame::bulk("ab"),
+                    Frame::bulk("3")
+                ]
+            ))
+        );
+        assert_eq!(
+            hscan(&store, "hscan hash 0 match b novalues").await,
+            Ok((0, vec![Frame::bulk("b")]))
+        );
+        assert_eq!(hscan(&store, "hscan missing 0").await, Ok((0, vec![])));
+    }
+
+    #[tokio::test]
+    async fn test_hscan_big_hash_with_a_cursor() {
+        let store = Store::new();
+        let mut hash = Hash::new();
+        for i in 0..1000 {
+            let field = format!("field:{i}");
+            hash.insert(field.as_bytes(), b"value", store.config.hash_limits());
+        }
+        store.upsert(&Key::from("hash"), || Value::Hash(hash), |_| ());
+
+        let mut cursor = 0;
+        let mut fields = Vec::new();
+        loop {
+            let (next_cursor, elements) = hscan(&store, &format!("hscan hash {cursor} novalues"))
+                .await
+                .unwrap();
+            assert!(elements.len() < 1000);
+            fields.extend(elements);
+            cursor = next_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
+        assert_eq!(fields.len(), 1000);
+    }
+
+    #[test]
+    fn test_hscan_invalid_arguments() {
+        let parse = |input: &str| {
+            let mut parts = inline_parts(input);
+            parts.next(); // Skip the command
+            HScanCommand::from_parts(parts).err()
+        };
+        assert_eq!(parse("hscan hash x").unwrap(), "invalid cursor");
+        assert_eq!(
+            parse("hscan hash 0 count 0").unwrap(),
+            ParseError::InvalidCommandOptionValue("COUNT must be positive").to_string()
+        );
+        assert_eq!(
+            Frame::error(parse("hscan hash 0 count -1").unwrap()).to_bytes(Protocol::Resp2),
+            b"-ERR Invalid command option value: COUNT must be positive\r\n"
+        );
+    }
+}
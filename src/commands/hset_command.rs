@@ -0,0 +1,182 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+    types::hash::Hash,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands setting hash fields only differ in their reply, and whether
+/// existing fields are overwritten
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HSetVariant {
+    /// `HSET key field value [field value ...]` -- Replies with the number of new fields
+    HSet,
+    /// `HMSET key field value [field value ...]` -- Replies with `OK`
+    HMSet,
+    /// `HSETNX key field value` -- Only sets a new field
+    HSetNx,
+}
+
+pub struct HSetCommand {
+    pub key: Key,
+    pub pairs: Vec<(Bytes, Bytes)>,
+    pub variant: HSetVariant,
+}
+
+impl HSetCommand {
+    pub fn from_parts_as(
+        variant: HSetVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        let mut pairs = Vec::with_capacity(parts.len() / 2);
+        while let Some(field) = parts.next() {
+            let value = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+            pairs.push((field, value));
+        }
+        if pairs.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+        if variant == HSetVariant::HSetNx && pairs.len() > 1 {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::HSet(Self {
+            key,
+            pairs,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for HSetCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(HSetVariant::HSet, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let limits = store.config.hash_limits();
+        let added = store.upsert(
+            &self.key,
+            || Value::Hash(Hash::new()),
+            |value| {
+                let hash = value.as_hash_mut()?;
+                let mut added = 0;
+                for (field, value) in &self.pairs {
+                    if self.variant == HSetVariant::HSetNx && hash.contains(field) {
+                        continue;
+                    }
+                    added += hash.insert(field, value, limits) as i64;
+                }
+                Ok::<_, String>(added)
+            },
+        )?;
+
+        Ok(match self.variant {
+            HSetVariant::HSet | HSetVariant::HSetNx => Frame::Integer(added),
+            HSetVariant::HMSet => Frame::ok(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, WRONGTYPE},
+    };
+
+    use super::*;
+
+    async fn run(
+        store: &ConcurrentStore,
+        variant: HSetVariant,
+        input: &str,
+    ) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match HSetCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::HSet(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an HSet command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hset_variants() {
+        let store = Store::new();
+        assert_eq!(
+            run(&store, HSetVariant::HSet, "hset hash a 1 b 2").await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            run(&store, HSetVariant::HSet, "hset hash a 3 c 4").await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            run(&store, HSetVariant::HSetNx, "hsetnx hash a 5").await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(
+            run(&store, HSetVariant::HMSet, "hmset hash d 6").await,
+            Ok(Frame::ok())
+        );
+
+        let expected: Hash = [("a", "3"), ("b", "2"), ("c", "4"), ("d", "6")]
+            .into_iter()
+            .collect();
+        assert_eq!(store.get(&Key::from("hash")), Some(Value::Hash(expected)));
+    }
+
+    #[tokio::test]
+    async fn test_hset_invalid_arguments() {
+        let store = Store::new();
+        assert_eq!(
+            run(&store, HSetVariant::HSet, "hset hash a 1 b").await,
+            Err(ParseError::MissingValue.to_string())
+        );
+        assert_eq!(
+            run(&store, HSetVariant::HSetNx, "hsetnx hash a 1 b 2").await,
+            Err(ParseError::TooManyArguments.to_string())
+        );
+
+        store.set(Key::from("string"), Value::Str(Bytes::from("value")));
+        assert_eq!(
+            run(&store, HSetVariant::HSet, "hset string a 1").await,
+            Err(WRONGTYPE.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hset_converts_big_hashes() {
+        let store = Store::new();
+        let long_value = "x".repeat(store.config.hash_max_listpack_value + 1);
+        run(&store, HSetVariant::HSet, "hset hash a 1")
+            .await
+            .unwrap();
+        let encoding = |store: &ConcurrentStore| {
+            store.read(&Key::from("hash"), |value| {
+                value.as_hash().unwrap().encoding()
+            })
+        };
+        assert_eq!(encoding(&store), Some("listpack"));
+
+        run(
+            &store,
+            HSetVariant::HSet,
+            &format!("hset hash b {long_value}"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(encoding(&store), Some("hashtable"));
+    }
+}
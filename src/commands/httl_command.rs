@@ -0,0 +1,144 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client, parser::utils::ParseError, resp::Frame, store::Key, utils::current_epoch_millis,
+};
+
+use super::{hexpire_command::parse_fields, CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands reporting when hash fields expire only differ in the format of the reply
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HTtlVariant {
+    /// `HTTL key FIELDS numfields field [field ...]` -- Seconds left to live
+    HTtl,
+    /// `HPTTL key FIELDS numfields field [field ...]` -- Milliseconds left to live
+    HPTtl,
+    /// `HEXPIRETIME key FIELDS numfields field [field ...]` -- UNIX time in seconds at which the fields expire
+    HExpireTime,
+    /// `HPEXPIRETIME key FIELDS numfields field [field ...]` -- UNIX time in milliseconds at which the fields expire
+    HPExpireTime,
+}
+
+pub struct HTtlCommand {
+    pub key: Key,
+    pub variant: HTtlVariant,
+    pub fields: Vec<Bytes>,
+}
+
+impl HTtlCommand {
+    pub fn from_parts_as(
+        variant: HTtlVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let fields = parse_fields(parts)?;
+
+        Ok(CommandWrapper::HTtl(Self {
+            key,
+            variant,
+            fields,
+        }))
+    }
+
+    /// Replies for a field like `TTL` does for a key
+    fn reply(&self, expires_at: Option<Option<u128>>, now: u128) -> i64 {
+        let expires_at = match expires_at {
+            None => return -2,
+            Some(None) => return -1,
+            Some(Some(expires_at)) => expires_at,
+        };
+
+        let ttl = expires_at.saturating_sub(now);
+        let reply = match self.variant {
+            HTtlVariant::HTtl => (ttl + 500) / 1000,
+            HTtlVariant::HPTtl => ttl,
+            HTtlVariant::HExpireTime => expires_at / 1000,
+            HTtlVariant::HPExpireTime => expires_at,
+        };
+        reply as i64
+    }
+}
+
+impl CommandTrait for HTtlCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(HTtlVariant::HTtl, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let now = current_epoch_millis();
+        let replies = store
+            .read(&self.key, |value| {
+                let hash = value.as_hash()?;
+                Ok::<_, String>(
+                    self.fields
+                        .iter()
+                        .map(|field| Frame::Integer(self.reply(hash.field_expires_at(field), now)))
+                        .collect(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_else(|| vec![Frame::Integer(-2); self.fields.len()]);
+        Ok(Frame::Array(replies))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+        types::hash::Hash,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_httl_command() {
+        let store = Store::new();
+        let in_ten_seconds = current_epoch_millis() + 10_000;
+        let mut hash: Hash = [("a", "1"), ("b", "2")].into_iter().collect();
+        hash.set_field_expires_at(b"a", Some(in_ten_seconds));
+        store.upsert(&Key::from("hash"), || Value::Hash(hash), |_| ());
+
+        let httl = |variant, key: &str| {
+            let parts = inline_parts(&format!("{key} fields 3 a b missing"));
+            match HTtlCommand::from_parts_as(variant, parts).unwrap() {
+                CommandWrapper::HTtl(cmd) => cmd,
+                _ => panic!("Expected HTtl command"),
+            }
+        };
+        let integers = |frame: Frame| match frame {
+            Frame::Array(frames) => frames
+                .into_iter()
+                .map(|frame| match frame {
+                    Frame::Integer(n) => n,
+                    _ => panic!("Expected an integer"),
+                })
+                .collect::<Vec<_>>(),
+            _ => panic!("Expected an array"),
+        };
+
+        let mut client = Client::new();
+        let reply = httl(HTtlVariant::HTtl, "hash").execute(store.clone(), &mut client);
+        assert_eq!(integers(reply.await.unwrap()), [10, -1, -2]);
+        let reply = httl(HTtlVariant::HPTtl, "hash").execute(store.clone(), &mut client);
+        let reply = integers(reply.await.unwrap());
+        assert!(reply[0] > 9_000 && reply[0] <= 10_000);
+        let reply = httl(HTtlVariant::HExpireTime, "hash").execute(store.clone(), &mut client);
+        assert_eq!(
+            integers(reply.await.unwrap()),
+            [(in_ten_seconds / 1000) as i64, -1, -2]
+        );
+        let reply = httl(HTtlVariant::HPExpireTime, "hash").execute(store.clone(), &mut client);
+        assert_eq!(
+            integers(reply.await.unwrap()),
+            [in_ten_seconds as i64, -1, -2]
+        );
+        let reply = httl(HTtlVariant::HTtl, "missing").execute(store.clone(), &mut client);
+        assert_eq!(integers(reply.await.unwrap()), [-2, -2, -2]);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `INCR`, `DECR`, `INCRBY` and `DECRBY` all add an integer to a string
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum IncrByVariant {
+    /// `INCR key`
+    Incr,
+    /// `DECR key`
+    Decr,
+    /// `INCRBY key increment`
+    IncrBy,
+    /// `DECRBY key decrement`
+    DecrBy,
+}
+
+pub struct IncrByCommand {
+    pub key: Key,
+    /// Negative for decrements
+    pub increment: i64,
+}
+
+impl IncrByCommand {
+    pub fn from_parts_as(
+        variant: IncrByVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let mut by = || parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?);
+        let increment = match variant {
+            IncrByVariant::Incr => 1,
+            IncrByVariant::Decr => -1,
+            IncrByVariant::IncrBy => by()?,
+            IncrByVariant::DecrBy => by()?.checked_neg().ok_or("decrement would overflow")?,
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::IncrBy(Self { key, increment }))
+    }
+}
+
+impl CommandTrait for IncrByCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(IncrByVariant::IncrBy, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        // Like Redis, the time to live of the key is kept
+        let value = store.upsert(
+            &self.key,
+            || Value::Int(0),
+            |value| {
+                let sum = value
+                    .as_int()?
+                    .checked_add(self.increment)
+                    .ok_or("increment or decrement would overflow")?;
+                *value = Value::Int(sum);
+                Ok::<_, String>(sum)
+            },
+        )?;
+        Ok(Frame::Integer(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_incrby_command() {
+        let store = Store::new();
+        store.set(Key::from("text"), Value::Str(Bytes::from("01")));
+        store.set(Key::from("max"), Value::Int(i64::MAX));
+
+        let incrby =
+            |variant, input: &str| match IncrByCommand::from_parts_as(variant, inline_parts(input))
+            {
+                Ok(CommandWrapper::IncrBy(cmd)) => Ok(cmd),
+                Ok(_) => panic!("Expected an IncrBy command"),
+                Err(e) => Err(e),
+            };
+        let mut client = Client::new();
+        for (variant, input, expected) in [
+            (IncrByVariant::Incr, "counter", 1),
+            (IncrByVariant::IncrBy, "counter 10", 11),
+            (IncrByVariant::DecrBy, "counter 20", -9),
+            (IncrByVariant::Decr, "counter", -10),
+        ] {
+            let cmd = incrby(variant, input).unwrap();
+            assert_eq!(
+                cmd.execute(store.clone(), &mut client).await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+        assert_eq!(store.get(&Key::from("counter")), Some(Value::Int(-10)));
+
+        for (variant, input, expected) in [
+            (
+                IncrByVariant::Incr,
+                "text",
+                "value is not an integer or out of range",
+            ),
+            (
+                IncrByVariant::Incr,
+                "max",
+                "increment or decrement would overflow",
+            ),
+        ] {
+            let cmd = incrby(variant, input).unwrap();
+            assert_eq!(
+                cmd.execute(store.clone(), &mut client).await,
+                Err(expected.to_string())
+            );
+        }
+        assert_eq!(
+            incrby(IncrByVariant::DecrBy, "counter -9223372036854775808").err(),
+            Some("decrement would overflow".to_string())
+        );
+        assert_eq!(
+            incrby(IncrByVariant::IncrBy, "counter 1.5").err(),
+            Some("value is not an integer or out of range".to_string())
+        );
+    }
+}
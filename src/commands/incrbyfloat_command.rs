@@ -0,0 +1,119 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_finite_float, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    utils::format_human_float,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `INCRBYFLOAT key increment`
+pub struct IncrByFloatCommand {
+    pub key: Key,
+    pub increment: f64,
+}
+
+impl CommandTrait for IncrByFloatCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let increment =
+            parse_finite_float(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::IncrByFloat(Self { key, increment }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let value = store.upsert(
+            &self.key,
+            || Value::Int(0),
+            |value| {
+                let current = match value {
+                    Value::Int(int) => *int as f64,
+                    _ => parse_finite_float(&value.as_str()?)?,
+                };
+                let sum = current + self.increment;
+                if !sum.is_finite() {
+                    return Err("increment would produce NaN or Infinity".to_string());
+                }
+                // Like Redis, stored as a string even when the sum is a whole number
+                let sum = Bytes::from(format_human_float(sum));
+                *value = Value::Str(sum.clone());
+                Ok(sum)
+            },
+        )?;
+        Ok(Frame::bulk(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_incrbyfloat_command() {
+        let store = Store::new();
+        store.set(Key::from("value"), Value::Str(Bytes::from("10.50")));
+        store.set(Key::from("int"), Value::Int(3));
+        store.set(Key::from("text"), Value::Str(Bytes::from("abc")));
+        store.set(Key::from("tenth"), Value::Str(Bytes::from("0.2")));
+        store.set(Key::from("inf"), Value::Str(Bytes::from("inf")));
+        store.set(Key::from("huge"), Value::Str(Bytes::from("1e400")));
+
+        let incrbyfloat = |key: &'static str, increment| IncrByFloatCommand {
+            key: Key::from(key),
+            increment,
+        };
+        let mut client = Client::new();
+        for (key, increment, expected) in [
+            ("value", 0.1, "10.6"),
+            ("int", -0.5, "2.5"),
+            ("new", 5.0e3, "5000"),
+            ("tenth", 0.1, "0.3"),
+        ] {
+            assert_eq!(
+                incrbyfloat(key, increment)
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Ok(Frame::bulk(expected))
+            );
+        }
+        assert_eq!(
+            store.get(&Key::from("new")),
+            Some(Value::Str(Bytes::from("5000")))
+        );
+
+        for key in ["text", "inf", "huge"] {
+            assert_eq!(
+                incrbyfloat(key, 1.0)
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Err("value is not a valid float".to_string())
+            );
+        }
+        for input in ["k inf", "k -inf", "k 1e400"] {
+            assert_eq!(
+                IncrByFloatCommand::from_parts(inline_parts(input)).err(),
+                Some("value is not a valid float".to_string())
+            );
+        }
+        assert_eq!(
+            incrbyfloat("value", f64::INFINITY)
+                .execute(store.clone(), &mut client)
+                .await,
+            Err("increment would produce NaN or Infinity".to_string())
+        );
+    }
+}
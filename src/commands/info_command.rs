@@ -0,0 +1,158 @@
+use std::fmt::Write;
+
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::lowercase, resp::Frame, store::stats::Stats};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The sections of `INFO`, in the order they are reported
+const SECTIONS: [&str; 5] = ["server", "clients", "memory", "stats", "keyspace"];
+
+pub struct InfoCommand {
+    /// The requested sections, all of them if empty
+    pub sections: Vec<String>,
+}
+
+impl CommandTrait for InfoCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        let sections = parts
+            .map(|section| lowercase(&section))
+            .filter(|section| !matches!(section.as_str(), "all" | "default" | "everything"))
+            .collect();
+
+        Ok(CommandWrapper::Info(Self { sections }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let mut info = String::new();
+
+        for section in SECTIONS {
+            if !self.sections.is_empty() && !self.sections.iter().any(|s| s == section) {
+                continue;
+            }
+            if !info.is_empty() {
+                info.push_str("\r\n");
+            }
+
+            // Writing to a String can't fail
+            match section {
+                "server" => {
+                    let _ = write!(
+                        info,
+                        "# Server\r\n\
+                         kiwi_version:{}\r\n\
+                         process_id:{}\r\n\
+                         tcp_port:{}\r\n\
+                         hz:{}\r\n",
+                        env!("CARGO_PKG_VERSION"),
+                        std::process::id(),
+                        store.config.port,
+                        store.config.hz,
+                    );
+                }
+                "clients" => {
+                    let _ = write!(
+                        info,
+                        "# Clients\r\n\
+                         blocked_clients:{}\r\n",
+                        store.blocked_clients.len(),
+                    );
+                }
+                "memory" => {
+                    let _ = write!(
+                        info,
+                        "# Memory\r\n\
+                         used_memory:{}\r\n\
+                         maxmemory:{}\r\n\
+                         maxmemory_policy:{}\r\n",
+                        store.used_memory(),
+                        store.config.maxmemory,
+                        store.config.maxmemory_policy,
+                    );
+                }
+                "stats" => {
+                    let stats = &store.stats;
+                    let _ = write!(
+                        info,
+                        "# Stats\r\n\
+                         expired_keys:{}\r\n\
+                         expired_subkeys:{}\r\n\
+                         evicted_keys:{}\r\n\
+                         expired_time_cap_reached_count:{}\r\n\
+                         expire_cycle_cpu_milliseconds:{}\r\n\
+                         expire_cycles:{}\r\n\
+                         expire_cycle_last_time_us:{}\r\n",
+                        Stats::get(&stats.expired_keys),
+                        Stats::get(&stats.expired_fields),
+                        Stats::get(&stats.evicted_keys),
+                        Stats::get(&stats.expire_cycle_time_cap_reached),
+                        Stats::get(&stats.expire_cycle_time_used_us) / 1000,
+                        Stats::get(&stats.expire_cycles),
+                        Stats::get(&stats.expire_cycle_last_time_us),
+                    );
+                }
+                "keyspace" => {
+                    info.push_str("# Keyspace\r\n");
+                    let keys = store.map.len();
+                    if keys > 0 {
+                        let _ = write!(
+                            info,
+                            "db0:keys={keys},expires={},avg_ttl=0\r\n",
+                            store.volatile_len()
+                        );
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(Frame::Verbatim {
+            format: *b"txt",
+            text: Bytes::from(info),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[test]
+    fn test_info_command_from_input() {
+        let input = "info STATS all keyspace".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match InfoCommand::from_parts(parts).unwrap() {
+            CommandWrapper::Info(cmd) => {
+                assert_eq!(cmd.sections, vec!["stats", "keyspace"]);
+            }
+            _ => panic!("Expected an Info command"),
+        };
+    }
+
+    #[tokio::test]
+    async fn test_info_command_sections() {
+        let command = InfoCommand {
+            sections: vec!["stats".to_string()],
+        };
+        let frame = command
+            .execute(Store::new(), &mut Client::new())
+            .await
+            .unwrap();
+        match frame {
+            Frame::Verbatim { text, .. } => {
+                assert!(text.starts_with(b"# Stats\r\n"));
+                assert!(text.windows(15).any(|w| w == b"expired_keys:0\r"));
+                assert!(!text.windows(8).any(|w| w == b"# Server"));
+            }
+            _ => panic!("Expected a verbatim string"),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LCS key1 key2 [LEN] [IDX] [MINMATCHLEN min-match-len] [WITHMATCHLEN]`
+pub struct LcsCommand {
+    pub keys: (Key, Key),
+    /// Reply with the length of the longest common subsequence, rather than itself
+    pub len: bool,
+    /// Reply with where the matches are in either string
+    pub idx: bool,
+    /// Only the matches at least this long are listed with `IDX`
+    pub min_match_len: usize,
+    /// List the length of each match with `IDX`
+    pub with_match_len: bool,
+}
+
+impl CommandTrait for LcsCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let a = parts.next().ok_or(ParseError::MissingKeys.to_string())?;
+        let b = parts.next().ok_or(ParseError::MissingKeys.to_string())?;
+        let mut cmd = Self {
+            keys: (a, b),
+            len: false,
+            idx: false,
+            min_match_len: 0,
+            with_match_len: false,
+        };
+
+        while let Some(option) = parts.next() {
+            match lowercase(&option).as_str() {
+                "len" => cmd.len = true,
+                "idx" => cmd.idx = true,
+                "minmatchlen" => {
+                    let len = parse_int(&parts.next().ok_or("syntax error")?)?;
+                    cmd.min_match_len = len.max(0) as usize;
+                }
+                "withmatchlen" => cmd.with_match_len = true,
+                _ => return Err("syntax error".to_string()),
+            }
+        }
+        if cmd.len && cmd.idx {
+            return Err(
+                "If you want both the length and indexes, please just use IDX.".to_string(),
+            );
+        }
+
+        Ok(CommandWrapper::Lcs(cmd))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let (a, b) = store.atomically(|| {
+            let read = |key: &Key| {
+                let bytes = store.read(key, |value| {
+                    value
+                        .as_str()
+                        .map_err(|_| "The specified keys must contain string values".to_string())
+                });
+                bytes.transpose().map(Option::unwrap_or_default)
+            };
+            Ok::<_, String>((read(&self.keys.0)?, read(&self.keys.1)?))
+        })?;
+
+        // Like Redis, the table can't take more memory than a string could
+        let cells = (a.len() + 1).checked_mul(b.len() + 1);
+        let max_cells = store.config.proto_max_bulk_len / std::mem::size_of::<u32>();
+        if cells.is_none_or(|cells| cells > max_cells) {
+            return Err(
+                "Insufficient memory, transient memory for LCS exceeds proto-max-bulk-len"
+                    .to_string(),
+            );
+        }
+
+        let lcs = Lcs::new(&a, &b);
+        if self.len {
+            return Ok(Frame::Integer(lcs.len() as i64));
+        }
+        if !self.idx {
+            return Ok(Frame::bulk(lcs.subsequence()));
+        }
+
+        let matches = lcs
+            .matches()
+            .into_iter()
+            .filter(|m| m.len >= self.min_match_len)
+            .map(|m| {
+                let range = |start: usize| {
+                    Frame::Array(vec![
+                        Frame::Integer(start as i64),
+                        Frame::Integer((start + m.len - 1) as i64),
+                    ])
+                };
+                let mut frame = vec![range(m.a), range(m.b)];
+                if self.with_match_len {
+                    frame.push(Frame::Integer(m.len as i64));
+                }
+                Frame::Array(frame)
+            })
+            .collect();
+        Ok(Frame::Map(vec![
+            (Frame::bulk("matches"), Frame::Array(matches)),
+            (Frame::bulk("len"), Frame::Integer(lcs.len() as i64)),
+        ]))
+    }
+}
+
+/// A run of bytes common to both strings, starting at `a` in one and `b` in the other
+struct Match {
+    a: usize,
+    b: usize,
+    len: usize,
+}
+
+/// The longest common subsequence of two strings, by dynamic programming
+struct Lcs<'a> {
+    a: &'a [u8],
+    b: &'a [u8],
+    /// The length of the longest common subsequence of every pair of prefixes,
+    /// `a[..i]` and `b[..j]` at `i * (b.len() + 1) + j`
+    table: Vec<u32>,
+}
+
+impl<'a> Lcs<'a> {
+    fn new(a: &'a [u8], b: &'a [u8]) -> Self {
+        let width = b.len() + 1;
+        let mut table = vec![0u32; (a.len() + 1) * width];
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                table[i * width + j] = if a[i - 1] == b[j - 1] {
+                    table[(i - 1) * width + j - 1] + 1
+                } else {
+                    table[(i - 1) * width + j].max(table[i * width + j - 1])
+                };
+            }
+        }
+        Self { a, b, table }
+    }
+
+    fn at(&self, i: usize, j: usize) -> usize {
+        self.table[i * (self.b.len() + 1) + j] as usize
+    }
+
+    fn len(&self) -> usize {
+        self.at(self.a.len(), self.b.len())
+    }
+
+    /// Walks the table back from the end of both strings, calling `f` with the
+    /// position in either string of every byte of the subsequence, last first.
+    fn walk(&self, mut f: impl FnMut(usize, usize)) {
+        let (mut i, mut j) = (self.a.len(), self.b.len());
+        while i > 0 && j > 0 {
+            if self.a[i - 1] == self.b[j - 1] {
+                f(i - 1, j - 1);
+                i -= 1;
+                j -= 1;
+            } else if self.at(i - 1, j) > self.at(i, j - 1) {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+    }
+
+    fn subsequence(&self) -> Bytes {
+        let mut subsequence = Vec::with_capacity(self.len());
+        self.walk(|i, _| subsequence.push(self.a[i]));
+        subsequence.reverse();
+        Bytes::from(subsequence)
+    }
+
+    /// The runs of the subsequence that are contiguous in both strings, last first
+    /// like Redis lists them
+    fn matches(&self) -> Vec<Match> {
+        let mut matches: Vec<Match> = Vec::new();
+        self.walk(|i, j| match matches.last_mut() {
+            Some(last) if last.a == i + 1 && last.b == j + 1 => {
+                last.a = i;
+                last.b = j;
+                last.len += 1;
+            }
+            _ => matches.push(Match { a: i, b: j, len: 1 }),
+        });
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn lcs(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match LcsCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::Lcs(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an Lcs command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lcs_command() {
+        let store = Store::new();
+        store.set(Key::from("key1"), Value::Str(Bytes::from("ohmytext")));
+        store.set(Key::from("key2"), Value::Str(Bytes::from("mynewtext")));
+
+        assert_eq!(lcs(&store, "key1 key2").await, Ok(Frame::bulk("mytext")));
+        assert_eq!(lcs(&store, "key1 key2 len").await, Ok(Frame::Integer(6)));
+        assert_eq!(lcs(&store, "key1 missing").await, Ok(Frame::bulk("")));
+
+        let range = |start, end| Frame::Array(vec![Frame::Integer(start), Frame::Integer(end)]);
+        assert_eq!(
+            lcs(&store, "key1 key2 idx minmatchlen 4 withmatchlen").await,
+            Ok(Frame::Map(vec![
+                (
+                    Frame::bulk("matches"),
+                    Frame::Array(vec![Frame::Array(vec![
+                        range(4, 7),
+                        range(5, 8),
+                        Frame::Integer(4)
+                    ])])
+                ),
+                (Frame::bulk("len"), Frame::Integer(6)),
+            ]))
+        );
+        assert_eq!(
+            lcs(&store, "key1 key2 idx").await,
+            Ok(Frame::Map(vec![
+                (
+                    Frame::bulk("matches"),
+                    Frame::Array(vec![
+                        Frame::Array(vec![range(4, 7), range(5, 8)]),
+                        Frame::Array(vec![range(2, 3), range(0, 1)]),
+                    ])
+                ),
+                (Frame::bulk("len"), Frame::Integer(6)),
+            ]))
+        );
+
+        assert_eq!(
+            lcs(&store, "key1 key2 len idx").await,
+            Err("If you want both the length and indexes, please just use IDX.".to_string())
+        );
+        store.upsert(
+            &Key::from("list"),
+            || Value::List(["a"].into_iter().collect()),
+            |_| (),
+        );
+        assert_eq!(
+            lcs(&store, "key1 list").await,
+            Err("The specified keys must contain string values".to_string())
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    utils::resolve_index,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LINDEX key index`
+pub struct LIndexCommand {
+    pub key: Key,
+    pub index: i64,
+}
+
+impl CommandTrait for LIndexCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let index = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::LIndex(Self { key, index }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let element = store
+            .read(&self.key, |value| {
+                let list = value.as_list()?;
+                Ok::<_, String>(
+                    resolve_index(self.index, list.len())
+                        .and_then(|index| list.get(index))
+                        .map(Bytes::copy_from_slice),
+                )
+            })
+            .transpose()?
+            .flatten();
+        Ok(element.map_or(Frame::Null, Frame::bulk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lindex_command() {
+        let store = Store::new();
+        let list = Value::List(["a", "b", "c"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        let lindex = |key: &str, index| LIndexCommand {
+            key: Key::from(key.to_string()),
+            index,
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            lindex("list", 0).execute(store.clone(), &mut client).await,
+            Ok(Frame::bulk("a"))
+        );
+        assert_eq!(
+            lindex("list", -1).execute(store.clone(), &mut client).await,
+            Ok(Frame::bulk("c"))
+        );
+        assert_eq!(
+            lindex("list", 3).execute(store.clone(), &mut client).await,
+            Ok(Frame::Null)
+        );
+        assert_eq!(
+            lindex("missing", 0)
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Null)
+        );
+    }
+}
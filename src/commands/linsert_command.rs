@@ -0,0 +1,114 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LINSERT key <BEFORE | AFTER> pivot element`
+pub struct LInsertCommand {
+    pub key: Key,
+    /// Insert after the pivot instead of before it
+    pub after: bool,
+    pub pivot: Bytes,
+    pub element: Bytes,
+}
+
+impl CommandTrait for LInsertCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let after = match parts.next().map(|part| lowercase(&part)).as_deref() {
+            Some("before") => false,
+            Some("after") => true,
+            _ => {
+                return Err(
+                    ParseError::InvalidCommandOptions("Expected BEFORE or AFTER").to_string(),
+                )
+            }
+        };
+        let pivot = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let element = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::LInsert(Self {
+            key,
+            after,
+            pivot,
+            element,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store
+            .update(&self.key, |value| {
+                let list = value.as_list_mut()?;
+                let Some(pivot) = list.iter().position(|element| element == self.pivot) else {
+                    return Ok(-1);
+                };
+                list.insert(pivot + self.after as usize, &self.element);
+                Ok::<_, String>(list.len() as i64)
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    async fn linsert(store: &crate::store::ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match LInsertCommand::from_parts(parts)? {
+            CommandWrapper::LInsert(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an LInsert command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_linsert_command() {
+        let store = Store::new();
+        let list = Value::List(["a", "c"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        assert_eq!(
+            linsert(&store, "linsert list BEFORE c b").await,
+            Ok(Frame::Integer(3))
+        );
+        assert_eq!(
+            linsert(&store, "linsert list after c d").await,
+            Ok(Frame::Integer(4))
+        );
+        assert_eq!(
+            linsert(&store, "linsert list after x y").await,
+            Ok(Frame::Integer(-1))
+        );
+        assert_eq!(
+            linsert(&store, "linsert missing after a b").await,
+            Ok(Frame::Integer(0))
+        );
+        assert!(linsert(&store, "linsert list around a b").await.is_err());
+        assert_eq!(
+            store.get(&Key::from("list")),
+            Some(Value::List(["a", "b", "c", "d"].into_iter().collect()))
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LLEN key`
+pub struct LLenCommand {
+    pub key: Key,
+}
+
+impl CommandTrait for LLenCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::LLen(Self { key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store
+            .read(&self.key, |value| value.as_list().map(|list| list.len()))
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::store::{Store, Value, WRONGTYPE};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_llen_command() {
+        let store = Store::new();
+        let list = Value::List(["a", "b"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+        store.set(Key::from("string"), Value::Str(Bytes::from("value")));
+
+        let llen = |key: &'static str| LLenCommand {
+            key: Key::from(key),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            llen("list").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            llen("missing").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(
+            llen("string").execute(store.clone(), &mut client).await,
+            Err(WRONGTYPE.to_string())
+        );
+    }
+}
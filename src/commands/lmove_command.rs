@@ -0,0 +1,170 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_list_end, ParseError},
+    resp::Frame,
+    store::{Key, Store, Value},
+    types::list::{List, ListEnd},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `RPOPLPUSH` is `LMOVE` with the ends fixed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LMoveVariant {
+    /// `LMOVE source destination <LEFT | RIGHT> <LEFT | RIGHT>`
+    LMove,
+    /// `RPOPLPUSH source destination`
+    RPopLPush,
+}
+
+pub struct LMoveCommand {
+    pub source: Key,
+    pub destination: Key,
+    /// The end of the source to pop from
+    pub from: ListEnd,
+    /// The end of the destination to push to
+    pub to: ListEnd,
+}
+
+impl LMoveCommand {
+    pub fn from_parts_as(
+        variant: LMoveVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let source = parts.next().ok_or(ParseError::MissingKeys.to_string())?;
+        let destination = parts.next().ok_or(ParseError::MissingKeys.to_string())?;
+        let (from, to) = match variant {
+            LMoveVariant::LMove => {
+                let mut end = || -> Result<ListEnd, String> {
+                    parse_list_end(&parts.next().ok_or(ParseError::MissingValue.to_string())?)
+                };
+                (end()?, end()?)
+            }
+            LMoveVariant::RPopLPush => (ListEnd::Right, ListEnd::Left),
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::LMove(Self {
+            source,
+            destination,
+            from,
+            to,
+        }))
+    }
+}
+
+impl CommandTrait for LMoveCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(LMoveVariant::LMove, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let element = lmove(&store, &self.source, &self.destination, self.from, self.to)?;
+        Ok(element.map_or(Frame::Null, Frame::bulk))
+    }
+}
+
+/// Atomically pops an element from one end of `source` and pushes it to `destination`.
+///
+/// Returns `None` if `source` does not exist. Nothing is popped if either key
+/// holds something other than a list.
+pub fn lmove(
+    store: &Store,
+    source: &Key,
+    destination: &Key,
+    from: ListEnd,
+    to: ListEnd,
+) -> Result<Option<Bytes>, String> {
+    store.atomically(|| {
+        if store
+            .read(source, |value| value.as_list().map(|_| ()))
+            .transpose()?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        store
+            .read(destination, |value| value.as_list().map(|_| ()))
+            .transpose()?;
+
+        let element = store
+            .update(source, |value| value.as_list_mut().ok()?.pop(from))
+            .flatten();
+        if let Some(element) = &element {
+            store.upsert(
+                destination,
+                || Value::List(List::new()),
+                |value| value.as_list_mut().map(|list| list.push(to, element)),
+            )?;
+        }
+        Ok(element)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, WRONGTYPE},
+    };
+
+    use super::*;
+
+    async fn run(
+        store: &ConcurrentStore,
+        variant: LMoveVariant,
+        input: &str,
+    ) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match LMoveCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::LMove(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an LMove command"),
+        }
+    }
+
+    fn list(values: &[&str]) -> Option<Value> {
+        Some(Value::List(values.iter().collect()))
+    }
+
+    #[tokio::test]
+    async fn test_lmove_between_lists() {
+        let store = Store::new();
+        store.upsert(&Key::from("a"), || list(&["1", "2", "3"]).unwrap(), |_| ());
+
+        let reply = run(&store, LMoveVariant::LMove, "lmove a b left right").await;
+        assert_eq!(reply, Ok(Frame::bulk("1")));
+        let reply = run(&store, LMoveVariant::RPopLPush, "rpoplpush a b").await;
+        assert_eq!(reply, Ok(Frame::bulk("3")));
+        assert_eq!(store.get(&Key::from("a")), list(&["2"]));
+        assert_eq!(store.get(&Key::from("b")), list(&["3", "1"]));
+
+        // Rotating a list onto itself
+        let reply = run(&store, LMoveVariant::LMove, "lmove b b left right").await;
+        assert_eq!(reply, Ok(Frame::bulk("3")));
+        assert_eq!(store.get(&Key::from("b")), list(&["1", "3"]));
+
+        let reply = run(&store, LMoveVariant::LMove, "lmove missing b left right").await;
+        assert_eq!(reply, Ok(Frame::Null));
+    }
+
+    #[tokio::test]
+    async fn test_lmove_to_a_string_pops_nothing() {
+        let store = Store::new();
+        store.upsert(&Key::from("a"), || list(&["1"]).unwrap(), |_| ());
+        store.set(Key::from("string"), Value::Str(Bytes::from("value")));
+
+        let reply = run(&store, LMoveVariant::RPopLPush, "rpoplpush a string").await;
+        assert_eq!(reply, Err(WRONGTYPE.to_string()));
+        assert_eq!(store.get(&Key::from("a")), list(&["1"]));
+    }
+}
@@ -0,0 +1,163 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, parse_list_end, ParseError},
+    resp::Frame,
+    store::{Key, Store},
+    types::list::ListEnd,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LMPOP numkeys key [key ...] <LEFT | RIGHT> [COUNT count]`
+pub struct LMPopCommand {
+    pub keys: Vec<Key>,
+    pub end: ListEnd,
+    pub count: usize,
+}
+
+impl CommandTrait for LMPopCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        let (keys, end, count) = parse_lmpop_parts(parts)?;
+        Ok(CommandWrapper::LMPop(Self { keys, end, count }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        Ok(lmpop_frame(lmpop(
+            &store, &self.keys, self.end, self.count,
+        )?))
+    }
+}
+
+/// Parses the `numkeys key [key ...] <LEFT | RIGHT> [COUNT count]` shared with `BLMPOP`
+pub fn parse_lmpop_parts(mut parts: CommandParts) -> Result<(Vec<Key>, ListEnd, usize), String> {
+    let num_keys = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+    if num_keys <= 0 {
+        return Err("numkeys should be greater than 0".to_string());
+    }
+    let keys: Vec<Key> = parts.by_ref().take(num_keys as usize).collect();
+    if keys.len() < num_keys as usize {
+        return Err(ParseError::MissingKeys.to_string());
+    }
+    let end = parse_list_end(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+    let mut count = 1;
+    if let Some(option) = parts.next() {
+        if lowercase(&option) != "count" {
+            return Err(ParseError::InvalidCommandOptions(&format!(
+                "Unsupported option {}",
+                String::from_utf8_lossy(&option)
+            ))
+            .to_string());
+        }
+        let value = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        if value <= 0 {
+            return Err("count should be greater than 0".to_string());
+        }
+        count = value as usize;
+    }
+
+    if parts.next().is_some() {
+        return Err(ParseError::TooManyArguments.to_string());
+    }
+    Ok((keys, end, count))
+}
+
+/// Atomically pops up to `count` elements from the first non empty list of `keys`.
+///
+/// Returns `None` if none of the keys exist.
+pub fn lmpop(
+    store: &Store,
+    keys: &[Key],
+    end: ListEnd,
+    count: usize,
+) -> Result<Option<(Key, Vec<Bytes>)>, String> {
+    store.atomically(|| {
+        for key in keys {
+            let popped = store.update(key, |value| {
+                let list = value.as_list_mut()?;
+                let count = count.min(list.len());
+                Ok::<_, String>((0..count).filter_map(|_| list.pop(end)).collect())
+            });
+            if let Some(popped) = popped.transpose()? {
+                return Ok(Some((key.clone(), popped)));
+            }
+        }
+        Ok(None)
+    })
+}
+
+/// The `[key, [element ...]]` reply of `LMPOP`, or a null array if nothing was popped
+pub fn lmpop_frame(popped: Option<(Key, Vec<Bytes>)>) -> Frame {
+    match popped {
+        Some((key, elements)) => Frame::Array(vec![
+            Frame::bulk(key),
+            Frame::Array(elements.into_iter().map(Frame::bulk).collect()),
+        ]),
+        None => Frame::NullArray,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Value},
+    };
+
+    use super::*;
+
+    async fn lmpop(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match LMPopCommand::from_parts(parts)? {
+            CommandWrapper::LMPop(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an LMPop command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lmpop_from_the_first_non_empty_list() {
+        let store = Store::new();
+        let list = Value::List(["a", "b", "c"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        assert_eq!(
+            lmpop(&store, "lmpop 2 missing list right count 2").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("list"),
+                Frame::Array(vec![Frame::bulk("c"), Frame::bulk("b")])
+            ]))
+        );
+        assert_eq!(
+            lmpop(&store, "lmpop 1 list left count 5").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("list"),
+                Frame::Array(vec![Frame::bulk("a")])
+            ]))
+        );
+        assert_eq!(
+            lmpop(&store, "lmpop 1 list left").await,
+            Ok(Frame::NullArray)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lmpop_invalid_arguments() {
+        let store = Store::new();
+        assert_eq!(
+            lmpop(&store, "lmpop 0 list left").await,
+            Err("numkeys should be greater than 0".to_string())
+        );
+        assert_eq!(
+            lmpop(&store, "lmpop 1 list left count 0").await,
+            Err("count should be greater than 0".to_string())
+        );
+        assert!(lmpop(&store, "lmpop 2 list left").await.is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::list::ListEnd,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LPOP` and `RPOP` only differ in the end of the list popped from
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LPopVariant {
+    /// `LPOP key [count]`
+    LPop,
+    /// `RPOP key [count]`
+    RPop,
+}
+
+pub struct LPopCommand {
+    pub key: Key,
+    /// Pop this many elements and reply with an array, instead of a single element
+    pub count: Option<usize>,
+    pub variant: LPopVariant,
+}
+
+impl LPopCommand {
+    pub fn from_parts_as(
+        variant: LPopVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let count = match parts.next() {
+            Some(count) => Some(
+                usize::try_from(parse_int(&count)?)
+                    .map_err(|_| "value is out of range, must be positive")?,
+            ),
+            None => None,
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::LPop(Self {
+            key,
+            count,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for LPopCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(LPopVariant::LPop, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let end = match self.variant {
+            LPopVariant::LPop => ListEnd::Left,
+            LPopVariant::RPop => ListEnd::Right,
+        };
+        let popped = store
+            .update(&self.key, |value| {
+                let list = value.as_list_mut()?;
+                let count = self.count.unwrap_or(1).min(list.len());
+                Ok::<_, String>((0..count).filter_map(|_| list.pop(end)).collect::<Vec<_>>())
+            })
+            .transpose()?;
+
+        Ok(match (popped, self.count) {
+            (None, None) => Frame::Null,
+            (None, Some(_)) => Frame::NullArray,
+            (Some(mut popped), None) => popped.pop().map_or(Frame::Null, Frame::bulk),
+            (Some(popped), Some(_)) => Frame::Array(popped.into_iter().map(Frame::bulk).collect()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    async fn run(
+        store: &crate::store::ConcurrentStore,
+        variant: LPopVariant,
+        input: &str,
+    ) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match LPopCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::LPop(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an LPop command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pop_from_both_ends() {
+        let store = Store::new();
+        let list = Value::List(["a", "b", "c", "d"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        assert_eq!(
+            run(&store, LPopVariant::LPop, "lpop list").await,
+            Ok(Frame::bulk("a"))
+        );
+        assert_eq!(
+            run(&store, LPopVariant::RPop, "rpop list 2").await,
+            Ok(Frame::Array(vec![Frame::bulk("d"), Frame::bulk("c")]))
+        );
+        assert_eq!(
+            run(&store, LPopVariant::LPop, "lpop list 0").await,
+            Ok(Frame::Array(vec![]))
+        );
+        assert_eq!(
+            run(&store, LPopVariant::LPop, "lpop list 5").await,
+            Ok(Frame::Array(vec![Frame::bulk("b")]))
+        );
+
+        // The last pop deleted the list
+        assert_eq!(
+            run(&store, LPopVariant::LPop, "lpop list").await,
+            Ok(Frame::Null)
+        );
+        assert_eq!(
+            run(&store, LPopVariant::LPop, "lpop list 1").await,
+            Ok(Frame::NullArray)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pop_negative_count() {
+        let store = Store::new();
+        assert_eq!(
+            run(&store, LPopVariant::LPop, "lpop list -1").await,
+            Err("value is out of range, must be positive".to_string())
+        );
+    }
+}
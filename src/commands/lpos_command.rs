@@ -0,0 +1,203 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LPOS key element [RANK rank] [COUNT num-matches] [MAXLEN len]`
+pub struct LPosCommand {
+    pub key: Key,
+    pub element: Bytes,
+    pub options: LPosOptions,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LPosOptions {
+    /// Skip to the nth match, counting from the tail if negative
+    pub rank: i64,
+    /// Reply with up to this many matches instead of the first one, zero meaning all
+    pub count: Option<usize>,
+    /// Compare at most this many elements, zero meaning all
+    pub max_len: usize,
+}
+
+impl Default for LPosOptions {
+    fn default() -> Self {
+        Self {
+            rank: 1,
+            count: None,
+            max_len: 0,
+        }
+    }
+}
+
+impl CommandTrait for LPosCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let element = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        let mut options = LPosOptions::default();
+        while let Some(option) = parts.next() {
+            let value = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+            match lowercase(&option).as_str() {
+                "rank" if value == 0 => return Err("RANK can't be zero: use 1 to start from the first match, 2 from the second ... or use negative to start from the end of the list".to_string()),
+                "rank" => options.rank = value,
+                "count" => {
+                    options.count =
+                        Some(usize::try_from(value).map_err(|_| "COUNT can't be negative")?)
+                }
+                "maxlen" => {
+                    options.max_len = usize::try_from(value).map_err(|_| "MAXLEN can't be negative")?
+                }
+                _ => {
+                    return Err(ParseError::InvalidCommandOptions(&format!(
+                        "Unsupported option {}",
+                        String::from_utf8_lossy(&option)
+                    ))
+                    .to_string())
+                }
+            }
+        }
+
+        Ok(CommandWrapper::LPos(Self {
+            key,
+            element,
+            options,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let LPosOptions {
+            rank,
+            count,
+            max_len,
+        } = self.options;
+        let wanted = match count {
+            Some(0) => usize::MAX,
+            Some(count) => count,
+            None => 1,
+        };
+        let max_len = if max_len == 0 { usize::MAX } else { max_len };
+        let skip = (rank.unsigned_abs() - 1) as usize;
+
+        let matches = store
+            .read(&self.key, |value| {
+                let list = value.as_list()?;
+                let len = list.len();
+                let elements: Box<dyn Iterator<Item = (usize, &[u8])>> = if rank > 0 {
+                    Box::new(list.iter().enumerate())
+                } else {
+                    Box::new(list.iter().rev().enumerate().map(|(i, e)| (len - 1 - i, e)))
+                };
+                Ok::<_, String>(
+                    elements
+                        .take(max_len)
+                        .filter(|(_, element)| *element == self.element)
+                        .skip(skip)
+                        .take(wanted)
+                        .map(|(index, _)| index as i64)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(match count {
+            Some(_) => Frame::Array(matches.into_iter().map(Frame::Integer).collect()),
+            None => matches
+                .first()
+                .map_or(Frame::Null, |index| Frame::Integer(*index)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        resp::Protocol,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    async fn lpos(store: &crate::store::ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match LPosCommand::from_parts(parts)? {
+            CommandWrapper::LPos(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an LPos command"),
+        }
+    }
+
+    fn integers(values: &[i64]) -> Result<Frame, String> {
+        Ok(Frame::Array(
+            values.iter().copied().map(Frame::Integer).collect(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_lpos_command() {
+        let store = Store::new();
+        let list = Value::List(
+            ["a", "b", "c", "1", "2", "3", "c", "c"]
+                .into_iter()
+                .collect(),
+        );
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        assert_eq!(lpos(&store, "lpos list c").await, Ok(Frame::Integer(2)));
+        assert_eq!(lpos(&store, "lpos list x").await, Ok(Frame::Null));
+        assert_eq!(
+            lpos(&store, "lpos list c rank 2").await,
+            Ok(Frame::Integer(6))
+        );
+        assert_eq!(
+            lpos(&store, "lpos list c rank -1").await,
+            Ok(Frame::Integer(7))
+        );
+        assert_eq!(lpos(&store, "lpos list c count 2").await, integers(&[2, 6]));
+        assert_eq!(
+            lpos(&store, "lpos list c count 0 rank -1").await,
+            integers(&[7, 6, 2])
+        );
+        assert_eq!(
+            lpos(&store, "lpos list c count 0 maxlen 3").await,
+            integers(&[2])
+        );
+        assert_eq!(lpos(&store, "lpos missing c count 0").await, integers(&[]));
+    }
+
+    #[test]
+    fn test_lpos_command_invalid_options() {
+        let parse = |input: &str| {
+            let mut parts = inline_parts(input);
+            parts.next(); // Skip the command
+            LPosCommand::from_parts(parts).err()
+        };
+        // The errors start with an option, which isn't an error code
+        let encoded = |input: &str| Frame::error(parse(input).unwrap()).to_bytes(Protocol::Resp2);
+        assert!(encoded("lpos list c rank 0").starts_with(b"-ERR RANK can't be zero"));
+        assert_eq!(
+            encoded("lpos list c count -1"),
+            b"-ERR COUNT can't be negative\r\n"
+        );
+        assert_eq!(
+            encoded("lpos list c maxlen -1"),
+            b"-ERR MAXLEN can't be negative\r\n"
+        );
+        assert_eq!(
+            parse("lpos list c limit 1").unwrap(),
+            ParseError::InvalidCommandOptions("Unsupported option limit").to_string()
+        );
+    }
+}
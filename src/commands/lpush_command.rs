@@ -0,0 +1,144 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+    types::list::{List, ListEnd},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands pushing to a list only differ in the end pushed to, and whether
+/// the list must already exist
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LPushVariant {
+    /// `LPUSH key element [element ...]`
+    LPush,
+    /// `RPUSH key element [element ...]`
+    RPush,
+    /// `LPUSHX key element [element ...]` -- Only if the list exists
+    LPushX,
+    /// `RPUSHX key element [element ...]` -- Only if the list exists
+    RPushX,
+}
+
+pub struct LPushCommand {
+    pub key: Key,
+    pub elements: Vec<Bytes>,
+    pub variant: LPushVariant,
+}
+
+impl LPushCommand {
+    pub fn from_parts_as(
+        variant: LPushVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let elements: Vec<Bytes> = parts.collect();
+
+        if elements.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+
+        Ok(CommandWrapper::LPush(Self {
+            key,
+            elements,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for LPushCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(LPushVariant::LPush, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let end = match self.variant {
+            LPushVariant::LPush | LPushVariant::LPushX => ListEnd::Left,
+            LPushVariant::RPush | LPushVariant::RPushX => ListEnd::Right,
+        };
+        let push = |value: &mut Value| {
+            let list = value.as_list_mut()?;
+            for element in &self.elements {
+                list.push(end, element);
+            }
+            Ok::<_, String>(list.len())
+        };
+
+        let len = match self.variant {
+            LPushVariant::LPush | LPushVariant::RPush => {
+                store.upsert(&self.key, || Value::List(List::new()), push)?
+            }
+            LPushVariant::LPushX | LPushVariant::RPushX => {
+                store.update(&self.key, push).transpose()?.unwrap_or(0)
+            }
+        };
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    async fn run(
+        store: &crate::store::ConcurrentStore,
+        variant: LPushVariant,
+        input: &str,
+    ) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match LPushCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::LPush(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an LPush command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_to_both_ends() {
+        let store = Store::new();
+        assert_eq!(
+            run(&store, LPushVariant::LPush, "lpush list b a").await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            run(&store, LPushVariant::RPush, "rpush list c").await,
+            Ok(Frame::Integer(3))
+        );
+
+        let list = store.get(&Key::from("list")).unwrap();
+        assert_eq!(list, Value::List(["a", "b", "c"].into_iter().collect()));
+    }
+
+    #[tokio::test]
+    async fn test_pushx_needs_an_existing_list() {
+        let store = Store::new();
+        assert_eq!(
+            run(&store, LPushVariant::LPushX, "lpushx list a").await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(store.get(&Key::from("list")), None);
+    }
+
+    #[tokio::test]
+    async fn test_push_to_a_string_is_wrongtype() {
+        let store = Store::new();
+        store.set(Key::from("string"), Value::Str(Bytes::from("value")));
+        assert_eq!(
+            run(&store, LPushVariant::RPush, "rpush string a").await,
+            Err(crate::store::WRONGTYPE.to_string())
+        );
+        assert!(run(&store, LPushVariant::RPush, "rpush list")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    utils::resolve_range,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LRANGE key start stop`
+pub struct LRangeCommand {
+    pub key: Key,
+    pub start: i64,
+    pub stop: i64,
+}
+
+impl CommandTrait for LRangeCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let start = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let stop = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::LRange(Self { key, start, stop }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let elements = store
+            .read(&self.key, |value| {
+                let list = value.as_list()?;
+                let Some((start, end)) = resolve_range(self.start, self.stop, list.len()) else {
+                    return Ok(vec![]);
+                };
+                Ok::<_, String>(
+                    list.range(start, end)
+                        .map(|element| Frame::bulk(bytes::Bytes::copy_from_slice(element)))
+                        .collect(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Frame::Array(elements))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    async fn lrange(store: &crate::store::ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match LRangeCommand::from_parts(parts)? {
+            CommandWrapper::LRange(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an LRange command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lrange_command() {
+        let store = Store::new();
+        let list = Value::List(["a", "b", "c"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        let bulks = |values: &[&'static str]| {
+            Ok(Frame::Array(
+                values.iter().map(|v| Frame::bulk(*v)).collect(),
+            ))
+        };
+        assert_eq!(
+            lrange(&store, "lrange list 0 -1").await,
+            bulks(&["a", "b", "c"])
+        );
+        assert_eq!(
+            lrange(&store, "lrange list -2 100").await,
+            bulks(&["b", "c"])
+        );
+        assert_eq!(lrange(&store, "lrange list 2 1").await, bulks(&[]));
+        assert_eq!(lrange(&store, "lrange missing 0 -1").await, bulks(&[]));
+        assert_eq!(
+            lrange(&store, "lrange list a 1").await,
+            Err(ParseError::NotAnInteger.to_string())
+        );
+    }
+}
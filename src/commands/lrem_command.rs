@@ -0,0 +1,92 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LREM key count element`
+pub struct LRemCommand {
+    pub key: Key,
+    /// Removes this many matches from the head, or from the tail if negative, or all if zero
+    pub count: i64,
+    pub element: Bytes,
+}
+
+impl CommandTrait for LRemCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let count = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let element = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::LRem(Self {
+            key,
+            count,
+            element,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let limit = match self.count {
+            0 => usize::MAX,
+            count => count.unsigned_abs() as usize,
+        };
+        let removed = store
+            .update(&self.key, |value| {
+                let list = value.as_list_mut()?;
+                Ok::<_, String>(list.remove_matching(&self.element, limit, self.count < 0))
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(removed as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lrem_command() {
+        let store = Store::new();
+        let list = Value::List(["a", "b", "a", "c", "a", "a"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        let lrem = |count| LRemCommand {
+            key: Key::from("list"),
+            count,
+            element: Bytes::from("a"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            lrem(-1).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            lrem(1).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            store.get(&Key::from("list")),
+            Some(Value::List(["b", "a", "c", "a"].into_iter().collect()))
+        );
+        assert_eq!(
+            lrem(0).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(2))
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    utils::resolve_index,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LSET key index element`
+pub struct LSetCommand {
+    pub key: Key,
+    pub index: i64,
+    pub element: Bytes,
+}
+
+impl CommandTrait for LSetCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let index = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let element = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::LSet(Self {
+            key,
+            index,
+            element,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        store
+            .update(&self.key, |value| {
+                let list = value.as_list_mut()?;
+                let index = resolve_index(self.index, list.len()).ok_or("index out of range")?;
+                list.set(index, &self.element);
+                Ok(Frame::ok())
+            })
+            .ok_or("no such key")?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lset_command() {
+        let store = Store::new();
+        let list = Value::List(["a", "b", "c"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        let lset = |key: &'static str, index| LSetCommand {
+            key: Key::from(key),
+            index,
+            element: Bytes::from("x"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            lset("list", -1).execute(store.clone(), &mut client).await,
+            Ok(Frame::ok())
+        );
+        assert_eq!(
+            lset("list", 3).execute(store.clone(), &mut client).await,
+            Err("index out of range".to_string())
+        );
+        assert_eq!(
+            lset("missing", 0).execute(store.clone(), &mut client).await,
+            Err("no such key".to_string())
+        );
+        assert_eq!(
+            store.get(&Key::from("list")),
+            Some(Value::List(["a", "b", "x"].into_iter().collect()))
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::list::List,
+    utils::resolve_range,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `LTRIM key start stop`
+pub struct LTrimCommand {
+    pub key: Key,
+    pub start: i64,
+    pub stop: i64,
+}
+
+impl CommandTrait for LTrimCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let start = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let stop = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::LTrim(Self { key, start, stop }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        store
+            .update(&self.key, |value| {
+                let list = value.as_list_mut()?;
+                match resolve_range(self.start, self.stop, list.len()) {
+                    Some((start, end)) => list.trim(start, end),
+                    // The key is deleted once the list is empty
+                    None => *list = List::new(),
+                }
+                Ok::<_, String>(())
+            })
+            .transpose()?;
+        Ok(Frame::ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ltrim_command() {
+        let store = Store::new();
+        let list = Value::List(["a", "b", "c", "d"].into_iter().collect());
+        store.upsert(&Key::from("list"), || list, |_| ());
+
+        let ltrim = |start, stop| LTrimCommand {
+            key: Key::from("list"),
+            start,
+            stop,
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            ltrim(1, -2).execute(store.clone(), &mut client).await,
+            Ok(Frame::ok())
+        );
+        assert_eq!(
+            store.get(&Key::from("list")),
+            Some(Value::List(["b", "c"].into_iter().collect()))
+        );
+
+        assert_eq!(
+            ltrim(5, 10).execute(store.clone(), &mut client).await,
+            Ok(Frame::ok())
+        );
+        assert_eq!(store.get(&Key::from("list")), None);
+    }
+}
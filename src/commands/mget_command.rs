@@ -0,0 +1,73 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `MGET key [key ...]`
+pub struct MGetCommand {
+    pub keys: Vec<Key>,
+}
+
+impl CommandTrait for MGetCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        let keys: Vec<Key> = parts.collect();
+
+        if keys.is_empty() {
+            return Err(ParseError::MissingKeys.to_string());
+        }
+
+        Ok(CommandWrapper::MGet(Self { keys }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        // Like Redis, every key is read at the same point in time
+        let values = store.atomically(|| {
+            self.keys
+                .iter()
+                .map(|key| {
+                    // Keys of other types read as missing ones
+                    let value = store.read(key, |value| value.as_str().ok()).flatten();
+                    value.map_or(Frame::Null, Frame::bulk)
+                })
+                .collect()
+        });
+        Ok(Frame::Array(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mget_command() {
+        let store = Store::new();
+        store.set(Key::from("a"), Value::Str(Bytes::from("1")));
+        store.set(Key::from("b"), Value::Int(2));
+        store.upsert(
+            &Key::from("list"),
+            || Value::List(["a"].into_iter().collect()),
+            |_| (),
+        );
+
+        let cmd = MGetCommand {
+            keys: ["a", "missing", "list", "b"].map(Key::from).to_vec(),
+        };
+        assert_eq!(
+            cmd.execute(store, &mut Client::new()).await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("1"),
+                Frame::Null,
+                Frame::Null,
+                Frame::bulk("2")
+            ]))
+        );
+    }
+}
@@ -1,17 +1,170 @@
-use std::{future::Future, str::SplitWhitespace};
+use std::future::Future;
 
-use crate::store::ConcurrentStore;
+use bytes::Bytes;
+
+use crate::{client::Client, resp::Frame, store::ConcurrentStore};
 
 use self::{
-    del_command::DelCommand, get_command::GetCommand, help_command::HelpCommand,
-    set_command::SetCommand, touch_command::TouchCommand,
+    append_command::AppendCommand, bitcount_command::BitCountCommand,
+    bitfield_command::BitFieldCommand, bitop_command::BitOpCommand, bitpos_command::BitPosCommand,
+    blmove_command::BLMoveCommand, blmpop_command::BLMPopCommand, blpop_command::BLPopCommand,
+    bzmpop_command::BZMPopCommand, bzpopmin_command::BZPopMinCommand, del_command::DelCommand,
+    echo_command::EchoCommand, expire_command::ExpireCommand, geoadd_command::GeoAddCommand,
+    geodist_command::GeoDistCommand, geohash_command::GeoHashCommand,
+    geopos_command::GeoPosCommand, geosearch_command::GeoSearchCommand, get_command::GetCommand,
+    getbit_command::GetBitCommand, getdel_command::GetDelCommand, getex_command::GetExCommand,
+    getrange_command::GetRangeCommand, hdel_command::HDelCommand, hello_command::HelloCommand,
+    help_command::HelpCommand, hexpire_command::HExpireCommand, hget_command::HGetCommand,
+    hgetall_command::HGetAllCommand, hincrby_command::HIncrByCommand,
+    hincrbyfloat_command::HIncrByFloatCommand, hlen_command::HLenCommand,
+    hmget_command::HMGetCommand, hpersist_command::HPersistCommand,
+    hrandfield_command::HRandFieldCommand, hscan_command::HScanCommand, hset_command::HSetCommand,
+    httl_command::HTtlCommand, incrby_command::IncrByCommand,
+    incrbyfloat_command::IncrByFloatCommand, info_command::InfoCommand, lcs_command::LcsCommand,
+    lindex_command::LIndexCommand, linsert_command::LInsertCommand, llen_command::LLenCommand,
+    lmove_command::LMoveCommand, lmpop_command::LMPopCommand, lpop_command::LPopCommand,
+    lpos_command::LPosCommand, lpush_command::LPushCommand, lrange_command::LRangeCommand,
+    lrem_command::LRemCommand, lset_command::LSetCommand, ltrim_command::LTrimCommand,
+    mget_command::MGetCommand, mset_command::MSetCommand, object_command::ObjectCommand,
+    persist_command::PersistCommand, pfadd_command::PFAddCommand, pfcount_command::PFCountCommand,
+    pfdebug_command::PFDebugCommand, pfmerge_command::PFMergeCommand,
+    pfselftest_command::PFSelfTestCommand, ping_command::PingCommand, sadd_command::SAddCommand,
+    scard_command::SCardCommand, set_command::SetCommand, setbit_command::SetBitCommand,
+    setrange_command::SetRangeCommand, sinter_command::SInterCommand,
+    sintercard_command::SInterCardCommand, sinterstore_command::SInterStoreCommand,
+    sismember_command::SIsMemberCommand, smembers_command::SMembersCommand,
+    smove_command::SMoveCommand, spop_command::SPopCommand,
+    srandmember_command::SRandMemberCommand, srem_command::SRemCommand,
+    sscan_command::SScanCommand, strlen_command::StrLenCommand, touch_command::TouchCommand,
+    ttl_command::TtlCommand, xack_command::XAckCommand, xadd_command::XAddCommand,
+    xautoclaim_command::XAutoClaimCommand, xclaim_command::XClaimCommand,
+    xdel_command::XDelCommand, xgroup_command::XGroupCommand, xinfo_command::XInfoCommand,
+    xlen_command::XLenCommand, xpending_command::XPendingCommand, xrange_command::XRangeCommand,
+    xread_command::XReadCommand, xreadgroup_command::XReadGroupCommand,
+    xtrim_command::XTrimCommand, zadd_command::ZAddCommand, zcard_command::ZCardCommand,
+    zcount_command::ZCountCommand, zincrby_command::ZIncrByCommand,
+    zintercard_command::ZInterCardCommand, zmpop_command::ZMPopCommand,
+    zpopmin_command::ZPopMinCommand, zrandmember_command::ZRandMemberCommand,
+    zrange_command::ZRangeCommand, zrangestore_command::ZRangeStoreCommand,
+    zrank_command::ZRankCommand, zrem_command::ZRemCommand,
+    zremrangebyrank_command::ZRemRangeByRankCommand, zscore_command::ZScoreCommand,
+    zunion_command::ZUnionCommand, zunionstore_command::ZUnionStoreCommand,
 };
 
+pub mod append_command;
+pub mod bitcount_command;
+pub mod bitfield_command;
+pub mod bitop_command;
+pub mod bitpos_command;
+pub mod blmove_command;
+pub mod blmpop_command;
+pub mod blpop_command;
+pub mod bzmpop_command;
+pub mod bzpopmin_command;
 pub mod del_command;
+pub mod echo_command;
+pub mod expire_command;
+pub mod geoadd_command;
+pub mod geodist_command;
+pub mod geohash_command;
+pub mod geopos_command;
+pub mod geosearch_command;
 pub mod get_command;
+pub mod getbit_command;
+pub mod getdel_command;
+pub mod getex_command;
+pub mod getrange_command;
+pub mod hdel_command;
+pub mod hello_command;
 pub mod help_command;
+pub mod hexpire_command;
+pub mod hget_command;
+pub mod hgetall_command;
+pub mod hincrby_command;
+pub mod hincrbyfloat_command;
+pub mod hlen_command;
+pub mod hmget_command;
+pub mod hpersist_command;
+pub mod hrandfield_command;
+pub mod hscan_command;
+pub mod hset_command;
+pub mod httl_command;
+pub mod incrby_command;
+pub mod incrbyfloat_command;
+pub mod info_command;
+pub mod lcs_command;
+pub mod lindex_command;
+pub mod linsert_command;
+pub mod llen_command;
+pub mod lmove_command;
+pub mod lmpop_command;
+pub mod lpop_command;
+pub mod lpos_command;
+pub mod lpush_command;
+pub mod lrange_command;
+pub mod lrem_command;
+pub mod lset_command;
+pub mod ltrim_command;
+pub mod mget_command;
+pub mod mset_command;
+pub mod object_command;
+pub mod persist_command;
+pub mod pfadd_command;
+pub mod pfcount_command;
+pub mod pfdebug_command;
+pub mod pfmerge_command;
+pub mod pfselftest_command;
+pub mod ping_command;
+pub mod sadd_command;
+pub mod scard_command;
 pub mod set_command;
+pub mod setbit_command;
+pub mod setrange_command;
+pub mod sinter_command;
+pub mod sintercard_command;
+pub mod sinterstore_command;
+pub mod sismember_command;
+pub mod smembers_command;
+pub mod smove_command;
+pub mod spop_command;
+pub mod srandmember_command;
+pub mod srem_command;
+pub mod sscan_command;
+pub mod strlen_command;
 pub mod touch_command;
+pub mod ttl_command;
+pub mod xack_command;
+pub mod xadd_command;
+pub mod xautoclaim_command;
+pub mod xclaim_command;
+pub mod xdel_command;
+pub mod xgroup_command;
+pub mod xinfo_command;
+pub mod xlen_command;
+pub mod xpending_command;
+pub mod xrange_command;
+pub mod xread_command;
+pub mod xreadgroup_command;
+pub mod xtrim_command;
+pub mod zadd_command;
+pub mod zcard_command;
+pub mod zcount_command;
+pub mod zincrby_command;
+pub mod zintercard_command;
+pub mod zmpop_command;
+pub mod zpopmin_command;
+pub mod zrandmember_command;
+pub mod zrange_command;
+pub mod zrangestore_command;
+pub mod zrank_command;
+pub mod zrem_command;
+pub mod zremrangebyrank_command;
+pub mod zscore_command;
+pub mod zunion_command;
+pub mod zunionstore_command;
+
+/// The arguments of a command, not including the command name itself
+pub type CommandParts = std::vec::IntoIter<Bytes>;
 
 pub enum CommandWrapper {
     Set(SetCommand),
@@ -19,10 +172,240 @@ pub enum CommandWrapper {
     Del(DelCommand),
     Touch(TouchCommand),
     Help(HelpCommand),
+    Ping(PingCommand),
+    Echo(EchoCommand),
+    Hello(HelloCommand),
+    Expire(ExpireCommand),
+    Ttl(TtlCommand),
+    Persist(PersistCommand),
+    Info(InfoCommand),
+    Object(ObjectCommand),
+    LPush(LPushCommand),
+    LPop(LPopCommand),
+    LRange(LRangeCommand),
+    LIndex(LIndexCommand),
+    LLen(LLenCommand),
+    LSet(LSetCommand),
+    LInsert(LInsertCommand),
+    LRem(LRemCommand),
+    LTrim(LTrimCommand),
+    LPos(LPosCommand),
+    LMove(LMoveCommand),
+    LMPop(LMPopCommand),
+    BLPop(BLPopCommand),
+    BLMove(BLMoveCommand),
+    BLMPop(BLMPopCommand),
+    HSet(HSetCommand),
+    HGet(HGetCommand),
+    HMGet(HMGetCommand),
+    HDel(HDelCommand),
+    HGetAll(HGetAllCommand),
+    HLen(HLenCommand),
+    HIncrBy(HIncrByCommand),
+    HIncrByFloat(HIncrByFloatCommand),
+    HScan(HScanCommand),
+    HRandField(HRandFieldCommand),
+    HExpire(HExpireCommand),
+    HTtl(HTtlCommand),
+    HPersist(HPersistCommand),
+    SAdd(SAddCommand),
+    SRem(SRemCommand),
+    SMembers(SMembersCommand),
+    SCard(SCardCommand),
+    SIsMember(SIsMemberCommand),
+    SPop(SPopCommand),
+    SRandMember(SRandMemberCommand),
+    SMove(SMoveCommand),
+    SScan(SScanCommand),
+    SInter(SInterCommand),
+    SInterStore(SInterStoreCommand),
+    SInterCard(SInterCardCommand),
+    ZAdd(ZAddCommand),
+    ZIncrBy(ZIncrByCommand),
+    ZRem(ZRemCommand),
+    ZCard(ZCardCommand),
+    ZScore(ZScoreCommand),
+    ZRank(ZRankCommand),
+    ZCount(ZCountCommand),
+    ZRange(ZRangeCommand),
+    ZRemRangeByRank(ZRemRangeByRankCommand),
+    ZPopMin(ZPopMinCommand),
+    ZRandMember(ZRandMemberCommand),
+    ZUnion(ZUnionCommand),
+    ZUnionStore(ZUnionStoreCommand),
+    ZInterCard(ZInterCardCommand),
+    ZRangeStore(ZRangeStoreCommand),
+    ZMPop(ZMPopCommand),
+    BZPopMin(BZPopMinCommand),
+    BZMPop(BZMPopCommand),
+    XAdd(XAddCommand),
+    XRange(XRangeCommand),
+    XLen(XLenCommand),
+    XDel(XDelCommand),
+    XTrim(XTrimCommand),
+    XGroup(XGroupCommand),
+    XRead(XReadCommand),
+    XReadGroup(XReadGroupCommand),
+    XAck(XAckCommand),
+    XPending(XPendingCommand),
+    XClaim(XClaimCommand),
+    XAutoClaim(XAutoClaimCommand),
+    XInfo(XInfoCommand),
+    IncrBy(IncrByCommand),
+    IncrByFloat(IncrByFloatCommand),
+    Append(AppendCommand),
+    GetRange(GetRangeCommand),
+    SetRange(SetRangeCommand),
+    StrLen(StrLenCommand),
+    Lcs(LcsCommand),
+    MGet(MGetCommand),
+    MSet(MSetCommand),
+    GetDel(GetDelCommand),
+    GetEx(GetExCommand),
+    SetBit(SetBitCommand),
+    GetBit(GetBitCommand),
+    BitCount(BitCountCommand),
+    BitPos(BitPosCommand),
+    BitOp(BitOpCommand),
+    BitField(BitFieldCommand),
+    PFAdd(PFAddCommand),
+    PFCount(PFCountCommand),
+    PFMerge(PFMergeCommand),
+    PFDebug(PFDebugCommand),
+    PFSelfTest(PFSelfTestCommand),
+    GeoAdd(GeoAddCommand),
+    GeoDist(GeoDistCommand),
+    GeoHash(GeoHashCommand),
+    GeoPos(GeoPosCommand),
+    GeoSearch(GeoSearchCommand),
     Unknown(String),
     Empty,
 }
 
+impl CommandWrapper {
+    /// Whether the command may use more memory, so it's refused when memory is over
+    /// `maxmemory` and nothing can be evicted, like Redis' `denyoom` commands
+    ///
+    /// Every command is listed, so a new one can't be left out by mistake.
+    pub fn denies_oom(&self) -> bool {
+        match self {
+            CommandWrapper::Set(_)
+            | CommandWrapper::LPush(_)
+            | CommandWrapper::LSet(_)
+            | CommandWrapper::LInsert(_)
+            | CommandWrapper::LMove(_)
+            | CommandWrapper::BLMove(_)
+            | CommandWrapper::HSet(_)
+            | CommandWrapper::HIncrBy(_)
+            | CommandWrapper::HIncrByFloat(_)
+            | CommandWrapper::HExpire(_)
+            | CommandWrapper::SAdd(_)
+            | CommandWrapper::SInterStore(_)
+            | CommandWrapper::ZAdd(_)
+            | CommandWrapper::ZIncrBy(_)
+            | CommandWrapper::ZUnionStore(_)
+            | CommandWrapper::ZRangeStore(_)
+            | CommandWrapper::XAdd(_)
+            | CommandWrapper::XGroup(_)
+            | CommandWrapper::XReadGroup(_)
+            | CommandWrapper::XClaim(_)
+            | CommandWrapper::XAutoClaim(_)
+            | CommandWrapper::IncrBy(_)
+            | CommandWrapper::IncrByFloat(_)
+            | CommandWrapper::Append(_)
+            | CommandWrapper::SetRange(_)
+            | CommandWrapper::MSet(_)
+            | CommandWrapper::SetBit(_)
+            | CommandWrapper::BitOp(_)
+            | CommandWrapper::BitField(_)
+            | CommandWrapper::PFAdd(_)
+            | CommandWrapper::PFMerge(_)
+            | CommandWrapper::PFDebug(_)
+            | CommandWrapper::GeoAdd(_) => true,
+            CommandWrapper::GeoSearch(cmd) => cmd.store.is_some(),
+            CommandWrapper::Get(_)
+            | CommandWrapper::Del(_)
+            | CommandWrapper::Touch(_)
+            | CommandWrapper::Help(_)
+            | CommandWrapper::Ping(_)
+            | CommandWrapper::Echo(_)
+            | CommandWrapper::Hello(_)
+            | CommandWrapper::Expire(_)
+            | CommandWrapper::Ttl(_)
+            | CommandWrapper::Persist(_)
+            | CommandWrapper::Info(_)
+            | CommandWrapper::Object(_)
+            | CommandWrapper::LPop(_)
+            | CommandWrapper::LRange(_)
+            | CommandWrapper::LIndex(_)
+            | CommandWrapper::LLen(_)
+            | CommandWrapper::LRem(_)
+            | CommandWrapper::LTrim(_)
+            | CommandWrapper::LPos(_)
+            | CommandWrapper::LMPop(_)
+            | CommandWrapper::BLPop(_)
+            | CommandWrapper::BLMPop(_)
+            | CommandWrapper::HGet(_)
+            | CommandWrapper::HMGet(_)
+            | CommandWrapper::HDel(_)
+            | CommandWrapper::HGetAll(_)
+            | CommandWrapper::HLen(_)
+            | CommandWrapper::HScan(_)
+            | CommandWrapper::HRandField(_)
+            | CommandWrapper::HTtl(_)
+            | CommandWrapper::HPersist(_)
+            | CommandWrapper::SRem(_)
+            | CommandWrapper::SMembers(_)
+            | CommandWrapper::SCard(_)
+            | CommandWrapper::SIsMember(_)
+            | CommandWrapper::SPop(_)
+            | CommandWrapper::SRandMember(_)
+            | CommandWrapper::SMove(_)
+            | CommandWrapper::SScan(_)
+            | CommandWrapper::SInter(_)
+            | CommandWrapper::SInterCard(_)
+            | CommandWrapper::ZRem(_)
+            | CommandWrapper::ZCard(_)
+            | CommandWrapper::ZScore(_)
+            | CommandWrapper::ZRank(_)
+            | CommandWrapper::ZCount(_)
+            | CommandWrapper::ZRange(_)
+            | CommandWrapper::ZRemRangeByRank(_)
+            | CommandWrapper::ZPopMin(_)
+            | CommandWrapper::ZRandMember(_)
+            | CommandWrapper::ZUnion(_)
+            | CommandWrapper::ZInterCard(_)
+            | CommandWrapper::ZMPop(_)
+            | CommandWrapper::BZPopMin(_)
+            | CommandWrapper::BZMPop(_)
+            | CommandWrapper::XRange(_)
+            | CommandWrapper::XLen(_)
+            | CommandWrapper::XDel(_)
+            | CommandWrapper::XTrim(_)
+            | CommandWrapper::XRead(_)
+            | CommandWrapper::XAck(_)
+            | CommandWrapper::XPending(_)
+            | CommandWrapper::XInfo(_)
+            | CommandWrapper::GetRange(_)
+            | CommandWrapper::StrLen(_)
+            | CommandWrapper::Lcs(_)
+            | CommandWrapper::MGet(_)
+            | CommandWrapper::GetDel(_)
+            | CommandWrapper::GetEx(_)
+            | CommandWrapper::GetBit(_)
+            | CommandWrapper::BitCount(_)
+            | CommandWrapper::BitPos(_)
+            | CommandWrapper::PFCount(_)
+            | CommandWrapper::PFSelfTest(_)
+            | CommandWrapper::GeoDist(_)
+            | CommandWrapper::GeoHash(_)
+            | CommandWrapper::GeoPos(_)
+            | CommandWrapper::Unknown(_)
+            | CommandWrapper::Empty => false,
+        }
+    }
+}
+
 /// Used as an interface, not as a trait
 ///
 /// I'm just afraid that dynamic dispatches might slow things down a little
@@ -57,7 +440,10 @@ pub enum CommandWrapper {
 ///   - It seems that the enum approach and static dispatch is faster because it is stack-based, whereas the `dyn Trait` or `Box<dyn Trait>` approach is heap-based
 pub trait CommandTrait {
     /// The parts do not include the command itself
-    fn from_parts(parts: SplitWhitespace<'_>) -> Result<CommandWrapper, String>;
-    fn execute(self, store: ConcurrentStore)
-        -> impl Future<Output = Result<String, String>> + Send;
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String>;
+    fn execute(
+        self,
+        store: ConcurrentStore,
+        client: &mut Client,
+    ) -> impl Future<Output = Result<Frame, String>> + Send;
 }
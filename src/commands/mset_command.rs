@@ -0,0 +1,124 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    resp::Frame,
+    store::{Key, Value},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `MSET` and `MSETNX` both set many keys at once
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MSetVariant {
+    /// `MSET key value [key value ...]`
+    MSet,
+    /// `MSETNX key value [key value ...]` -- Sets the keys only if none of them exist
+    MSetNx,
+}
+
+pub struct MSetCommand {
+    pub pairs: Vec<(Key, Bytes)>,
+    pub variant: MSetVariant,
+}
+
+impl MSetCommand {
+    pub fn from_parts_as(
+        variant: MSetVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let mut pairs = Vec::with_capacity(parts.len() / 2);
+        while let Some(key) = parts.next() {
+            let value = parts.next().ok_or_else(|| wrong_arity(variant))?;
+            pairs.push((key, value));
+        }
+
+        if pairs.is_empty() {
+            return Err(wrong_arity(variant));
+        }
+
+        Ok(CommandWrapper::MSet(Self { pairs, variant }))
+    }
+}
+
+fn wrong_arity(variant: MSetVariant) -> String {
+    let name = match variant {
+        MSetVariant::MSet => "mset",
+        MSetVariant::MSetNx => "msetnx",
+    };
+    format!("wrong number of arguments for '{name}' command")
+}
+
+impl CommandTrait for MSetCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(MSetVariant::MSet, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        // Other clients see either none or all of the keys set, never some of them
+        let set = store.atomically(|| {
+            let nx = self.variant == MSetVariant::MSetNx;
+            if nx
+                && self
+                    .pairs
+                    .iter()
+                    .any(|(key, _)| store.read(key, |_| ()).is_some())
+            {
+                return false;
+            }
+            for (key, value) in self.pairs {
+                store.set(key, Value::string(value));
+            }
+            true
+        });
+
+        match self.variant {
+            MSetVariant::MSet => Ok(Frame::ok()),
+            MSetVariant::MSetNx => Ok(Frame::Integer(set as i64)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mset_command() {
+        let store = Store::new();
+        let mset =
+            |variant, input: &str| match MSetCommand::from_parts_as(variant, inline_parts(input)) {
+                Ok(CommandWrapper::MSet(cmd)) => Ok(cmd),
+                Ok(_) => panic!("Expected an MSet command"),
+                Err(e) => Err(e),
+            };
+        let mut client = Client::new();
+
+        for (variant, input, expected) in [
+            (MSetVariant::MSet, "a 1 b 2", Frame::ok()),
+            (MSetVariant::MSetNx, "c 3 a 4", Frame::Integer(0)),
+            (MSetVariant::MSetNx, "c 3 d 4", Frame::Integer(1)),
+            (MSetVariant::MSet, "a x a y", Frame::ok()),
+        ] {
+            let frame = mset(variant, input)
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await;
+            assert_eq!(frame, Ok(expected), "{input}");
+        }
+        assert_eq!(store.get(&Key::from("a")), Some(Value::Str("y".into())));
+        assert_eq!(store.get(&Key::from("b")), Some(Value::Int(2)));
+        assert_eq!(store.get(&Key::from("d")), Some(Value::Int(4)));
+
+        assert_eq!(
+            mset(MSetVariant::MSetNx, "a 1 b").err(),
+            Some("wrong number of arguments for 'msetnx' command".to_string())
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The subcommands of `OBJECT`, which inspect the internals of a key
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ObjectSubcommand {
+    /// `OBJECT ENCODING key` -- How the value is laid out in memory
+    Encoding,
+    /// `OBJECT FREQ key` -- The logarithmic access frequency counter, under an LFU policy
+    Freq,
+}
+
+pub struct ObjectCommand {
+    pub subcommand: ObjectSubcommand,
+    pub key: Key,
+}
+
+impl CommandTrait for ObjectCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let subcommand = parts
+            .next()
+            .ok_or(ParseError::InvalidCommandOptions("No subcommand provided").to_string())?;
+        let subcommand = match lowercase(&subcommand).as_str() {
+            "encoding" => ObjectSubcommand::Encoding,
+            "freq" => ObjectSubcommand::Freq,
+            _ => {
+                return Err(format!(
+                    "unknown subcommand '{}'. Try OBJECT HELP.",
+                    String::from_utf8_lossy(&subcommand)
+                ))
+            }
+        };
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::Object(Self { subcommand, key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        match self.subcommand {
+            ObjectSubcommand::Encoding => {
+                Ok(store.encoding(&self.key).map_or(Frame::Null, Frame::bulk))
+            }
+            ObjectSubcommand::Freq => {
+                if !store.config.maxmemory_policy.is_lfu() {
+                    return Err("An LFU maxmemory policy is not selected, access frequency not tracked. Please note that when switching between policies at runtime LRU and LFU data will take some time to adjust.".to_string());
+                }
+                Ok(store
+                    .lfu_frequency(&self.key)
+                    .map_or(Frame::Null, |frequency| Frame::Integer(frequency as i64)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        config::{Config, EvictionPolicy},
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    fn object_command(input: &str) -> Result<ObjectCommand, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match ObjectCommand::from_parts(parts)? {
+            CommandWrapper::Object(cmd) => Ok(cmd),
+            _ => panic!("Expected an Object command"),
+        }
+    }
+
+    #[test]
+    fn test_object_command_from_input() {
+        let cmd = object_command("object FREQ key").unwrap();
+        assert_eq!(cmd.subcommand, ObjectSubcommand::Freq);
+        assert_eq!(cmd.key, "key");
+
+        let cmd = object_command("object encoding key").unwrap();
+        assert_eq!(cmd.subcommand, ObjectSubcommand::Encoding);
+
+        assert!(object_command("object nope key").is_err());
+        assert!(object_command("object freq").is_err());
+        assert!(object_command("object freq key other").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_object_freq_needs_an_lfu_policy() {
+        let mut client = Client::new();
+        let store = Store::new();
+        let result = object_command("object freq key")
+            .unwrap()
+            .execute(store, &mut client)
+            .await;
+        assert!(result.is_err());
+
+        let store = Store::with_config(Config {
+            maxmemory_policy: EvictionPolicy::AllkeysLfu,
+            ..Config::default()
+        });
+        store.set(Key::from("key"), Value::Str(Bytes::from("value")));
+        let frame = object_command("object freq key")
+            .unwrap()
+            .execute(store.clone(), &mut client)
+            .await;
+        assert_eq!(frame, Ok(Frame::Integer(5)));
+        let frame = object_command("object freq missing")
+            .unwrap()
+            .execute(store, &mut client)
+            .await;
+        assert_eq!(frame, Ok(Frame::Null));
+    }
+
+    #[tokio::test]
+    async fn test_object_encoding() {
+        let mut client = Client::new();
+        let store = Store::new();
+        store.set(Key::from("int"), Value::string(Bytes::from("-42")));
+        store.set(Key::from("padded"), Value::string(Bytes::from("042")));
+        store.set(Key::from("long"), Value::Str(Bytes::from(vec![b'x'; 45])));
+        store.set(
+            Key::from("set"),
+            Value::Set(["1", "2"].into_iter().collect()),
+        );
+
+        for (key, expected) in [
+            ("int", Frame::bulk("int")),
+            ("padded", Frame::bulk("embstr")),
+            ("long", Frame::bulk("raw")),
+            ("set", Frame::bulk("intset")),
+            ("missing", Frame::Null),
+        ] {
+            let frame = object_command(&format!("object encoding {key}"))
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await;
+            assert_eq!(frame, Ok(expected));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+pub struct PersistCommand {
+    pub key: Key,
+}
+
+impl CommandTrait for PersistCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::Persist(Self { key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        Ok(Frame::Integer(store.persist(&self.key) as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::utils::inline_parts;
+
+    use super::*;
+
+    #[test]
+    fn test_persist_command_from_input() {
+        let input = "persist key".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match PersistCommand::from_parts(parts).unwrap() {
+            CommandWrapper::Persist(cmd) => {
+                assert_eq!(cmd.key, "key");
+            }
+            _ => panic!("Expected a Persist command"),
+        };
+    }
+
+    #[test]
+    fn test_persist_command_from_input_missing_key() {
+        let input = "persist".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match PersistCommand::from_parts(parts) {
+            Err(e) => assert_eq!(e, ParseError::MissingKey.to_string()),
+            _ => panic!("Expected an error"),
+        };
+    }
+}
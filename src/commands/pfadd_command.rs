@@ -0,0 +1,100 @@
+use std::cell::Cell;
+
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+    types::hyperloglog::HyperLogLog,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `PFADD key [element [element ...]]`
+pub struct PFAddCommand {
+    pub key: Key,
+    pub elements: Vec<Bytes>,
+}
+
+impl CommandTrait for PFAddCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let elements = parts.collect();
+        Ok(CommandWrapper::PFAdd(Self { key, elements }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let max_bytes = store.config.hll_sparse_max_bytes;
+        let created = Cell::new(false);
+        let updated = store.upsert(
+            &self.key,
+            || {
+                created.set(true);
+                Value::Str(HyperLogLog::new().to_bytes(max_bytes))
+            },
+            |value| {
+                let mut hll = HyperLogLog::parse(&value.as_str()?)?;
+                // Like Redis, creating the key counts as an update even without elements
+                let mut updated = created.get();
+                for element in &self.elements {
+                    updated |= hll.add(element);
+                }
+                if updated {
+                    *value = Value::Str(hll.to_bytes(max_bytes));
+                }
+                Ok::<_, String>(updated)
+            },
+        )?;
+        Ok(Frame::Integer(updated as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store},
+        types::hyperloglog::INVALID,
+    };
+
+    use super::*;
+
+    async fn pfadd(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match PFAddCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::PFAdd(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a PFAdd command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pfadd_command() {
+        let store = Store::new();
+        store.set(Key::from("text"), Value::Str(Bytes::from("HYLL?")));
+
+        for (input, expected) in [
+            ("empty", 1),
+            ("empty", 0),
+            ("hll a b c", 1),
+            ("hll a b", 0),
+            ("hll d", 1),
+        ] {
+            assert_eq!(
+                pfadd(&store, input).await,
+                Ok(Frame::Integer(expected)),
+                "{input}"
+            );
+        }
+        let hll = store.read(&Key::from("hll"), |value| {
+            HyperLogLog::parse(&value.as_str().unwrap()).unwrap()
+        });
+        assert_eq!(hll.unwrap().count(), 4);
+
+        assert_eq!(pfadd(&store, "text a").await, Err(INVALID.to_string()));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    client::Client, parser::utils::ParseError, resp::Frame, store::Key,
+    types::hyperloglog::HyperLogLog,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `PFCOUNT key [key ...]`
+pub struct PFCountCommand {
+    pub keys: Vec<Key>,
+}
+
+impl CommandTrait for PFCountCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        let keys: Vec<Key> = parts.collect();
+        if keys.is_empty() {
+            return Err(ParseError::MissingKeys.to_string());
+        }
+        Ok(CommandWrapper::PFCount(Self { keys }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        if let [key] = &self.keys[..] {
+            // Like Redis, the count is cached in the string until it changes
+            let count = store
+                .update(key, |value| {
+                    let bytes = value.as_str()?;
+                    if let Some(count) = HyperLogLog::cached_count(&bytes)? {
+                        return Ok(count);
+                    }
+                    let count = HyperLogLog::parse(&bytes)?.count();
+                    drop(bytes);
+                    value.str_mut(|buf| HyperLogLog::cache_count(buf, count))?;
+                    Ok::<_, String>(count)
+                })
+                .transpose()?
+                .unwrap_or(0);
+            return Ok(Frame::Integer(count as i64));
+        }
+
+        // Several keys are counted as their union, which isn't cached anywhere
+        let mut union = HyperLogLog::new();
+        for key in &self.keys {
+            let hll = store
+                .read(key, |value| HyperLogLog::parse(&value.as_str()?))
+                .transpose()?;
+            if let Some(hll) = hll {
+                union.merge(&hll);
+            }
+        }
+        Ok(Frame::Integer(union.count() as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn pfcount(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match PFCountCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::PFCount(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a PFCount command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pfcount_command() {
+        let store = Store::new();
+        for (key, elements) in [("a", ["1", "2", "3"]), ("b", ["3", "4", "5"])] {
+            let mut hll = HyperLogLog::new();
+            for element in elements {
+                hll.add(element.as_bytes());
+            }
+            store.set(Key::from(key), Value::Str(hll.to_bytes(3000)));
+        }
+
+        assert_eq!(pfcount(&store, "a").await, Ok(Frame::Integer(3)));
+        let cached = store.read(&Key::from("a"), |value| {
+            HyperLogLog::cached_count(&value.as_str().unwrap())
+        });
+        assert_eq!(cached, Some(Ok(Some(3))));
+
+        assert_eq!(pfcount(&store, "a b missing").await, Ok(Frame::Integer(5)));
+        assert_eq!(pfcount(&store, "missing").await, Ok(Frame::Integer(0)));
+
+        store.set(Key::from("text"), Value::Str(Bytes::from("text")));
+        assert!(pfcount(&store, "a text").await.is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::hyperloglog::{self, Encoding, HyperLogLog},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The subcommands of `PFDEBUG`, which inspect the internals of a HyperLogLog
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PFDebugSubcommand {
+    /// `PFDEBUG GETREG key` -- The registers, turning the HyperLogLog dense
+    GetReg,
+    /// `PFDEBUG DECODE key` -- The opcodes of a sparse HyperLogLog
+    Decode,
+    /// `PFDEBUG ENCODING key` -- Whether the HyperLogLog is sparse or dense
+    Encoding,
+    /// `PFDEBUG TODENSE key` -- Turns the HyperLogLog dense, replying whether it wasn't
+    ToDense,
+}
+
+pub struct PFDebugCommand {
+    pub subcommand: PFDebugSubcommand,
+    pub key: Key,
+}
+
+impl CommandTrait for PFDebugCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let subcommand = parts
+            .next()
+            .ok_or(ParseError::InvalidCommandOptions("No subcommand provided").to_string())?;
+        let subcommand = match lowercase(&subcommand).as_str() {
+            "getreg" => PFDebugSubcommand::GetReg,
+            "decode" => PFDebugSubcommand::Decode,
+            "encoding" => PFDebugSubcommand::Encoding,
+            "todense" => PFDebugSubcommand::ToDense,
+            _ => {
+                return Err(format!(
+                    "Unknown PFDEBUG subcommand '{}'",
+                    String::from_utf8_lossy(&subcommand)
+                ))
+            }
+        };
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::PFDebug(Self { subcommand, key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let max_bytes = store.config.hll_sparse_max_bytes;
+        store
+            .update(&self.key, |value| {
+                let bytes = value.as_str()?;
+                let mut hll = HyperLogLog::parse(&bytes)?;
+                let sparse = hll.encoding() == Encoding::Sparse;
+                match self.subcommand {
+                    PFDebugSubcommand::Decode => {
+                        let ops = hyperloglog::sparse_ops(&bytes)?;
+                        let ops: Vec<String> = ops.iter().map(ToString::to_string).collect();
+                        Ok(Frame::Simple(ops.join(" ")))
+                    }
+                    PFDebugSubcommand::Encoding => Ok(Frame::Simple(
+                        if sparse { "sparse" } else { "dense" }.to_string(),
+                    )),
+                    PFDebugSubcommand::GetReg | PFDebugSubcommand::ToDense => {
+                        if sparse {
+                            hll.to_dense();
+                            *value = Value::Str(hll.to_bytes(max_bytes));
+                        }
+                        Ok(match self.subcommand {
+                            PFDebugSubcommand::GetReg => Frame::Array(
+                                hll.registers()
+                                    .iter()
+                                    .map(|&register| Frame::Integer(register as i64))
+                                    .collect(),
+                            ),
+                            _ => Frame::Integer(sparse as i64),
+                        })
+                    }
+                }
+            })
+            .unwrap_or_else(|| Err("The specified key does not exist".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store},
+    };
+
+    use super::*;
+
+    async fn pfdebug(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match PFDebugCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::PFDebug(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a PFDebug command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pfdebug_command() {
+        let store = Store::new();
+        store.set(
+            Key::from("hll"),
+            Value::Str(HyperLogLog::new().to_bytes(3000)),
+        );
+
+        let simple = |reply: &str| Ok(Frame::Simple(reply.to_string()));
+        assert_eq!(pfdebug(&store, "encoding hll").await, simple("sparse"));
+        assert_eq!(pfdebug(&store, "decode hll").await, simple("XZ:16384"));
+        assert_eq!(pfdebug(&store, "todense hll").await, Ok(Frame::Integer(1)));
+        assert_eq!(pfdebug(&store, "todense hll").await, Ok(Frame::Integer(0)));
+        assert_eq!(pfdebug(&store, "encoding hll").await, simple("dense"));
+        assert_eq!(
+            pfdebug(&store, "decode hll").await,
+            Err("HLL encoding is not sparse".to_string())
+        );
+        match pfdebug(&store, "getreg hll").await {
+            Ok(Frame::Array(registers)) => assert_eq!(registers.len(), hyperloglog::REGISTERS),
+            reply => panic!("Expected the registers, got {reply:?}"),
+        }
+
+        assert_eq!(
+            pfdebug(&store, "encoding missing").await,
+            Err("The specified key does not exist".to_string())
+        );
+        assert!(pfdebug(&store, "nope hll").await.is_err());
+    }
+}
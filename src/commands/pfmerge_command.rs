@@ -0,0 +1,106 @@
+use std::iter;
+
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+    types::hyperloglog::{Encoding, HyperLogLog},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `PFMERGE destkey [sourcekey [sourcekey ...]]`
+pub struct PFMergeCommand {
+    pub destination: Key,
+    pub keys: Vec<Key>,
+}
+
+impl CommandTrait for PFMergeCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let destination = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let keys = parts.collect();
+        Ok(CommandWrapper::PFMerge(Self { destination, keys }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        store.atomically(|| {
+            // Like Redis, the destination is merged too, and the result is dense if any of them is
+            let mut merged = HyperLogLog::new();
+            for key in iter::once(&self.destination).chain(&self.keys) {
+                let hll = store
+                    .read(key, |value| HyperLogLog::parse(&value.as_str()?))
+                    .transpose()?;
+                if let Some(hll) = hll {
+                    if hll.encoding() == Encoding::Dense {
+                        merged.to_dense();
+                    }
+                    merged.merge(&hll);
+                }
+            }
+
+            let merged = merged.to_bytes(store.config.hll_sparse_max_bytes);
+            // Changed in place, so the time to live of the destination is kept
+            store.upsert(
+                &self.destination,
+                || Value::Str(Bytes::new()),
+                |value| *value = Value::Str(merged),
+            );
+            Ok::<_, String>(())
+        })?;
+        Ok(Frame::ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store},
+    };
+
+    use super::*;
+
+    async fn pfmerge(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match PFMergeCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::PFMerge(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a PFMerge command"),
+        }
+    }
+
+    fn hll(store: &ConcurrentStore, key: &'static str) -> HyperLogLog {
+        store
+            .read(&Key::from(key), |value| {
+                HyperLogLog::parse(&value.as_str().unwrap()).unwrap()
+            })
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pfmerge_command() {
+        let store = Store::new();
+        for (key, elements, max_bytes) in [("a", ["1", "2"], 3000), ("b", ["2", "3"], 0)] {
+            let mut hll = HyperLogLog::new();
+            for element in elements {
+                hll.add(element.as_bytes());
+            }
+            store.set(Key::from(key), Value::Str(hll.to_bytes(max_bytes)));
+        }
+
+        assert_eq!(pfmerge(&store, "dst a").await, Ok(Frame::ok()));
+        assert_eq!(hll(&store, "dst").encoding(), Encoding::Sparse);
+        assert_eq!(pfmerge(&store, "dst b missing").await, Ok(Frame::ok()));
+        let mut merged = hll(&store, "dst");
+        assert_eq!(merged.encoding(), Encoding::Dense);
+        assert_eq!(merged.count(), 3);
+
+        assert_eq!(pfmerge(&store, "empty").await, Ok(Frame::ok()));
+        assert_eq!(hll(&store, "empty").count(), 0);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, types::hyperloglog};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `PFSELFTEST`
+pub struct PFSelfTestCommand;
+
+impl CommandTrait for PFSelfTestCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+        Ok(CommandWrapper::PFSelfTest(Self))
+    }
+
+    async fn execute(
+        self,
+        _store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        hyperloglog::self_test()?;
+        Ok(Frame::ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::Store;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pfselftest_command() {
+        assert_eq!(
+            PFSelfTestCommand
+                .execute(Store::new(), &mut Client::new())
+                .await,
+            Ok(Frame::ok())
+        );
+    }
+}
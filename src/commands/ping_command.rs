@@ -0,0 +1,63 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+pub struct PingCommand {
+    pub message: Option<Bytes>,
+}
+
+impl CommandTrait for PingCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let message = parts.next();
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::Ping(Self { message }))
+    }
+
+    async fn execute(
+        self,
+        _store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        match self.message {
+            Some(message) => Ok(Frame::bulk(message)),
+            None => Ok(Frame::Simple("PONG".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::utils::inline_parts;
+
+    use super::*;
+
+    #[test]
+    fn test_ping_command_from_input() {
+        let input = "ping hello".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match PingCommand::from_parts(parts).unwrap() {
+            CommandWrapper::Ping(cmd) => {
+                assert_eq!(cmd.message, Some(Bytes::from("hello")));
+            }
+            _ => panic!("Expected a Ping command"),
+        };
+    }
+
+    #[test]
+    fn test_ping_command_from_input_too_many_arguments() {
+        let input = "ping hello world".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match PingCommand::from_parts(parts) {
+            Err(e) => assert_eq!(e, ParseError::TooManyArguments.to_string()),
+            _ => panic!("Expected an error"),
+        };
+    }
+}
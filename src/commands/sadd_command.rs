@@ -0,0 +1,91 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+    types::set::Set,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `SADD key member [member ...]`
+pub struct SAddCommand {
+    pub key: Key,
+    pub members: Vec<Bytes>,
+}
+
+impl CommandTrait for SAddCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let members: Vec<Bytes> = parts.collect();
+
+        if members.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+
+        Ok(CommandWrapper::SAdd(Self { key, members }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let max_intset_entries = store.config.set_max_intset_entries;
+        let added = store.upsert(
+            &self.key,
+            || Value::Set(Set::new()),
+            |value| {
+                let set = value.as_set_mut()?;
+                Ok::<_, String>(
+                    self.members
+                        .iter()
+                        .filter(|member| set.insert(member, max_intset_entries))
+                        .count(),
+                )
+            },
+        )?;
+        Ok(Frame::Integer(added as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, WRONGTYPE},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sadd_command() {
+        let store = Store::new();
+        let sadd = |input: &str| match SAddCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::SAdd(cmd)) => cmd,
+            _ => panic!("Expected an SAdd command"),
+        };
+        let mut client = Client::new();
+
+        assert_eq!(
+            sadd("set 1 2 2").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            sadd("set 2 a").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            store.get(&Key::from("set")),
+            Some(Value::Set(["1", "2", "a"].into_iter().collect()))
+        );
+
+        store.set(Key::from("string"), Value::Str(Bytes::from("value")));
+        assert_eq!(
+            sadd("string a").execute(store.clone(), &mut client).await,
+            Err(WRONGTYPE.to_string())
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `SCARD key`
+pub struct SCardCommand {
+    pub key: Key,
+}
+
+impl CommandTrait for SCardCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::SCard(Self { key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store
+            .read(&self.key, |value| value.as_set().map(|set| set.len()))
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scard_command() {
+        let store = Store::new();
+        let set = Value::Set(["a", "b"].into_iter().collect());
+        store.upsert(&Key::from("set"), || set, |_| ());
+
+        let scard = |key: &'static str| SCardCommand {
+            key: Key::from(key),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            scard("set").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            scard("missing").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(0))
+        );
+    }
+}
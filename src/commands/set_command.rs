@@ -1,10 +1,41 @@
-use std::str::SplitWhitespace;
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::{Key, SetOptions, Value},
+    utils::current_epoch_millis,
+};
 
-use crate::{parser::utils::ParseError, store::Value};
+use super::{CommandParts, CommandTrait, CommandWrapper};
 
-use super::{CommandTrait, CommandWrapper};
+/// `SETNX`, `SETEX`, `PSETEX` and `GETSET` are `SET` with an option baked in
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SetVariant {
+    /// `SET key value [NX | XX] [GET] [EX seconds | PX milliseconds | EXAT unix-time-seconds | PXAT unix-time-milliseconds | KEEPTTL]`
+    Set,
+    /// `SETNX key value` -- `SET key value NX`, replying whether the key was set
+    SetNx,
+    /// `SETEX key seconds value` -- `SET key value EX seconds`
+    SetEx,
+    /// `PSETEX key milliseconds value` -- `SET key value PX milliseconds`
+    PSetEx,
+    /// `GETSET key value` -- `SET key value GET`
+    GetSet,
+}
+
+impl SetVariant {
+    fn name(&self) -> &'static str {
+        match self {
+            SetVariant::Set => "set",
+            SetVariant::SetNx => "setnx",
+            SetVariant::SetEx => "setex",
+            SetVariant::PSetEx => "psetex",
+            SetVariant::GetSet => "getset",
+        }
+    }
+}
 
-#[derive(PartialEq)]
+#[derive(Debug, PartialEq)]
 pub enum SetXxNx {
     /// Set the key only if it already exists
     Xx,
@@ -12,101 +43,227 @@ pub enum SetXxNx {
     Nx,
 }
 
+/// How the time to live of the key is set, only one can be given
+#[derive(Debug, PartialEq)]
+pub enum SetExpiry {
+    /// `EX seconds`
+    Ex(i64),
+    /// `PX milliseconds`
+    Px(i64),
+    /// `EXAT unix-time-seconds`
+    ExAt(i64),
+    /// `PXAT unix-time-milliseconds`
+    PxAt(i64),
+    /// `KEEPTTL` -- Retain the time to live of the key
+    KeepTtl,
+}
+
+impl SetExpiry {
+    /// Resolves the time to milliseconds since UNIX epoch.
+    ///
+    /// Returns `None` for `KEEPTTL`, and an error naming `command` if the time isn't
+    /// positive or overflows.
+    pub fn expires_at(&self, now: u128, command: &str) -> Result<Option<u128>, String> {
+        let (time, multiplier, relative) = match *self {
+            SetExpiry::Ex(time) => (time, 1000, true),
+            SetExpiry::Px(time) => (time, 1, true),
+            SetExpiry::ExAt(time) => (time, 1000, false),
+            SetExpiry::PxAt(time) => (time, 1, false),
+            SetExpiry::KeepTtl => return Ok(None),
+        };
+
+        let millis = (time > 0)
+            .then(|| time.checked_mul(multiplier))
+            .flatten()
+            .and_then(|millis| match relative {
+                true => millis.checked_add(now as i64),
+                false => Some(millis),
+            })
+            .ok_or(format!("invalid expire time in '{command}' command"))?;
+        Ok(Some(millis as u128))
+    }
+}
+
 pub struct SetCommandOptions {
     /// Set the key only if it already exists or does not exist (`x` is for existence)
     pub x: Option<SetXxNx>,
     /// Get the value of the key before setting it
-    pub get: Option<bool>,
-    /// timestamp-milliseconds -- Set the specified Unix time at which the key will expire, in milliseconds (a positive integer)
-    pub pxat: Option<u64>,
-    /// Retain the time to live of the key
-    pub keep_ttl: Option<bool>,
+    pub get: bool,
+    /// Set or retain the time to live of the key, or discard it if `None`
+    pub expiry: Option<SetExpiry>,
 }
 
 impl SetCommandOptions {
     fn get_options_from_parts(
-        parts: &mut SplitWhitespace<'_>,
+        parts: &mut CommandParts,
     ) -> Result<Option<SetCommandOptions>, String> {
         let mut options = SetCommandOptions {
             x: None,
-            get: None,
-            pxat: None,
-            keep_ttl: None,
+            get: false,
+            expiry: None,
         };
 
         while let Some(part) = parts.next() {
-            match part.to_lowercase().as_str() {
-                "xx" => {
-                    if options.x == Some(SetXxNx::Nx) {
+            let option = lowercase(&part);
+            match option.as_str() {
+                "xx" | "nx" => {
+                    if options.x.is_some() {
                         return Err(ParseError::InvalidCommandOptions(
                             "Cannot use nx and xx together",
                         )
                         .to_string());
                     }
-                    options.x = Some(SetXxNx::Xx);
+                    options.x = Some(match option.as_str() {
+                        "xx" => SetXxNx::Xx,
+                        _ => SetXxNx::Nx,
+                    });
                 }
-                "nx" => {
-                    if options.x == Some(SetXxNx::Xx) {
+                "get" => options.get = true,
+                "ex" | "px" | "exat" | "pxat" | "keepttl" => {
+                    if options.expiry.is_some() {
                         return Err(ParseError::InvalidCommandOptions(
-                            "Cannot use nx and xx together",
+                            "Cannot use more than one of ex, px, exat, pxat and keepttl",
                         )
                         .to_string());
                     }
-                    options.x = Some(SetXxNx::Nx);
+                    let mut time = || -> Result<i64, String> {
+                        parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)
+                    };
+                    options.expiry = Some(match option.as_str() {
+                        "ex" => SetExpiry::Ex(time()?),
+                        "px" => SetExpiry::Px(time()?),
+                        "exat" => SetExpiry::ExAt(time()?),
+                        "pxat" => SetExpiry::PxAt(time()?),
+                        _ => SetExpiry::KeepTtl,
+                    });
+                }
+                _ => {
+                    return Err(ParseError::InvalidCommandOptions(&format!(
+                        "Unsupported option {}",
+                        String::from_utf8_lossy(&part)
+                    ))
+                    .to_string())
                 }
-                "get" => options.get = Some(true),
-                "keepttl" => options.keep_ttl = Some(true),
-                // TODO: ex | px | exat | pxat
-                _ => break,
             }
         }
 
-        // TODO: if all options fields are None, return None
+        if options.x.is_none() && !options.get && options.expiry.is_none() {
+            return Ok(None);
+        }
 
         Ok(Some(options))
     }
 }
 
 pub struct SetCommand {
-    pub key: String,
+    pub key: Key,
     pub value: Value,
     pub options: Option<SetCommandOptions>,
+    pub variant: SetVariant,
 }
 
-impl CommandTrait for SetCommand {
-    fn from_parts(mut parts: SplitWhitespace<'_>) -> Result<CommandWrapper, String> {
+impl SetCommand {
+    pub fn from_parts_as(
+        variant: SetVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
         let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let mut time = || parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?);
+        let expiry = match variant {
+            SetVariant::SetEx => Some(SetExpiry::Ex(time()?)),
+            SetVariant::PSetEx => Some(SetExpiry::Px(time()?)),
+            _ => None,
+        };
         let value = parts.next().ok_or(ParseError::MissingValue.to_string())?;
 
-        let options = SetCommandOptions::get_options_from_parts(&mut parts)?;
+        let options = match variant {
+            SetVariant::Set => SetCommandOptions::get_options_from_parts(&mut parts)?,
+            SetVariant::SetNx => Some(SetCommandOptions {
+                x: Some(SetXxNx::Nx),
+                get: false,
+                expiry: None,
+            }),
+            SetVariant::SetEx | SetVariant::PSetEx => Some(SetCommandOptions {
+                x: None,
+                get: false,
+                expiry,
+            }),
+            SetVariant::GetSet => Some(SetCommandOptions {
+                x: None,
+                get: true,
+                expiry: None,
+            }),
+        };
+        if variant != SetVariant::Set && parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
 
         Ok(CommandWrapper::Set(Self {
-            key: key.to_string(),
-            value: Value::Str(value.to_string()),
+            key,
+            value: Value::string(value),
             options,
+            variant,
         }))
     }
+}
+
+impl CommandTrait for SetCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(SetVariant::Set, parts)
+    }
 
-    async fn execute(self, store: crate::store::ConcurrentStore) -> Result<String, String> {
-        // TODO: Implement executing the options
-        store.set(self.key, self.value);
-        Ok("OK".to_string())
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let Some(options) = self.options else {
+            store.set(self.key, self.value);
+            return Ok(Frame::ok());
+        };
+
+        let expires_at = match &options.expiry {
+            Some(expiry) => expiry.expires_at(current_epoch_millis(), self.variant.name())?,
+            None => None,
+        };
+        let store_options = SetOptions {
+            nx: options.x == Some(SetXxNx::Nx),
+            xx: options.x == Some(SetXxNx::Xx),
+            expires_at,
+            keep_ttl: options.expiry == Some(SetExpiry::KeepTtl),
+            get: options.get,
+        };
+
+        let (set, previous) = store.set_with(self.key, self.value, store_options)?;
+        if self.variant == SetVariant::SetNx {
+            return Ok(Frame::Integer(set as i64));
+        }
+        match (options.get, set) {
+            // With GET, the previous value is the reply whether the key was set or not
+            (true, _) => Ok(previous.map_or(Frame::Null, Frame::from)),
+            (false, true) => Ok(Frame::ok()),
+            (false, false) => Ok(Frame::Null),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use bytes::Bytes;
+
+    use crate::{parser::utils::inline_parts, store::Store};
+
     use super::*;
 
     #[test]
     fn test_set_command_from_input() {
         let input = "set key value".to_string();
-        let mut parts = input.split_whitespace();
+        let mut parts = inline_parts(&input);
         parts.next(); // Skip the command
         match SetCommand::from_parts(parts).unwrap() {
             CommandWrapper::Set(cmd) => {
                 assert_eq!(cmd.key, "key");
-                assert_eq!(cmd.value, Value::Str("value".to_string()));
+                assert_eq!(cmd.value, Value::Str(Bytes::from("value")));
             }
             _ => panic!("Expected a Set command"),
         };
@@ -115,7 +272,7 @@ mod tests {
     #[test]
     fn test_set_command_from_input_missing_key() {
         let input = "set".to_string();
-        let mut parts = input.split_whitespace();
+        let mut parts = inline_parts(&input);
         parts.next(); // Skip the command
         match SetCommand::from_parts(parts) {
             Err(e) => assert_eq!(e, ParseError::MissingKey.to_string()),
@@ -126,11 +283,132 @@ mod tests {
     #[test]
     fn test_set_command_from_input_missing_value() {
         let input = "set key".to_string();
-        let mut parts = input.split_whitespace();
+        let mut parts = inline_parts(&input);
         parts.next(); // Skip the command
         match SetCommand::from_parts(parts) {
             Err(e) => assert_eq!(e, ParseError::MissingValue.to_string()),
             _ => panic!("Expected an error"),
         };
     }
+
+    fn options_of(input: &str) -> Result<Option<SetCommandOptions>, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match SetCommand::from_parts(parts)? {
+            CommandWrapper::Set(cmd) => Ok(cmd.options),
+            _ => panic!("Expected a Set command"),
+        }
+    }
+
+    #[test]
+    fn test_set_command_from_input_with_options() {
+        assert!(options_of("set key value").unwrap().is_none());
+
+        let options = options_of("set key value NX get px 100").unwrap().unwrap();
+        assert_eq!(options.x, Some(SetXxNx::Nx));
+        assert!(options.get);
+        assert_eq!(options.expiry, Some(SetExpiry::Px(100)));
+
+        let options = options_of("set key value keepttl xx").unwrap().unwrap();
+        assert_eq!(options.x, Some(SetXxNx::Xx));
+        assert_eq!(options.expiry, Some(SetExpiry::KeepTtl));
+    }
+
+    #[test]
+    fn test_set_command_from_input_conflicting_options() {
+        for input in [
+            "set key value nx xx",
+            "set key value ex 10 keepttl",
+            "set key value ex 10 px 100",
+            "set key value pxat 1 exat 1",
+            "set key value ex",
+            "set key value ex ten",
+            "set key value nope",
+        ] {
+            assert!(options_of(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_set_expiry_expires_at() {
+        assert_eq!(SetExpiry::Ex(2).expires_at(1000, "set"), Ok(Some(3000)));
+        assert_eq!(SetExpiry::Px(2).expires_at(1000, "set"), Ok(Some(1002)));
+        assert_eq!(SetExpiry::ExAt(2).expires_at(1000, "set"), Ok(Some(2000)));
+        assert_eq!(SetExpiry::PxAt(2).expires_at(1000, "set"), Ok(Some(2)));
+        assert_eq!(SetExpiry::KeepTtl.expires_at(1000, "set"), Ok(None));
+        assert!(SetExpiry::Ex(0).expires_at(1000, "set").is_err());
+        assert!(SetExpiry::Px(-1).expires_at(1000, "set").is_err());
+        assert!(SetExpiry::Ex(i64::MAX).expires_at(1000, "set").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_command_execute_nx_get() {
+        let store = Store::new();
+        let mut client = Client::new();
+        let set = |input: &str| match SetCommand::from_parts({
+            let mut parts = inline_parts(input);
+            parts.next(); // Skip the command
+            parts
+        }) {
+            Ok(CommandWrapper::Set(cmd)) => cmd,
+            _ => panic!("Expected a Set command"),
+        };
+
+        let frame = set("set key 1 nx get px 100000")
+            .execute(store.clone(), &mut client)
+            .await;
+        assert_eq!(frame, Ok(Frame::Null));
+        let frame = set("set key 2 nx get")
+            .execute(store.clone(), &mut client)
+            .await;
+        assert_eq!(frame, Ok(Frame::bulk("1")));
+        let frame = set("set key 2 nx")
+            .execute(store.clone(), &mut client)
+            .await;
+        assert_eq!(frame, Ok(Frame::Null));
+        let frame = set("set key 3 xx keepttl")
+            .execute(store.clone(), &mut client)
+            .await;
+        assert_eq!(frame, Ok(Frame::ok()));
+
+        assert_eq!(store.get(&Key::from("key")), Some(Value::Int(3)));
+        assert!(matches!(store.expires_at(&Key::from("key")), Some(Some(_))));
+    }
+
+    #[tokio::test]
+    async fn test_set_variants() {
+        let store = Store::new();
+        let mut client = Client::new();
+        let set =
+            |variant, input: &str| match SetCommand::from_parts_as(variant, inline_parts(input)) {
+                Ok(CommandWrapper::Set(cmd)) => Ok(cmd),
+                Ok(_) => panic!("Expected a Set command"),
+                Err(e) => Err(e),
+            };
+
+        for (variant, input, expected) in [
+            (SetVariant::SetNx, "key 1", Frame::Integer(1)),
+            (SetVariant::SetNx, "key 2", Frame::Integer(0)),
+            (SetVariant::GetSet, "key 3", Frame::bulk("1")),
+            (SetVariant::GetSet, "new 1", Frame::Null),
+            (SetVariant::PSetEx, "key 100000 4", Frame::ok()),
+        ] {
+            let frame = set(variant, input)
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await;
+            assert_eq!(frame, Ok(expected), "{input}");
+        }
+        assert_eq!(store.get(&Key::from("key")), Some(Value::Int(4)));
+        assert!(matches!(store.expires_at(&Key::from("key")), Some(Some(_))));
+
+        assert_eq!(
+            set(SetVariant::SetEx, "key 0 value")
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await,
+            Err("invalid expire time in 'setex' command".to_string())
+        );
+        assert!(set(SetVariant::SetNx, "key value nx").is_err());
+    }
 }
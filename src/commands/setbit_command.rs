@@ -0,0 +1,106 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    config::Config,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::bitmap,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `SETBIT key offset value`
+pub struct SetBitCommand {
+    pub key: Key,
+    /// Not checked against `proto-max-bulk-len` until executed
+    pub offset: i64,
+    pub bit: bool,
+}
+
+impl CommandTrait for SetBitCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let offset = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let offset = parse_int(&offset).map_err(|_| OFFSET_OUT_OF_RANGE)?;
+        let bit = match &parts.next().ok_or(ParseError::MissingValue.to_string())?[..] {
+            b"0" => false,
+            b"1" => true,
+            _ => return Err("bit is not an integer or out of range".to_string()),
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::SetBit(Self { key, offset, bit }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let offset = check_bit_offset(self.offset, &store.config)?;
+        let previous = store.upsert(
+            &self.key,
+            || Value::Str(Bytes::new()),
+            |value| value.str_mut(|buf| bitmap::set_bit(buf, offset, self.bit)),
+        )?;
+        Ok(Frame::Integer(previous as i64))
+    }
+}
+
+pub const OFFSET_OUT_OF_RANGE: &str = "bit offset is not an integer or out of range";
+
+/// Like Redis, bits can only be addressed within a string of up to `proto-max-bulk-len`
+pub fn check_bit_offset(offset: i64, config: &Config) -> Result<usize, String> {
+    usize::try_from(offset)
+        .ok()
+        .filter(|offset| offset / 8 < config.proto_max_bulk_len)
+        .ok_or(OFFSET_OUT_OF_RANGE.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_setbit_command() {
+        let store = Store::new();
+        let setbit = |input: &str| match SetBitCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::SetBit(cmd)) => Ok(cmd),
+            Ok(_) => panic!("Expected a SetBit command"),
+            Err(e) => Err(e),
+        };
+        let mut client = Client::new();
+        for (input, expected) in [("key 7 1", 0), ("key 7 0", 1), ("key 17 1", 0)] {
+            assert_eq!(
+                setbit(input)
+                    .unwrap()
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+        assert_eq!(
+            store.get(&Key::from("key")),
+            Some(Value::Str(Bytes::from_static(&[0x00, 0x00, 0x40])))
+        );
+
+        assert_eq!(
+            setbit("key 7 2").err(),
+            Some("bit is not an integer or out of range".to_string())
+        );
+        assert_eq!(
+            setbit("key 4294967296 1")
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await,
+            Err(OFFSET_OUT_OF_RANGE.to_string())
+        );
+    }
+}
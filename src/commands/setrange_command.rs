@@ -0,0 +1,130 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+};
+
+use super::{append_command::check_string_len, CommandParts, CommandTrait, CommandWrapper};
+
+/// `SETRANGE key offset value`
+pub struct SetRangeCommand {
+    pub key: Key,
+    pub offset: usize,
+    pub value: Bytes,
+}
+
+impl CommandTrait for SetRangeCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let offset = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let offset = usize::try_from(offset).map_err(|_| "offset is out of range")?;
+        let value = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::SetRange(Self { key, offset, value }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let end = self.offset.saturating_add(self.value.len());
+        let len = store.atomically(|| {
+            // Like Redis, setting nothing doesn't create the key
+            let len = store.update(&self.key, |value| {
+                let len = value.as_str()?.len();
+                if self.value.is_empty() {
+                    return Ok(len);
+                }
+                check_string_len(end, &store.config)?;
+                value.str_mut(|buf| {
+                    if buf.len() < end {
+                        // Zero padded past the end of the string
+                        buf.resize(end, 0);
+                    }
+                    buf[self.offset..end].copy_from_slice(&self.value);
+                    buf.len()
+                })
+            });
+            match len {
+                Some(len) => len,
+                None if self.value.is_empty() => Ok(0),
+                None => {
+                    check_string_len(end, &store.config)?;
+                    let mut bytes = vec![0; self.offset];
+                    bytes.extend_from_slice(&self.value);
+                    store.set(self.key.clone(), Value::Str(Bytes::from(bytes)));
+                    Ok(end)
+                }
+            }
+        })?;
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_setrange_command() {
+        let store = Store::new();
+        store.set(Key::from("key"), Value::Str(Bytes::from("Hello World")));
+
+        let setrange = |input: &str| match SetRangeCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::SetRange(cmd)) => Ok(cmd),
+            Ok(_) => panic!("Expected a SetRange command"),
+            Err(e) => Err(e),
+        };
+        let mut client = Client::new();
+        for (input, expected) in [("key 6 Redis", 11), ("key 11 !", 12), ("padded 3 abc", 6)] {
+            assert_eq!(
+                setrange(input)
+                    .unwrap()
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+        assert_eq!(
+            store.get(&Key::from("key")),
+            Some(Value::Str("Hello Redis!".into()))
+        );
+        assert_eq!(
+            store.get(&Key::from("padded")),
+            Some(Value::Str("\0\0\0abc".into()))
+        );
+
+        let empty = SetRangeCommand {
+            key: Key::from("empty"),
+            offset: 5,
+            value: Bytes::new(),
+        };
+        assert_eq!(
+            empty.execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(store.get(&Key::from("empty")), None);
+
+        assert_eq!(
+            setrange("key -1 a").err(),
+            Some("offset is out of range".to_string())
+        );
+        assert_eq!(
+            setrange("key 536870912 a")
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await,
+            Err("string exceeds maximum allowed size (proto-max-bulk-len)".to_string())
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Store},
+    types::set::Set,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The set algebra commands only differ in how the sets are combined
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SInterVariant {
+    /// `SINTER key [key ...]` -- The members of every set
+    SInter,
+    /// `SUNION key [key ...]` -- The members of any set
+    SUnion,
+    /// `SDIFF key [key ...]` -- The members of the first set that aren't in the others
+    SDiff,
+}
+
+pub struct SInterCommand {
+    pub keys: Vec<Key>,
+    pub variant: SInterVariant,
+}
+
+impl SInterCommand {
+    pub fn from_parts_as(
+        variant: SInterVariant,
+        parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let keys: Vec<Key> = parts.collect();
+
+        if keys.is_empty() {
+            return Err(ParseError::MissingKeys.to_string());
+        }
+
+        Ok(CommandWrapper::SInter(Self { keys, variant }))
+    }
+}
+
+impl CommandTrait for SInterCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(SInterVariant::SInter, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let set = combine_sets(&store, &self.keys, self.variant)?;
+        Ok(Frame::Set(set.iter().map(Frame::bulk).collect()))
+    }
+}
+
+/// Combines the sets of `keys` as one snapshot, a missing key being an empty set.
+///
+/// Every key must hold a set, even the ones that don't change the result.
+pub fn combine_sets(store: &Store, keys: &[Key], variant: SInterVariant) -> Result<Set, String> {
+    let max_intset_entries = store.config.set_max_intset_entries;
+    // Only one set can be read at a time, so the members are copied out of the
+    // smallest (or the first) set and then filtered through the others
+    let members_of = |key: &Key| {
+        store
+            .read(key, |value| value.as_set().map(|set| set.iter().collect()))
+            .unwrap_or(Ok(Vec::new()))
+    };
+
+    store.atomically(|| {
+        let mut lens = Vec::with_capacity(keys.len());
+        for key in keys {
+            let len = store.read(key, |value| value.as_set().map(Set::len));
+            lens.push(len.transpose()?.unwrap_or(0));
+        }
+
+        let mut result = Set::new();
+        let members: Vec<Bytes> = match variant {
+            SInterVariant::SUnion => {
+                for key in keys {
+                    store.read(key, |value| {
+                        for member in value.as_set().iter().flat_map(|set| set.iter()) {
+                            result.insert(&member, max_intset_entries);
+                        }
+                    });
+                }
+                return Ok(result);
+            }
+            SInterVariant::SInter => {
+                let (smallest, _) = lens
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, len)| **len)
+                    .expect("there is at least one key");
+                let mut members = members_of(&keys[smallest])?;
+                for (i, key) in keys.iter().enumerate() {
+                    if i == smallest || members.is_empty() {
+                        continue;
+                    }
+                    let set = store.read(key, |value| {
+                        members.retain(|member| value.as_set().is_ok_and(|s| s.contains(member)))
+                    });
+                    if set.is_none() {
+                        members.clear();
+                    }
+                }
+                members
+            }
+            SInterVariant::SDiff => {
+                let mut members = members_of(&keys[0])?;
+                for key in &keys[1..] {
+                    if members.is_empty() {
+                        break;
+                    }
+                    store.read(key, |value| {
+                        members.retain(|member| !value.as_set().is_ok_and(|s| s.contains(member)))
+                    });
+                }
+                members
+            }
+        };
+        for member in members {
+            result.insert(&member, max_intset_entries);
+        }
+        Ok(result)
+    })
+}
+
+/// Counts the members of every set of `keys`, stopping at `limit` members, as
+/// one snapshot.
+///
+/// Rather than intersecting the sets, each member of the smallest set is looked
+/// up in the others, so that reaching `limit` saves the rest of the work.
+pub fn intersection_len(store: &Store, keys: &[Key], limit: usize) -> Result<usize, String> {
+    store.atomically(|| {
+        let mut lens = Vec::with_capacity(keys.len());
+        for (i, key) in keys.iter().enumerate() {
+            let len = store.read(key, |value| value.as_set().map(Set::len));
+            lens.push((len.transpose()?.unwrap_or(0), i));
+        }
+        // Members of the smallest sets are the likeliest to be missing
+        lens.sort_unstable();
+        if lens[0].0 == 0 {
+            return Ok(0);
+        }
+
+        let smallest = &keys[lens[0].1];
+        let members: Vec<Bytes> = store
+            .read(smallest, |value| {
+                value.as_set().map(|set| set.iter().collect())
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let mut len = 0;
+        for member in members {
+            let in_all = lens[1..].iter().all(|&(_, i)| {
+                store
+                    .read(&keys[i], |value| {
+                        value.as_set().is_ok_and(|s| s.contains(&member))
+                    })
+                    .unwrap_or(false)
+            });
+            if in_all {
+                len += 1;
+                if len == limit {
+                    break;
+                }
+            }
+        }
+        Ok(len)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Value, WRONGTYPE},
+    };
+
+    use super::*;
+
+    fn store_with_sets() -> ConcurrentStore {
+        let store = Store::new();
+        for (key, members) in [
+            ("a", &["1", "2", "3", "x"][..]),
+            ("b", &["2", "3", "4"][..]),
+        ] {
+            let set = Value::Set(members.iter().collect());
+            store.upsert(&Key::from(key), || set, |_| ());
+        }
+        store
+    }
+
+    fn members(store: &Store, keys: &str, variant: SInterVariant) -> Result<Vec<String>, String> {
+        let keys: Vec<Key> = inline_parts(keys).collect();
+        let mut members: Vec<String> = combine_sets(store, &keys, variant)?
+            .iter()
+            .map(|member| String::from_utf8_lossy(&member).to_string())
+            .collect();
+        members.sort();
+        Ok(members)
+    }
+
+    #[test]
+    fn test_combine_sets() {
+        let store = store_with_sets();
+        assert_eq!(
+            members(&store, "a b", SInterVariant::SInter),
+            Ok(vec!["2".into(), "3".into()])
+        );
+        assert_eq!(
+            members(&store, "a b missing", SInterVariant::SInter),
+            Ok(vec![])
+        );
+        assert_eq!(
+            members(&store, "a b missing", SInterVariant::SUnion),
+            Ok(vec!["1", "2", "3", "4", "x"]
+                .into_iter()
+                .map(String::from)
+                .collect())
+        );
+        assert_eq!(
+            members(&store, "a missing b", SInterVariant::SDiff),
+            Ok(vec!["1".into(), "x".into()])
+        );
+        assert_eq!(
+            members(&store, "missing a", SInterVariant::SDiff),
+            Ok(vec![])
+        );
+
+        // Every key is checked, even when the result is known to be empty
+        store.set(Key::from("string"), Value::Str(Bytes::from("value")));
+        assert_eq!(
+            members(&store, "missing string", SInterVariant::SInter),
+            Err(WRONGTYPE.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sinter_command() {
+        let store = store_with_sets();
+        let mut parts = inline_parts("sinter a b");
+        parts.next(); // Skip the command
+        let CommandWrapper::SInter(cmd) = SInterCommand::from_parts(parts).unwrap() else {
+            panic!("Expected an SInter command");
+        };
+        // The intersection is an intset, so it's sorted
+        assert_eq!(
+            cmd.execute(store, &mut Client::new()).await,
+            Ok(Frame::Set(vec![Frame::bulk("2"), Frame::bulk("3")]))
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{sinter_command::intersection_len, CommandParts, CommandTrait, CommandWrapper};
+
+/// `SINTERCARD numkeys key [key ...] [LIMIT limit]`
+pub struct SInterCardCommand {
+    pub keys: Vec<Key>,
+    /// Stop counting at this many members, `0` meaning no limit
+    pub limit: usize,
+}
+
+impl CommandTrait for SInterCardCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let num_keys = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        if num_keys <= 0 {
+            return Err("numkeys should be greater than 0".to_string());
+        }
+        let keys: Vec<Key> = parts.by_ref().take(num_keys as usize).collect();
+        if keys.len() < num_keys as usize {
+            return Err("Number of keys can't be greater than number of args".to_string());
+        }
+
+        let mut limit = 0;
+        while let Some(option) = parts.next() {
+            if lowercase(&option) != "limit" {
+                return Err(ParseError::InvalidCommandOptions(&format!(
+                    "Unsupported option {}",
+                    String::from_utf8_lossy(&option)
+                ))
+                .to_string());
+            }
+            let value = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+            limit = usize::try_from(value).map_err(|_| "LIMIT can't be negative")?;
+        }
+
+        Ok(CommandWrapper::SInterCard(Self { keys, limit }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let limit = match self.limit {
+            0 => usize::MAX,
+            limit => limit,
+        };
+        let len = intersection_len(&store, &self.keys, limit)?;
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        resp::Protocol,
+        store::{Store, Value, WRONGTYPE},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sintercard_command() {
+        let store = Store::new();
+        for (key, members) in [("a", &["1", "2", "3"][..]), ("b", &["2", "3", "4"][..])] {
+            let set = Value::Set(members.iter().collect());
+            store.upsert(&Key::from(key), || set, |_| ());
+        }
+
+        let sintercard = |input: &str| SInterCardCommand::from_parts(inline_parts(input));
+        let mut client = Client::new();
+        for (input, expected) in [
+            ("2 a b", 2),
+            ("2 a b limit 1", 1),
+            ("2 a b limit 0", 2),
+            ("2 a b limit 5", 2),
+            ("2 a missing", 0),
+            ("1 a", 3),
+        ] {
+            let Ok(CommandWrapper::SInterCard(cmd)) = sintercard(input) else {
+                panic!("Expected an SInterCard command");
+            };
+            assert_eq!(
+                cmd.execute(store.clone(), &mut client).await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+
+        store.set(Key::from("s"), Value::Str("x".into()));
+        let Ok(CommandWrapper::SInterCard(cmd)) = sintercard("3 a b s limit 1") else {
+            panic!("Expected an SInterCard command");
+        };
+        assert_eq!(
+            cmd.execute(store.clone(), &mut client).await,
+            Err(WRONGTYPE.to_string())
+        );
+
+        let error = |input: &str| sintercard(input).err().unwrap();
+        assert_eq!(error("0 a"), "numkeys should be greater than 0");
+        assert_eq!(
+            error("3 a b"),
+            "Number of keys can't be greater than number of args"
+        );
+        assert_eq!(
+            Frame::error(error("2 a b limit -1")).to_bytes(Protocol::Resp2),
+            b"-ERR LIMIT can't be negative\r\n"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+};
+
+use super::{
+    sinter_command::{combine_sets, SInterVariant},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `SINTERSTORE`, `SUNIONSTORE` and `SDIFFSTORE destination key [key ...]`
+///
+/// Like their counterparts of [`SInterVariant`], but the result is stored at
+/// `destination`, replacing whatever it held.
+pub struct SInterStoreCommand {
+    pub destination: Key,
+    pub keys: Vec<Key>,
+    pub variant: SInterVariant,
+}
+
+impl SInterStoreCommand {
+    pub fn from_parts_as(
+        variant: SInterVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let destination = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let keys: Vec<Key> = parts.collect();
+
+        if keys.is_empty() {
+            return Err(ParseError::MissingKeys.to_string());
+        }
+
+        Ok(CommandWrapper::SInterStore(Self {
+            destination,
+            keys,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for SInterStoreCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(SInterVariant::SInter, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store.atomically(|| {
+            let set = combine_sets(&store, &self.keys, self.variant)?;
+            let len = set.len();
+            // Like Redis, an empty result deletes the destination
+            match set.is_empty() {
+                true => _ = store.del(&self.destination),
+                false => store.set(self.destination.clone(), Value::Set(set)),
+            }
+            Ok::<_, String>(len)
+        })?;
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sinterstore_variants() {
+        let store = Store::new();
+        for (key, members) in [("a", &["1", "2"][..]), ("b", &["2", "3"][..])] {
+            let set = Value::Set(members.iter().collect());
+            store.upsert(&Key::from(key), || set, |_| ());
+        }
+        store.set(Key::from("dst"), Value::Str(Bytes::from("value")));
+
+        let run = |variant, input: &str| match SInterStoreCommand::from_parts_as(
+            variant,
+            inline_parts(input),
+        ) {
+            Ok(CommandWrapper::SInterStore(cmd)) => cmd,
+            _ => panic!("Expected an SInterStore command"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            run(SInterVariant::SUnion, "dst a b")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(3))
+        );
+        assert_eq!(
+            store.get(&Key::from("dst")),
+            Some(Value::Set(["1", "2", "3"].into_iter().collect()))
+        );
+
+        // The destination can be one of the keys
+        assert_eq!(
+            run(SInterVariant::SDiff, "a a b")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            store.get(&Key::from("a")),
+            Some(Value::Set(["1"].into_iter().collect()))
+        );
+
+        assert_eq!(
+            run(SInterVariant::SInter, "dst a b")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(store.get(&Key::from("dst")), None);
+    }
+}
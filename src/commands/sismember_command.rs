@@ -0,0 +1,122 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands checking set membership only differ in how many members they check
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SIsMemberVariant {
+    /// `SISMEMBER key member` -- Whether the member is in the set
+    SIsMember,
+    /// `SMISMEMBER key member [member ...]` -- Whether each member is in the set
+    SMIsMember,
+}
+
+pub struct SIsMemberCommand {
+    pub key: Key,
+    pub members: Vec<Bytes>,
+    pub variant: SIsMemberVariant,
+}
+
+impl SIsMemberCommand {
+    pub fn from_parts_as(
+        variant: SIsMemberVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let members: Vec<Bytes> = parts.collect();
+
+        if members.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+        if variant == SIsMemberVariant::SIsMember && members.len() > 1 {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::SIsMember(Self {
+            key,
+            members,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for SIsMemberCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(SIsMemberVariant::SIsMember, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let mut found = store
+            .read(&self.key, |value| {
+                let set = value.as_set()?;
+                Ok::<_, String>(
+                    self.members
+                        .iter()
+                        .map(|member| Frame::Integer(set.contains(member) as i64))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_else(|| vec![Frame::Integer(0); self.members.len()]);
+
+        Ok(match self.variant {
+            SIsMemberVariant::SIsMember => found.pop().expect("one member was checked"),
+            SIsMemberVariant::SMIsMember => Frame::Array(found),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sismember_variants() {
+        let store = Store::new();
+        let set = Value::Set(["a", "1"].into_iter().collect());
+        store.upsert(&Key::from("set"), || set, |_| ());
+
+        let run = |variant, input: &str| match SIsMemberCommand::from_parts_as(
+            variant,
+            inline_parts(input),
+        ) {
+            Ok(CommandWrapper::SIsMember(cmd)) => cmd,
+            _ => panic!("Expected an SIsMember command"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            run(SIsMemberVariant::SIsMember, "set 1")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            run(SIsMemberVariant::SMIsMember, "set b a")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Array(vec![Frame::Integer(0), Frame::Integer(1)]))
+        );
+        assert_eq!(
+            run(SIsMemberVariant::SMIsMember, "missing a")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Array(vec![Frame::Integer(0)]))
+        );
+        assert!(SIsMemberCommand::from_parts_as(
+            SIsMemberVariant::SIsMember,
+            inline_parts("set a b")
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `SMEMBERS key`
+pub struct SMembersCommand {
+    pub key: Key,
+}
+
+impl CommandTrait for SMembersCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::SMembers(Self { key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let members = store
+            .read(&self.key, |value| {
+                let set = value.as_set()?;
+                Ok::<_, String>(set.iter().map(Frame::bulk).collect())
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Frame::Set(members))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_smembers_command() {
+        let store = Store::new();
+        let set = Value::Set(["3", "1", "2"].into_iter().collect());
+        store.upsert(&Key::from("set"), || set, |_| ());
+
+        let smembers = |key: &'static str| SMembersCommand {
+            key: Key::from(key),
+        };
+        let mut client = Client::new();
+        // Intsets are sorted
+        assert_eq!(
+            smembers("set").execute(store.clone(), &mut client).await,
+            Ok(Frame::Set(vec![
+                Frame::bulk("1"),
+                Frame::bulk("2"),
+                Frame::bulk("3")
+            ]))
+        );
+        assert_eq!(
+            smembers("missing")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Set(vec![]))
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+    types::set::Set,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `SMOVE source destination member`
+pub struct SMoveCommand {
+    pub source: Key,
+    pub destination: Key,
+    pub member: Bytes,
+}
+
+impl CommandTrait for SMoveCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let source = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let destination = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let member = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::SMove(Self {
+            source,
+            destination,
+            member,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let max_intset_entries = store.config.set_max_intset_entries;
+        let moved = store.atomically(|| {
+            let Some(found) = store
+                .read(&self.source, |value| {
+                    value.as_set().map(|set| set.contains(&self.member))
+                })
+                .transpose()?
+            else {
+                return Ok(false);
+            };
+            store
+                .read(&self.destination, |value| value.as_set().map(|_| ()))
+                .transpose()?;
+
+            // Moving a member to the set it's in leaves it there
+            if !found || self.source == self.destination {
+                return Ok(found);
+            }
+            store.update(&self.source, |value| {
+                value.as_set_mut().map(|set| set.remove(&self.member))
+            });
+            store.upsert(
+                &self.destination,
+                || Value::Set(Set::new()),
+                |value| {
+                    value
+                        .as_set_mut()
+                        .map(|set| set.insert(&self.member, max_intset_entries))
+                },
+            )?;
+            Ok::<_, String>(true)
+        })?;
+        Ok(Frame::Integer(moved as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, WRONGTYPE},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_smove_command() {
+        let store = Store::new();
+        let set = Value::Set(["a", "b"].into_iter().collect());
+        store.upsert(&Key::from("src"), || set, |_| ());
+        store.set(Key::from("string"), Value::Str(Bytes::from("value")));
+
+        let smove = |input: &str| match SMoveCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::SMove(cmd)) => cmd,
+            _ => panic!("Expected an SMove command"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            smove("src dst a").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            smove("src dst c").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(
+            smove("src src b").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            smove("src string b")
+                .execute(store.clone(), &mut client)
+                .await,
+            Err(WRONGTYPE.to_string())
+        );
+        assert_eq!(
+            smove("src dst b").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(store.get(&Key::from("src")), None);
+        assert_eq!(
+            store.get(&Key::from("dst")),
+            Some(Value::Set(["a", "b"].into_iter().collect()))
+        );
+    }
+}
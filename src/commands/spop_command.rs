@@ -0,0 +1,102 @@
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `SPOP key [count]`
+pub struct SPopCommand {
+    pub key: Key,
+    /// Pop this many members and reply with a set, instead of a single member
+    pub count: Option<usize>,
+}
+
+impl CommandTrait for SPopCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let count = match parts.next() {
+            Some(count) => Some(
+                usize::try_from(parse_int(&count)?)
+                    .map_err(|_| "value is out of range, must be positive")?,
+            ),
+            None => None,
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::SPop(Self { key, count }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let popped = store
+            .update(&self.key, |value| {
+                let set = value.as_set_mut()?;
+                let mut rng = rand::thread_rng();
+                let count = self.count.unwrap_or(1).min(set.len());
+                Ok::<_, String>(
+                    (0..count)
+                        .filter_map(|_| set.pop(&mut rng))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(match self.count {
+            None => popped.into_iter().next().map_or(Frame::Null, Frame::bulk),
+            Some(_) => Frame::Set(popped.into_iter().map(Frame::bulk).collect()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spop_command() {
+        let store = Store::new();
+        let set = Value::Set(["a", "b", "c"].into_iter().collect());
+        store.upsert(&Key::from("set"), || set, |_| ());
+
+        let spop = |input: &str| match SPopCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::SPop(cmd)) => cmd,
+            _ => panic!("Expected an SPop command"),
+        };
+        let mut client = Client::new();
+        let Ok(Frame::Bulk(member)) = spop("set").execute(store.clone(), &mut client).await else {
+            panic!("Expected a member");
+        };
+        let Ok(Frame::Set(members)) = spop("set 5").execute(store.clone(), &mut client).await
+        else {
+            panic!("Expected a set");
+        };
+        assert_eq!(members.len(), 2);
+        assert!(!members.contains(&Frame::Bulk(member)));
+        assert_eq!(store.get(&Key::from("set")), None);
+
+        assert_eq!(
+            spop("set").execute(store.clone(), &mut client).await,
+            Ok(Frame::Null)
+        );
+        assert_eq!(
+            spop("set 2").execute(store.clone(), &mut client).await,
+            Ok(Frame::Set(vec![]))
+        );
+        assert!(SPopCommand::from_parts(inline_parts("set -1")).is_err());
+    }
+}
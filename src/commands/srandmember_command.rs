@@ -0,0 +1,136 @@
+use bytes::Bytes;
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `SRANDMEMBER key [count]`
+pub struct SRandMemberCommand {
+    pub key: Key,
+    /// Reply with this many distinct members, or with repetitions if negative,
+    /// instead of a single member
+    pub count: Option<i64>,
+}
+
+impl CommandTrait for SRandMemberCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let count = parts.next().map(|count| parse_int(&count)).transpose()?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+        // Like Redis, the count is capped so the reply fits in memory
+        if count.is_some_and(|count| count.unsigned_abs() > (i64::MAX / 2) as u64) {
+            return Err("value is out of range".to_string());
+        }
+
+        Ok(CommandWrapper::SRandMember(Self { key, count }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let picked = store
+            .read(&self.key, |value| {
+                let set = value.as_set()?;
+                let mut rng = rand::thread_rng();
+                let members: Vec<Bytes> = match self.count {
+                    None => set.random(&mut rng).into_iter().collect(),
+                    Some(count) if count >= 0 => {
+                        let mut members = set.iter().choose_multiple(&mut rng, count as usize);
+                        // `choose_multiple` keeps the order of the set when it can
+                        rand::seq::SliceRandom::shuffle(&mut members[..], &mut rng);
+                        members
+                    }
+                    // Members may repeat, so they're picked one at a time
+                    Some(count) => {
+                        let all: Vec<_> = set.iter().collect();
+                        (0..count.unsigned_abs())
+                            .map(|_| all[rng.gen_range(0..all.len())].clone())
+                            .collect()
+                    }
+                };
+                Ok::<_, String>(members)
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(match self.count {
+            None => picked.into_iter().next().map_or(Frame::Null, Frame::bulk),
+            Some(_) => Frame::Array(picked.into_iter().map(Frame::bulk).collect()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn srandmember(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match SRandMemberCommand::from_parts(parts)? {
+            CommandWrapper::SRandMember(cmd) => {
+                cmd.execute(store.clone(), &mut Client::new()).await
+            }
+            _ => panic!("Expected an SRandMember command"),
+        }
+    }
+
+    fn len(frame: Frame) -> usize {
+        match frame {
+            Frame::Array(frames) => frames.len(),
+            _ => panic!("Expected an array"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_srandmember_command() {
+        let store = Store::new();
+        let set = Value::Set(["a", "b", "c"].into_iter().collect());
+        store.upsert(&Key::from("set"), || set, |_| ());
+
+        let member = srandmember(&store, "srandmember set").await.unwrap();
+        assert!(["a", "b", "c"].iter().any(|m| member == Frame::bulk(*m)));
+
+        assert_eq!(
+            len(srandmember(&store, "srandmember set 2").await.unwrap()),
+            2
+        );
+        assert_eq!(
+            len(srandmember(&store, "srandmember set 5").await.unwrap()),
+            3
+        );
+        assert_eq!(
+            len(srandmember(&store, "srandmember set -5").await.unwrap()),
+            5
+        );
+        assert_eq!(
+            srandmember(&store, "srandmember missing").await,
+            Ok(Frame::Null)
+        );
+        assert_eq!(
+            srandmember(&store, "srandmember missing 2").await,
+            Ok(Frame::Array(vec![]))
+        );
+        // Nothing is removed
+        assert_eq!(
+            store.read(&Key::from("set"), |value| value.as_set().unwrap().len()),
+            Some(3)
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `SREM key member [member ...]`
+pub struct SRemCommand {
+    pub key: Key,
+    pub members: Vec<Bytes>,
+}
+
+impl CommandTrait for SRemCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let members: Vec<Bytes> = parts.collect();
+
+        if members.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+
+        Ok(CommandWrapper::SRem(Self { key, members }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let removed = store
+            .update(&self.key, |value| {
+                let set = value.as_set_mut()?;
+                Ok::<_, String>(self.members.iter().filter(|m| set.remove(m)).count())
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(removed as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_srem_deletes_the_emptied_set() {
+        let store = Store::new();
+        let set = Value::Set(["a", "b"].into_iter().collect());
+        store.upsert(&Key::from("set"), || set, |_| ());
+
+        let srem = |members: &[&'static str]| SRemCommand {
+            key: Key::from("set"),
+            members: members.iter().map(|m| Bytes::from(*m)).collect(),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            srem(&["a", "c"]).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            srem(&["b"]).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(store.get(&Key::from("set")), None);
+        assert_eq!(
+            srem(&["b"]).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(0))
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::Key,
+    types::scan::{cursor_hash, scan},
+};
+
+use super::{
+    hscan_command::{parse_cursor, scan_frame, ScanOptions},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `SSCAN key cursor [MATCH pattern] [COUNT count]`
+pub struct SScanCommand {
+    pub key: Key,
+    pub cursor: u64,
+    pub options: ScanOptions,
+}
+
+impl CommandTrait for SScanCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let cursor = parse_cursor(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let options = ScanOptions::from_parts(parts, false)?;
+
+        Ok(CommandWrapper::SScan(Self {
+            key,
+            cursor,
+            options,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let options = &self.options;
+        let scanned = store
+            .read(&self.key, |value| {
+                let set = value.as_set()?;
+                // Like Redis, intsets are scanned in one go
+                let (cursor, members) = if set.is_intset() {
+                    (0, set.iter().collect())
+                } else {
+                    let members = set.iter().map(|member| (cursor_hash(&member), member));
+                    scan(members, self.cursor, options.count)
+                };
+
+                let elements = members
+                    .into_iter()
+                    .filter(|member| options.matches(member))
+                    .map(Frame::bulk)
+                    .collect();
+                Ok::<_, String>((cursor, elements))
+            })
+            .transpose()?;
+
+        let (cursor, elements) = scanned.unwrap_or_default();
+        Ok(scan_frame(cursor, elements))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+        types::set::Set,
+    };
+
+    use super::*;
+
+    async fn sscan(store: &ConcurrentStore, input: &str) -> Result<(u64, Vec<Frame>), String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        let CommandWrapper::SScan(cmd) = SScanCommand::from_parts(parts)? else {
+            panic!("Expected an SScan command");
+        };
+        match cmd.execute(store.clone(), &mut Client::new()).await? {
+            Frame::Array(mut reply) => {
+                let Some(Frame::Array(elements)) = reply.pop() else {
+                    panic!("Expected the elements");
+                };
+                let Some(Frame::Bulk(cursor)) = reply.pop() else {
+                    panic!("Expected the cursor");
+                };
+                Ok((
+                    std::str::from_utf8(&cursor).unwrap().parse().unwrap(),
+                    elements,
+                ))
+            }
+            _ => panic!("Expected an array"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sscan_intset_in_one_go() {
+        let store = Store::new();
+        let set = Value::Set(["1", "10", "2"].into_iter().collect());
+        store.upsert(&Key::from("set"), || set, |_| ());
+
+        assert_eq!(
+            sscan(&store, "sscan set 0 match 1* count 1").await,
+            Ok((0, vec![Frame::bulk("1"), Frame::bulk("10")]))
+        );
+        assert!(sscan(&store, "sscan set 0 novalues").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sscan_big_set_with_a_cursor() {
+        let store = Store::new();
+        let mut set = Set::new();
+        for i in 0..1000 {
+            set.insert(format!("member:{i}").as_bytes(), 512);
+        }
+        store.upsert(&Key::from("set"), || Value::Set(set), |_| ());
+
+        let mut cursor = 0;
+        let mut members = Vec::new();
+        loop {
+            let (next_cursor, elements) =
+                sscan(&store, &format!("sscan set {cursor}")).await.unwrap();
+            assert!(elements.len() < 1000);
+            members.extend(elements);
+            cursor = next_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
+        members.sort_by_key(|member| format!("{member:?}"));
+        members.dedup();
+        assert_eq!(members.len(), 1000);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `STRLEN key`
+pub struct StrLenCommand {
+    pub key: Key,
+}
+
+impl CommandTrait for StrLenCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::StrLen(Self { key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store
+            .read(&self.key, |value| value.as_str().map(|bytes| bytes.len()))
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_strlen_command() {
+        let store = Store::new();
+        store.set(Key::from("key"), Value::Str(Bytes::from("value")));
+        store.set(Key::from("int"), Value::Int(-10));
+        store.upsert(
+            &Key::from("list"),
+            || Value::List(["a"].into_iter().collect()),
+            |_| (),
+        );
+
+        let strlen = |key: &'static str| StrLenCommand {
+            key: Key::from(key),
+        };
+        let mut client = Client::new();
+        for (key, expected) in [("key", 5), ("int", 3), ("missing", 0)] {
+            assert_eq!(
+                strlen(key).execute(store.clone(), &mut client).await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+        assert!(strlen("list").execute(store, &mut client).await.is_err());
+    }
+}
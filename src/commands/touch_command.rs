@@ -1,16 +1,14 @@
-use std::str::SplitWhitespace;
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
 
-use crate::parser::utils::ParseError;
-
-use super::{CommandTrait, CommandWrapper};
+use super::{CommandParts, CommandTrait, CommandWrapper};
 
 pub struct TouchCommand {
-    pub keys: Vec<String>,
+    pub keys: Vec<Key>,
 }
 
 impl CommandTrait for TouchCommand {
-    fn from_parts(parts: SplitWhitespace<'_>) -> Result<CommandWrapper, String> {
-        let keys = parts.map(|s| s.to_string()).collect::<Vec<String>>();
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        let keys = parts.collect::<Vec<Key>>();
 
         if keys.is_empty() {
             return Err(ParseError::MissingKeys.to_string());
@@ -19,19 +17,25 @@ impl CommandTrait for TouchCommand {
         Ok(CommandWrapper::Touch(Self { keys }))
     }
 
-    async fn execute(self, store: crate::store::ConcurrentStore) -> Result<String, String> {
-        Ok(store.touch_many(self.keys).to_string())
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        Ok(Frame::Integer(store.touch_many(self.keys) as i64))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::parser::utils::inline_parts;
+
     use super::*;
 
     #[test]
     fn test_touch_command_from_input() {
         let input = "touch x y".to_string();
-        let mut parts = input.split_whitespace();
+        let mut parts = inline_parts(&input);
         parts.next(); // Skip the command
         match TouchCommand::from_parts(parts).unwrap() {
             CommandWrapper::Touch(cmd) => {
@@ -44,7 +48,7 @@ mod tests {
     #[test]
     fn test_touch_command_from_input_missing_keys() {
         let input = "touch".to_string();
-        let mut parts = input.split_whitespace();
+        let mut parts = inline_parts(&input);
         parts.next(); // Skip the command
         match TouchCommand::from_parts(parts) {
             Err(e) => assert_eq!(e, ParseError::MissingKeys.to_string()),
@@ -0,0 +1,127 @@
+use crate::{
+    client::Client, parser::utils::ParseError, resp::Frame, store::Key, utils::current_epoch_millis,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands reporting when a key expires only differ in the format of the reply
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TtlVariant {
+    /// `TTL key` -- Seconds left to live
+    Ttl,
+    /// `PTTL key` -- Milliseconds left to live
+    PTtl,
+    /// `EXPIRETIME key` -- UNIX time in seconds at which the key expires
+    ExpireTime,
+    /// `PEXPIRETIME key` -- UNIX time in milliseconds at which the key expires
+    PExpireTime,
+}
+
+pub struct TtlCommand {
+    pub key: Key,
+    pub variant: TtlVariant,
+}
+
+impl TtlCommand {
+    pub fn from_parts_as(
+        variant: TtlVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::Ttl(Self { key, variant }))
+    }
+}
+
+impl CommandTrait for TtlCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(TtlVariant::Ttl, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let expires_at = match store.expires_at(&self.key) {
+            None => return Ok(Frame::Integer(-2)),
+            Some(None) => return Ok(Frame::Integer(-1)),
+            Some(Some(expires_at)) => expires_at,
+        };
+
+        let ttl = expires_at.saturating_sub(current_epoch_millis());
+        let reply = match self.variant {
+            TtlVariant::Ttl => (ttl + 500) / 1000,
+            TtlVariant::PTtl => ttl,
+            TtlVariant::ExpireTime => expires_at / 1000,
+            TtlVariant::PExpireTime => expires_at,
+        };
+        Ok(Frame::Integer(reply as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ExpireCondition, Store, Value},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_ttl_command_from_input() {
+        let input = "pttl key".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match TtlCommand::from_parts_as(TtlVariant::PTtl, parts).unwrap() {
+            CommandWrapper::Ttl(cmd) => {
+                assert_eq!(cmd.key, "key");
+                assert_eq!(cmd.variant, TtlVariant::PTtl);
+            }
+            _ => panic!("Expected a Ttl command"),
+        };
+    }
+
+    #[test]
+    fn test_ttl_command_from_input_missing_key() {
+        let input = "ttl".to_string();
+        let mut parts = inline_parts(&input);
+        parts.next(); // Skip the command
+        match TtlCommand::from_parts(parts) {
+            Err(e) => assert_eq!(e, ParseError::MissingKey.to_string()),
+            _ => panic!("Expected an error"),
+        };
+    }
+
+    #[tokio::test]
+    async fn test_ttl_command_replies() {
+        let store = Store::new();
+        let mut client = Client::new();
+        let key = Key::from("key");
+        let ttl = |variant| TtlCommand {
+            key: key.clone(),
+            variant,
+        };
+
+        let reply = ttl(TtlVariant::Ttl).execute(store.clone(), &mut client);
+        assert_eq!(reply.await, Ok(Frame::Integer(-2)));
+
+        store.set(key.clone(), Value::Str(Bytes::from("value")));
+        let reply = ttl(TtlVariant::Ttl).execute(store.clone(), &mut client);
+        assert_eq!(reply.await, Ok(Frame::Integer(-1)));
+
+        let expires_at = current_epoch_millis() + 10_000;
+        store.expire(&key, expires_at, ExpireCondition::default());
+        let reply = ttl(TtlVariant::Ttl).execute(store.clone(), &mut client);
+        assert_eq!(reply.await, Ok(Frame::Integer(10)));
+        let reply = ttl(TtlVariant::PExpireTime).execute(store.clone(), &mut client);
+        assert_eq!(reply.await, Ok(Frame::Integer(expires_at as i64)));
+    }
+}
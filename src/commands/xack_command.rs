@@ -0,0 +1,91 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client, parser::utils::ParseError, resp::Frame, store::Key, types::stream::StreamId,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `XACK key group id [id ...]`
+pub struct XAckCommand {
+    pub key: Key,
+    pub group: Bytes,
+    pub ids: Vec<StreamId>,
+}
+
+impl CommandTrait for XAckCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let group = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let ids = parts
+            .map(|part| StreamId::parse(&part, 0))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if ids.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+
+        Ok(CommandWrapper::XAck(Self { key, group, ids }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let acked = store
+            .update(&self.key, |value| {
+                let stream = value.as_stream_mut()?;
+                let acked = match stream.group_mut(&self.group) {
+                    Some(group) => self.ids.iter().filter(|&&id| group.ack(id)).count(),
+                    None => 0,
+                };
+                Ok::<_, String>(acked)
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(acked as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+        types::{consumer_group::ConsumerGroup, stream::Stream},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_xack_command() {
+        let store = Store::new();
+        let mut stream = Stream::new();
+        let mut group = ConsumerGroup::new(StreamId::MIN, None);
+        let consumer = Bytes::from("alice");
+        group.create_consumer(&consumer, 0);
+        group.deliver(StreamId::new(1, 0), &consumer, 0);
+        group.deliver(StreamId::new(2, 0), &consumer, 0);
+        stream.create_group("g".into(), group);
+        store.upsert(&Key::from("s"), || Value::Stream(stream), |_| ());
+
+        let xack = |input: &str| match XAckCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::XAck(cmd)) => cmd,
+            _ => panic!("Expected an XAck command"),
+        };
+        let mut client = Client::new();
+        for (input, expected) in [
+            ("s g 1-0 1-0 3-0", 1),
+            ("s nope 2-0", 0),
+            ("missing g 2-0", 0),
+            ("s g 2", 1),
+        ] {
+            assert_eq!(
+                xack(input).execute(store.clone(), &mut client).await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+        assert!(XAckCommand::from_parts(inline_parts("s g")).is_err());
+    }
+}
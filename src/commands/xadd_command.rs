@@ -0,0 +1,179 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::stream::{NewId, Stream, Trim},
+};
+
+use super::{
+    xtrim_command::{parse_trim_limit, parse_trim_strategy, trim_with_limit},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `XADD key [NOMKSTREAM] [<MAXLEN | MINID> [= | ~] threshold [LIMIT count]] <* | id> field value [field value ...]`
+pub struct XAddCommand {
+    pub key: Key,
+    /// Only add to a stream that already exists
+    pub no_mkstream: bool,
+    /// The trim applied right after adding the entry
+    pub trim: Option<Trim>,
+    pub id: NewId,
+    pub fields: Vec<(Bytes, Bytes)>,
+}
+
+impl CommandTrait for XAddCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let wrong_arity = || "wrong number of arguments for 'xadd' command".to_string();
+
+        let mut no_mkstream = false;
+        let mut strategy = None;
+        let mut limit = None;
+        // The options go on until the ID
+        let id = loop {
+            let part = parts.next().ok_or_else(wrong_arity)?;
+            match lowercase(&part).as_str() {
+                "nomkstream" => no_mkstream = true,
+                option @ ("maxlen" | "minid") => {
+                    strategy = Some(parse_trim_strategy(option, &mut parts)?)
+                }
+                "limit" => limit = Some(parse_trim_limit(&mut parts)?),
+                _ => break NewId::parse(&part)?,
+            }
+        };
+
+        let trim = match strategy {
+            Some((strategy, approx)) => Some(trim_with_limit(strategy, approx, limit)?),
+            None if limit.is_some() => {
+                return Err(
+                    "syntax error, LIMIT cannot be used without specifying a trimming strategy"
+                        .to_string(),
+                )
+            }
+            None => None,
+        };
+
+        let rest: Vec<Bytes> = parts.collect();
+        if rest.is_empty() || !rest.len().is_multiple_of(2) {
+            return Err(wrong_arity());
+        }
+        let fields = rest
+            .chunks_exact(2)
+            .map(|pair| (pair[0].clone(), pair[1].clone()))
+            .collect();
+
+        Ok(CommandWrapper::XAdd(Self {
+            key,
+            no_mkstream,
+            trim,
+            id,
+            fields,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let add = |value: &mut Value| {
+            let stream = value.as_stream_mut()?;
+            let id = stream.next_id(self.id)?;
+            stream.add(id, &self.fields);
+            if let Some(trim) = self.trim {
+                stream.trim(trim);
+            }
+            Ok::<_, String>(id)
+        };
+
+        // A new stream can't reject an ID, as `0-0` is rejected while parsing
+        let id = if self.no_mkstream {
+            match store.update(&self.key, add).transpose()? {
+                Some(id) => id,
+                None => return Ok(Frame::Null),
+            }
+        } else {
+            store.upsert(&self.key, || Value::Stream(Stream::new()), add)?
+        };
+        // Streams outlive their entries, so adding to one must wake its readers too
+        store.signal_key_ready(&self.key);
+        Ok(Frame::bulk(id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store, types::stream::StreamId};
+
+    use super::*;
+
+    fn xadd(input: &str) -> Result<XAddCommand, String> {
+        match XAddCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::XAdd(cmd) => Ok(cmd),
+            _ => panic!("Expected an XAdd command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_xadd_command() {
+        let store = Store::new();
+        let mut client = Client::new();
+        for (input, expected) in [
+            ("s nomkstream * f v", Frame::Null),
+            ("s 1-1 f v", Frame::bulk("1-1")),
+            ("s 1-* f v g w", Frame::bulk("1-2")),
+            ("s maxlen 2 5 f v", Frame::bulk("5-0")),
+        ] {
+            assert_eq!(
+                xadd(input)
+                    .unwrap()
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Ok(expected)
+            );
+        }
+        assert_eq!(
+            xadd("s 5 f v")
+                .unwrap()
+                .execute(store.clone(), &mut client)
+                .await,
+            Err(
+                "The ID specified in XADD is equal or smaller than the target stream top item"
+                    .to_string()
+            )
+        );
+
+        let entries = store
+            .read(&Key::from("s"), |value| {
+                let stream = value.as_stream().unwrap();
+                stream.range(StreamId::MIN, StreamId::MAX, false, None)
+            })
+            .unwrap();
+        let ids: Vec<_> = entries.iter().map(|entry| entry.id.to_string()).collect();
+        assert_eq!(ids, ["1-2", "5-0"]);
+        assert_eq!(entries[0].fields.len(), 2);
+    }
+
+    #[test]
+    fn test_xadd_invalid_arguments() {
+        let wrong_arity = Some("wrong number of arguments for 'xadd' command".to_string());
+        assert_eq!(xadd("s *").err(), wrong_arity);
+        assert_eq!(xadd("s * f").err(), wrong_arity);
+        assert_eq!(
+            xadd("s 0-0 f v").err(),
+            Some("The ID specified in XADD must be greater than 0-0".to_string())
+        );
+        assert_eq!(
+            xadd("s limit 10 * f v").err(),
+            Some(
+                "syntax error, LIMIT cannot be used without specifying a trimming strategy"
+                    .to_string()
+            )
+        );
+        assert!(xadd("s minid ~ 5 limit 10 * f v").is_ok());
+        assert!(xadd("s nope f v").is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::stream::StreamId,
+    utils::current_epoch_millis,
+};
+
+use super::{
+    xclaim_command::{claim, Delivery},
+    xgroup_command::no_such_key_or_group,
+    xrange_command::parse_range_bound,
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// How many entries `XAUTOCLAIM` claims by default
+const DEFAULT_COUNT: usize = 100;
+
+/// How many pending entries `XAUTOCLAIM` looks at for every entry it may claim
+const ATTEMPTS_FACTOR: usize = 10;
+
+/// `XAUTOCLAIM key group consumer min-idle-time start [COUNT count] [JUSTID]`
+pub struct XAutoClaimCommand {
+    pub key: Key,
+    pub group: Bytes,
+    pub consumer: Bytes,
+    /// Only the entries delivered at least this many milliseconds ago are claimed
+    pub min_idle: u128,
+    pub start: StreamId,
+    pub count: usize,
+    /// Reply with the IDs alone, without counting a delivery
+    pub just_id: bool,
+}
+
+impl CommandTrait for XAutoClaimCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let group = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let consumer = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let min_idle = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let min_idle = parse_int(&min_idle)
+            .map_err(|_| "Invalid min-idle-time argument for XAUTOCLAIM")?
+            .max(0) as u128;
+        let start = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let start = parse_range_bound(&start, true)?;
+
+        let mut count = DEFAULT_COUNT;
+        let mut just_id = false;
+        while let Some(option) = parts.next() {
+            match lowercase(&option).as_str() {
+                "count" => {
+                    let value = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+                    count = match parse_int(&value)? {
+                        value @ 1.. => value as usize,
+                        _ => return Err("COUNT must be > 0".to_string()),
+                    };
+                }
+                "justid" => just_id = true,
+                _ => return Err("syntax error".to_string()),
+            }
+        }
+
+        Ok(CommandWrapper::XAutoClaim(Self {
+            key,
+            group,
+            consumer,
+            min_idle,
+            start,
+            count,
+            just_id,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let now = current_epoch_millis();
+        let delivery = Delivery {
+            at: now,
+            retry_count: None,
+            just_id: self.just_id,
+        };
+
+        store
+            .update(&self.key, |value| {
+                let stream = value.as_stream_mut()?;
+                let group = stream
+                    .group(&self.group)
+                    .ok_or_else(|| no_such_key_or_group(&self.key, &self.group))?;
+
+                // Like Redis, only so many pending entries are looked at, to keep
+                // the command fast when few of them are idle enough
+                let mut attempts = self.count.saturating_mul(ATTEMPTS_FACTOR);
+                let mut claimed = Vec::new();
+                let mut deleted = Vec::new();
+                let mut pending = group.pending().range(self.start..).peekable();
+                while claimed.len() < self.count && attempts > 0 {
+                    let Some((&id, entry)) = pending.next() else {
+                        break;
+                    };
+                    attempts -= 1;
+                    if now.saturating_sub(entry.delivered_at) < self.min_idle {
+                        continue;
+                    }
+                    match stream.get(id) {
+                        Some(entry) => claimed.push(entry),
+                        None => deleted.push(id),
+                    }
+                }
+                let cursor = pending.peek().map_or(StreamId::MIN, |(&id, _)| id);
+
+                let group = stream.group_mut(&self.group).expect("checked above");
+                for &id in &deleted {
+                    group.ack(id);
+                }
+                let consumer = group.touch_consumer(&self.consumer, now);
+                if !claimed.is_empty() {
+                    consumer.active_at = Some(now);
+                }
+                for entry in &claimed {
+                    claim(group, entry.id, &self.consumer, &delivery);
+                }
+
+                let claimed = claimed
+                    .into_iter()
+                    .map(|entry| match self.just_id {
+                        true => Frame::bulk(entry.id.to_string()),
+                        false => Frame::from(entry),
+                    })
+                    .collect();
+                let deleted = deleted
+                    .into_iter()
+                    .map(|id| Frame::bulk(id.to_string()))
+                    .collect();
+                Ok(Frame::Array(vec![
+                    Frame::bulk(cursor.to_string()),
+                    Frame::Array(claimed),
+                    Frame::Array(deleted),
+                ]))
+            })
+            .unwrap_or_else(|| Err(no_such_key_or_group(&self.key, &self.group)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        resp::Protocol,
+        store::{ConcurrentStore, Store, Value},
+        types::{consumer_group::ConsumerGroup, stream::Stream},
+    };
+
+    use super::*;
+
+    async fn xautoclaim(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match XAutoClaimCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::XAutoClaim(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an XAutoClaim command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_xautoclaim_command() {
+        let store = Store::new();
+        let mut stream = Stream::new();
+        let fields = [(Bytes::from("f"), Bytes::from("v"))];
+        let mut group = ConsumerGroup::new(StreamId::new(5, 0), None);
+        let alice = Bytes::from("alice");
+        group.create_consumer(&alice, 0);
+        let now = current_epoch_millis();
+        for ms in 1..=5 {
+            stream.add(StreamId::new(ms, 0), &fields);
+            let delivered_at = if ms == 3 { now } else { now - 3_600_000 };
+            group.deliver(StreamId::new(ms, 0), &alice, delivered_at);
+        }
+        stream.delete(StreamId::new(2, 0));
+        stream.create_group("g".into(), group);
+        store.upsert(&Key::from("s"), || Value::Stream(stream), |_| ());
+
+        let ids =
+            |ids: &[&'static str]| Frame::Array(ids.iter().map(|&id| Frame::bulk(id)).collect());
+        assert_eq!(
+            xautoclaim(&store, "s g bob 60000 - count 2 justid").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("5-0"),
+                ids(&["1-0", "4-0"]),
+                ids(&["2-0"])
+            ]))
+        );
+        assert_eq!(
+            xautoclaim(&store, "s g bob 0 (4-0").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("0-0"),
+                Frame::Array(vec![Frame::Array(vec![
+                    Frame::bulk("5-0"),
+                    Frame::Array(vec![Frame::bulk("f"), Frame::bulk("v")])
+                ])]),
+                ids(&[])
+            ]))
+        );
+
+        store.read(&Key::from("s"), |value| {
+            let group = value.as_stream().unwrap().group(b"g").unwrap();
+            let pending = group.pending();
+            assert_eq!(pending.len(), 4);
+            assert_eq!(pending[&StreamId::new(1, 0)].consumer, "bob");
+            assert_eq!(pending[&StreamId::new(1, 0)].deliveries, 1);
+            assert_eq!(pending[&StreamId::new(3, 0)].consumer, "alice");
+            assert_eq!(pending[&StreamId::new(5, 0)].deliveries, 2);
+        });
+
+        assert_eq!(
+            Frame::error(xautoclaim(&store, "s g bob 0 0 count 0").await.unwrap_err())
+                .to_bytes(Protocol::Resp2),
+            b"-ERR COUNT must be > 0\r\n"
+        );
+        assert_eq!(
+            xautoclaim(&store, "missing g bob 0 0").await,
+            Err("NOGROUP No such key 'missing' or consumer group 'g'".to_string())
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::{consumer_group::ConsumerGroup, stream::StreamId},
+    utils::current_epoch_millis,
+};
+
+use super::{xgroup_command::no_such_key_or_group, CommandParts, CommandTrait, CommandWrapper};
+
+/// `XCLAIM key group consumer min-idle-time id [id ...] [IDLE ms] [TIME unix-time-milliseconds] [RETRYCOUNT count] [FORCE] [JUSTID] [LASTID lastid]`
+pub struct XClaimCommand {
+    pub key: Key,
+    pub group: Bytes,
+    pub consumer: Bytes,
+    /// Only the entries delivered at least this many milliseconds ago are claimed
+    pub min_idle: u128,
+    pub ids: Vec<StreamId>,
+    /// How long ago the claimed entries are recorded as delivered, in milliseconds
+    pub idle: Option<u128>,
+    /// When the claimed entries are recorded as delivered, in milliseconds since UNIX epoch
+    pub time: Option<u128>,
+    pub retry_count: Option<u64>,
+    /// Claim entries that aren't pending, as long as they exist
+    pub force: bool,
+    /// Reply with the IDs alone, without counting a delivery
+    pub just_id: bool,
+    /// Moves the last ID of the group forward
+    pub last_id: Option<StreamId>,
+}
+
+/// How claimed entries are recorded as delivered
+pub struct Delivery {
+    /// In milliseconds since UNIX epoch
+    pub at: u128,
+    /// Sets the delivery count, rather than counting one more delivery
+    pub retry_count: Option<u64>,
+    /// Claiming just the IDs doesn't count as a delivery
+    pub just_id: bool,
+}
+
+impl CommandTrait for XClaimCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let group = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let consumer = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let min_idle = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let min_idle = parse_int(&min_idle)
+            .map_err(|_| "Invalid min-idle-time argument for XCLAIM")?
+            .max(0) as u128;
+
+        let mut cmd = Self {
+            key,
+            group,
+            consumer,
+            min_idle,
+            ids: Vec::new(),
+            idle: None,
+            time: None,
+            retry_count: None,
+            force: false,
+            just_id: false,
+            last_id: None,
+        };
+
+        // The IDs go on until the first option
+        let mut parts = parts.peekable();
+        while let Some(id) = parts.peek().and_then(|part| StreamId::parse(part, 0).ok()) {
+            cmd.ids.push(id);
+            parts.next();
+        }
+        if cmd.ids.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+
+        let number = |parts: &mut std::iter::Peekable<CommandParts>| {
+            let value = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+            Ok::<_, String>(parse_int(&value)?.max(0) as u128)
+        };
+        while let Some(option) = parts.next() {
+            match lowercase(&option).as_str() {
+                "idle" => cmd.idle = Some(number(&mut parts)?),
+                "time" => cmd.time = Some(number(&mut parts)?),
+                "retrycount" => cmd.retry_count = Some(number(&mut parts)? as u64),
+                "force" => cmd.force = true,
+                "justid" => cmd.just_id = true,
+                "lastid" => {
+                    let id = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+                    cmd.last_id = Some(StreamId::parse(&id, 0)?);
+                }
+                _ => {
+                    return Err(format!(
+                        "Unrecognized XCLAIM option '{}'",
+                        String::from_utf8_lossy(&option)
+                    ))
+                }
+            }
+        }
+
+        Ok(CommandWrapper::XClaim(cmd))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let now = current_epoch_millis();
+        let delivery = Delivery {
+            at: match (self.time, self.idle) {
+                (Some(time), _) => time,
+                (None, Some(idle)) => now.saturating_sub(idle),
+                (None, None) => now,
+            },
+            retry_count: self.retry_count,
+            just_id: self.just_id,
+        };
+
+        store
+            .update(&self.key, |value| {
+                let stream = value.as_stream_mut()?;
+                let no_group = || no_such_key_or_group(&self.key, &self.group);
+                let group = stream.group(&self.group).ok_or_else(no_group)?;
+
+                // Which entries are claimed is worked out before the group is changed
+                let mut claimed = Vec::new();
+                let mut deleted = Vec::new();
+                for &id in &self.ids {
+                    let pending = group.pending().get(&id);
+                    let entry = stream.get(id);
+                    match (pending, entry) {
+                        // Like Redis, entries deleted from the stream leave the group too
+                        (Some(_), None) => deleted.push(id),
+                        (None, Some(entry)) if self.force => claimed.push((entry, false)),
+                        (Some(pending), Some(entry))
+                            if now.saturating_sub(pending.delivered_at) >= self.min_idle =>
+                        {
+                            claimed.push((entry, true))
+                        }
+                        _ => {}
+                    }
+                }
+
+                let group = stream.group_mut(&self.group).expect("checked above");
+                for id in deleted {
+                    group.ack(id);
+                }
+                group.touch_consumer(&self.consumer, now);
+                for (entry, pending) in &claimed {
+                    if !pending {
+                        group.deliver(entry.id, &self.consumer, now);
+                    }
+                    claim(group, entry.id, &self.consumer, &delivery);
+                }
+                if !claimed.is_empty() {
+                    group.touch_consumer(&self.consumer, now).active_at = Some(now);
+                }
+                if let Some(last_id) = self.last_id {
+                    group.last_id = group.last_id.max(last_id);
+                }
+
+                Ok(Frame::Array(
+                    claimed
+                        .into_iter()
+                        .map(|(entry, _)| match self.just_id {
+                            true => Frame::bulk(entry.id.to_string()),
+                            false => Frame::from(entry),
+                        })
+                        .collect(),
+                ))
+            })
+            .unwrap_or_else(|| Err(no_such_key_or_group(&self.key, &self.group)))
+    }
+}
+
+/// Makes a consumer, which must exist, own a pending entry.
+pub fn claim(group: &mut ConsumerGroup, id: StreamId, consumer: &Bytes, delivery: &Delivery) {
+    group.assign(id, consumer, delivery.at);
+    if let Some(entry) = group.pending_entry_mut(id) {
+        entry.delivered_at = delivery.at;
+        match delivery.retry_count {
+            Some(count) => entry.deliveries = count,
+            None if !delivery.just_id => entry.deliveries += 1,
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+        types::stream::Stream,
+    };
+
+    use super::*;
+
+    async fn xclaim(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match XClaimCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::XClaim(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an XClaim command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_xclaim_command() {
+        let store = Store::new();
+        let mut stream = Stream::new();
+        let fields = [(Bytes::from("f"), Bytes::from("v"))];
+        for ms in 1..=4 {
+            stream.add(StreamId::new(ms, 0), &fields);
+        }
+        let mut group = ConsumerGroup::new(StreamId::new(3, 0), None);
+        let alice = Bytes::from("alice");
+        group.create_consumer(&alice, 0);
+        let an_hour_ago = current_epoch_millis() - 3_600_000;
+        for ms in 1..=3 {
+            group.deliver(StreamId::new(ms, 0), &alice, an_hour_ago);
+        }
+        stream.delete(StreamId::new(2, 0));
+        stream.create_group("g".into(), group);
+        store.upsert(&Key::from("s"), || Value::Stream(stream), |_| ());
+
+        assert_eq!(
+            xclaim(&store, "s g bob 60000 1-0 2-0 4-0 justid lastid 9-0").await,
+            Ok(Frame::Array(vec![Frame::bulk("1-0")]))
+        );
+        assert_eq!(
+            xclaim(&store, "s g bob 60000 3-0 retrycount 7").await,
+            Ok(Frame::Array(vec![Frame::Array(vec![
+                Frame::bulk("3-0"),
+                Frame::Array(vec![Frame::bulk("f"), Frame::bulk("v")])
+            ])]))
+        );
+        // Just claimed, so not idle enough to be claimed again, unlike forced new entries
+        assert_eq!(
+            xclaim(&store, "s g alice 60000 3-0 4-0 force justid").await,
+            Ok(Frame::Array(vec![Frame::bulk("4-0")]))
+        );
+
+        store.read(&Key::from("s"), |value| {
+            let group = value.as_stream().unwrap().group(b"g").unwrap();
+            let pending = group.pending();
+            assert_eq!(pending.len(), 3);
+            assert!(!pending.contains_key(&StreamId::new(2, 0)));
+            assert_eq!(pending[&StreamId::new(1, 0)].deliveries, 1);
+            assert_eq!(pending[&StreamId::new(3, 0)].deliveries, 7);
+            assert_eq!(pending[&StreamId::new(3, 0)].consumer, "bob");
+            assert_eq!(pending[&StreamId::new(4, 0)].consumer, "alice");
+            assert_eq!(group.last_id, StreamId::new(9, 0));
+        });
+
+        assert_eq!(
+            xclaim(&store, "s g bob 0 1-0 nope").await,
+            Err("Unrecognized XCLAIM option 'nope'".to_string())
+        );
+        assert_eq!(
+            xclaim(&store, "s nope bob 0 1-0").await,
+            Err("NOGROUP No such key 's' or consumer group 'nope'".to_string())
+        );
+    }
+}
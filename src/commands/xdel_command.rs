@@ -0,0 +1,96 @@
+use crate::{
+    client::Client, parser::utils::ParseError, resp::Frame, store::Key, types::stream::StreamId,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `XDEL key id [id ...]`
+pub struct XDelCommand {
+    pub key: Key,
+    pub ids: Vec<StreamId>,
+}
+
+impl CommandTrait for XDelCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let ids = parts
+            .map(|part| StreamId::parse(&part, 0))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if ids.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+
+        Ok(CommandWrapper::XDel(Self { key, ids }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let deleted = store
+            .update(&self.key, |value| {
+                let stream = value.as_stream_mut()?;
+                Ok::<_, String>(self.ids.iter().filter(|&&id| stream.delete(id)).count())
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(deleted as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+        types::stream::Stream,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_xdel_command() {
+        let store = Store::new();
+        let mut stream = Stream::new();
+        for seq in 1..=3 {
+            stream.add(
+                StreamId::new(1, seq),
+                &[(Bytes::from("f"), Bytes::from("v"))],
+            );
+        }
+        store.upsert(&Key::from("s"), || Value::Stream(stream), |_| ());
+
+        let xdel = |input: &str| match XDelCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::XDel(cmd)) => cmd,
+            _ => panic!("Expected an XDel command"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            xdel("s 1-1 1-1 1-3 9-9")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            xdel("missing 1-2")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(0))
+        );
+
+        // Like Redis, a stream stays around once empty
+        assert_eq!(
+            xdel("s 1-2").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            store.read(&Key::from("s"), |value| value.as_stream().unwrap().len()),
+            Some(0)
+        );
+        assert!(XDelCommand::from_parts(inline_parts("s 1-x")).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::{
+        consumer_group::ConsumerGroup,
+        stream::{Stream, StreamId},
+    },
+    utils::current_epoch_millis,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The subcommands of `XGROUP`, which manage the consumer groups of a stream
+#[derive(Debug, PartialEq, Clone)]
+pub enum XGroupSubcommand {
+    /// `XGROUP CREATE key group <id | $> [MKSTREAM] [ENTRIESREAD entries-read]`
+    Create {
+        /// `None` for `$`, the last ID of the stream
+        id: Option<StreamId>,
+        mkstream: bool,
+        entries_read: Option<u64>,
+    },
+    /// `XGROUP SETID key group <id | $> [ENTRIESREAD entries-read]`
+    SetId {
+        /// `None` for `$`, the last ID of the stream
+        id: Option<StreamId>,
+        entries_read: Option<u64>,
+    },
+    /// `XGROUP DESTROY key group`
+    Destroy,
+    /// `XGROUP CREATECONSUMER key group consumer`
+    CreateConsumer(Bytes),
+    /// `XGROUP DELCONSUMER key group consumer`
+    DelConsumer(Bytes),
+}
+
+pub struct XGroupCommand {
+    pub subcommand: XGroupSubcommand,
+    pub key: Key,
+    pub group: Bytes,
+}
+
+impl CommandTrait for XGroupCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let subcommand = parts
+            .next()
+            .ok_or(ParseError::InvalidCommandOptions("No subcommand provided").to_string())?;
+        let subcommand = lowercase(&subcommand);
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let group = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        let subcommand = match subcommand.as_str() {
+            "create" | "setid" => {
+                let id = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+                let id = match &id[..] {
+                    b"$" => None,
+                    id => Some(StreamId::parse(id, 0)?),
+                };
+
+                let (mut mkstream, mut entries_read) = (false, None);
+                while let Some(option) = parts.next() {
+                    match lowercase(&option).as_str() {
+                        "mkstream" if subcommand == "create" => mkstream = true,
+                        "entriesread" => {
+                            let value = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+                            entries_read = match parse_int(&value)? {
+                                -1 => None,
+                                read if read >= 0 => Some(read as u64),
+                                _ => {
+                                    return Err(
+                                        "value for ENTRIESREAD must be positive or -1".to_string()
+                                    )
+                                }
+                            };
+                        }
+                        _ => return Err("syntax error".to_string()),
+                    }
+                }
+
+                match subcommand.as_str() {
+                    "create" => XGroupSubcommand::Create {
+                        id,
+                        mkstream,
+                        entries_read,
+                    },
+                    _ => XGroupSubcommand::SetId { id, entries_read },
+                }
+            }
+            "destroy" => XGroupSubcommand::Destroy,
+            "createconsumer" | "delconsumer" => {
+                let consumer = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+                match subcommand.as_str() {
+                    "createconsumer" => XGroupSubcommand::CreateConsumer(consumer),
+                    _ => XGroupSubcommand::DelConsumer(consumer),
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "unknown subcommand '{subcommand}'. Try XGROUP HELP."
+                ))
+            }
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::XGroup(Self {
+            subcommand,
+            key,
+            group,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let no_key = || {
+            "The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically.".to_string()
+        };
+        let no_group = || no_such_group(&self.key, &self.group);
+
+        let run = |value: &mut Value| {
+            let stream = value.as_stream_mut()?;
+            match &self.subcommand {
+                XGroupSubcommand::Create {
+                    id, entries_read, ..
+                } => {
+                    let id = id.unwrap_or(stream.last_id());
+                    let group = ConsumerGroup::new(id, *entries_read);
+                    if !stream.create_group(self.group.clone(), group) {
+                        return Err("BUSYGROUP Consumer Group name already exists".to_string());
+                    }
+                    Ok(Frame::ok())
+                }
+                XGroupSubcommand::SetId { id, entries_read } => {
+                    let last_id = id.unwrap_or(stream.last_id());
+                    let group = stream.group_mut(&self.group).ok_or_else(no_group)?;
+                    group.last_id = last_id;
+                    group.entries_read = *entries_read;
+                    Ok(Frame::ok())
+                }
+                XGroupSubcommand::Destroy => {
+                    Ok(Frame::Integer(stream.destroy_group(&self.group) as i64))
+                }
+                XGroupSubcommand::CreateConsumer(consumer) => {
+                    let group = stream.group_mut(&self.group).ok_or_else(no_group)?;
+                    let created = group.create_consumer(consumer, current_epoch_millis());
+                    Ok(Frame::Integer(created as i64))
+                }
+                XGroupSubcommand::DelConsumer(consumer) => {
+                    let group = stream.group_mut(&self.group).ok_or_else(no_group)?;
+                    Ok(Frame::Integer(
+                        group.delete_consumer(consumer).unwrap_or(0) as i64
+                    ))
+                }
+            }
+        };
+
+        let reply = match self.subcommand {
+            XGroupSubcommand::Create { mkstream: true, .. } => {
+                store.upsert(&self.key, || Value::Stream(Stream::new()), run)
+            }
+            _ => store.update(&self.key, run).ok_or_else(no_key)?,
+        };
+        // Clients blocked reading from a destroyed group are told it's gone
+        if self.subcommand == XGroupSubcommand::Destroy {
+            store.signal_key_ready(&self.key);
+        }
+        reply
+    }
+}
+
+/// The error of commands run against a consumer group that doesn't exist
+pub fn no_such_group(key: &Key, group: &[u8]) -> String {
+    format!(
+        "NOGROUP No such consumer group '{}' for key name '{}'",
+        String::from_utf8_lossy(group),
+        String::from_utf8_lossy(key)
+    )
+}
+
+/// The error of commands run against a stream or a consumer group that doesn't exist
+pub fn no_such_key_or_group(key: &Key, group: &[u8]) -> String {
+    format!(
+        "NOGROUP No such key '{}' or consumer group '{}'",
+        String::from_utf8_lossy(key),
+        String::from_utf8_lossy(group)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    fn xgroup(input: &str) -> Result<XGroupCommand, String> {
+        match XGroupCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::XGroup(cmd) => Ok(cmd),
+            _ => panic!("Expected an XGroup command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_xgroup_command() {
+        let store = Store::new();
+        let mut client = Client::new();
+        for (input, expected) in [
+            ("create s g $ mkstream", Ok(Frame::ok())),
+            (
+                "create s g 0",
+                Err("BUSYGROUP Consumer Group name already exists".to_string()),
+            ),
+            ("setid s g 5-1 entriesread 3", Ok(Frame::ok())),
+            ("createconsumer s g alice", Ok(Frame::Integer(1))),
+            ("createconsumer s g alice", Ok(Frame::Integer(0))),
+            ("delconsumer s g alice", Ok(Frame::Integer(0))),
+            (
+                "delconsumer s nope alice",
+                Err("NOGROUP No such consumer group 'nope' for key name 's'".to_string()),
+            ),
+            ("destroy s g", Ok(Frame::Integer(1))),
+            ("destroy s g", Ok(Frame::Integer(0))),
+        ] {
+            assert_eq!(
+                xgroup(input)
+                    .unwrap()
+                    .execute(store.clone(), &mut client)
+                    .await,
+                expected,
+                "{input}"
+            );
+        }
+
+        let result = xgroup("create missing g $")
+            .unwrap()
+            .execute(store.clone(), &mut client)
+            .await;
+        assert!(result
+            .unwrap_err()
+            .starts_with("The XGROUP subcommand requires the key"));
+    }
+
+    #[test]
+    fn test_xgroup_parses_ids() {
+        assert_eq!(
+            xgroup("setid s g $ entriesread -1").unwrap().subcommand,
+            XGroupSubcommand::SetId {
+                id: None,
+                entries_read: None
+            }
+        );
+        assert_eq!(
+            xgroup("create s g 7 mkstream").unwrap().subcommand,
+            XGroupSubcommand::Create {
+                id: Some(StreamId::new(7, 0)),
+                mkstream: true,
+                entries_read: None
+            }
+        );
+        assert!(xgroup("setid s g $ mkstream").is_err());
+        assert!(xgroup("create s g $ entriesread -2").is_err());
+        assert!(xgroup("nope s g").is_err());
+    }
+}
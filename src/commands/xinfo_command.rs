@@ -0,0 +1,230 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::Frame,
+    store::Key,
+    types::stream::StreamId,
+    utils::current_epoch_millis,
+};
+
+use super::{xgroup_command::no_such_group, CommandParts, CommandTrait, CommandWrapper};
+
+/// The subcommands of `XINFO`, which inspect a stream and its consumer groups
+#[derive(Debug, PartialEq, Clone)]
+pub enum XInfoSubcommand {
+    /// `XINFO STREAM key` -- The length, IDs and first and last entries of a stream
+    Stream,
+    /// `XINFO GROUPS key` -- The consumer groups of a stream
+    Groups,
+    /// `XINFO CONSUMERS key group` -- The consumers of a group
+    Consumers(Bytes),
+}
+
+pub struct XInfoCommand {
+    pub subcommand: XInfoSubcommand,
+    pub key: Key,
+}
+
+impl CommandTrait for XInfoCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let subcommand = parts
+            .next()
+            .ok_or(ParseError::InvalidCommandOptions("No subcommand provided").to_string())?;
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let subcommand = match lowercase(&subcommand).as_str() {
+            "stream" => XInfoSubcommand::Stream,
+            "groups" => XInfoSubcommand::Groups,
+            "consumers" => XInfoSubcommand::Consumers(
+                parts.next().ok_or(ParseError::MissingValue.to_string())?,
+            ),
+            _ => {
+                return Err(format!(
+                    "unknown subcommand '{}'. Try XINFO HELP.",
+                    String::from_utf8_lossy(&subcommand)
+                ))
+            }
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::XInfo(Self { subcommand, key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let now = current_epoch_millis();
+        let id = |id: StreamId| Frame::bulk(id.to_string());
+        store
+            .read(&self.key, |value| {
+                let stream = value.as_stream()?;
+                match &self.subcommand {
+                    XInfoSubcommand::Stream => {
+                        let first = stream.first_entry();
+                        let first_id = first.as_ref().map_or(StreamId::MIN, |entry| entry.id);
+                        Ok(Frame::Map(vec![
+                            (Frame::bulk("length"), Frame::Integer(stream.len() as i64)),
+                            (Frame::bulk("last-generated-id"), id(stream.last_id())),
+                            (
+                                Frame::bulk("max-deleted-entry-id"),
+                                id(stream.max_deleted_id()),
+                            ),
+                            (
+                                Frame::bulk("entries-added"),
+                                Frame::Integer(stream.entries_added() as i64),
+                            ),
+                            (Frame::bulk("recorded-first-entry-id"), id(first_id)),
+                            (
+                                Frame::bulk("groups"),
+                                Frame::Integer(stream.groups().len() as i64),
+                            ),
+                            (
+                                Frame::bulk("first-entry"),
+                                first.map_or(Frame::Null, Frame::from),
+                            ),
+                            (
+                                Frame::bulk("last-entry"),
+                                stream.last_entry().map_or(Frame::Null, Frame::from),
+                            ),
+                        ]))
+                    }
+                    XInfoSubcommand::Groups => Ok(Frame::Array(
+                        stream
+                            .groups()
+                            .iter()
+                            .map(|(name, group)| {
+                                let or_null = |n: Option<u64>| {
+                                    n.map_or(Frame::Null, |n| Frame::Integer(n as i64))
+                                };
+                                Frame::Map(vec![
+                                    (Frame::bulk("name"), Frame::bulk(name.clone())),
+                                    (
+                                        Frame::bulk("consumers"),
+                                        Frame::Integer(group.consumers().len() as i64),
+                                    ),
+                                    (
+                                        Frame::bulk("pending"),
+                                        Frame::Integer(group.pending().len() as i64),
+                                    ),
+                                    (Frame::bulk("last-delivered-id"), id(group.last_id)),
+                                    (Frame::bulk("entries-read"), or_null(group.entries_read)),
+                                    (Frame::bulk("lag"), or_null(stream.lag(group))),
+                                ])
+                            })
+                            .collect(),
+                    )),
+                    XInfoSubcommand::Consumers(name) => {
+                        let group = stream
+                            .group(name)
+                            .ok_or_else(|| no_such_group(&self.key, name))?;
+                        Ok(Frame::Array(
+                            group
+                                .consumers()
+                                .iter()
+                                .map(|(name, consumer)| {
+                                    // Like Redis, -1 for consumers that never read anything
+                                    let inactive = consumer
+                                        .active_at
+                                        .map_or(-1, |at| now.saturating_sub(at) as i64);
+                                    Frame::Map(vec![
+                                        (Frame::bulk("name"), Frame::bulk(name.clone())),
+                                        (
+                                            Frame::bulk("pending"),
+                                            Frame::Integer(consumer.pending_len() as i64),
+                                        ),
+                                        (
+                                            Frame::bulk("idle"),
+                                            Frame::Integer(
+                                                now.saturating_sub(consumer.seen_at) as i64
+                                            ),
+                                        ),
+                                        (Frame::bulk("inactive"), Frame::Integer(inactive)),
+                                    ])
+                                })
+                                .collect(),
+                        ))
+                    }
+                }
+            })
+            .unwrap_or_else(|| Err("no such key".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+        types::{consumer_group::ConsumerGroup, stream::Stream},
+    };
+
+    use super::*;
+
+    async fn xinfo(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match XInfoCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::XInfo(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an XInfo command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_xinfo_command() {
+        let store = Store::new();
+        let mut stream = Stream::new();
+        let fields = [(Bytes::from("f"), Bytes::from("v"))];
+        stream.add(StreamId::new(1, 0), &fields);
+        stream.add(StreamId::new(2, 0), &fields);
+        let mut group = ConsumerGroup::new(StreamId::new(1, 0), Some(1));
+        let alice = Bytes::from("alice");
+        group.create_consumer(&alice, current_epoch_millis());
+        group.deliver(StreamId::new(1, 0), &alice, 0);
+        stream.create_group("g".into(), group);
+        store.upsert(&Key::from("s"), || Value::Stream(stream), |_| ());
+
+        let Ok(Frame::Map(info)) = xinfo(&store, "stream s").await else {
+            panic!("Expected a map");
+        };
+        assert_eq!(info[0], (Frame::bulk("length"), Frame::Integer(2)));
+        assert_eq!(
+            info[1],
+            (Frame::bulk("last-generated-id"), Frame::bulk("2-0"))
+        );
+        assert_eq!(info[5], (Frame::bulk("groups"), Frame::Integer(1)));
+
+        assert_eq!(
+            xinfo(&store, "groups s").await,
+            Ok(Frame::Array(vec![Frame::Map(vec![
+                (Frame::bulk("name"), Frame::bulk("g")),
+                (Frame::bulk("consumers"), Frame::Integer(1)),
+                (Frame::bulk("pending"), Frame::Integer(1)),
+                (Frame::bulk("last-delivered-id"), Frame::bulk("1-0")),
+                (Frame::bulk("entries-read"), Frame::Integer(1)),
+                (Frame::bulk("lag"), Frame::Integer(1)),
+            ])]))
+        );
+
+        let Ok(Frame::Array(consumers)) = xinfo(&store, "consumers s g").await else {
+            panic!("Expected an array");
+        };
+        let Frame::Map(consumer) = &consumers[0] else {
+            panic!("Expected a map");
+        };
+        assert_eq!(consumer[1], (Frame::bulk("pending"), Frame::Integer(1)));
+        assert_eq!(consumer[3], (Frame::bulk("inactive"), Frame::Integer(-1)));
+
+        assert_eq!(
+            xinfo(&store, "consumers s nope").await,
+            Err("NOGROUP No such consumer group 'nope' for key name 's'".to_string())
+        );
+        assert_eq!(
+            xinfo(&store, "stream missing").await,
+            Err("no such key".to_string())
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `XLEN key`
+pub struct XLenCommand {
+    pub key: Key,
+}
+
+impl CommandTrait for XLenCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::XLen(Self { key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store
+            .read(&self.key, |value| {
+                value.as_stream().map(|stream| stream.len())
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        store::{Store, Value},
+        types::stream::{Stream, StreamId},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_xlen_command() {
+        let store = Store::new();
+        let mut stream = Stream::new();
+        stream.add(StreamId::new(1, 0), &[(Bytes::from("f"), Bytes::from("v"))]);
+        store.upsert(&Key::from("s"), || Value::Stream(stream), |_| ());
+        store.upsert(&Key::from("str"), || Value::Str("v".into()), |_| ());
+
+        let xlen = |key: &'static str| XLenCommand {
+            key: Key::from(key),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            xlen("s").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            xlen("missing").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(0))
+        );
+        assert!(xlen("str")
+            .execute(store.clone(), &mut client)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::stream::StreamId,
+    utils::current_epoch_millis,
+};
+
+use super::{
+    xgroup_command::no_such_key_or_group, xrange_command::parse_range_bound, CommandParts,
+    CommandTrait, CommandWrapper,
+};
+
+/// `XPENDING key group [[IDLE min-idle-time] start end count [consumer]]`
+pub struct XPendingCommand {
+    pub key: Key,
+    pub group: Bytes,
+    /// Lists the pending entries in this range, rather than summing them up
+    pub range: Option<PendingRange>,
+}
+
+pub struct PendingRange {
+    /// Only the entries delivered at least this many milliseconds ago
+    pub min_idle: u128,
+    pub start: StreamId,
+    pub end: StreamId,
+    pub count: usize,
+    /// Only the entries pending for this consumer
+    pub consumer: Option<Bytes>,
+}
+
+impl CommandTrait for XPendingCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let group = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        let range = match parts.next() {
+            None => None,
+            Some(mut start) => {
+                let mut min_idle = 0;
+                if lowercase(&start) == "idle" {
+                    let idle =
+                        parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+                    min_idle = idle.max(0) as u128;
+                    start = parts.next().ok_or("syntax error")?;
+                }
+                let end = parts.next().ok_or("syntax error")?;
+                let count = parts.next().ok_or("syntax error")?;
+                Some(PendingRange {
+                    min_idle,
+                    start: parse_range_bound(&start, true)?,
+                    end: parse_range_bound(&end, false)?,
+                    // Like Redis, a negative count lists nothing
+                    count: parse_int(&count)?.max(0) as usize,
+                    consumer: parts.next(),
+                })
+            }
+        };
+
+        if parts.next().is_some() {
+            return Err("syntax error".to_string());
+        }
+
+        Ok(CommandWrapper::XPending(Self { key, group, range }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let now = current_epoch_millis();
+        store
+            .read(&self.key, |value| {
+                let stream = value.as_stream()?;
+                let group = stream
+                    .group(&self.group)
+                    .ok_or_else(|| no_such_key_or_group(&self.key, &self.group))?;
+                let pending = group.pending();
+
+                let Some(range) = &self.range else {
+                    let (Some((first, _)), Some((last, _))) =
+                        (pending.first_key_value(), pending.last_key_value())
+                    else {
+                        return Ok(Frame::Array(vec![
+                            Frame::Integer(0),
+                            Frame::Null,
+                            Frame::Null,
+                            Frame::NullArray,
+                        ]));
+                    };
+                    let consumers = group
+                        .consumers()
+                        .iter()
+                        .filter(|(_, consumer)| consumer.pending_len() > 0)
+                        .map(|(name, consumer)| {
+                            Frame::Array(vec![
+                                Frame::bulk(name.clone()),
+                                Frame::bulk(consumer.pending_len().to_string()),
+                            ])
+                        })
+                        .collect();
+                    return Ok(Frame::Array(vec![
+                        Frame::Integer(pending.len() as i64),
+                        Frame::bulk(first.to_string()),
+                        Frame::bulk(last.to_string()),
+                        Frame::Array(consumers),
+                    ]));
+                };
+
+                if range.start > range.end {
+                    return Ok(Frame::Array(Vec::new()));
+                }
+                let entries = pending
+                    .range(range.start..=range.end)
+                    .filter(|(_, entry)| {
+                        range
+                            .consumer
+                            .as_ref()
+                            .is_none_or(|name| *name == entry.consumer)
+                    })
+                    .map(|(id, entry)| (id, entry, now.saturating_sub(entry.delivered_at)))
+                    .filter(|(_, _, idle)| *idle >= range.min_idle)
+                    .take(range.count)
+                    .map(|(id, entry, idle)| {
+                        Frame::Array(vec![
+                            Frame::bulk(id.to_string()),
+                            Frame::bulk(entry.consumer.clone()),
+                            Frame::Integer(idle as i64),
+                            Frame::Integer(entry.deliveries as i64),
+                        ])
+                    })
+                    .collect();
+                Ok(Frame::Array(entries))
+            })
+            .unwrap_or_else(|| Err(no_such_key_or_group(&self.key, &self.group)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+        types::{consumer_group::ConsumerGroup, stream::Stream},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_xpending_command() {
+        let store = Store::new();
+        let mut stream = Stream::new();
+        let mut group = ConsumerGroup::new(StreamId::MIN, None);
+        let (alice, bob) = (Bytes::from("alice"), Bytes::from("bob"));
+        group.create_consumer(&alice, 0);
+        group.create_consumer(&bob, 0);
+        let now = current_epoch_millis();
+        group.deliver(StreamId::new(1, 0), &alice, now - 60_000);
+        group.deliver(StreamId::new(2, 0), &bob, now);
+        group.deliver(StreamId::new(3, 0), &alice, now);
+        stream.create_group("g".into(), group);
+        stream.create_group("empty".into(), ConsumerGroup::new(StreamId::MIN, None));
+        store.upsert(&Key::from("s"), || Value::Stream(stream), |_| ());
+
+        let xpending = |input: &str| match XPendingCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::XPending(cmd)) => cmd,
+            _ => panic!("Expected an XPending command"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            xpending("s g").execute(store.clone(), &mut client).await,
+            Ok(Frame::Array(vec![
+                Frame::Integer(3),
+                Frame::bulk("1-0"),
+                Frame::bulk("3-0"),
+                Frame::Array(vec![
+                    Frame::Array(vec![Frame::bulk("alice"), Frame::bulk("2")]),
+                    Frame::Array(vec![Frame::bulk("bob"), Frame::bulk("1")]),
+                ])
+            ]))
+        );
+        assert_eq!(
+            xpending("s empty")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Array(vec![
+                Frame::Integer(0),
+                Frame::Null,
+                Frame::Null,
+                Frame::NullArray
+            ]))
+        );
+
+        let Ok(Frame::Array(entries)) = xpending("s g idle 30000 - + 10")
+            .execute(store.clone(), &mut client)
+            .await
+        else {
+            panic!("Expected an array");
+        };
+        assert_eq!(entries.len(), 1);
+        let Frame::Array(entry) = &entries[0] else {
+            panic!("Expected an entry");
+        };
+        assert_eq!(entry[..2], [Frame::bulk("1-0"), Frame::bulk("alice")]);
+        assert_eq!(entry[3], Frame::Integer(1));
+
+        let Ok(Frame::Array(entries)) = xpending("s g (1-0 + 1 alice")
+            .execute(store.clone(), &mut client)
+            .await
+        else {
+            panic!("Expected an array");
+        };
+        assert_eq!(entries.len(), 1);
+
+        assert_eq!(
+            xpending("s nope").execute(store.clone(), &mut client).await,
+            Err("NOGROUP No such key 's' or consumer group 'nope'".to_string())
+        );
+        assert!(XPendingCommand::from_parts(inline_parts("s g - +")).is_err());
+    }
+}
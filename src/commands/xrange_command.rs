@@ -0,0 +1,216 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::stream::StreamId,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `XRANGE` and `XREVRANGE` only differ in the order of the entries, and of the range bounds
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum XRangeVariant {
+    /// `XRANGE key start end [COUNT count]`
+    XRange,
+    /// `XREVRANGE key end start [COUNT count]`
+    XRevRange,
+}
+
+pub struct XRangeCommand {
+    pub key: Key,
+    pub start: StreamId,
+    pub end: StreamId,
+    pub count: Option<usize>,
+    pub variant: XRangeVariant,
+}
+
+impl XRangeCommand {
+    pub fn from_parts_as(
+        variant: XRangeVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let first = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let second = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let (start, end) = match variant {
+            XRangeVariant::XRange => (first, second),
+            XRangeVariant::XRevRange => (second, first),
+        };
+        let start = parse_range_bound(&start, true)?;
+        let end = parse_range_bound(&end, false)?;
+
+        let mut count = None;
+        while let Some(option) = parts.next() {
+            if lowercase(&option) != "count" {
+                return Err("syntax error".to_string());
+            }
+            let value = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+            // Like Redis, a negative count returns nothing
+            count = Some(value.max(0) as usize);
+        }
+
+        Ok(CommandWrapper::XRange(Self {
+            key,
+            start,
+            end,
+            count,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for XRangeCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(XRangeVariant::XRange, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let rev = self.variant == XRangeVariant::XRevRange;
+        let entries = store
+            .read(&self.key, |value| {
+                let stream = value.as_stream()?;
+                Ok::<_, String>(stream.range(self.start, self.end, rev, self.count))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Frame::Array(entries.into_iter().map(Frame::from).collect()))
+    }
+}
+
+/// Parses a bound of a range of IDs, which can be `-` or `+` for either end of
+/// the stream, and is exclusive when prefixed with `(`.
+///
+/// An ID without a sequence number covers every entry of that millisecond.
+pub fn parse_range_bound(part: &[u8], is_start: bool) -> Result<StreamId, String> {
+    match part {
+        b"-" => return Ok(StreamId::MIN),
+        b"+" => return Ok(StreamId::MAX),
+        _ => {}
+    }
+
+    let missing_seq = if is_start { 0 } else { u64::MAX };
+    match part.strip_prefix(b"(") {
+        Some(part) => {
+            let id = StreamId::parse(part, missing_seq)?;
+            let excluded = if is_start { id.next() } else { id.prev() };
+            excluded.ok_or(match is_start {
+                true => "invalid start ID for the interval".to_string(),
+                false => "invalid end ID for the interval".to_string(),
+            })
+        }
+        None => StreamId::parse(part, missing_seq),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+        types::stream::Stream,
+    };
+
+    use super::*;
+
+    fn ids(frame: Frame) -> Vec<Frame> {
+        let Frame::Array(entries) = frame else {
+            panic!("Expected an array");
+        };
+        entries
+            .into_iter()
+            .map(|entry| match entry {
+                Frame::Array(mut parts) => parts.remove(0),
+                _ => panic!("Expected an entry"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_xrange_command() {
+        let store = Store::new();
+        let mut stream = Stream::new();
+        for (ms, seq) in [(1, 0), (1, 1), (2, 0), (3, 0)] {
+            stream.add(
+                StreamId::new(ms, seq),
+                &[(Bytes::from("f"), Bytes::from("v"))],
+            );
+        }
+        store.upsert(&Key::from("s"), || Value::Stream(stream), |_| ());
+
+        let mut client = Client::new();
+        for (variant, input, expected) in [
+            (
+                XRangeVariant::XRange,
+                "s - +",
+                vec!["1-0", "1-1", "2-0", "3-0"],
+            ),
+            (XRangeVariant::XRange, "s 1 1", vec!["1-0", "1-1"]),
+            (
+                XRangeVariant::XRange,
+                "s (1-0 + count 2",
+                vec!["1-1", "2-0"],
+            ),
+            (
+                XRangeVariant::XRevRange,
+                "s (3-0 - count 2",
+                vec!["2-0", "1-1"],
+            ),
+            (XRangeVariant::XRevRange, "s - +", vec![]),
+            (XRangeVariant::XRange, "missing - +", vec![]),
+        ] {
+            let Ok(CommandWrapper::XRange(cmd)) =
+                XRangeCommand::from_parts_as(variant, inline_parts(input))
+            else {
+                panic!("Expected an XRange command");
+            };
+            let expected: Vec<_> = expected.into_iter().map(Frame::bulk).collect();
+            assert_eq!(
+                ids(cmd.execute(store.clone(), &mut client).await.unwrap()),
+                expected
+            );
+        }
+
+        let entry = XRangeCommand {
+            key: Key::from("s"),
+            start: StreamId::new(3, 0),
+            end: StreamId::MAX,
+            count: None,
+            variant: XRangeVariant::XRange,
+        };
+        assert_eq!(
+            entry.execute(store.clone(), &mut client).await,
+            Ok(Frame::Array(vec![Frame::Array(vec![
+                Frame::bulk("3-0"),
+                Frame::Array(vec![Frame::bulk("f"), Frame::bulk("v")])
+            ])]))
+        );
+    }
+
+    #[test]
+    fn test_parse_range_bound() {
+        assert_eq!(
+            parse_range_bound(b"5", false),
+            Ok(StreamId::new(5, u64::MAX))
+        );
+        assert_eq!(
+            parse_range_bound(b"(5-0", false),
+            Ok(StreamId::new(4, u64::MAX))
+        );
+        assert_eq!(
+            parse_range_bound(b"(0-0", false),
+            Err("invalid end ID for the interval".to_string())
+        );
+        assert_eq!(
+            parse_range_bound(b"(18446744073709551615-18446744073709551615", true),
+            Err("invalid start ID for the interval".to_string())
+        );
+        assert!(parse_range_bound(b"abc", true).is_err());
+    }
+}
@@ -0,0 +1,287 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::{Frame, Protocol},
+    store::{Key, Store},
+    types::stream::StreamId,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `XREAD [COUNT count] [BLOCK milliseconds] STREAMS key [key ...] id [id ...]`
+pub struct XReadCommand {
+    /// Every key with the ID the entries read from it must be after, `None` for
+    /// `$`, the last ID of the stream
+    pub streams: Vec<(Key, Option<StreamId>)>,
+    pub count: Option<usize>,
+    /// `None` unless blocking, which is forever if the timeout is `None`
+    pub block: Option<Option<Duration>>,
+}
+
+/// The options shared by `XREAD` and `XREADGROUP`
+pub struct ReadOptions {
+    pub count: Option<usize>,
+    pub block: Option<Option<Duration>>,
+    /// Only `XREADGROUP` takes `NOACK`
+    pub no_ack: bool,
+    pub keys: Vec<Key>,
+    /// As many IDs as keys, not parsed yet, as each command takes different ones
+    pub ids: Vec<Bytes>,
+}
+
+impl ReadOptions {
+    /// Parses the options and streams of `XREAD`, or `XREADGROUP` if `group`,
+    /// after its `GROUP group consumer`.
+    pub fn parse(mut parts: CommandParts, group: bool) -> Result<Self, String> {
+        let mut options = ReadOptions {
+            count: None,
+            block: None,
+            no_ack: false,
+            keys: Vec::new(),
+            ids: Vec::new(),
+        };
+
+        loop {
+            let option = parts.next().ok_or("syntax error")?;
+            match lowercase(&option).as_str() {
+                "count" => {
+                    let count =
+                        parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+                    // Like Redis, a count that isn't positive doesn't limit anything
+                    options.count = (count > 0).then_some(count as usize);
+                }
+                "block" => {
+                    let timeout =
+                        parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+                    options.block = match timeout {
+                        ..0 => return Err("timeout is negative".to_string()),
+                        0 => Some(None),
+                        ms => Some(Some(Duration::from_millis(ms as u64))),
+                    };
+                }
+                "noack" if group => options.no_ack = true,
+                "streams" => break,
+                _ => return Err("syntax error".to_string()),
+            }
+        }
+
+        let mut rest: Vec<Bytes> = parts.collect();
+        if rest.is_empty() || !rest.len().is_multiple_of(2) {
+            return Err(match group {
+                true => "Unbalanced 'xreadgroup' list of streams: for each stream key an ID or '>' must be specified.",
+                false => "Unbalanced 'xread' list of streams: for each stream key an ID or '$' must be specified.",
+            }
+            .to_string());
+        }
+        options.ids = rest.split_off(rest.len() / 2);
+        options.keys = rest;
+        Ok(options)
+    }
+}
+
+impl CommandTrait for XReadCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        let options = ReadOptions::parse(parts, false)?;
+        let ids = options
+            .ids
+            .iter()
+            .map(|id| match &id[..] {
+                b"$" => Ok(None),
+                id => StreamId::parse(id, 0).map(Some),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(CommandWrapper::XRead(Self {
+            streams: options.keys.into_iter().zip(ids).collect(),
+            count: options.count,
+            block: options.block,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        client: &mut Client,
+    ) -> Result<Frame, String> {
+        let protocol = client.protocol;
+        let count = self.count;
+
+        // `$` stands for the last ID when the command runs, to only read what comes next
+        let mut streams = Vec::with_capacity(self.streams.len());
+        for (key, id) in self.streams {
+            let id = match id {
+                Some(id) => id,
+                None => store
+                    .read(&key, |value| {
+                        value.as_stream().map(|stream| stream.last_id())
+                    })
+                    .transpose()?
+                    .unwrap_or(StreamId::MIN),
+            };
+            streams.push((key, id));
+        }
+
+        let mut read = Vec::new();
+        for (key, id) in &streams {
+            if let Some(entries) = read_after(&store, key, *id, count)? {
+                read.push((key.clone(), entries));
+            }
+        }
+        let Some(timeout) = self.block.filter(|_| read.is_empty()) else {
+            return Ok(streams_frame(read, protocol));
+        };
+
+        let keys = streams.iter().map(|(key, _)| key.clone()).collect();
+        let serve = Box::new(move |store: &Store, key: &Key| {
+            let (_, id) = streams.iter().find(|(k, _)| k == key)?;
+            match read_after(store, key, *id, count) {
+                Ok(Some(entries)) => {
+                    Some(Ok(streams_frame(vec![(key.clone(), entries)], protocol)))
+                }
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            }
+        });
+        store
+            .block_on(keys, timeout, |value| value.as_stream().is_ok(), serve)
+            .await
+            .unwrap_or(Ok(Frame::NullArray))
+    }
+}
+
+/// The entries of a stream after `id`, or `None` if there are none
+fn read_after(
+    store: &Store,
+    key: &Key,
+    id: StreamId,
+    count: Option<usize>,
+) -> Result<Option<Frame>, String> {
+    let entries = store
+        .read(key, |value| {
+            let stream = value.as_stream()?;
+            let entries = match id.next() {
+                Some(start) => stream.range(start, StreamId::MAX, false, count),
+                None => Vec::new(),
+            };
+            Ok::<_, String>(entries)
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok((!entries.is_empty()).then(|| Frame::Array(entries.into_iter().map(Frame::from).collect())))
+}
+
+/// The reply of `XREAD` and `XREADGROUP`, the entries read from each stream,
+/// or a null array if nothing was read.
+///
+/// RESP3 maps every key to its entries, where RESP2 pairs them in arrays.
+pub fn streams_frame(streams: Vec<(Key, Frame)>, protocol: Protocol) -> Frame {
+    if streams.is_empty() {
+        return Frame::NullArray;
+    }
+    match protocol {
+        Protocol::Resp3 => Frame::Map(
+            streams
+                .into_iter()
+                .map(|(key, entries)| (Frame::bulk(key), entries))
+                .collect(),
+        ),
+        Protocol::Resp2 => Frame::Array(
+            streams
+                .into_iter()
+                .map(|(key, entries)| Frame::Array(vec![Frame::bulk(key), entries]))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Value},
+        types::stream::Stream,
+    };
+
+    use super::*;
+
+    async fn xread(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match XReadCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::XRead(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an XRead command"),
+        }
+    }
+
+    fn add(store: &Store, key: &str, id: StreamId) {
+        let key = Key::from(key.to_string());
+        store.upsert(
+            &key,
+            || Value::Stream(Stream::new()),
+            |value| {
+                let fields = [(Bytes::from("f"), Bytes::from("v"))];
+                value.as_stream_mut().unwrap().add(id, &fields)
+            },
+        );
+        store.signal_key_ready(&key);
+        store.serve_blocked_clients();
+    }
+
+    fn entry(id: &'static str) -> Frame {
+        Frame::Array(vec![
+            Frame::bulk(id),
+            Frame::Array(vec![Frame::bulk("f"), Frame::bulk("v")]),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_xread_command() {
+        let store = Store::new();
+        add(&store, "a", StreamId::new(1, 0));
+        add(&store, "a", StreamId::new(2, 0));
+        add(&store, "b", StreamId::new(3, 0));
+
+        assert_eq!(
+            xread(&store, "count 1 streams a b missing 1-0 0 0").await,
+            Ok(Frame::Array(vec![
+                Frame::Array(vec![Frame::bulk("a"), Frame::Array(vec![entry("2-0")])]),
+                Frame::Array(vec![Frame::bulk("b"), Frame::Array(vec![entry("3-0")])]),
+            ]))
+        );
+        assert_eq!(
+            xread(&store, "block 10 streams a b $ $").await,
+            Ok(Frame::NullArray)
+        );
+        assert!(store.blocked_clients.is_empty());
+        assert_eq!(
+            xread(&store, "streams a b 0").await,
+            Err("Unbalanced 'xread' list of streams: for each stream key an ID or '$' must be specified.".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_xread_blocks_until_an_entry_is_added() {
+        let store = Store::new();
+        add(&store, "a", StreamId::new(1, 0));
+        let client = {
+            let store = store.clone();
+            tokio::spawn(async move { xread(&store, "block 0 streams missing a $ $").await })
+        };
+        while store.blocked_clients.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        add(&store, "a", StreamId::new(2, 0));
+        assert_eq!(
+            client.await.unwrap(),
+            Ok(Frame::Array(vec![Frame::Array(vec![
+                Frame::bulk("a"),
+                Frame::Array(vec![entry("2-0")])
+            ])]))
+        );
+        assert!(store.blocked_clients.is_empty());
+    }
+}
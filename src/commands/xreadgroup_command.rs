@@ -0,0 +1,280 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::Frame,
+    store::{Key, Store},
+    types::stream::StreamId,
+    utils::current_epoch_millis,
+};
+
+use super::{
+    xread_command::{streams_frame, ReadOptions},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `XREADGROUP GROUP group consumer [COUNT count] [BLOCK milliseconds] [NOACK] STREAMS key [key ...] id [id ...]`
+pub struct XReadGroupCommand {
+    pub group: Bytes,
+    pub consumer: Bytes,
+    /// Every key with the ID to read from, `None` for `>`, the entries never
+    /// delivered to the group. Other IDs read the entries pending for the consumer.
+    pub streams: Vec<(Key, Option<StreamId>)>,
+    pub count: Option<usize>,
+    /// `None` unless blocking, which is forever if the timeout is `None`
+    pub block: Option<Option<Duration>>,
+    /// Whether delivered entries are acknowledged right away
+    pub no_ack: bool,
+}
+
+impl CommandTrait for XReadGroupCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let option = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        if lowercase(&option) != "group" {
+            return Err("syntax error".to_string());
+        }
+        let group = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let consumer = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        let options = ReadOptions::parse(parts, true)?;
+        let ids = options
+            .ids
+            .iter()
+            .map(|id| match &id[..] {
+                b">" => Ok(None),
+                id => StreamId::parse(id, 0).map(Some),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(CommandWrapper::XReadGroup(Self {
+            group,
+            consumer,
+            streams: options.keys.into_iter().zip(ids).collect(),
+            count: options.count,
+            block: options.block,
+            no_ack: options.no_ack,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        client: &mut Client,
+    ) -> Result<Frame, String> {
+        let protocol = client.protocol;
+        let read = store.atomically(|| {
+            // Like Redis, nothing is read unless every group exists
+            for (key, _) in &self.streams {
+                let exists = store
+                    .read(key, |value| {
+                        value
+                            .as_stream()
+                            .map(|stream| stream.group(&self.group).is_some())
+                    })
+                    .transpose()?;
+                if exists != Some(true) {
+                    return Err(no_such_key_or_group(key, &self.group));
+                }
+            }
+
+            let mut read = Vec::new();
+            for (key, id) in &self.streams {
+                let entries = self.read(&store, key, *id)?;
+                if let Some(entries) = entries {
+                    read.push((key.clone(), entries));
+                }
+            }
+            Ok(read)
+        })?;
+
+        // Only reading new entries can block, as pending ones are there already
+        let new_only = self.streams.iter().all(|(_, id)| id.is_none());
+        let timeout = match self.block {
+            Some(timeout) if read.is_empty() && new_only => timeout,
+            _ => return Ok(streams_frame(read, protocol)),
+        };
+
+        let keys = self.streams.iter().map(|(key, _)| key.clone()).collect();
+        let serve = Box::new(
+            move |store: &Store, key: &Key| match self.read(store, key, None) {
+                Ok(Some(entries)) => {
+                    Some(Ok(streams_frame(vec![(key.clone(), entries)], protocol)))
+                }
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            },
+        );
+        store
+            .block_on(keys, timeout, |value| value.as_stream().is_ok(), serve)
+            .await
+            .unwrap_or(Ok(Frame::NullArray))
+    }
+}
+
+impl XReadGroupCommand {
+    /// Reads the entries of a stream for the consumer, from `id` if given.
+    ///
+    /// Returns `None` if there are no new entries, where there's always a reply
+    /// for pending entries, even without any.
+    fn read(
+        &self,
+        store: &Store,
+        key: &Key,
+        id: Option<StreamId>,
+    ) -> Result<Option<Frame>, String> {
+        let now = current_epoch_millis();
+        let entries = store.update(key, |value| {
+            let stream = value.as_stream_mut()?;
+            let no_group = || no_such_key_or_group(key, &self.group);
+
+            let frames: Vec<Frame> = match id {
+                None => stream
+                    .read_group(&self.group, &self.consumer, self.count, self.no_ack, now)
+                    .ok_or_else(no_group)?
+                    .into_iter()
+                    .map(Frame::from)
+                    .collect(),
+                Some(start) => stream
+                    .read_group_pending(&self.group, &self.consumer, start, self.count, now)
+                    .ok_or_else(no_group)?
+                    .into_iter()
+                    .map(|(id, entry)| match entry {
+                        Some(entry) => Frame::from(entry),
+                        // Like Redis, pending entries deleted since have no fields
+                        None => Frame::Array(vec![Frame::bulk(id.to_string()), Frame::NullArray]),
+                    })
+                    .collect(),
+            };
+            Ok::<_, String>((id.is_some() || !frames.is_empty()).then_some(Frame::Array(frames)))
+        });
+        // The stream may be gone by the time a blocked client is served
+        entries.unwrap_or_else(|| Err(no_such_key_or_group(key, &self.group)))
+    }
+}
+
+/// `XREADGROUP` tells which command couldn't find the group, unlike the others
+fn no_such_key_or_group(key: &Key, group: &[u8]) -> String {
+    format!(
+        "{} in XREADGROUP with GROUP option",
+        super::xgroup_command::no_such_key_or_group(key, group)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Value},
+        types::{consumer_group::ConsumerGroup, stream::Stream},
+    };
+
+    use super::*;
+
+    async fn xreadgroup(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        match XReadGroupCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::XReadGroup(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected an XReadGroup command"),
+        }
+    }
+
+    fn add(store: &Store, id: StreamId) {
+        let key = Key::from("s");
+        store.upsert(
+            &key,
+            || {
+                let mut stream = Stream::new();
+                stream.create_group("g".into(), ConsumerGroup::new(StreamId::MIN, Some(0)));
+                Value::Stream(stream)
+            },
+            |value| {
+                let fields = [(Bytes::from("f"), Bytes::from("v"))];
+                value.as_stream_mut().unwrap().add(id, &fields)
+            },
+        );
+        store.signal_key_ready(&key);
+        store.serve_blocked_clients();
+    }
+
+    fn reply(entries: Vec<Frame>) -> Frame {
+        Frame::Array(vec![Frame::Array(vec![
+            Frame::bulk("s"),
+            Frame::Array(entries),
+        ])])
+    }
+
+    fn entry(id: &'static str) -> Frame {
+        Frame::Array(vec![
+            Frame::bulk(id),
+            Frame::Array(vec![Frame::bulk("f"), Frame::bulk("v")]),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_xreadgroup_command() {
+        let store = Store::new();
+        add(&store, StreamId::new(1, 0));
+        add(&store, StreamId::new(2, 0));
+
+        assert_eq!(
+            xreadgroup(&store, "group g alice count 1 streams s >").await,
+            Ok(reply(vec![entry("1-0")]))
+        );
+        assert_eq!(
+            xreadgroup(&store, "group g bob noack streams s >").await,
+            Ok(reply(vec![entry("2-0")]))
+        );
+        assert_eq!(
+            xreadgroup(&store, "group g bob streams s >").await,
+            Ok(Frame::NullArray)
+        );
+
+        // Pending entries are read again from the given ID, even once deleted
+        assert_eq!(
+            xreadgroup(&store, "group g bob streams s 0").await,
+            Ok(reply(vec![]))
+        );
+        store.update(&Key::from("s"), |value| {
+            value.as_stream_mut().unwrap().delete(StreamId::new(1, 0))
+        });
+        assert_eq!(
+            xreadgroup(&store, "group g alice streams s 0").await,
+            Ok(reply(vec![Frame::Array(vec![
+                Frame::bulk("1-0"),
+                Frame::NullArray
+            ])]))
+        );
+
+        assert_eq!(
+            xreadgroup(&store, "group nope alice streams s >").await,
+            Err(
+                "NOGROUP No such key 's' or consumer group 'nope' in XREADGROUP with GROUP option"
+                    .to_string()
+            )
+        );
+        assert!(xreadgroup(&store, "group g alice streams s").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_xreadgroup_blocks_until_an_entry_is_added() {
+        let store = Store::new();
+        add(&store, StreamId::new(1, 0));
+        xreadgroup(&store, "group g alice streams s >")
+            .await
+            .unwrap();
+
+        let client = {
+            let store = store.clone();
+            tokio::spawn(async move { xreadgroup(&store, "group g bob block 0 streams s >").await })
+        };
+        while store.blocked_clients.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        add(&store, StreamId::new(2, 0));
+        assert_eq!(client.await.unwrap(), Ok(reply(vec![entry("2-0")])));
+        assert!(store.blocked_clients.is_empty());
+    }
+}
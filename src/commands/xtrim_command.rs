@@ -0,0 +1,173 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::stream::{StreamId, Trim, TrimStrategy, APPROX_TRIM_LIMIT},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `XTRIM key <MAXLEN | MINID> [= | ~] threshold [LIMIT count]`
+pub struct XTrimCommand {
+    pub key: Key,
+    pub trim: Trim,
+}
+
+impl CommandTrait for XTrimCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let option = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let (strategy, approx) = parse_trim_strategy(&lowercase(&option), &mut parts)?;
+
+        let mut limit = None;
+        while let Some(option) = parts.next() {
+            match lowercase(&option).as_str() {
+                "limit" => limit = Some(parse_trim_limit(&mut parts)?),
+                _ => return Err("syntax error".to_string()),
+            }
+        }
+
+        let trim = trim_with_limit(strategy, approx, limit)?;
+        Ok(CommandWrapper::XTrim(Self { key, trim }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let trimmed = store
+            .update(&self.key, |value| {
+                value.as_stream_mut().map(|stream| stream.trim(self.trim))
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(trimmed as i64))
+    }
+}
+
+/// Parses the `[= | ~] threshold` following `option`, the `MAXLEN` or `MINID`
+/// shared by `XTRIM` and `XADD`.
+///
+/// Returns the strategy and whether it's approximate.
+pub fn parse_trim_strategy(
+    option: &str,
+    parts: &mut CommandParts,
+) -> Result<(TrimStrategy, bool), String> {
+    let mut threshold = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+    let approx = &threshold[..] == b"~";
+    if approx || &threshold[..] == b"=" {
+        threshold = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+    }
+
+    let strategy = match option {
+        "maxlen" => {
+            let max = parse_int(&threshold)?;
+            let max = usize::try_from(max).map_err(|_| "The MAXLEN argument must be >= 0.")?;
+            TrimStrategy::MaxLen(max)
+        }
+        "minid" => TrimStrategy::MinId(StreamId::parse(&threshold, 0)?),
+        _ => return Err("syntax error".to_string()),
+    };
+    Ok((strategy, approx))
+}
+
+/// Parses the count following `LIMIT`
+pub fn parse_trim_limit(parts: &mut CommandParts) -> Result<usize, String> {
+    let limit = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+    usize::try_from(limit).map_err(|_| "The LIMIT argument must be >= 0.".to_string())
+}
+
+/// Only approximate trims can be limited, and are by default, like Redis
+pub fn trim_with_limit(
+    strategy: TrimStrategy,
+    approx: bool,
+    limit: Option<usize>,
+) -> Result<Trim, String> {
+    let limit = match (approx, limit) {
+        (true, limit) => limit.unwrap_or(APPROX_TRIM_LIMIT),
+        (false, None) => 0,
+        (false, Some(_)) => {
+            return Err(
+                "syntax error, LIMIT cannot be used without the special ~ option".to_string(),
+            )
+        }
+    };
+    Ok(Trim {
+        strategy,
+        approx,
+        limit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+        types::stream::Stream,
+    };
+
+    use super::*;
+
+    fn xtrim(input: &str) -> Result<XTrimCommand, String> {
+        match XTrimCommand::from_parts(inline_parts(input))? {
+            CommandWrapper::XTrim(cmd) => Ok(cmd),
+            _ => panic!("Expected an XTrim command"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_xtrim_command() {
+        let store = Store::new();
+        let mut stream = Stream::new();
+        for seq in 1..=5 {
+            stream.add(
+                StreamId::new(1, seq),
+                &[(Bytes::from("f"), Bytes::from("v"))],
+            );
+        }
+        store.upsert(&Key::from("s"), || Value::Stream(stream), |_| ());
+
+        let mut client = Client::new();
+        for (input, expected) in [
+            ("s maxlen ~ 2", 0),
+            ("s minid = 1-3", 2),
+            ("s maxlen 1", 2),
+            ("missing maxlen 0", 0),
+        ] {
+            assert_eq!(
+                xtrim(input)
+                    .unwrap()
+                    .execute(store.clone(), &mut client)
+                    .await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+        assert_eq!(
+            store.read(&Key::from("s"), |value| value.as_stream().unwrap().len()),
+            Some(1)
+        );
+
+        assert_eq!(
+            xtrim("s maxlen ~ 2 limit 5").unwrap().trim,
+            Trim {
+                strategy: TrimStrategy::MaxLen(2),
+                approx: true,
+                limit: 5
+            }
+        );
+        assert_eq!(
+            xtrim("s maxlen 2 limit 5").err(),
+            Some("syntax error, LIMIT cannot be used without the special ~ option".to_string())
+        );
+        assert_eq!(
+            xtrim("s maxlen -1").err(),
+            Some("The MAXLEN argument must be >= 0.".to_string())
+        );
+        assert_eq!(xtrim("s size 1").err(), Some("syntax error".to_string()));
+    }
+}
@@ -0,0 +1,297 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_float, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::zset::ZSet,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `ZADD key [NX | XX] [GT | LT] [CH] [INCR] score member [score member ...]`
+pub struct ZAddCommand {
+    pub key: Key,
+    pub options: ZAddOptions,
+    pub pairs: Vec<(f64, Bytes)>,
+}
+
+/// The options of `ZADD`, deciding which members are added or updated
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct ZAddOptions {
+    /// Only add new members
+    pub nx: bool,
+    /// Only update existing members
+    pub xx: bool,
+    /// Only update members to a greater score
+    pub gt: bool,
+    /// Only update members to a lower score
+    pub lt: bool,
+    /// Reply with the number of members added or updated, instead of added
+    pub ch: bool,
+    /// Add the score to the current one, like `ZINCRBY`
+    pub incr: bool,
+}
+
+/// What `ZADD` did to a member
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Added {
+    New(f64),
+    Updated(f64),
+    Unchanged(f64),
+    /// The options prevented the member from being added or updated
+    Skipped,
+}
+
+impl Added {
+    pub fn score(&self) -> Option<f64> {
+        match self {
+            Added::New(score) | Added::Updated(score) | Added::Unchanged(score) => Some(*score),
+            Added::Skipped => None,
+        }
+    }
+}
+
+/// Adds a member or updates its score as `ZADD` would with the options.
+pub fn add_member(
+    zset: &mut ZSet,
+    member: &Bytes,
+    score: f64,
+    options: ZAddOptions,
+) -> Result<Added, String> {
+    let Some(current) = zset.score(member) else {
+        if options.xx {
+            return Ok(Added::Skipped);
+        }
+        zset.insert(member.clone(), score);
+        return Ok(Added::New(score));
+    };
+    if options.nx {
+        return Ok(Added::Skipped);
+    }
+
+    let score = match options.incr {
+        true => current + score,
+        false => score,
+    };
+    if score.is_nan() {
+        return Err("resulting score is not a number (NaN)".to_string());
+    }
+    if (options.gt && score <= current) || (options.lt && score >= current) {
+        return Ok(Added::Skipped);
+    }
+    if score == current {
+        return Ok(Added::Unchanged(score));
+    }
+    zset.insert(member.clone(), score);
+    Ok(Added::Updated(score))
+}
+
+impl CommandTrait for ZAddCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        let mut parts = parts.peekable();
+        let mut options = ZAddOptions::default();
+        while let Some(part) = parts.peek() {
+            match lowercase(part).as_str() {
+                "nx" => options.nx = true,
+                "xx" => options.xx = true,
+                "gt" => options.gt = true,
+                "lt" => options.lt = true,
+                "ch" => options.ch = true,
+                "incr" => options.incr = true,
+                _ => break,
+            }
+            parts.next();
+        }
+
+        let rest: Vec<Bytes> = parts.collect();
+        if rest.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+        if !rest.len().is_multiple_of(2) {
+            return Err("syntax error".to_string());
+        }
+        if options.nx && options.xx {
+            return Err("XX and NX options at the same time are not compatible".to_string());
+        }
+        if [options.nx, options.gt, options.lt]
+            .iter()
+            .filter(|option| **option)
+            .count()
+            > 1
+        {
+            return Err(
+                "GT, LT, and/or NX options at the same time are not compatible".to_string(),
+            );
+        }
+        if options.incr && rest.len() > 2 {
+            return Err("INCR option supports a single increment-element pair".to_string());
+        }
+
+        let pairs = rest
+            .chunks(2)
+            .map(|pair| Ok((parse_float(&pair[0])?, pair[1].clone())))
+            .collect::<Result<_, String>>()?;
+
+        Ok(CommandWrapper::ZAdd(Self {
+            key,
+            options,
+            pairs,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let added = store.upsert(
+            &self.key,
+            || Value::ZSet(ZSet::new()),
+            |value| {
+                let zset = value.as_zset_mut()?;
+                self.pairs
+                    .iter()
+                    .map(|(score, member)| add_member(zset, member, *score, self.options))
+                    .collect::<Result<Vec<_>, String>>()
+            },
+        )?;
+
+        if self.options.incr {
+            return Ok(added[0].score().map_or(Frame::Null, Frame::Double));
+        }
+        let counted = added
+            .iter()
+            .filter(|added| match added {
+                Added::New(_) => true,
+                Added::Updated(_) => self.options.ch,
+                Added::Unchanged(_) | Added::Skipped => false,
+            })
+            .count();
+        Ok(Frame::Integer(counted as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        resp::Protocol,
+        store::{ConcurrentStore, Store},
+    };
+
+    use super::*;
+
+    async fn zadd(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match ZAddCommand::from_parts(parts)? {
+            CommandWrapper::ZAdd(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a ZAdd command"),
+        }
+    }
+
+    #[test]
+    fn test_zadd_command_from_input() {
+        let error = |input: &str| {
+            let mut parts = inline_parts(input);
+            parts.next();
+            let error = ZAddCommand::from_parts(parts).err().unwrap();
+            Frame::error(error).to_bytes(Protocol::Resp2)
+        };
+        assert_eq!(
+            error("zadd key nx xx 1 a"),
+            b"-ERR XX and NX options at the same time are not compatible\r\n"
+        );
+        assert_eq!(
+            error("zadd key gt lt 1 a"),
+            b"-ERR GT, LT, and/or NX options at the same time are not compatible\r\n"
+        );
+        assert_eq!(
+            error("zadd key incr 1 a 2 b"),
+            b"-ERR INCR option supports a single increment-element pair\r\n"
+        );
+        assert_eq!(error("zadd key 1 a 2"), b"-ERR syntax error\r\n");
+        assert_eq!(
+            error("zadd key one a"),
+            b"-ERR value is not a valid float\r\n"
+        );
+        assert_eq!(
+            error("zadd key nan a"),
+            b"-ERR value is not a valid float\r\n"
+        );
+        assert_eq!(
+            error("zadd key 1e400 a"),
+            b"-ERR value is not a valid float\r\n"
+        );
+        assert_eq!(
+            error("zadd key infinity a"),
+            b"-ERR value is not a valid float\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zadd_command() {
+        let store = Store::new();
+        assert_eq!(zadd(&store, "zadd z 1 a 2 b").await, Ok(Frame::Integer(2)));
+        assert_eq!(zadd(&store, "zadd z 3 a 3 c").await, Ok(Frame::Integer(1)));
+        assert_eq!(
+            zadd(&store, "zadd z ch 4 a 3 c 1 d").await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            zadd(&store, "zadd z xx ch 5 a 1 e").await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            zadd(&store, "zadd z nx ch 0 a 1 e").await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            zadd(&store, "zadd z gt ch 1 a 6 b").await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            zadd(&store, "zadd z lt ch 1 a 7 b").await,
+            Ok(Frame::Integer(1))
+        );
+
+        let score = |member: &[u8]| {
+            store
+                .read(&Key::from("z"), |value| {
+                    value.as_zset().unwrap().score(member)
+                })
+                .flatten()
+        };
+        assert_eq!(score(b"a"), Some(1.0));
+        assert_eq!(score(b"b"), Some(6.0));
+        assert_eq!(score(b"e"), Some(1.0));
+
+        assert_eq!(
+            zadd(&store, "zadd z incr 2.5 a").await,
+            Ok(Frame::Double(3.5))
+        );
+        assert_eq!(zadd(&store, "zadd z incr nx 1 a").await, Ok(Frame::Null));
+        assert_eq!(zadd(&store, "zadd z incr gt -1 a").await, Ok(Frame::Null));
+        assert_eq!(
+            zadd(&store, "zadd z incr xx 1 missing").await,
+            Ok(Frame::Null)
+        );
+        assert_eq!(zadd(&store, "zadd z 1 inf").await, Ok(Frame::Integer(1)));
+        assert_eq!(zadd(&store, "zadd z +inf inf").await, Ok(Frame::Integer(0)));
+        assert_eq!(
+            zadd(&store, "zadd z incr -inf inf").await,
+            Err("resulting score is not a number (NaN)".to_string())
+        );
+
+        // Nothing is created when no member is added
+        assert_eq!(
+            zadd(&store, "zadd other xx 1 a").await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(store.get(&Key::from("other")), None);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `ZCARD key`
+pub struct ZCardCommand {
+    pub key: Key,
+}
+
+impl CommandTrait for ZCardCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::ZCard(Self { key }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store
+            .read(&self.key, |value| value.as_zset().map(|zset| zset.len()))
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zcard_command() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0)].into_iter().collect());
+        store.upsert(&Key::from("zset"), || zset, |_| ());
+
+        let zcard = |key: &'static str| ZCardCommand {
+            key: Key::from(key),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            zcard("zset").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            zcard("missing").execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(0))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::Key,
+    types::zset::{LexBound, ScoreBound},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands counting members in a range only differ in how the range is given
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ZCountVariant {
+    /// `ZCOUNT key min max` -- Members with a score between `min` and `max`
+    ZCount,
+    /// `ZLEXCOUNT key min max` -- Members between `min` and `max`, when every score is the same
+    ZLexCount,
+}
+
+/// The range of members to count
+#[derive(Debug, PartialEq, Clone)]
+pub enum CountRange {
+    Score(ScoreBound, ScoreBound),
+    Lex(LexBound, LexBound),
+}
+
+pub struct ZCountCommand {
+    pub key: Key,
+    pub range: CountRange,
+}
+
+impl ZCountCommand {
+    pub fn from_parts_as(
+        variant: ZCountVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let min = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let max = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        let range = match variant {
+            ZCountVariant::ZCount => {
+                CountRange::Score(ScoreBound::parse(&min)?, ScoreBound::parse(&max)?)
+            }
+            ZCountVariant::ZLexCount => {
+                CountRange::Lex(LexBound::parse(&min)?, LexBound::parse(&max)?)
+            }
+        };
+
+        Ok(CommandWrapper::ZCount(Self { key, range }))
+    }
+}
+
+impl CommandTrait for ZCountCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(ZCountVariant::ZCount, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let count = store
+            .read(&self.key, |value| {
+                let zset = value.as_zset()?;
+                let ranks = match &self.range {
+                    CountRange::Score(min, max) => zset.score_ranks(*min, *max),
+                    CountRange::Lex(min, max) => zset.lex_ranks(min, max),
+                };
+                Ok::<_, String>(ranks.len())
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(count as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zcount_variants() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0), ("c", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("zset"), || zset, |_| ());
+        let lex = Value::ZSet(["a", "b", "c", "d"].map(|m| (m, 0.0)).into_iter().collect());
+        store.upsert(&Key::from("lex"), || lex, |_| ());
+
+        let run = |variant, input: &str| {
+            let cmd = ZCountCommand::from_parts_as(variant, inline_parts(input));
+            let store = store.clone();
+            async move {
+                match cmd {
+                    Ok(CommandWrapper::ZCount(cmd)) => cmd.execute(store, &mut Client::new()).await,
+                    _ => panic!("Expected a ZCount command"),
+                }
+            }
+        };
+
+        assert_eq!(
+            run(ZCountVariant::ZCount, "zset -inf +inf").await,
+            Ok(Frame::Integer(3))
+        );
+        assert_eq!(
+            run(ZCountVariant::ZCount, "zset (1 3").await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            run(ZCountVariant::ZLexCount, "lex [b +").await,
+            Ok(Frame::Integer(3))
+        );
+        assert_eq!(
+            run(ZCountVariant::ZLexCount, "missing - +").await,
+            Ok(Frame::Integer(0))
+        );
+        assert!(
+            ZCountCommand::from_parts_as(ZCountVariant::ZLexCount, inline_parts("lex a +"))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_float, ParseError},
+    resp::Frame,
+    store::{Key, Value},
+    types::zset::ZSet,
+};
+
+use super::{
+    zadd_command::{add_member, ZAddOptions},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `ZINCRBY key increment member`
+pub struct ZIncrByCommand {
+    pub key: Key,
+    pub increment: f64,
+    pub member: Bytes,
+}
+
+impl CommandTrait for ZIncrByCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let increment = parse_float(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        let member = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::ZIncrBy(Self {
+            key,
+            increment,
+            member,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let options = ZAddOptions {
+            incr: true,
+            ..Default::default()
+        };
+        let added = store.upsert(
+            &self.key,
+            || Value::ZSet(ZSet::new()),
+            |value| add_member(value.as_zset_mut()?, &self.member, self.increment, options),
+        )?;
+        Ok(added.score().map_or(Frame::Null, Frame::Double))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zincrby_command() {
+        let store = Store::new();
+        let zincrby = |input: &str| match ZIncrByCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::ZIncrBy(cmd)) => cmd,
+            _ => panic!("Expected a ZIncrBy command"),
+        };
+        let mut client = Client::new();
+
+        let reply = zincrby("z 1.5 a").execute(store.clone(), &mut client).await;
+        assert_eq!(reply, Ok(Frame::Double(1.5)));
+        let reply = zincrby("z -3 a").execute(store.clone(), &mut client).await;
+        assert_eq!(reply, Ok(Frame::Double(-1.5)));
+        let reply = zincrby("z inf a").execute(store.clone(), &mut client).await;
+        assert_eq!(reply, Ok(Frame::Double(f64::INFINITY)));
+        let reply = zincrby("z -inf a")
+            .execute(store.clone(), &mut client)
+            .await;
+        assert_eq!(
+            reply,
+            Err("resulting score is not a number (NaN)".to_string())
+        );
+        assert!(ZIncrByCommand::from_parts(inline_parts("z one a")).is_err());
+        assert!(ZIncrByCommand::from_parts(inline_parts("z 1e400 a")).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{zunion_command::intersection_len, CommandParts, CommandTrait, CommandWrapper};
+
+/// `ZINTERCARD numkeys key [key ...] [LIMIT limit]`
+pub struct ZInterCardCommand {
+    pub keys: Vec<Key>,
+    /// Stop counting at this many members, `0` meaning no limit
+    pub limit: usize,
+}
+
+impl CommandTrait for ZInterCardCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let num_keys = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        if num_keys <= 0 {
+            return Err("numkeys should be greater than 0".to_string());
+        }
+        let keys: Vec<Key> = parts.by_ref().take(num_keys as usize).collect();
+        if keys.len() < num_keys as usize {
+            return Err("Number of keys can't be greater than number of args".to_string());
+        }
+
+        let mut limit = 0;
+        while let Some(option) = parts.next() {
+            if lowercase(&option) != "limit" {
+                return Err(ParseError::InvalidCommandOptions(&format!(
+                    "Unsupported option {}",
+                    String::from_utf8_lossy(&option)
+                ))
+                .to_string());
+            }
+            let value = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+            limit = usize::try_from(value).map_err(|_| "LIMIT can't be negative")?;
+        }
+
+        Ok(CommandWrapper::ZInterCard(Self { keys, limit }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let limit = match self.limit {
+            0 => usize::MAX,
+            limit => limit,
+        };
+        let len = intersection_len(&store, &self.keys, limit)?;
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        resp::Protocol,
+        store::{Store, Value, WRONGTYPE},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zintercard_command() {
+        let store = Store::new();
+        let a = Value::ZSet([("x", 1.0), ("y", 2.0), ("z", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("a"), || a, |_| ());
+        let b = Value::Set(["y", "z", "w"].into_iter().collect());
+        store.upsert(&Key::from("b"), || b, |_| ());
+
+        let zintercard = |input: &str| ZInterCardCommand::from_parts(inline_parts(input));
+        let mut client = Client::new();
+        for (input, expected) in [
+            ("2 a b", 2),
+            ("2 a b limit 1", 1),
+            ("2 a b limit 5", 2),
+            ("2 a missing", 0),
+            ("1 a", 3),
+        ] {
+            let Ok(CommandWrapper::ZInterCard(cmd)) = zintercard(input) else {
+                panic!("Expected a ZInterCard command");
+            };
+            assert_eq!(
+                cmd.execute(store.clone(), &mut client).await,
+                Ok(Frame::Integer(expected))
+            );
+        }
+
+        store.set(Key::from("s"), Value::Str("x".into()));
+        let Ok(CommandWrapper::ZInterCard(cmd)) = zintercard("3 a b s limit 1") else {
+            panic!("Expected a ZInterCard command");
+        };
+        assert_eq!(
+            cmd.execute(store.clone(), &mut client).await,
+            Err(WRONGTYPE.to_string())
+        );
+
+        assert_eq!(
+            zintercard("0 a").err(),
+            Some("numkeys should be greater than 0".to_string())
+        );
+        assert_eq!(
+            zintercard("3 a b").err(),
+            Some("Number of keys can't be greater than number of args".to_string())
+        );
+        assert_eq!(
+            Frame::error(zintercard("2 a b limit -1").err().unwrap()).to_bytes(Protocol::Resp2),
+            b"-ERR LIMIT can't be negative\r\n"
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::{Key, Store},
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `ZMPOP numkeys key [key ...] <MIN | MAX> [COUNT count]`
+pub struct ZMPopCommand {
+    pub keys: Vec<Key>,
+    /// Whether the members with the highest scores are popped
+    pub max: bool,
+    pub count: usize,
+}
+
+impl CommandTrait for ZMPopCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        let (keys, max, count) = parse_zmpop_parts(parts)?;
+        Ok(CommandWrapper::ZMPop(Self { keys, max, count }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        Ok(zmpop_frame(zmpop(
+            &store, &self.keys, self.max, self.count,
+        )?))
+    }
+}
+
+/// Parses the `numkeys key [key ...] <MIN | MAX> [COUNT count]` shared with `BZMPOP`
+pub fn parse_zmpop_parts(mut parts: CommandParts) -> Result<(Vec<Key>, bool, usize), String> {
+    let num_keys = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+    if num_keys <= 0 {
+        return Err("numkeys should be greater than 0".to_string());
+    }
+    let keys: Vec<Key> = parts.by_ref().take(num_keys as usize).collect();
+    if keys.len() < num_keys as usize {
+        return Err(ParseError::MissingKeys.to_string());
+    }
+    let max = match parts.next().map(|part| lowercase(&part)).as_deref() {
+        Some("min") => false,
+        Some("max") => true,
+        _ => return Err("syntax error".to_string()),
+    };
+
+    let mut count = 1;
+    if let Some(option) = parts.next() {
+        if lowercase(&option) != "count" {
+            return Err(ParseError::InvalidCommandOptions(&format!(
+                "Unsupported option {}",
+                String::from_utf8_lossy(&option)
+            ))
+            .to_string());
+        }
+        let value = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        if value <= 0 {
+            return Err("count should be greater than 0".to_string());
+        }
+        count = value as usize;
+    }
+
+    if parts.next().is_some() {
+        return Err(ParseError::TooManyArguments.to_string());
+    }
+    Ok((keys, max, count))
+}
+
+/// Members popped from a sorted set, with the key of the sorted set
+pub type Popped = (Key, Vec<(Bytes, f64)>);
+
+/// Atomically pops up to `count` members from the first non empty sorted set of `keys`.
+///
+/// Returns `None` if none of the keys exist.
+pub fn zmpop(
+    store: &Store,
+    keys: &[Key],
+    max: bool,
+    count: usize,
+) -> Result<Option<Popped>, String> {
+    store.atomically(|| {
+        for key in keys {
+            let popped = store.update(key, |value| {
+                let zset = value.as_zset_mut()?;
+                let count = count.min(zset.len());
+                Ok::<_, String>((0..count).filter_map(|_| zset.pop(max)).collect())
+            });
+            if let Some(popped) = popped.transpose()? {
+                return Ok(Some((key.clone(), popped)));
+            }
+        }
+        Ok(None)
+    })
+}
+
+/// The `[key, [[member, score] ...]]` reply of `ZMPOP`, or a null array if nothing was popped
+pub fn zmpop_frame(popped: Option<Popped>) -> Frame {
+    match popped {
+        Some((key, members)) => Frame::Array(vec![
+            Frame::bulk(key),
+            Frame::Array(
+                members
+                    .into_iter()
+                    .map(|(member, score)| {
+                        Frame::Array(vec![Frame::bulk(member), Frame::Double(score)])
+                    })
+                    .collect(),
+            ),
+        ]),
+        None => Frame::NullArray,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Value},
+    };
+
+    use super::*;
+
+    async fn zmpop(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match ZMPopCommand::from_parts(parts)? {
+            CommandWrapper::ZMPop(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a ZMPop command"),
+        }
+    }
+
+    fn pair(member: &'static str, score: f64) -> Frame {
+        Frame::Array(vec![Frame::bulk(member), Frame::Double(score)])
+    }
+
+    #[tokio::test]
+    async fn test_zmpop_from_the_first_non_empty_sorted_set() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0), ("c", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("z"), || zset, |_| ());
+
+        assert_eq!(
+            zmpop(&store, "zmpop 2 missing z max count 2").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("z"),
+                Frame::Array(vec![pair("c", 3.0), pair("b", 2.0)])
+            ]))
+        );
+        assert_eq!(
+            zmpop(&store, "zmpop 1 z min count 5").await,
+            Ok(Frame::Array(vec![
+                Frame::bulk("z"),
+                Frame::Array(vec![pair("a", 1.0)])
+            ]))
+        );
+        assert_eq!(zmpop(&store, "zmpop 1 z min").await, Ok(Frame::NullArray));
+    }
+
+    #[tokio::test]
+    async fn test_zmpop_invalid_arguments() {
+        let store = Store::new();
+        assert_eq!(
+            zmpop(&store, "zmpop 0 z min").await,
+            Err("numkeys should be greater than 0".to_string())
+        );
+        assert_eq!(
+            zmpop(&store, "zmpop 1 z min count 0").await,
+            Err("count should be greater than 0".to_string())
+        );
+        assert_eq!(
+            zmpop(&store, "zmpop 1 z left").await,
+            Err("syntax error".to_string())
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{zrange_command::members_frame, CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands popping members only differ in which end they pop from
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ZPopMinVariant {
+    /// `ZPOPMIN key [count]` -- Pops the members with the lowest scores
+    ZPopMin,
+    /// `ZPOPMAX key [count]` -- Pops the members with the highest scores
+    ZPopMax,
+}
+
+pub struct ZPopMinCommand {
+    pub key: Key,
+    /// Pop this many members and reply with them as pairs in RESP3, instead of
+    /// a single member
+    pub count: Option<usize>,
+    pub variant: ZPopMinVariant,
+}
+
+impl ZPopMinCommand {
+    pub fn from_parts_as(
+        variant: ZPopMinVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let count = match parts.next() {
+            Some(count) => Some(
+                usize::try_from(parse_int(&count)?)
+                    .map_err(|_| "value is out of range, must be positive")?,
+            ),
+            None => None,
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::ZPopMin(Self {
+            key,
+            count,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for ZPopMinCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(ZPopMinVariant::ZPopMin, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        client: &mut Client,
+    ) -> Result<Frame, String> {
+        let max = self.variant == ZPopMinVariant::ZPopMax;
+        let popped: Vec<(Bytes, f64)> = store
+            .update(&self.key, |value| {
+                let zset = value.as_zset_mut()?;
+                let count = self.count.unwrap_or(1).min(zset.len());
+                Ok::<_, String>((0..count).filter_map(|_| zset.pop(max)).collect())
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(match self.count {
+            // A single member is replied in a row with its score, whatever the protocol
+            None => Frame::Array(
+                popped
+                    .into_iter()
+                    .flat_map(|(member, score)| [Frame::bulk(member), Frame::Double(score)])
+                    .collect(),
+            ),
+            Some(_) => members_frame(popped, true, client.protocol),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        resp::Protocol,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zpopmin_variants() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0), ("c", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("z"), || zset, |_| ());
+
+        let run = |variant, input: &str, protocol| {
+            let mut client = Client::new();
+            client.protocol = protocol;
+            let store = store.clone();
+            let cmd = ZPopMinCommand::from_parts_as(variant, inline_parts(input));
+            async move {
+                match cmd? {
+                    CommandWrapper::ZPopMin(cmd) => cmd.execute(store, &mut client).await,
+                    _ => panic!("Expected a ZPopMin command"),
+                }
+            }
+        };
+
+        assert_eq!(
+            run(ZPopMinVariant::ZPopMin, "z", Protocol::Resp3).await,
+            Ok(Frame::Array(vec![Frame::bulk("a"), Frame::Double(1.0)]))
+        );
+        assert_eq!(
+            run(ZPopMinVariant::ZPopMax, "z 1", Protocol::Resp3).await,
+            Ok(Frame::Array(vec![Frame::Array(vec![
+                Frame::bulk("c"),
+                Frame::Double(3.0)
+            ])]))
+        );
+        assert_eq!(
+            run(ZPopMinVariant::ZPopMax, "z 5", Protocol::Resp2).await,
+            Ok(Frame::Array(vec![Frame::bulk("b"), Frame::Double(2.0)]))
+        );
+        assert_eq!(store.get(&Key::from("z")), None);
+        assert_eq!(
+            run(ZPopMinVariant::ZPopMin, "z", Protocol::Resp2).await,
+            Ok(Frame::Array(vec![]))
+        );
+        assert_eq!(
+            run(ZPopMinVariant::ZPopMin, "z -1", Protocol::Resp2).await,
+            Err("value is out of range, must be positive".to_string())
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use bytes::Bytes;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{zrange_command::members_frame, CommandParts, CommandTrait, CommandWrapper};
+
+/// `ZRANDMEMBER key [count [WITHSCORES]]`
+pub struct ZRandMemberCommand {
+    pub key: Key,
+    /// Reply with this many distinct members, or with repetitions if negative,
+    /// instead of a single member
+    pub count: Option<i64>,
+    pub with_scores: bool,
+}
+
+impl CommandTrait for ZRandMemberCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let count = parts.next().map(|count| parse_int(&count)).transpose()?;
+        let with_scores = match parts.next() {
+            Some(option) if lowercase(&option) == "withscores" => true,
+            Some(option) => {
+                return Err(ParseError::InvalidCommandOptions(&format!(
+                    "Unsupported option {}",
+                    String::from_utf8_lossy(&option)
+                ))
+                .to_string())
+            }
+            None => false,
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+        // Like Redis, the count is capped so the reply fits in memory
+        if count.is_some_and(|count| count.unsigned_abs() > (i64::MAX / 2) as u64) {
+            return Err("value is out of range".to_string());
+        }
+
+        Ok(CommandWrapper::ZRandMember(Self {
+            key,
+            count,
+            with_scores,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        client: &mut Client,
+    ) -> Result<Frame, String> {
+        let picked: Vec<(Bytes, f64)> = store
+            .read(&self.key, |value| {
+                let zset = value.as_zset()?;
+                let mut rng = rand::thread_rng();
+                let len = zset.len();
+                // Members are picked by rank, which the skiplist looks up in O(log n)
+                let ranks: Vec<usize> = match self.count {
+                    None => vec![rng.gen_range(0..len)],
+                    Some(count) if count >= 0 => {
+                        let count = (count as usize).min(len);
+                        let mut ranks = rand::seq::index::sample(&mut rng, len, count).into_vec();
+                        ranks.shuffle(&mut rng);
+                        ranks
+                    }
+                    Some(count) => (0..count.unsigned_abs())
+                        .map(|_| rng.gen_range(0..len))
+                        .collect(),
+                };
+                Ok::<_, String>(
+                    ranks
+                        .into_iter()
+                        .filter_map(|rank| zset.get(rank))
+                        .map(|(member, score)| (member.clone(), score))
+                        .collect(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        match self.count {
+            None => Ok(picked
+                .into_iter()
+                .next()
+                .map_or(Frame::Null, |(member, _)| Frame::bulk(member))),
+            Some(_) => Ok(members_frame(picked, self.with_scores, client.protocol)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn zrandmember(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        parts.next(); // Skip the command
+        match ZRandMemberCommand::from_parts(parts)? {
+            CommandWrapper::ZRandMember(cmd) => {
+                cmd.execute(store.clone(), &mut Client::new()).await
+            }
+            _ => panic!("Expected a ZRandMember command"),
+        }
+    }
+
+    fn len(frame: Frame) -> usize {
+        match frame {
+            Frame::Array(frames) => frames.len(),
+            _ => panic!("Expected an array"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zrandmember_command() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0), ("c", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("z"), || zset, |_| ());
+
+        let member = zrandmember(&store, "zrandmember z").await.unwrap();
+        assert!(["a", "b", "c"].iter().any(|m| member == Frame::bulk(*m)));
+
+        assert_eq!(
+            len(zrandmember(&store, "zrandmember z 2").await.unwrap()),
+            2
+        );
+        assert_eq!(
+            len(zrandmember(&store, "zrandmember z 5").await.unwrap()),
+            3
+        );
+        assert_eq!(
+            len(zrandmember(&store, "zrandmember z -5").await.unwrap()),
+            5
+        );
+        assert_eq!(
+            len(zrandmember(&store, "zrandmember z 3 withscores")
+                .await
+                .unwrap()),
+            6
+        );
+        assert_eq!(
+            zrandmember(&store, "zrandmember missing").await,
+            Ok(Frame::Null)
+        );
+        assert_eq!(
+            zrandmember(&store, "zrandmember missing -2").await,
+            Ok(Frame::Array(vec![]))
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_int, ParseError},
+    resp::{Frame, Protocol},
+    store::Key,
+    types::zset::{LexBound, ScoreBound, ZSet},
+    utils::resolve_range,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `ZRANGE` and its legacy forms, which only differ in the options they take
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ZRangeVariant {
+    /// `ZRANGE key start stop [BYSCORE | BYLEX] [REV] [LIMIT offset count] [WITHSCORES]`
+    ZRange,
+    /// `ZREVRANGE key start stop [WITHSCORES]`
+    ZRevRange,
+    /// `ZRANGEBYSCORE key min max [WITHSCORES] [LIMIT offset count]`
+    ZRangeByScore,
+    /// `ZREVRANGEBYSCORE key max min [WITHSCORES] [LIMIT offset count]`
+    ZRevRangeByScore,
+    /// `ZRANGEBYLEX key min max [LIMIT offset count]`
+    ZRangeByLex,
+    /// `ZREVRANGEBYLEX key max min [LIMIT offset count]`
+    ZRevRangeByLex,
+}
+
+/// Which members a range covers
+#[derive(Debug, PartialEq, Clone)]
+pub enum RangeBy {
+    /// Inclusive ranks, negative ones counting from the end
+    Rank(i64, i64),
+    Score(ScoreBound, ScoreBound),
+    Lex(LexBound, LexBound),
+}
+
+/// A range of a sorted set, as given to `ZRANGE` and `ZRANGESTORE`
+#[derive(Debug, PartialEq, Clone)]
+pub struct ZRange {
+    /// The bounds, from the lowest to the highest even when reversed
+    pub by: RangeBy,
+    /// Whether members are taken from the highest score
+    pub rev: bool,
+    /// How many members to skip, and how many to take at most if not negative
+    pub limit: Option<(i64, i64)>,
+}
+
+pub struct ZRangeCommand {
+    pub key: Key,
+    pub range: ZRange,
+    pub with_scores: bool,
+}
+
+impl ZRange {
+    /// Parses the range and options of `ZRANGE` or one of its legacy forms,
+    /// returning whether `WITHSCORES` was given along with the range.
+    pub fn parse(
+        variant: ZRangeVariant,
+        parts: &mut impl Iterator<Item = Bytes>,
+    ) -> Result<(Self, bool), String> {
+        let start = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let stop = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        let (mut by_score, mut by_lex, mut rev) = match variant {
+            ZRangeVariant::ZRange => (false, false, false),
+            ZRangeVariant::ZRevRange => (false, false, true),
+            ZRangeVariant::ZRangeByScore => (true, false, false),
+            ZRangeVariant::ZRevRangeByScore => (true, false, true),
+            ZRangeVariant::ZRangeByLex => (false, true, false),
+            ZRangeVariant::ZRevRangeByLex => (false, true, true),
+        };
+        let mut limit = None;
+        let mut with_scores = false;
+        while let Some(option) = parts.next() {
+            let option = lowercase(&option);
+            match (variant, option.as_str()) {
+                (ZRangeVariant::ZRange, "byscore") => by_score = true,
+                (ZRangeVariant::ZRange, "bylex") => by_lex = true,
+                (ZRangeVariant::ZRange, "rev") => rev = true,
+                (
+                    ZRangeVariant::ZRange
+                    | ZRangeVariant::ZRevRange
+                    | ZRangeVariant::ZRangeByScore
+                    | ZRangeVariant::ZRevRangeByScore,
+                    "withscores",
+                ) => with_scores = true,
+                (
+                    ZRangeVariant::ZRange
+                    | ZRangeVariant::ZRangeByScore
+                    | ZRangeVariant::ZRevRangeByScore
+                    | ZRangeVariant::ZRangeByLex
+                    | ZRangeVariant::ZRevRangeByLex,
+                    "limit",
+                ) => {
+                    let mut next_int = || {
+                        parts
+                            .next()
+                            .ok_or(ParseError::MissingValue.to_string())
+                            .and_then(|part| parse_int(&part))
+                    };
+                    limit = Some((next_int()?, next_int()?));
+                }
+                _ => {
+                    return Err(ParseError::InvalidCommandOptions(&format!(
+                        "Unsupported option {option}"
+                    ))
+                    .to_string())
+                }
+            }
+        }
+
+        if by_score && by_lex {
+            return Err("syntax error".to_string());
+        }
+        if limit.is_some() && !by_score && !by_lex {
+            return Err(
+                "syntax error, LIMIT is only supported in combination with either BYSCORE or BYLEX"
+                    .to_string(),
+            );
+        }
+        if with_scores && by_lex {
+            return Err(
+                "syntax error, WITHSCORES not supported in combination with BYLEX".to_string(),
+            );
+        }
+
+        // Reversed score and lexicographical ranges start from the highest bound
+        let (min, max) = match rev && (by_score || by_lex) {
+            true => (stop, start),
+            false => (start, stop),
+        };
+        let by = if by_score {
+            RangeBy::Score(ScoreBound::parse(&min)?, ScoreBound::parse(&max)?)
+        } else if by_lex {
+            RangeBy::Lex(LexBound::parse(&min)?, LexBound::parse(&max)?)
+        } else {
+            RangeBy::Rank(parse_int(&min)?, parse_int(&max)?)
+        };
+
+        Ok((Self { by, rev, limit }, with_scores))
+    }
+
+    /// The members of the sorted set in the range, in order, with their scores.
+    pub fn members(&self, zset: &ZSet) -> Vec<(Bytes, f64)> {
+        let (ranks, offset, count) = match &self.by {
+            RangeBy::Rank(start, stop) => {
+                let Some((start, end)) = resolve_range(*start, *stop, zset.len()) else {
+                    return vec![];
+                };
+                // Ranks count from the highest score when reversed
+                let ranks = match self.rev {
+                    true => zset.len() - end..zset.len() - start,
+                    false => start..end,
+                };
+                (ranks, 0, usize::MAX)
+            }
+            RangeBy::Score(min, max) => self.limited(zset.score_ranks(*min, *max)),
+            RangeBy::Lex(min, max) => self.limited(zset.lex_ranks(min, max)),
+        };
+        let Some(len) = ranks.len().checked_sub(offset).map(|len| len.min(count)) else {
+            return vec![];
+        };
+        if len == 0 {
+            return vec![];
+        }
+
+        let first = match self.rev {
+            true => ranks.end - 1 - offset,
+            false => ranks.start + offset,
+        };
+        zset.iter_from(first, self.rev)
+            .take(len)
+            .map(|(member, score)| (member.clone(), score))
+            .collect()
+    }
+
+    /// The ranks with the offset and count of `LIMIT`
+    fn limited(&self, ranks: std::ops::Range<usize>) -> (std::ops::Range<usize>, usize, usize) {
+        match self.limit {
+            // A negative offset selects nothing, like in Redis
+            Some((offset, _)) if offset < 0 => (0..0, 0, 0),
+            Some((offset, count)) => (
+                ranks,
+                offset as usize,
+                usize::try_from(count).unwrap_or(usize::MAX),
+            ),
+            None => (ranks, 0, usize::MAX),
+        }
+    }
+}
+
+impl ZRangeCommand {
+    pub fn from_parts_as(
+        variant: ZRangeVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let (range, with_scores) = ZRange::parse(variant, &mut parts)?;
+
+        Ok(CommandWrapper::ZRange(Self {
+            key,
+            range,
+            with_scores,
+        }))
+    }
+}
+
+/// Replies with members and maybe their scores, paired up in RESP3 and in a row in RESP2
+pub fn members_frame(members: Vec<(Bytes, f64)>, with_scores: bool, protocol: Protocol) -> Frame {
+    Frame::Array(match with_scores {
+        false => members
+            .into_iter()
+            .map(|(member, _)| Frame::bulk(member))
+            .collect(),
+        true if protocol == Protocol::Resp3 => members
+            .into_iter()
+            .map(|(member, score)| Frame::Array(vec![Frame::bulk(member), Frame::Double(score)]))
+            .collect(),
+        true => members
+            .into_iter()
+            .flat_map(|(member, score)| [Frame::bulk(member), Frame::Double(score)])
+            .collect(),
+    })
+}
+
+impl CommandTrait for ZRangeCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(ZRangeVariant::ZRange, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        client: &mut Client,
+    ) -> Result<Frame, String> {
+        let members = store
+            .read(&self.key, |value| {
+                value.as_zset().map(|zset| self.range.members(zset))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(members_frame(members, self.with_scores, client.protocol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{ConcurrentStore, Store, Value},
+    };
+
+    use super::*;
+
+    async fn zrange(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        let variant = match parts.next().unwrap().as_ref() {
+            b"zrange" => ZRangeVariant::ZRange,
+            b"zrevrange" => ZRangeVariant::ZRevRange,
+            b"zrangebyscore" => ZRangeVariant::ZRangeByScore,
+            b"zrevrangebyscore" => ZRangeVariant::ZRevRangeByScore,
+            b"zrangebylex" => ZRangeVariant::ZRangeByLex,
+            _ => ZRangeVariant::ZRevRangeByLex,
+        };
+        match ZRangeCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::ZRange(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a ZRange command"),
+        }
+    }
+
+    fn bulks(members: &[&'static str]) -> Result<Frame, String> {
+        Ok(Frame::Array(
+            members.iter().map(|m| Frame::bulk(*m)).collect(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_zrange_by_rank() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0), ("c", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("z"), || zset, |_| ());
+
+        assert_eq!(
+            zrange(&store, "zrange z 0 -1").await,
+            bulks(&["a", "b", "c"])
+        );
+        assert_eq!(zrange(&store, "zrange z 1 5").await, bulks(&["b", "c"]));
+        assert_eq!(zrange(&store, "zrange z 0 0 rev").await, bulks(&["c"]));
+        assert_eq!(zrange(&store, "zrevrange z 1 -1").await, bulks(&["b", "a"]));
+        assert_eq!(zrange(&store, "zrange z 2 1").await, bulks(&[]));
+        assert_eq!(zrange(&store, "zrange missing 0 -1").await, bulks(&[]));
+        assert_eq!(
+            zrange(&store, "zrange z 0 0 withscores").await,
+            Ok(Frame::Array(vec![Frame::bulk("a"), Frame::Double(1.0)]))
+        );
+        assert_eq!(
+            zrange(&store, "zrange z 0 1 limit 0 1").await,
+            Err(
+                "syntax error, LIMIT is only supported in combination with either BYSCORE or BYLEX"
+                    .to_string()
+            )
+        );
+        assert!(zrange(&store, "zrevrange z 0 1 limit 0 1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_zrange_by_score_and_lex() {
+        let store = Store::new();
+        let zset = Value::ZSet(
+            [("a", 1.0), ("b", 2.0), ("c", 3.0), ("d", 4.0)]
+                .into_iter()
+                .collect(),
+        );
+        store.upsert(&Key::from("z"), || zset, |_| ());
+        let lex = Value::ZSet(["a", "b", "c", "d"].map(|m| (m, 0.0)).into_iter().collect());
+        store.upsert(&Key::from("lex"), || lex, |_| ());
+
+        assert_eq!(
+            zrange(&store, "zrange z (1 3 byscore").await,
+            bulks(&["b", "c"])
+        );
+        assert_eq!(
+            zrange(&store, "zrange z +inf -inf byscore rev limit 1 2").await,
+            bulks(&["c", "b"])
+        );
+        assert_eq!(
+            zrange(&store, "zrangebyscore z -inf +inf limit 2 -1").await,
+            bulks(&["c", "d"])
+        );
+        assert_eq!(
+            zrange(&store, "zrevrangebyscore z 3 1 withscores limit 0 1").await,
+            Ok(Frame::Array(vec![Frame::bulk("c"), Frame::Double(3.0)]))
+        );
+        assert_eq!(
+            zrange(&store, "zrangebyscore z 1 4 limit -1 2").await,
+            bulks(&[])
+        );
+        assert_eq!(
+            zrange(&store, "zrangebyscore z 1 4 limit 5 2").await,
+            bulks(&[])
+        );
+
+        assert_eq!(
+            zrange(&store, "zrange lex [b + bylex").await,
+            bulks(&["b", "c", "d"])
+        );
+        assert_eq!(
+            zrange(&store, "zrevrangebylex lex (d - limit 0 2").await,
+            bulks(&["c", "b"])
+        );
+        assert_eq!(
+            zrange(&store, "zrange lex - + bylex withscores").await,
+            Err("syntax error, WITHSCORES not supported in combination with BYLEX".to_string())
+        );
+        assert!(zrange(&store, "zrangebylex lex - + withscores")
+            .await
+            .is_err());
+        assert!(zrange(&store, "zrange z a b byscore").await.is_err());
+    }
+
+    #[test]
+    fn test_members_frame_pairs_up_scores_in_resp3() {
+        let members = vec![(Bytes::from("a"), 1.0)];
+        assert_eq!(
+            members_frame(members, true, Protocol::Resp3),
+            Frame::Array(vec![Frame::Array(vec![
+                Frame::bulk("a"),
+                Frame::Double(1.0)
+            ])])
+        );
+    }
+}
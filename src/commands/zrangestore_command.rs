@@ -0,0 +1,106 @@
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+    types::zset::ZSet,
+};
+
+use super::{
+    zrange_command::{ZRange, ZRangeVariant},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `ZRANGESTORE dst src min max [BYSCORE | BYLEX] [REV] [LIMIT offset count]`
+pub struct ZRangeStoreCommand {
+    pub destination: Key,
+    pub source: Key,
+    pub range: ZRange,
+}
+
+impl CommandTrait for ZRangeStoreCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let destination = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let source = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let (range, with_scores) = ZRange::parse(ZRangeVariant::ZRange, &mut parts)?;
+
+        if with_scores {
+            return Err(
+                ParseError::InvalidCommandOptions("Unsupported option withscores").to_string(),
+            );
+        }
+
+        Ok(CommandWrapper::ZRangeStore(Self {
+            destination,
+            source,
+            range,
+        }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store.atomically(|| {
+            let members = store
+                .read(&self.source, |value| {
+                    value.as_zset().map(|zset| self.range.members(zset))
+                })
+                .transpose()?
+                .unwrap_or_default();
+
+            let len = members.len();
+            // Like Redis, an empty range deletes the destination
+            if members.is_empty() {
+                store.del(&self.destination);
+                return Ok::<_, String>(0);
+            }
+            let mut zset = ZSet::new();
+            for (member, score) in members {
+                zset.insert(member, score);
+            }
+            store.set(self.destination.clone(), Value::ZSet(zset));
+            Ok(len)
+        })?;
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zrangestore_command() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0), ("c", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("src"), || zset, |_| ());
+
+        let zrangestore = |input: &str| match ZRangeStoreCommand::from_parts(inline_parts(input)) {
+            Ok(CommandWrapper::ZRangeStore(cmd)) => cmd,
+            _ => panic!("Expected a ZRangeStore command"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            zrangestore("dst src +inf (1 byscore rev limit 0 1")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            store.get(&Key::from("dst")),
+            Some(Value::ZSet([("c", 3.0)].into_iter().collect()))
+        );
+        assert_eq!(
+            zrangestore("dst src 5 10")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(store.get(&Key::from("dst")), None);
+        assert!(ZRangeStoreCommand::from_parts(inline_parts("dst src 0 -1 withscores")).is_err());
+    }
+}
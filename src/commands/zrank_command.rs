@@ -0,0 +1,137 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, ParseError},
+    resp::Frame,
+    store::Key,
+};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands looking ranks up only differ in the order members are ranked in
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ZRankVariant {
+    /// `ZRANK key member [WITHSCORE]` -- The rank from the lowest score
+    ZRank,
+    /// `ZREVRANK key member [WITHSCORE]` -- The rank from the highest score
+    ZRevRank,
+}
+
+pub struct ZRankCommand {
+    pub key: Key,
+    pub member: Bytes,
+    pub with_score: bool,
+    pub variant: ZRankVariant,
+}
+
+impl ZRankCommand {
+    pub fn from_parts_as(
+        variant: ZRankVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let member = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let with_score = match parts.next() {
+            Some(option) if lowercase(&option) == "withscore" => true,
+            Some(option) => {
+                return Err(ParseError::InvalidCommandOptions(&format!(
+                    "Unsupported option {}",
+                    String::from_utf8_lossy(&option)
+                ))
+                .to_string())
+            }
+            None => false,
+        };
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::ZRank(Self {
+            key,
+            member,
+            with_score,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for ZRankCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(ZRankVariant::ZRank, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let reverse = self.variant == ZRankVariant::ZRevRank;
+        let ranked = store
+            .read(&self.key, |value| {
+                let zset = value.as_zset()?;
+                Ok::<_, String>(
+                    zset.rank(&self.member, reverse)
+                        .zip(zset.score(&self.member)),
+                )
+            })
+            .transpose()?
+            .flatten();
+
+        Ok(match (ranked, self.with_score) {
+            (Some((rank, score)), true) => {
+                Frame::Array(vec![Frame::Integer(rank as i64), Frame::Double(score)])
+            }
+            (Some((rank, _)), false) => Frame::Integer(rank as i64),
+            (None, true) => Frame::NullArray,
+            (None, false) => Frame::Null,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zrank_variants() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0), ("c", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("zset"), || zset, |_| ());
+
+        let run = |variant, input: &str| {
+            let cmd = ZRankCommand::from_parts_as(variant, inline_parts(input));
+            let store = store.clone();
+            async move {
+                match cmd {
+                    Ok(CommandWrapper::ZRank(cmd)) => cmd.execute(store, &mut Client::new()).await,
+                    _ => panic!("Expected a ZRank command"),
+                }
+            }
+        };
+
+        assert_eq!(
+            run(ZRankVariant::ZRank, "zset a").await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(
+            run(ZRankVariant::ZRevRank, "zset a").await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            run(ZRankVariant::ZRank, "zset b withscore").await,
+            Ok(Frame::Array(vec![Frame::Integer(1), Frame::Double(2.0)]))
+        );
+        assert_eq!(run(ZRankVariant::ZRank, "zset d").await, Ok(Frame::Null));
+        assert_eq!(
+            run(ZRankVariant::ZRevRank, "missing a withscore").await,
+            Ok(Frame::NullArray)
+        );
+    }
+}
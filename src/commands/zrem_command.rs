@@ -0,0 +1,77 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// `ZREM key member [member ...]`
+pub struct ZRemCommand {
+    pub key: Key,
+    pub members: Vec<Bytes>,
+}
+
+impl CommandTrait for ZRemCommand {
+    fn from_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let members: Vec<Bytes> = parts.collect();
+
+        if members.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+
+        Ok(CommandWrapper::ZRem(Self { key, members }))
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let removed = store
+            .update(&self.key, |value| {
+                let zset = value.as_zset_mut()?;
+                Ok::<_, String>(
+                    self.members
+                        .iter()
+                        .filter(|m| zset.remove(m).is_some())
+                        .count(),
+                )
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(removed as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{Store, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zrem_deletes_the_emptied_sorted_set() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.0), ("b", 2.0)].into_iter().collect());
+        store.upsert(&Key::from("zset"), || zset, |_| ());
+
+        let zrem = |members: &[&'static str]| ZRemCommand {
+            key: Key::from("zset"),
+            members: members.iter().map(|m| Bytes::from(*m)).collect(),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            zrem(&["a", "c"]).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            zrem(&["b"]).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(store.get(&Key::from("zset")), None);
+        assert_eq!(
+            zrem(&["b"]).execute(store.clone(), &mut client).await,
+            Ok(Frame::Integer(0))
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{
+    client::Client,
+    parser::utils::{parse_int, ParseError},
+    resp::Frame,
+    store::Key,
+    types::zset::{LexBound, ScoreBound},
+    utils::resolve_range,
+};
+
+use super::{zrange_command::RangeBy, CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands removing a range of members only differ in how the range is given
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ZRemRangeByRankVariant {
+    /// `ZREMRANGEBYRANK key start stop` -- Members between two ranks, negative ones counting from the end
+    ZRemRangeByRank,
+    /// `ZREMRANGEBYSCORE key min max` -- Members with a score between `min` and `max`
+    ZRemRangeByScore,
+    /// `ZREMRANGEBYLEX key min max` -- Members between `min` and `max`, when every score is the same
+    ZRemRangeByLex,
+}
+
+pub struct ZRemRangeByRankCommand {
+    pub key: Key,
+    pub range: RangeBy,
+}
+
+impl ZRemRangeByRankCommand {
+    pub fn from_parts_as(
+        variant: ZRemRangeByRankVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let min = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+        let max = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+
+        if parts.next().is_some() {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        let range = match variant {
+            ZRemRangeByRankVariant::ZRemRangeByRank => {
+                RangeBy::Rank(parse_int(&min)?, parse_int(&max)?)
+            }
+            ZRemRangeByRankVariant::ZRemRangeByScore => {
+                RangeBy::Score(ScoreBound::parse(&min)?, ScoreBound::parse(&max)?)
+            }
+            ZRemRangeByRankVariant::ZRemRangeByLex => {
+                RangeBy::Lex(LexBound::parse(&min)?, LexBound::parse(&max)?)
+            }
+        };
+
+        Ok(CommandWrapper::ZRemRangeByRank(Self { key, range }))
+    }
+}
+
+impl CommandTrait for ZRemRangeByRankCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(ZRemRangeByRankVariant::ZRemRangeByRank, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let removed = store
+            .update(&self.key, |value| {
+                let zset = value.as_zset_mut()?;
+                let ranks = match &self.range {
+                    RangeBy::Rank(start, stop) => match resolve_range(*start, *stop, zset.len()) {
+                        Some((start, end)) => start..end,
+                        None => 0..0,
+                    },
+                    RangeBy::Score(min, max) => zset.score_ranks(*min, *max),
+                    RangeBy::Lex(min, max) => zset.lex_ranks(min, max),
+                };
+                Ok::<_, String>(zset.remove_ranks(ranks))
+            })
+            .transpose()?
+            .unwrap_or(0);
+        Ok(Frame::Integer(removed as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zremrangebyrank_variants() {
+        let store = Store::new();
+        let zset = Value::ZSet(
+            [("a", 1.0), ("b", 2.0), ("c", 3.0), ("d", 4.0), ("e", 5.0)]
+                .into_iter()
+                .collect(),
+        );
+        store.upsert(&Key::from("z"), || zset, |_| ());
+
+        let run = |variant, input: &str| {
+            let cmd = ZRemRangeByRankCommand::from_parts_as(variant, inline_parts(input));
+            let store = store.clone();
+            async move {
+                match cmd {
+                    Ok(CommandWrapper::ZRemRangeByRank(cmd)) => {
+                        cmd.execute(store, &mut Client::new()).await
+                    }
+                    _ => panic!("Expected a ZRemRangeByRank command"),
+                }
+            }
+        };
+
+        assert_eq!(
+            run(ZRemRangeByRankVariant::ZRemRangeByRank, "z -1 -1").await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            run(ZRemRangeByRankVariant::ZRemRangeByScore, "z (1 3").await,
+            Ok(Frame::Integer(2))
+        );
+        assert_eq!(
+            run(ZRemRangeByRankVariant::ZRemRangeByLex, "z [a [a").await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            run(ZRemRangeByRankVariant::ZRemRangeByRank, "z 5 10").await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(
+            run(ZRemRangeByRankVariant::ZRemRangeByRank, "z 0 -1").await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(store.get(&Key::from("z")), None);
+    }
+}
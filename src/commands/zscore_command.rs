@@ -0,0 +1,116 @@
+use bytes::Bytes;
+
+use crate::{client::Client, parser::utils::ParseError, resp::Frame, store::Key};
+
+use super::{CommandParts, CommandTrait, CommandWrapper};
+
+/// The commands looking scores up only differ in how many members they look up
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ZScoreVariant {
+    /// `ZSCORE key member` -- The score of the member
+    ZScore,
+    /// `ZMSCORE key member [member ...]` -- The score of each member
+    ZMScore,
+}
+
+pub struct ZScoreCommand {
+    pub key: Key,
+    pub members: Vec<Bytes>,
+    pub variant: ZScoreVariant,
+}
+
+impl ZScoreCommand {
+    pub fn from_parts_as(
+        variant: ZScoreVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let key = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let members: Vec<Bytes> = parts.collect();
+
+        if members.is_empty() {
+            return Err(ParseError::MissingValue.to_string());
+        }
+        if variant == ZScoreVariant::ZScore && members.len() > 1 {
+            return Err(ParseError::TooManyArguments.to_string());
+        }
+
+        Ok(CommandWrapper::ZScore(Self {
+            key,
+            members,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for ZScoreCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(ZScoreVariant::ZScore, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let mut scores = store
+            .read(&self.key, |value| {
+                let zset = value.as_zset()?;
+                Ok::<_, String>(
+                    self.members
+                        .iter()
+                        .map(|member| zset.score(member).map_or(Frame::Null, Frame::Double))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .transpose()?
+            .unwrap_or_else(|| vec![Frame::Null; self.members.len()]);
+
+        Ok(match self.variant {
+            ZScoreVariant::ZScore => scores.pop().expect("one member was looked up"),
+            ZScoreVariant::ZMScore => Frame::Array(scores),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::utils::inline_parts,
+        store::{Store, Value},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zscore_variants() {
+        let store = Store::new();
+        let zset = Value::ZSet([("a", 1.5), ("b", 2.0)].into_iter().collect());
+        store.upsert(&Key::from("zset"), || zset, |_| ());
+
+        let run = |variant, input: &str| {
+            let cmd = ZScoreCommand::from_parts_as(variant, inline_parts(input));
+            let store = store.clone();
+            async move {
+                match cmd {
+                    Ok(CommandWrapper::ZScore(cmd)) => cmd.execute(store, &mut Client::new()).await,
+                    _ => panic!("Expected a ZScore command"),
+                }
+            }
+        };
+
+        assert_eq!(
+            run(ZScoreVariant::ZScore, "zset a").await,
+            Ok(Frame::Double(1.5))
+        );
+        assert_eq!(run(ZScoreVariant::ZScore, "zset c").await, Ok(Frame::Null));
+        assert_eq!(
+            run(ZScoreVariant::ZMScore, "zset b c").await,
+            Ok(Frame::Array(vec![Frame::Double(2.0), Frame::Null]))
+        );
+        assert_eq!(
+            run(ZScoreVariant::ZMScore, "missing a b").await,
+            Ok(Frame::Array(vec![Frame::Null, Frame::Null]))
+        );
+        assert!(ZScoreCommand::from_parts(inline_parts("zset a b")).is_err());
+    }
+}
@@ -0,0 +1,446 @@
+use bytes::Bytes;
+
+use crate::{
+    client::Client,
+    parser::utils::{lowercase, parse_float, parse_int, ParseError},
+    resp::Frame,
+    store::{Key, Store, Value, WRONGTYPE},
+    types::zset::ZSet,
+};
+
+use super::{zrange_command::members_frame, CommandParts, CommandTrait, CommandWrapper};
+
+/// The sorted set algebra commands only differ in how the sorted sets are combined
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ZUnionVariant {
+    /// `ZUNION numkeys key [key ...] [WEIGHTS weight [weight ...]] [AGGREGATE SUM | MIN | MAX] [WITHSCORES]`
+    /// -- The members of any sorted set
+    ZUnion,
+    /// `ZINTER numkeys key [key ...] [WEIGHTS weight [weight ...]] [AGGREGATE SUM | MIN | MAX] [WITHSCORES]`
+    /// -- The members of every sorted set
+    ZInter,
+    /// `ZDIFF numkeys key [key ...] [WITHSCORES]` -- The members of the first sorted
+    /// set that aren't in the others
+    ZDiff,
+}
+
+/// How the scores of a member in several sorted sets are combined
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum Aggregate {
+    #[default]
+    Sum,
+    Min,
+    Max,
+}
+
+/// The sorted sets to combine, and how
+#[derive(Debug, PartialEq, Clone)]
+pub struct ZUnionInputs {
+    pub keys: Vec<Key>,
+    /// The factor of the scores of each sorted set, all `1` if empty
+    pub weights: Vec<f64>,
+    pub aggregate: Aggregate,
+}
+
+pub struct ZUnionCommand {
+    pub inputs: ZUnionInputs,
+    pub variant: ZUnionVariant,
+    pub with_scores: bool,
+}
+
+impl ZUnionVariant {
+    fn name(self, store: bool) -> &'static str {
+        match (self, store) {
+            (ZUnionVariant::ZUnion, false) => "zunion",
+            (ZUnionVariant::ZInter, false) => "zinter",
+            (ZUnionVariant::ZDiff, false) => "zdiff",
+            (ZUnionVariant::ZUnion, true) => "zunionstore",
+            (ZUnionVariant::ZInter, true) => "zinterstore",
+            (ZUnionVariant::ZDiff, true) => "zdiffstore",
+        }
+    }
+}
+
+impl Aggregate {
+    fn apply(self, a: f64, b: f64) -> f64 {
+        match self {
+            // Like Redis, adding opposite infinities makes zero rather than NaN
+            Aggregate::Sum => zero_if_nan(a + b),
+            Aggregate::Min => a.min(b),
+            Aggregate::Max => a.max(b),
+        }
+    }
+}
+
+fn zero_if_nan(score: f64) -> f64 {
+    match score.is_nan() {
+        true => 0.0,
+        false => score,
+    }
+}
+
+impl ZUnionInputs {
+    /// Parses `numkeys key [key ...]` and the options that follow, returning
+    /// whether `WITHSCORES` was given, which the `STORE` variants don't take.
+    pub fn parse(
+        variant: ZUnionVariant,
+        store: bool,
+        parts: &mut CommandParts,
+    ) -> Result<(Self, bool), String> {
+        let num_keys = parse_int(&parts.next().ok_or(ParseError::MissingValue.to_string())?)?;
+        if num_keys <= 0 {
+            return Err(format!(
+                "at least 1 input key is needed for '{}' command",
+                variant.name(store)
+            ));
+        }
+        let keys: Vec<Key> = parts.by_ref().take(num_keys as usize).collect();
+        if keys.len() < num_keys as usize {
+            return Err("syntax error".to_string());
+        }
+
+        let mut weights = Vec::new();
+        let mut aggregate = Aggregate::default();
+        let mut with_scores = false;
+        while let Some(option) = parts.next() {
+            let option = lowercase(&option);
+            match option.as_str() {
+                "weights" if variant != ZUnionVariant::ZDiff => {
+                    weights = parts
+                        .by_ref()
+                        .take(keys.len())
+                        .map(|weight| {
+                            parse_float(&weight)
+                                .map_err(|_| "weight value is not a float".to_string())
+                        })
+                        .collect::<Result<_, String>>()?;
+                    if weights.len() < keys.len() {
+                        return Err("syntax error".to_string());
+                    }
+                }
+                "aggregate" if variant != ZUnionVariant::ZDiff => {
+                    let value = parts.next().ok_or(ParseError::MissingValue.to_string())?;
+                    aggregate = match lowercase(&value).as_str() {
+                        "sum" => Aggregate::Sum,
+                        "min" => Aggregate::Min,
+                        "max" => Aggregate::Max,
+                        _ => return Err("syntax error".to_string()),
+                    };
+                }
+                "withscores" if !store => with_scores = true,
+                _ => {
+                    return Err(ParseError::InvalidCommandOptions(&format!(
+                        "Unsupported option {option}"
+                    ))
+                    .to_string())
+                }
+            }
+        }
+
+        Ok((
+            Self {
+                keys,
+                weights,
+                aggregate,
+            },
+            with_scores,
+        ))
+    }
+}
+
+impl ZUnionCommand {
+    pub fn from_parts_as(
+        variant: ZUnionVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let (inputs, with_scores) = ZUnionInputs::parse(variant, false, &mut parts)?;
+
+        Ok(CommandWrapper::ZUnion(Self {
+            inputs,
+            variant,
+            with_scores,
+        }))
+    }
+}
+
+impl CommandTrait for ZUnionCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(ZUnionVariant::ZUnion, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        client: &mut Client,
+    ) -> Result<Frame, String> {
+        let zset = combine_zsets(&store, &self.inputs, self.variant)?;
+        let members = zset
+            .iter()
+            .map(|(member, score)| (member.clone(), score))
+            .collect();
+        Ok(members_frame(members, self.with_scores, client.protocol))
+    }
+}
+
+/// How many members a sorted set has, a set counting as a sorted set
+fn len_of(value: &Value) -> Result<usize, String> {
+    match value {
+        Value::ZSet(zset) => Ok(zset.len()),
+        Value::Set(set) => Ok(set.len()),
+        _ => Err(WRONGTYPE.to_string()),
+    }
+}
+
+/// The members of a sorted set with their scores, the members of a set all
+/// scoring `1` like in Redis
+fn members_of(value: &Value) -> Result<Vec<(Bytes, f64)>, String> {
+    match value {
+        Value::ZSet(zset) => Ok(zset
+            .iter()
+            .map(|(member, score)| (member.clone(), score))
+            .collect()),
+        Value::Set(set) => Ok(set.iter().map(|member| (member, 1.0)).collect()),
+        _ => Err(WRONGTYPE.to_string()),
+    }
+}
+
+fn score_in(value: &Value, member: &[u8]) -> Option<f64> {
+    match value {
+        Value::ZSet(zset) => zset.score(member),
+        Value::Set(set) => set.contains(member).then_some(1.0),
+        _ => None,
+    }
+}
+
+/// Combines the sorted sets of `inputs` as one snapshot, a missing key being an
+/// empty sorted set and a set being a sorted set whose scores are all `1`.
+///
+/// Every key must hold a sorted set or a set, even the ones that don't change the result.
+pub fn combine_zsets(
+    store: &Store,
+    inputs: &ZUnionInputs,
+    variant: ZUnionVariant,
+) -> Result<ZSet, String> {
+    let keys = &inputs.keys;
+    let weight = |i: usize| inputs.weights.get(i).copied().unwrap_or(1.0);
+    let weighted_members_of = |i: usize| -> Result<Vec<(Bytes, f64)>, String> {
+        let members = store.read(&keys[i], members_of).transpose()?;
+        Ok(members
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(member, score)| (member, zero_if_nan(score * weight(i))))
+            .collect())
+    };
+
+    // Only one sorted set can be read at a time, so the members are copied out
+    // of one of them and then combined with the others
+    store.atomically(|| {
+        let mut lens = Vec::with_capacity(keys.len());
+        for key in keys {
+            lens.push(store.read(key, len_of).transpose()?.unwrap_or(0));
+        }
+
+        let members = match variant {
+            ZUnionVariant::ZUnion => {
+                let mut result = ZSet::new();
+                for i in 0..keys.len() {
+                    for (member, score) in weighted_members_of(i)? {
+                        let score = match result.score(&member) {
+                            Some(current) => inputs.aggregate.apply(current, score),
+                            None => score,
+                        };
+                        result.insert(member, score);
+                    }
+                }
+                return Ok(result);
+            }
+            ZUnionVariant::ZInter => {
+                let (smallest, _) = lens
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, len)| **len)
+                    .expect("there is at least one key");
+                let mut members = weighted_members_of(smallest)?;
+                for (i, key) in keys.iter().enumerate() {
+                    if i == smallest || members.is_empty() {
+                        continue;
+                    }
+                    let read = store.read(key, |value| {
+                        members.retain_mut(|(member, score)| match score_in(value, member) {
+                            Some(other) => {
+                                let other = zero_if_nan(other * weight(i));
+                                *score = inputs.aggregate.apply(*score, other);
+                                true
+                            }
+                            None => false,
+                        })
+                    });
+                    if read.is_none() {
+                        members.clear();
+                    }
+                }
+                members
+            }
+            ZUnionVariant::ZDiff => {
+                let mut members = weighted_members_of(0)?;
+                for key in &keys[1..] {
+                    if members.is_empty() {
+                        break;
+                    }
+                    store.read(key, |value| {
+                        members.retain(|(member, _)| score_in(value, member).is_none())
+                    });
+                }
+                members
+            }
+        };
+        let mut result = ZSet::new();
+        for (member, score) in members {
+            result.insert(member, score);
+        }
+        Ok(result)
+    })
+}
+
+/// Counts the members of every sorted set or set of `keys`, stopping at `limit`
+/// members, as one snapshot.
+///
+/// Rather than intersecting them, each member of the smallest one is looked up
+/// in the others, so that reaching `limit` saves the rest of the work.
+pub fn intersection_len(store: &Store, keys: &[Key], limit: usize) -> Result<usize, String> {
+    store.atomically(|| {
+        let mut lens = Vec::with_capacity(keys.len());
+        for (i, key) in keys.iter().enumerate() {
+            lens.push((store.read(key, len_of).transpose()?.unwrap_or(0), i));
+        }
+        // Members of the smallest ones are the likeliest to be missing
+        lens.sort_unstable();
+        if lens[0].0 == 0 {
+            return Ok(0);
+        }
+
+        let members = store
+            .read(&keys[lens[0].1], members_of)
+            .transpose()?
+            .unwrap_or_default();
+        let mut len = 0;
+        for (member, _) in members {
+            let in_all = lens[1..].iter().all(|&(_, i)| {
+                store
+                    .read(&keys[i], |value| score_in(value, &member).is_some())
+                    .unwrap_or(false)
+            });
+            if in_all {
+                len += 1;
+                if len == limit {
+                    break;
+                }
+            }
+        }
+        Ok(len)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::utils::inline_parts, store::ConcurrentStore};
+
+    use super::*;
+
+    fn store_with_zsets() -> ConcurrentStore {
+        let store = Store::new();
+        let a = Value::ZSet([("x", 1.0), ("y", 2.0), ("z", 3.0)].into_iter().collect());
+        store.upsert(&Key::from("a"), || a, |_| ());
+        let b = Value::ZSet([("y", 10.0), ("z", 20.0), ("w", 5.0)].into_iter().collect());
+        store.upsert(&Key::from("b"), || b, |_| ());
+        let set = Value::Set(["z", "v"].into_iter().collect());
+        store.upsert(&Key::from("set"), || set, |_| ());
+        store
+    }
+
+    async fn run(store: &ConcurrentStore, input: &str) -> Result<Frame, String> {
+        let mut parts = inline_parts(input);
+        let variant = match parts.next().unwrap().as_ref() {
+            b"zunion" => ZUnionVariant::ZUnion,
+            b"zinter" => ZUnionVariant::ZInter,
+            _ => ZUnionVariant::ZDiff,
+        };
+        match ZUnionCommand::from_parts_as(variant, parts)? {
+            CommandWrapper::ZUnion(cmd) => cmd.execute(store.clone(), &mut Client::new()).await,
+            _ => panic!("Expected a ZUnion command"),
+        }
+    }
+
+    fn scored(pairs: &[(&'static str, f64)]) -> Result<Frame, String> {
+        Ok(Frame::Array(
+            pairs
+                .iter()
+                .flat_map(|(member, score)| [Frame::bulk(*member), Frame::Double(*score)])
+                .collect(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_zunion_variants() {
+        let store = store_with_zsets();
+        assert_eq!(
+            run(&store, "zunion 2 a b withscores").await,
+            scored(&[("x", 1.0), ("w", 5.0), ("y", 12.0), ("z", 23.0)])
+        );
+        assert_eq!(
+            run(
+                &store,
+                "zunion 2 a b weights 2 0.5 aggregate max withscores"
+            )
+            .await,
+            scored(&[("x", 2.0), ("w", 2.5), ("y", 5.0), ("z", 10.0)])
+        );
+        assert_eq!(
+            run(&store, "zinter 3 a b set aggregate min withscores").await,
+            scored(&[("z", 1.0)])
+        );
+        assert_eq!(
+            run(&store, "zinter 2 a missing").await,
+            Ok(Frame::Array(vec![]))
+        );
+        assert_eq!(
+            run(&store, "zdiff 3 a set missing").await,
+            Ok(Frame::Array(vec![Frame::bulk("x"), Frame::bulk("y")]))
+        );
+        assert_eq!(
+            run(&store, "zunion 1 a weights inf aggregate sum withscores").await,
+            scored(&[
+                ("x", f64::INFINITY),
+                ("y", f64::INFINITY),
+                ("z", f64::INFINITY)
+            ])
+        );
+
+        store.set(Key::from("string"), Value::Str(Bytes::from("value")));
+        assert_eq!(
+            run(&store, "zinter 2 missing string").await,
+            Err(WRONGTYPE.to_string())
+        );
+    }
+
+    #[test]
+    fn test_zunion_command_from_input() {
+        let error = |input: &str| {
+            ZUnionCommand::from_parts_as(ZUnionVariant::ZUnion, inline_parts(input))
+                .err()
+                .unwrap()
+        };
+        assert_eq!(
+            error("0 a"),
+            "at least 1 input key is needed for 'zunion' command"
+        );
+        assert_eq!(error("3 a b"), "syntax error");
+        assert_eq!(error("2 a b weights 1"), "syntax error");
+        assert_eq!(error("2 a b weights 1 x"), "weight value is not a float");
+        assert_eq!(error("2 a b aggregate avg"), "syntax error");
+        assert!(
+            ZUnionCommand::from_parts_as(ZUnionVariant::ZDiff, inline_parts("1 a weights 1"))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    client::Client,
+    parser::utils::ParseError,
+    resp::Frame,
+    store::{Key, Value},
+};
+
+use super::{
+    zunion_command::{combine_zsets, ZUnionInputs, ZUnionVariant},
+    CommandParts, CommandTrait, CommandWrapper,
+};
+
+/// `ZUNIONSTORE`, `ZINTERSTORE` and `ZDIFFSTORE destination numkeys key [key ...] ...`
+///
+/// Like their counterparts of [`ZUnionVariant`], without `WITHSCORES`, but the
+/// result is stored at `destination`, replacing whatever it held.
+pub struct ZUnionStoreCommand {
+    pub destination: Key,
+    pub inputs: ZUnionInputs,
+    pub variant: ZUnionVariant,
+}
+
+impl ZUnionStoreCommand {
+    pub fn from_parts_as(
+        variant: ZUnionVariant,
+        mut parts: CommandParts,
+    ) -> Result<CommandWrapper, String> {
+        let destination = parts.next().ok_or(ParseError::MissingKey.to_string())?;
+        let (inputs, _) = ZUnionInputs::parse(variant, true, &mut parts)?;
+
+        Ok(CommandWrapper::ZUnionStore(Self {
+            destination,
+            inputs,
+            variant,
+        }))
+    }
+}
+
+impl CommandTrait for ZUnionStoreCommand {
+    fn from_parts(parts: CommandParts) -> Result<CommandWrapper, String> {
+        Self::from_parts_as(ZUnionVariant::ZUnion, parts)
+    }
+
+    async fn execute(
+        self,
+        store: crate::store::ConcurrentStore,
+        _client: &mut Client,
+    ) -> Result<Frame, String> {
+        let len = store.atomically(|| {
+            let zset = combine_zsets(&store, &self.inputs, self.variant)?;
+            let len = zset.len();
+            // Like Redis, an empty result deletes the destination
+            match zset.is_empty() {
+                true => _ = store.del(&self.destination),
+                false => store.set(self.destination.clone(), Value::ZSet(zset)),
+            }
+            Ok::<_, String>(len)
+        })?;
+        Ok(Frame::Integer(len as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{parser::utils::inline_parts, store::Store};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_zunionstore_variants() {
+        let store = Store::new();
+        let a = Value::ZSet([("x", 1.0), ("y", 2.0)].into_iter().collect());
+        store.upsert(&Key::from("a"), || a, |_| ());
+        let b = Value::ZSet([("y", 3.0), ("z", 4.0)].into_iter().collect());
+        store.upsert(&Key::from("b"), || b, |_| ());
+        store.set(Key::from("dst"), Value::Str(Bytes::from("value")));
+
+        let run = |variant, input: &str| match ZUnionStoreCommand::from_parts_as(
+            variant,
+            inline_parts(input),
+        ) {
+            Ok(CommandWrapper::ZUnionStore(cmd)) => cmd,
+            _ => panic!("Expected a ZUnionStore command"),
+        };
+        let mut client = Client::new();
+        assert_eq!(
+            run(ZUnionVariant::ZUnion, "dst 2 a b weights 1 2")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(3))
+        );
+        assert_eq!(
+            store.get(&Key::from("dst")),
+            Some(Value::ZSet(
+                [("x", 1.0), ("y", 8.0), ("z", 8.0)].into_iter().collect()
+            ))
+        );
+
+        // The destination can be one of the keys
+        assert_eq!(
+            run(ZUnionVariant::ZInter, "a 2 a b aggregate max")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(1))
+        );
+        assert_eq!(
+            store.get(&Key::from("a")),
+            Some(Value::ZSet([("y", 3.0)].into_iter().collect()))
+        );
+
+        assert_eq!(
+            run(ZUnionVariant::ZDiff, "dst 2 a b")
+                .execute(store.clone(), &mut client)
+                .await,
+            Ok(Frame::Integer(0))
+        );
+        assert_eq!(store.get(&Key::from("dst")), None);
+
+        assert!(ZUnionStoreCommand::from_parts_as(
+            ZUnionVariant::ZUnion,
+            inline_parts("dst 1 a withscores")
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use strum::{Display, EnumString};
+
+use crate::types::listpack::ListPackLimits;
+
+/// Server configuration.
+///
+/// Fields are named after their `redis.conf` directives, and can be set from the
+/// command line the same way `redis-server` accepts them, e.g. `--port 6380`.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// `bind`
+    pub bind: String,
+    /// `port`
+    pub port: u16,
+    /// `proto-max-bulk-len` -- The largest bulk string a client can send
+    pub proto_max_bulk_len: usize,
+    /// `client-query-buffer-limit` -- The most unprocessed input buffered per client
+    pub client_query_buffer_limit: usize,
+    /// `hz` -- How many times per second background tasks like the active expire cycle run
+    pub hz: u32,
+    /// `active-expire-effort` -- From 1 to 10, how hard the active expire cycle works
+    /// to reclaim expired keys, at the cost of more CPU
+    pub active_expire_effort: u32,
+    /// `maxmemory` -- The most memory keys can use before eviction kicks in, 0 for no limit
+    pub maxmemory: usize,
+    /// `maxmemory-policy` -- How keys are picked for eviction
+    pub maxmemory_policy: EvictionPolicy,
+    /// `maxmemory-samples` -- How many keys are sampled to find one to evict
+    pub maxmemory_samples: usize,
+    /// `lfu-log-factor` -- How many accesses it takes to saturate the LFU counter, higher is more
+    pub lfu_log_factor: u32,
+    /// `lfu-decay-time` -- Minutes without access after which the LFU counter is decremented
+    pub lfu_decay_time: u32,
+    /// `hash-max-listpack-entries` -- The most fields a hash holds in a listpack
+    pub hash_max_listpack_entries: usize,
+    /// `hash-max-listpack-value` -- The longest field or value a hash holds in a listpack
+    pub hash_max_listpack_value: usize,
+    /// `set-max-intset-entries` -- The most integers a set holds in an intset
+    pub set_max_intset_entries: usize,
+    /// `hll-sparse-max-bytes` -- The largest a HyperLogLog grows in the sparse encoding,
+    /// header included, before it turns dense
+    pub hll_sparse_max_bytes: usize,
+}
+
+/// The `maxmemory-policy` values, named like in `redis.conf`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, EnumString, Display)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum EvictionPolicy {
+    /// Don't evict anything, refuse commands that need more memory instead
+    #[default]
+    #[strum(serialize = "noeviction")]
+    NoEviction,
+    /// Evict the least recently used keys
+    AllkeysLru,
+    /// Evict the least recently used keys with a time to live
+    VolatileLru,
+    /// Evict the least frequently used keys
+    AllkeysLfu,
+    /// Evict the least frequently used keys with a time to live
+    VolatileLfu,
+    /// Evict random keys
+    AllkeysRandom,
+    /// Evict random keys with a time to live
+    VolatileRandom,
+    /// Evict the keys with a time to live that expire the soonest
+    VolatileTtl,
+}
+
+impl EvictionPolicy {
+    /// Whether keys are evicted by access frequency, which is only tracked then
+    pub fn is_lfu(&self) -> bool {
+        matches!(
+            self,
+            EvictionPolicy::AllkeysLfu | EvictionPolicy::VolatileLfu
+        )
+    }
+
+    /// Whether keys without a time to live can be evicted too
+    pub fn is_allkeys(&self) -> bool {
+        matches!(
+            self,
+            EvictionPolicy::AllkeysLru | EvictionPolicy::AllkeysLfu | EvictionPolicy::AllkeysRandom
+        )
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            bind: "127.0.0.1".to_string(),
+            port: 6131,
+            proto_max_bulk_len: 512 * 1024 * 1024,
+            client_query_buffer_limit: 1024 * 1024 * 1024,
+            hz: 10,
+            active_expire_effort: 1,
+            maxmemory: 0,
+            maxmemory_policy: EvictionPolicy::default(),
+            maxmemory_samples: 5,
+            lfu_log_factor: 10,
+            lfu_decay_time: 1,
+            hash_max_listpack_entries: 128,
+            hash_max_listpack_value: 64,
+            set_max_intset_entries: 512,
+            hll_sparse_max_bytes: 3000,
+        }
+    }
+}
+
+impl Config {
+    /// Builds a config from `--directive value` pairs, not including the program name.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut config = Config::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let directive = arg
+                .strip_prefix("--")
+                .ok_or(format!(
+                    "Invalid argument '{arg}', expected '--<directive>'"
+                ))?
+                .to_lowercase();
+            let value = args
+                .next()
+                .ok_or(format!("No value provided for '--{directive}'"))?;
+            config.set(&directive, &value)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Sets a single directive from its string value.
+    pub fn set(&mut self, directive: &str, value: &str) -> Result<(), String> {
+        let invalid = || format!("Invalid value '{value}' for '{directive}'");
+
+        match directive {
+            "bind" => self.bind = value.to_string(),
+            "port" => self.port = value.parse().map_err(|_| invalid())?,
+            "proto-max-bulk-len" => {
+                self.proto_max_bulk_len = parse_memory(value).ok_or_else(invalid)?
+            }
+            "client-query-buffer-limit" => {
+                self.client_query_buffer_limit = parse_memory(value).ok_or_else(invalid)?
+            }
+            "hz" => {
+                self.hz = value
+                    .parse()
+                    .ok()
+                    .filter(|hz| (1..=500).contains(hz))
+                    .ok_or_else(invalid)?
+            }
+            "active-expire-effort" => {
+                self.active_expire_effort = value
+                    .parse()
+                    .ok()
+                    .filter(|effort| (1..=10).contains(effort))
+                    .ok_or_else(invalid)?
+            }
+            "maxmemory" => self.maxmemory = parse_memory(value).ok_or_else(invalid)?,
+            "maxmemory-policy" => self.maxmemory_policy = value.parse().map_err(|_| invalid())?,
+            "maxmemory-samples" => {
+                self.maxmemory_samples = value
+                    .parse()
+                    .ok()
+                    .filter(|samples| (1..=64).contains(samples))
+                    .ok_or_else(invalid)?
+            }
+            "lfu-log-factor" => self.lfu_log_factor = value.parse().map_err(|_| invalid())?,
+            "lfu-decay-time" => self.lfu_decay_time = value.parse().map_err(|_| invalid())?,
+            // Listpacks replaced ziplists, whose directives are still accepted
+            "hash-max-listpack-entries" | "hash-max-ziplist-entries" => {
+                self.hash_max_listpack_entries = value.parse().map_err(|_| invalid())?
+            }
+            "hash-max-listpack-value" | "hash-max-ziplist-value" => {
+                self.hash_max_listpack_value = value.parse().map_err(|_| invalid())?
+            }
+            "set-max-intset-entries" => {
+                self.set_max_intset_entries = value.parse().map_err(|_| invalid())?
+            }
+            "hll-sparse-max-bytes" => {
+                self.hll_sparse_max_bytes = parse_memory(value).ok_or_else(invalid)?
+            }
+            _ => return Err(format!("Unknown directive '{directive}'")),
+        }
+
+        Ok(())
+    }
+
+    /// When hashes are converted from a listpack to a hash table
+    pub fn hash_limits(&self) -> ListPackLimits {
+        ListPackLimits {
+            max_entries: self.hash_max_listpack_entries,
+            max_value: self.hash_max_listpack_value,
+        }
+    }
+}
+
+/// Parses a memory size the way `redis.conf` does.
+///
+/// `1k` is 1000 bytes while `1kb` is 1024 bytes, and the same goes for `m`/`mb` and `g`/`gb`.
+pub fn parse_memory(value: &str) -> Option<usize> {
+    let value = value.to_lowercase();
+    let digits_end = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(digits_end);
+
+    let multiplier = match unit {
+        "" | "b" => 1,
+        "k" => 1000,
+        "kb" => 1024,
+        "m" => 1000 * 1000,
+        "mb" => 1024 * 1024,
+        "g" => 1000 * 1000 * 1000,
+        "gb" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+
+    number.parse::<usize>().ok()?.checked_mul(multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_from_args() {
+        let args = ["--port", "6380", "--proto-max-bulk-len", "1mb"].map(String::from);
+        let config = Config::from_args(args).unwrap();
+        assert_eq!(config.port, 6380);
+        assert_eq!(config.proto_max_bulk_len, 1024 * 1024);
+        assert_eq!(config.bind, "127.0.0.1");
+    }
+
+    #[test]
+    fn test_config_eviction_policy() {
+        let args = ["--maxmemory", "100mb", "--maxmemory-policy", "allkeys-LRU"];
+        let config = Config::from_args(args.map(String::from)).unwrap();
+        assert_eq!(config.maxmemory, 100 * 1024 * 1024);
+        assert_eq!(config.maxmemory_policy, EvictionPolicy::AllkeysLru);
+        assert_eq!(EvictionPolicy::NoEviction.to_string(), "noeviction");
+        assert_eq!(EvictionPolicy::VolatileTtl.to_string(), "volatile-ttl");
+        assert!(Config::from_args(["--maxmemory-policy", "lru"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn test_config_from_args_errors() {
+        assert!(Config::from_args(["port".to_string()]).is_err());
+        assert!(Config::from_args(["--port".to_string()]).is_err());
+        assert!(Config::from_args(["--port", "x"].map(String::from)).is_err());
+        assert!(Config::from_args(["--nope", "1"].map(String::from)).is_err());
+        assert!(Config::from_args(["--hz", "0"].map(String::from)).is_err());
+        assert!(Config::from_args(["--active-expire-effort", "11"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn test_parse_memory() {
+        assert_eq!(parse_memory("100"), Some(100));
+        assert_eq!(parse_memory("1k"), Some(1000));
+        assert_eq!(parse_memory("1KB"), Some(1024));
+        assert_eq!(parse_memory("2gb"), Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_memory("1tb"), None);
+        assert_eq!(parse_memory("mb"), None);
+    }
+}
@@ -1,17 +1,146 @@
 use crate::{
-    commands::{CommandTrait, CommandWrapper},
+    client::Client,
+    commands::{CommandParts, CommandTrait, CommandWrapper},
     parser::Parser,
+    resp::Frame,
     store::ConcurrentStore,
 };
 
-pub async fn handle_command(input: String, store: ConcurrentStore) -> Result<String, String> {
-    match Parser::parse_input(input)? {
-        CommandWrapper::Set(cmd) => cmd.execute(store).await,
-        CommandWrapper::Get(cmd) => cmd.execute(store).await,
-        CommandWrapper::Del(cmd) => cmd.execute(store).await,
-        CommandWrapper::Touch(cmd) => cmd.execute(store).await,
-        CommandWrapper::Help(cmd) => cmd.execute(store).await,
-        CommandWrapper::Unknown(cmd) => Ok(format!("Unknown command: {}", cmd)),
-        CommandWrapper::Empty => Ok("".to_string()),
+pub async fn handle_command(
+    parts: CommandParts,
+    store: ConcurrentStore,
+    client: &mut Client,
+) -> Result<Frame, String> {
+    let command = Parser::parse_parts(parts)?;
+
+    if !store.evict_if_needed() && command.denies_oom() {
+        return Err("OOM command not allowed when used memory > 'maxmemory'.".to_string());
+    }
+
+    let result = execute_command(command, store.clone(), client).await;
+    // Like Redis, clients blocked on keys the command created are served right after it
+    store.serve_blocked_clients();
+    result
+}
+
+async fn execute_command(
+    command: CommandWrapper,
+    store: ConcurrentStore,
+    client: &mut Client,
+) -> Result<Frame, String> {
+    match command {
+        CommandWrapper::Set(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Get(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Del(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Touch(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Help(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Ping(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Echo(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Hello(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Expire(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Ttl(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Persist(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Info(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Object(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LPush(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LPop(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LRange(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LIndex(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LLen(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LSet(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LInsert(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LRem(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LTrim(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LPos(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LMove(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::LMPop(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::BLPop(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::BLMove(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::BLMPop(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HSet(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HGet(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HMGet(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HDel(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HGetAll(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HLen(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HIncrBy(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HIncrByFloat(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HScan(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HRandField(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HExpire(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HTtl(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::HPersist(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SAdd(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SRem(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SMembers(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SCard(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SIsMember(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SPop(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SRandMember(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SMove(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SScan(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SInter(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SInterStore(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SInterCard(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZAdd(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZIncrBy(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZRem(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZCard(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZScore(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZRank(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZCount(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZRange(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZRemRangeByRank(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZPopMin(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZRandMember(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZUnion(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZUnionStore(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZInterCard(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZRangeStore(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::ZMPop(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::BZPopMin(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::BZMPop(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XAdd(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XRange(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XLen(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XDel(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XTrim(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XGroup(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XRead(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XReadGroup(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XAck(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XPending(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XClaim(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XAutoClaim(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::XInfo(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::IncrBy(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::IncrByFloat(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Append(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::GetRange(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SetRange(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::StrLen(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Lcs(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::MGet(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::MSet(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::GetDel(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::GetEx(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::SetBit(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::GetBit(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::BitCount(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::BitPos(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::BitOp(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::BitField(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::PFAdd(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::PFCount(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::PFMerge(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::PFDebug(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::PFSelfTest(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::GeoAdd(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::GeoDist(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::GeoHash(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::GeoPos(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::GeoSearch(cmd) => cmd.execute(store, client).await,
+        CommandWrapper::Unknown(cmd) => Err(format!("unknown command '{cmd}'")),
+        CommandWrapper::Empty => Ok(Frame::Null),
     }
 }
@@ -1,4 +1,9 @@
+pub mod client;
 pub mod commands;
+pub mod config;
 pub mod executer;
 pub mod parser;
+pub mod resp;
 pub mod store;
+pub mod types;
+pub mod utils;
@@ -1,6 +1,14 @@
+use std::{
+    future::{poll_fn, Future},
+    task::Poll,
+};
+
 use lib::{
+    client::Client,
+    config::Config,
     executer::handle_command,
-    store::{ConcurrentStore, Store},
+    resp::{CommandDecoder, Frame},
+    store::{expire_cycle::run_active_expire, ConcurrentStore, Store},
 };
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
@@ -9,14 +17,16 @@ use tokio::{
 
 #[tokio::main]
 async fn main() {
-    let store = Store::new();
+    let config = Config::from_args(std::env::args().skip(1))
+        .unwrap_or_else(|e| panic!("Failed to read config\nError: {e}"));
+
+    let addr = format!("{}:{}", config.bind, config.port);
+    let store = Store::with_config(config);
+    tokio::spawn(run_active_expire(store.clone()));
 
-    let host = "127.0.0.1";
-    let port = "6131";
-    let addr = format!("{host}:{port}");
     let listener = TcpListener::bind(&addr)
         .await
-        .unwrap_or_else(|e| panic!("Failed to bind to port {port}\nError: {e}"));
+        .unwrap_or_else(|e| panic!("Failed to bind to {addr}\nError: {e}"));
     println!("Listening on {}", addr);
 
     loop {
@@ -30,32 +40,95 @@ async fn main() {
             handle_connection(socket, store).await;
         });
     }
-
 }
 
 async fn handle_connection(socket: TcpStream, store: ConcurrentStore) {
     let (mut reader, mut writer) = tokio::io::split(socket);
+    let mut client = Client::new();
+    let mut decoder = CommandDecoder::new(&store.config);
 
     loop {
-        let mut buf = vec![0; 1024];
-        let n = match reader.read(&mut buf).await {
-            Ok(n) if n == 0 => return,
-            Ok(n) => n,
+        // Reply to every complete command buffered so far, so a pipeline gets
+        // all of its replies in as few writes as possible
+        let mut response = Vec::new();
+        loop {
+            let parts = match decoder.decode() {
+                Ok(Some(parts)) => parts,
+                Ok(None) => break,
+                Err(e) => {
+                    // Like Redis, reply with the protocol error and close the connection
+                    Frame::error(e).encode(&mut response, client.protocol);
+                    let _ = writer.write_all(&response).await;
+                    return;
+                }
+            };
+
+            if parts.is_empty() {
+                continue;
+            }
+
+            let result = {
+                // A blocking command doesn't change the protocol
+                let protocol = client.protocol;
+                let command = handle_command(parts.into_iter(), store.clone(), &mut client);
+                tokio::pin!(command);
+
+                // Most commands complete right away. The others block (like `BLPOP`),
+                // so reply to the commands before them first
+                match poll_fn(|cx| Poll::Ready(command.as_mut().poll(cx))).await {
+                    Poll::Ready(result) => result,
+                    Poll::Pending => {
+                        if let Err(e) = writer.write_all(&response).await {
+                            eprintln!("Failed to write to socket: {}", e);
+                            return;
+                        }
+                        response.clear();
+
+                        // Keep reading while blocked, so a client that disconnects stops waiting
+                        loop {
+                            tokio::select! {
+                                result = &mut command => break result,
+                                read = reader.read_buf(decoder.buffer_mut()) => match read {
+                                    Ok(0) => return,
+                                    Ok(_) => {
+                                        // Input is only decoded once the command completes, so limit it here
+                                        if let Err(e) = decoder.check_query_buffer() {
+                                            Frame::error(e).encode(&mut response, protocol);
+                                            let _ = writer.write_all(&response).await;
+                                            return;
+                                        }
+                                    }
+                                    Err(e) => {
+                                        eprintln!("Failed to read from socket: {}", e);
+                                        return;
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+            };
+            let frame = match result {
+                Ok(frame) => frame,
+                Err(e) => Frame::error(e),
+            };
+            frame.encode(&mut response, client.protocol);
+        }
+
+        if !response.is_empty() {
+            if let Err(e) = writer.write_all(&response).await {
+                eprintln!("Failed to write to socket: {}", e);
+                return;
+            }
+        }
+
+        match reader.read_buf(decoder.buffer_mut()).await {
+            Ok(0) => return,
+            Ok(_) => (),
             Err(e) => {
                 eprintln!("Failed to read from socket: {}", e);
                 return;
             }
-        };
-
-        let input = String::from_utf8_lossy(&buf[..n]).to_string();
-        let response = match handle_command(input, store.clone()).await {
-            Ok(response) => response,
-            Err(e) => e,
-        } + "\n\n";
-
-        if let Err(e) = writer.write_all(response.as_bytes()).await {
-            eprintln!("Failed to write to socket: {}", e);
-            return;
         }
     }
 }
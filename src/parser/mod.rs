@@ -1,36 +1,455 @@
 use crate::commands::{
-    del_command::DelCommand, get_command::GetCommand, help_command::HelpCommand,
-    set_command::SetCommand, touch_command::TouchCommand, CommandTrait, CommandWrapper,
+    append_command::AppendCommand,
+    bitcount_command::BitCountCommand,
+    bitfield_command::{BitFieldCommand, BitFieldVariant},
+    bitop_command::BitOpCommand,
+    bitpos_command::BitPosCommand,
+    blmove_command::{BLMoveCommand, BLMoveVariant},
+    blmpop_command::BLMPopCommand,
+    blpop_command::{BLPopCommand, BLPopVariant},
+    bzmpop_command::BZMPopCommand,
+    bzpopmin_command::{BZPopMinCommand, BZPopMinVariant},
+    del_command::DelCommand,
+    echo_command::EchoCommand,
+    expire_command::{ExpireCommand, ExpireVariant},
+    geoadd_command::GeoAddCommand,
+    geodist_command::GeoDistCommand,
+    geohash_command::GeoHashCommand,
+    geopos_command::GeoPosCommand,
+    geosearch_command::{GeoSearchCommand, GeoSearchVariant},
+    get_command::GetCommand,
+    getbit_command::GetBitCommand,
+    getdel_command::GetDelCommand,
+    getex_command::GetExCommand,
+    getrange_command::GetRangeCommand,
+    hdel_command::HDelCommand,
+    hello_command::HelloCommand,
+    help_command::HelpCommand,
+    hexpire_command::{HExpireCommand, HExpireVariant},
+    hget_command::{HGetCommand, HGetVariant},
+    hgetall_command::{HGetAllCommand, HGetAllVariant},
+    hincrby_command::HIncrByCommand,
+    hincrbyfloat_command::HIncrByFloatCommand,
+    hlen_command::HLenCommand,
+    hmget_command::HMGetCommand,
+    hpersist_command::HPersistCommand,
+    hrandfield_command::HRandFieldCommand,
+    hscan_command::HScanCommand,
+    hset_command::{HSetCommand, HSetVariant},
+    httl_command::{HTtlCommand, HTtlVariant},
+    incrby_command::{IncrByCommand, IncrByVariant},
+    incrbyfloat_command::IncrByFloatCommand,
+    info_command::InfoCommand,
+    lcs_command::LcsCommand,
+    lindex_command::LIndexCommand,
+    linsert_command::LInsertCommand,
+    llen_command::LLenCommand,
+    lmove_command::{LMoveCommand, LMoveVariant},
+    lmpop_command::LMPopCommand,
+    lpop_command::{LPopCommand, LPopVariant},
+    lpos_command::LPosCommand,
+    lpush_command::{LPushCommand, LPushVariant},
+    lrange_command::LRangeCommand,
+    lrem_command::LRemCommand,
+    lset_command::LSetCommand,
+    ltrim_command::LTrimCommand,
+    mget_command::MGetCommand,
+    mset_command::{MSetCommand, MSetVariant},
+    object_command::ObjectCommand,
+    persist_command::PersistCommand,
+    pfadd_command::PFAddCommand,
+    pfcount_command::PFCountCommand,
+    pfdebug_command::PFDebugCommand,
+    pfmerge_command::PFMergeCommand,
+    pfselftest_command::PFSelfTestCommand,
+    ping_command::PingCommand,
+    sadd_command::SAddCommand,
+    scard_command::SCardCommand,
+    set_command::{SetCommand, SetVariant},
+    setbit_command::SetBitCommand,
+    setrange_command::SetRangeCommand,
+    sinter_command::{SInterCommand, SInterVariant},
+    sintercard_command::SInterCardCommand,
+    sinterstore_command::SInterStoreCommand,
+    sismember_command::{SIsMemberCommand, SIsMemberVariant},
+    smembers_command::SMembersCommand,
+    smove_command::SMoveCommand,
+    spop_command::SPopCommand,
+    srandmember_command::SRandMemberCommand,
+    srem_command::SRemCommand,
+    sscan_command::SScanCommand,
+    strlen_command::StrLenCommand,
+    touch_command::TouchCommand,
+    ttl_command::{TtlCommand, TtlVariant},
+    xack_command::XAckCommand,
+    xadd_command::XAddCommand,
+    xautoclaim_command::XAutoClaimCommand,
+    xclaim_command::XClaimCommand,
+    xdel_command::XDelCommand,
+    xgroup_command::XGroupCommand,
+    xinfo_command::XInfoCommand,
+    xlen_command::XLenCommand,
+    xpending_command::XPendingCommand,
+    xrange_command::{XRangeCommand, XRangeVariant},
+    xread_command::XReadCommand,
+    xreadgroup_command::XReadGroupCommand,
+    xtrim_command::XTrimCommand,
+    zadd_command::ZAddCommand,
+    zcard_command::ZCardCommand,
+    zcount_command::{ZCountCommand, ZCountVariant},
+    zincrby_command::ZIncrByCommand,
+    zintercard_command::ZInterCardCommand,
+    zmpop_command::ZMPopCommand,
+    zpopmin_command::{ZPopMinCommand, ZPopMinVariant},
+    zrandmember_command::ZRandMemberCommand,
+    zrange_command::{ZRangeCommand, ZRangeVariant},
+    zrangestore_command::ZRangeStoreCommand,
+    zrank_command::{ZRankCommand, ZRankVariant},
+    zrem_command::ZRemCommand,
+    zremrangebyrank_command::{ZRemRangeByRankCommand, ZRemRangeByRankVariant},
+    zscore_command::{ZScoreCommand, ZScoreVariant},
+    zunion_command::{ZUnionCommand, ZUnionVariant},
+    zunionstore_command::ZUnionStoreCommand,
+    CommandParts, CommandTrait, CommandWrapper,
 };
 
+use self::utils::{inline_parts, lowercase};
+
 pub mod utils;
 
 pub struct Parser;
 
 impl Parser {
+    /// Parses an inline command, e.g. `set x y`
     pub fn parse_input(input: String) -> Result<CommandWrapper, String> {
-        let mut parts = input.trim().split_whitespace();
-
-        let command_case_insensitive = parts.next().map(|s| s.to_lowercase());
-
-        match command_case_insensitive.as_deref() {
-            Some("set") => SetCommand::from_parts(parts),
-            Some("get") => GetCommand::from_parts(parts),
-            Some("del") => DelCommand::from_parts(parts),
-            Some("touch") => TouchCommand::from_parts(parts),
-            Some("help") => HelpCommand::from_parts(parts),
-            Some(cmd) => parse_unknown_command(cmd),
-            None => Ok(CommandWrapper::Empty),
+        Self::parse_parts(inline_parts(&input))
+    }
+
+    /// Parses a command from its parts, the first part being the command name
+    pub fn parse_parts(mut parts: CommandParts) -> Result<CommandWrapper, String> {
+        let Some(name) = parts.next().map(|s| lowercase(&s)) else {
+            return Ok(CommandWrapper::Empty);
+        };
+        match COMMANDS.iter().find(|(command, _)| *command == name) {
+            Some((_, from_parts)) => from_parts(parts),
+            None => parse_unknown_command(&name),
         }
     }
+
+    /// The names of every command, lowercase
+    pub fn command_names() -> impl Iterator<Item = &'static str> {
+        COMMANDS.iter().map(|(name, _)| *name)
+    }
 }
 
+/// Every command by name, with what parses its parts
+const COMMANDS: &[(&str, FromParts)] = &[
+    ("set", SetCommand::from_parts),
+    ("setnx", |parts| {
+        SetCommand::from_parts_as(SetVariant::SetNx, parts)
+    }),
+    ("setex", |parts| {
+        SetCommand::from_parts_as(SetVariant::SetEx, parts)
+    }),
+    ("psetex", |parts| {
+        SetCommand::from_parts_as(SetVariant::PSetEx, parts)
+    }),
+    ("getset", |parts| {
+        SetCommand::from_parts_as(SetVariant::GetSet, parts)
+    }),
+    ("get", GetCommand::from_parts),
+    ("del", DelCommand::from_parts),
+    ("touch", TouchCommand::from_parts),
+    ("help", HelpCommand::from_parts),
+    ("ping", PingCommand::from_parts),
+    ("echo", EchoCommand::from_parts),
+    ("hello", HelloCommand::from_parts),
+    ("expire", ExpireCommand::from_parts),
+    ("pexpire", |parts| {
+        ExpireCommand::from_parts_as(ExpireVariant::PExpire, parts)
+    }),
+    ("expireat", |parts| {
+        ExpireCommand::from_parts_as(ExpireVariant::ExpireAt, parts)
+    }),
+    ("pexpireat", |parts| {
+        ExpireCommand::from_parts_as(ExpireVariant::PExpireAt, parts)
+    }),
+    ("ttl", TtlCommand::from_parts),
+    ("pttl", |parts| {
+        TtlCommand::from_parts_as(TtlVariant::PTtl, parts)
+    }),
+    ("expiretime", |parts| {
+        TtlCommand::from_parts_as(TtlVariant::ExpireTime, parts)
+    }),
+    ("pexpiretime", |parts| {
+        TtlCommand::from_parts_as(TtlVariant::PExpireTime, parts)
+    }),
+    ("persist", PersistCommand::from_parts),
+    ("info", InfoCommand::from_parts),
+    ("object", ObjectCommand::from_parts),
+    ("lpush", LPushCommand::from_parts),
+    ("rpush", |parts| {
+        LPushCommand::from_parts_as(LPushVariant::RPush, parts)
+    }),
+    ("lpushx", |parts| {
+        LPushCommand::from_parts_as(LPushVariant::LPushX, parts)
+    }),
+    ("rpushx", |parts| {
+        LPushCommand::from_parts_as(LPushVariant::RPushX, parts)
+    }),
+    ("lpop", LPopCommand::from_parts),
+    ("rpop", |parts| {
+        LPopCommand::from_parts_as(LPopVariant::RPop, parts)
+    }),
+    ("lrange", LRangeCommand::from_parts),
+    ("lindex", LIndexCommand::from_parts),
+    ("llen", LLenCommand::from_parts),
+    ("lset", LSetCommand::from_parts),
+    ("linsert", LInsertCommand::from_parts),
+    ("lrem", LRemCommand::from_parts),
+    ("ltrim", LTrimCommand::from_parts),
+    ("lpos", LPosCommand::from_parts),
+    ("lmove", LMoveCommand::from_parts),
+    ("rpoplpush", |parts| {
+        LMoveCommand::from_parts_as(LMoveVariant::RPopLPush, parts)
+    }),
+    ("lmpop", LMPopCommand::from_parts),
+    ("blpop", BLPopCommand::from_parts),
+    ("brpop", |parts| {
+        BLPopCommand::from_parts_as(BLPopVariant::BRPop, parts)
+    }),
+    ("blmove", BLMoveCommand::from_parts),
+    ("brpoplpush", |parts| {
+        BLMoveCommand::from_parts_as(BLMoveVariant::BRPopLPush, parts)
+    }),
+    ("blmpop", BLMPopCommand::from_parts),
+    ("hset", HSetCommand::from_parts),
+    ("hmset", |parts| {
+        HSetCommand::from_parts_as(HSetVariant::HMSet, parts)
+    }),
+    ("hsetnx", |parts| {
+        HSetCommand::from_parts_as(HSetVariant::HSetNx, parts)
+    }),
+    ("hget", HGetCommand::from_parts),
+    ("hexists", |parts| {
+        HGetCommand::from_parts_as(HGetVariant::HExists, parts)
+    }),
+    ("hstrlen", |parts| {
+        HGetCommand::from_parts_as(HGetVariant::HStrLen, parts)
+    }),
+    ("hmget", HMGetCommand::from_parts),
+    ("hdel", HDelCommand::from_parts),
+    ("hgetall", HGetAllCommand::from_parts),
+    ("hkeys", |parts| {
+        HGetAllCommand::from_parts_as(HGetAllVariant::HKeys, parts)
+    }),
+    ("hvals", |parts| {
+        HGetAllCommand::from_parts_as(HGetAllVariant::HVals, parts)
+    }),
+    ("hlen", HLenCommand::from_parts),
+    ("hincrby", HIncrByCommand::from_parts),
+    ("hincrbyfloat", HIncrByFloatCommand::from_parts),
+    ("hscan", HScanCommand::from_parts),
+    ("hrandfield", HRandFieldCommand::from_parts),
+    ("hexpire", HExpireCommand::from_parts),
+    ("hpexpire", |parts| {
+        HExpireCommand::from_parts_as(HExpireVariant::HPExpire, parts)
+    }),
+    ("hexpireat", |parts| {
+        HExpireCommand::from_parts_as(HExpireVariant::HExpireAt, parts)
+    }),
+    ("hpexpireat", |parts| {
+        HExpireCommand::from_parts_as(HExpireVariant::HPExpireAt, parts)
+    }),
+    ("httl", HTtlCommand::from_parts),
+    ("hpttl", |parts| {
+        HTtlCommand::from_parts_as(HTtlVariant::HPTtl, parts)
+    }),
+    ("hexpiretime", |parts| {
+        HTtlCommand::from_parts_as(HTtlVariant::HExpireTime, parts)
+    }),
+    ("hpexpiretime", |parts| {
+        HTtlCommand::from_parts_as(HTtlVariant::HPExpireTime, parts)
+    }),
+    ("hpersist", HPersistCommand::from_parts),
+    ("sadd", SAddCommand::from_parts),
+    ("srem", SRemCommand::from_parts),
+    ("smembers", SMembersCommand::from_parts),
+    ("scard", SCardCommand::from_parts),
+    ("sismember", SIsMemberCommand::from_parts),
+    ("smismember", |parts| {
+        SIsMemberCommand::from_parts_as(SIsMemberVariant::SMIsMember, parts)
+    }),
+    ("spop", SPopCommand::from_parts),
+    ("srandmember", SRandMemberCommand::from_parts),
+    ("smove", SMoveCommand::from_parts),
+    ("sscan", SScanCommand::from_parts),
+    ("sinter", SInterCommand::from_parts),
+    ("sunion", |parts| {
+        SInterCommand::from_parts_as(SInterVariant::SUnion, parts)
+    }),
+    ("sdiff", |parts| {
+        SInterCommand::from_parts_as(SInterVariant::SDiff, parts)
+    }),
+    ("sinterstore", SInterStoreCommand::from_parts),
+    ("sunionstore", |parts| {
+        SInterStoreCommand::from_parts_as(SInterVariant::SUnion, parts)
+    }),
+    ("sdiffstore", |parts| {
+        SInterStoreCommand::from_parts_as(SInterVariant::SDiff, parts)
+    }),
+    ("sintercard", SInterCardCommand::from_parts),
+    ("zadd", ZAddCommand::from_parts),
+    ("zincrby", ZIncrByCommand::from_parts),
+    ("zrem", ZRemCommand::from_parts),
+    ("zcard", ZCardCommand::from_parts),
+    ("zscore", ZScoreCommand::from_parts),
+    ("zmscore", |parts| {
+        ZScoreCommand::from_parts_as(ZScoreVariant::ZMScore, parts)
+    }),
+    ("zrank", ZRankCommand::from_parts),
+    ("zrevrank", |parts| {
+        ZRankCommand::from_parts_as(ZRankVariant::ZRevRank, parts)
+    }),
+    ("zcount", ZCountCommand::from_parts),
+    ("zlexcount", |parts| {
+        ZCountCommand::from_parts_as(ZCountVariant::ZLexCount, parts)
+    }),
+    ("zrange", ZRangeCommand::from_parts),
+    ("zrevrange", |parts| {
+        ZRangeCommand::from_parts_as(ZRangeVariant::ZRevRange, parts)
+    }),
+    ("zrangebyscore", |parts| {
+        ZRangeCommand::from_parts_as(ZRangeVariant::ZRangeByScore, parts)
+    }),
+    ("zrevrangebyscore", |parts| {
+        ZRangeCommand::from_parts_as(ZRangeVariant::ZRevRangeByScore, parts)
+    }),
+    ("zrangebylex", |parts| {
+        ZRangeCommand::from_parts_as(ZRangeVariant::ZRangeByLex, parts)
+    }),
+    ("zrevrangebylex", |parts| {
+        ZRangeCommand::from_parts_as(ZRangeVariant::ZRevRangeByLex, parts)
+    }),
+    ("zremrangebyrank", ZRemRangeByRankCommand::from_parts),
+    ("zremrangebyscore", |parts| {
+        ZRemRangeByRankCommand::from_parts_as(ZRemRangeByRankVariant::ZRemRangeByScore, parts)
+    }),
+    ("zremrangebylex", |parts| {
+        ZRemRangeByRankCommand::from_parts_as(ZRemRangeByRankVariant::ZRemRangeByLex, parts)
+    }),
+    ("zpopmin", ZPopMinCommand::from_parts),
+    ("zpopmax", |parts| {
+        ZPopMinCommand::from_parts_as(ZPopMinVariant::ZPopMax, parts)
+    }),
+    ("zrandmember", ZRandMemberCommand::from_parts),
+    ("zunion", ZUnionCommand::from_parts),
+    ("zinter", |parts| {
+        ZUnionCommand::from_parts_as(ZUnionVariant::ZInter, parts)
+    }),
+    ("zdiff", |parts| {
+        ZUnionCommand::from_parts_as(ZUnionVariant::ZDiff, parts)
+    }),
+    ("zunionstore", ZUnionStoreCommand::from_parts),
+    ("zinterstore", |parts| {
+        ZUnionStoreCommand::from_parts_as(ZUnionVariant::ZInter, parts)
+    }),
+    ("zdiffstore", |parts| {
+        ZUnionStoreCommand::from_parts_as(ZUnionVariant::ZDiff, parts)
+    }),
+    ("zintercard", ZInterCardCommand::from_parts),
+    ("zrangestore", ZRangeStoreCommand::from_parts),
+    ("zmpop", ZMPopCommand::from_parts),
+    ("bzpopmin", BZPopMinCommand::from_parts),
+    ("bzpopmax", |parts| {
+        BZPopMinCommand::from_parts_as(BZPopMinVariant::BZPopMax, parts)
+    }),
+    ("bzmpop", BZMPopCommand::from_parts),
+    ("xadd", XAddCommand::from_parts),
+    ("xrange", XRangeCommand::from_parts),
+    ("xrevrange", |parts| {
+        XRangeCommand::from_parts_as(XRangeVariant::XRevRange, parts)
+    }),
+    ("xlen", XLenCommand::from_parts),
+    ("xdel", XDelCommand::from_parts),
+    ("xtrim", XTrimCommand::from_parts),
+    ("xgroup", XGroupCommand::from_parts),
+    ("xread", XReadCommand::from_parts),
+    ("xreadgroup", XReadGroupCommand::from_parts),
+    ("xack", XAckCommand::from_parts),
+    ("xpending", XPendingCommand::from_parts),
+    ("xclaim", XClaimCommand::from_parts),
+    ("xautoclaim", XAutoClaimCommand::from_parts),
+    ("xinfo", XInfoCommand::from_parts),
+    ("incr", |parts| {
+        IncrByCommand::from_parts_as(IncrByVariant::Incr, parts)
+    }),
+    ("decr", |parts| {
+        IncrByCommand::from_parts_as(IncrByVariant::Decr, parts)
+    }),
+    ("incrby", IncrByCommand::from_parts),
+    ("decrby", |parts| {
+        IncrByCommand::from_parts_as(IncrByVariant::DecrBy, parts)
+    }),
+    ("incrbyfloat", IncrByFloatCommand::from_parts),
+    ("append", AppendCommand::from_parts),
+    ("getrange", GetRangeCommand::from_parts),
+    ("setrange", SetRangeCommand::from_parts),
+    ("strlen", StrLenCommand::from_parts),
+    ("lcs", LcsCommand::from_parts),
+    ("mget", MGetCommand::from_parts),
+    ("mset", MSetCommand::from_parts),
+    ("msetnx", |parts| {
+        MSetCommand::from_parts_as(MSetVariant::MSetNx, parts)
+    }),
+    ("getdel", GetDelCommand::from_parts),
+    ("getex", GetExCommand::from_parts),
+    ("setbit", SetBitCommand::from_parts),
+    ("getbit", GetBitCommand::from_parts),
+    ("bitcount", BitCountCommand::from_parts),
+    ("bitpos", BitPosCommand::from_parts),
+    ("bitop", BitOpCommand::from_parts),
+    ("bitfield", BitFieldCommand::from_parts),
+    ("bitfield_ro", |parts| {
+        BitFieldCommand::from_parts_as(BitFieldVariant::BitFieldRo, parts)
+    }),
+    ("pfadd", PFAddCommand::from_parts),
+    ("pfcount", PFCountCommand::from_parts),
+    ("pfmerge", PFMergeCommand::from_parts),
+    ("pfdebug", PFDebugCommand::from_parts),
+    ("pfselftest", PFSelfTestCommand::from_parts),
+    ("geoadd", GeoAddCommand::from_parts),
+    ("geodist", GeoDistCommand::from_parts),
+    ("geohash", GeoHashCommand::from_parts),
+    ("geopos", GeoPosCommand::from_parts),
+    ("geosearch", GeoSearchCommand::from_parts),
+    ("geosearchstore", |parts| {
+        GeoSearchCommand::from_parts_as(GeoSearchVariant::GeoSearchStore, parts)
+    }),
+    ("georadius", |parts| {
+        GeoSearchCommand::from_parts_as(GeoSearchVariant::GeoRadius, parts)
+    }),
+    ("georadius_ro", |parts| {
+        GeoSearchCommand::from_parts_as(GeoSearchVariant::GeoRadiusRo, parts)
+    }),
+    ("georadiusbymember", |parts| {
+        GeoSearchCommand::from_parts_as(GeoSearchVariant::GeoRadiusByMember, parts)
+    }),
+    ("georadiusbymember_ro", |parts| {
+        GeoSearchCommand::from_parts_as(GeoSearchVariant::GeoRadiusByMemberRo, parts)
+    }),
+];
+
+type FromParts = fn(CommandParts) -> Result<CommandWrapper, String>;
+
 fn parse_unknown_command(cmd: &str) -> Result<CommandWrapper, String> {
     Ok(CommandWrapper::Unknown(cmd.to_string()))
 }
 
 #[cfg(test)]
 mod tests {
+    use bytes::Bytes;
+
     use super::*;
 
     #[test]
@@ -78,6 +497,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_input_of_ping_command() {
+        let input = "PING".to_string();
+        match Parser::parse_input(input) {
+            Ok(CommandWrapper::Ping(..)) => (),
+            _ => panic!("Expected Command::Ping"),
+        }
+    }
+
+    #[test]
+    fn test_parse_parts_of_echo_command() {
+        let parts = vec![Bytes::from("echo"), Bytes::from("hello world")];
+        match Parser::parse_parts(parts.into_iter()) {
+            Ok(CommandWrapper::Echo(cmd)) => assert_eq!(cmd.message, "hello world"),
+            _ => panic!("Expected Command::Echo"),
+        }
+    }
+
     #[test]
     fn test_parse_input_of_unknown_command() {
         let input = "not-a-command".to_string();
@@ -97,4 +534,35 @@ mod tests {
             _ => panic!("Expected Command::Empty"),
         }
     }
+
+    #[test]
+    fn test_commands_growing_memory_deny_oom() {
+        let denies_oom = |input: &str| Parser::parse_input(input.to_string()).unwrap().denies_oom();
+        for input in [
+            "set x y",
+            "xreadgroup group g c streams s >",
+            "xclaim s g c 0 0-1",
+            "xautoclaim s g c 0 0",
+            "georadius k 0 0 1 km store dst",
+        ] {
+            assert!(denies_oom(input), "{input}");
+        }
+        for input in [
+            "get x",
+            "del x",
+            "xread streams s 0",
+            "georadius k 0 0 1 km",
+        ] {
+            assert!(!denies_oom(input), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_command_names_are_unique_and_lowercase() {
+        let names: Vec<&str> = Parser::command_names().collect();
+        for (i, name) in names.iter().enumerate() {
+            assert_eq!(name.to_lowercase(), *name);
+            assert!(!names[..i].contains(name), "{name} is listed twice");
+        }
+    }
 }
@@ -1,7 +1,15 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use crate::{commands::CommandParts, types::list::ListEnd};
+
 pub enum ParseError<'a> {
     MissingKey,
     MissingKeys,
     MissingValue,
+    TooManyArguments,
+    NotAnInteger,
     InvalidCommandOptions(&'a str),
     InvalidCommandOptionValue(&'a str),
 }
@@ -12,6 +20,8 @@ impl<'a> std::fmt::Display for ParseError<'a> {
             ParseError::MissingKey => write!(f, "No key provided"),
             ParseError::MissingKeys => write!(f, "No keys provided"),
             ParseError::MissingValue => write!(f, "No value provided"),
+            ParseError::TooManyArguments => write!(f, "Too many arguments provided"),
+            ParseError::NotAnInteger => write!(f, "value is not an integer or out of range"),
             ParseError::InvalidCommandOptions(msg) => write!(f, "Invalid command options: {msg}"),
             ParseError::InvalidCommandOptionValue(msg) => {
                 write!(f, "Invalid command option value: {msg}")
@@ -19,3 +29,78 @@ impl<'a> std::fmt::Display for ParseError<'a> {
         }
     }
 }
+
+/// Splits an inline command into its parts
+pub fn inline_parts(input: &str) -> CommandParts {
+    input
+        .split_whitespace()
+        .map(|s| Bytes::copy_from_slice(s.as_bytes()))
+        .collect::<Vec<Bytes>>()
+        .into_iter()
+}
+
+/// Lowercases a command part, to match command names and options case insensitively
+pub fn lowercase(part: &[u8]) -> String {
+    String::from_utf8_lossy(part).to_lowercase()
+}
+
+/// Parses a command part as an integer, the way Redis parses numeric arguments
+pub fn parse_int(part: &[u8]) -> Result<i64, String> {
+    std::str::from_utf8(part)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or(ParseError::NotAnInteger.to_string())
+}
+
+/// Parses a command part as a float, the way Redis parses floating point arguments
+///
+/// Infinities are only taken as `inf`, `+inf` or `-inf`, rather than from
+/// numbers too large for a float.
+pub fn parse_float(part: &[u8]) -> Result<f64, String> {
+    let float =
+        std::str::from_utf8(part)
+            .ok()
+            .and_then(|s| match s.to_ascii_lowercase().as_str() {
+                "inf" | "+inf" => Some(f64::INFINITY),
+                "-inf" => Some(f64::NEG_INFINITY),
+                _ => s.parse::<f64>().ok().filter(|f| f.is_finite()),
+            });
+    float.ok_or("value is not a valid float".to_string())
+}
+
+/// Parses a command part as a float like [`parse_float`], but without infinities,
+/// as `INCRBYFLOAT` and `HINCRBYFLOAT` take.
+pub fn parse_finite_float(part: &[u8]) -> Result<f64, String> {
+    let float = parse_float(part)?;
+    match float.is_finite() {
+        true => Ok(float),
+        false => Err("value is not a valid float".to_string()),
+    }
+}
+
+/// Parses the timeout of blocking commands, in seconds with decimals, zero meaning forever
+pub fn parse_timeout(part: &[u8]) -> Result<Option<Duration>, String> {
+    let timeout: f64 = std::str::from_utf8(part)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .filter(|timeout: &f64| timeout.is_finite())
+        .ok_or("timeout is not a float or out of range")?;
+    if timeout < 0.0 {
+        return Err("timeout is negative".to_string());
+    }
+    if timeout == 0.0 {
+        return Ok(None);
+    }
+    Duration::try_from_secs_f64(timeout)
+        .map(Some)
+        .map_err(|_| "timeout is out of range".to_string())
+}
+
+/// Parses the `LEFT` or `RIGHT` argument of list commands
+pub fn parse_list_end(part: &[u8]) -> Result<ListEnd, String> {
+    match lowercase(part).as_str() {
+        "left" => Ok(ListEnd::Left),
+        "right" => Ok(ListEnd::Right),
+        _ => Err(ParseError::InvalidCommandOptions("Expected LEFT or RIGHT").to_string()),
+    }
+}
@@ -0,0 +1,186 @@
+use bytes::Bytes;
+
+use super::Frame;
+
+/// Decodes a single frame from the start of `buf`.
+///
+/// Returns `Ok(None)` if `buf` doesn't hold a complete frame yet, otherwise the
+/// frame and the number of bytes it was encoded in.
+pub fn decode(buf: &[u8]) -> Result<Option<(Frame, usize)>, String> {
+    decode_at(buf, 0)
+}
+
+fn decode_at(buf: &[u8], pos: usize) -> Result<Option<(Frame, usize)>, String> {
+    let Some(&type_byte) = buf.get(pos) else {
+        return Ok(None);
+    };
+    let Some((line, next)) = read_line(buf, pos + 1) else {
+        return Ok(None);
+    };
+
+    let frame = match type_byte {
+        b'+' => Frame::Simple(String::from_utf8_lossy(line).to_string()),
+        b'-' => Frame::Error(String::from_utf8_lossy(line).to_string()),
+        b':' => Frame::Integer(parse_int(line).ok_or("Protocol error: invalid integer")?),
+        b'_' => Frame::Null,
+        b'#' => match line {
+            b"t" => Frame::Boolean(true),
+            b"f" => Frame::Boolean(false),
+            _ => return Err("Protocol error: invalid boolean".to_string()),
+        },
+        b',' => Frame::Double(
+            std::str::from_utf8(line)
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .ok_or("Protocol error: invalid double")?,
+        ),
+        b'(' => Frame::BigNumber(String::from_utf8_lossy(line).to_string()),
+        b'$' | b'=' => {
+            let Some((bytes, next)) = decode_bulk(buf, pos + 1)? else {
+                return Ok(None);
+            };
+            let frame = match (type_byte, bytes) {
+                (_, None) => Frame::Null,
+                (b'$', Some(bytes)) => Frame::Bulk(bytes),
+                (_, Some(bytes)) => {
+                    if bytes.len() < 4 || bytes[3] != b':' {
+                        return Err("Protocol error: invalid verbatim string".to_string());
+                    }
+                    Frame::Verbatim {
+                        format: [bytes[0], bytes[1], bytes[2]],
+                        text: bytes.slice(4..),
+                    }
+                }
+            };
+            return Ok(Some((frame, next)));
+        }
+        b'*' | b'~' | b'>' | b'%' => {
+            let len = parse_int(line).ok_or("Protocol error: invalid multibulk length")?;
+            if len < 0 {
+                return Ok(Some((Frame::NullArray, next)));
+            }
+
+            // Maps are encoded as a flat sequence of keys and values
+            let count = match type_byte {
+                b'%' => len
+                    .checked_mul(2)
+                    .ok_or("Protocol error: invalid multibulk length")?,
+                _ => len,
+            };
+            let mut frames = Vec::with_capacity(count.min(1024) as usize);
+            let mut next = next;
+            for _ in 0..count {
+                match decode_at(buf, next)? {
+                    Some((frame, after)) => {
+                        frames.push(frame);
+                        next = after;
+                    }
+                    None => return Ok(None),
+                }
+            }
+
+            let frame = match type_byte {
+                b'~' => Frame::Set(frames),
+                b'>' => Frame::Push(frames),
+                b'%' => {
+                    let mut pairs = Vec::with_capacity(frames.len() / 2);
+                    let mut frames = frames.into_iter();
+                    while let (Some(key), Some(value)) = (frames.next(), frames.next()) {
+                        pairs.push((key, value));
+                    }
+                    Frame::Map(pairs)
+                }
+                _ => Frame::Array(frames),
+            };
+            return Ok(Some((frame, next)));
+        }
+        c => return Err(format!("Protocol error: invalid type byte '{}'", c as char)),
+    };
+
+    Ok(Some((frame, next)))
+}
+
+/// Decodes the length line and payload of a bulk string, starting right after the `$`.
+///
+/// A `None` payload is the `$-1` nil bulk string.
+fn decode_bulk(buf: &[u8], pos: usize) -> Result<Option<(Option<Bytes>, usize)>, String> {
+    let Some((line, start)) = read_line(buf, pos) else {
+        return Ok(None);
+    };
+    let len = parse_int(line).ok_or("Protocol error: invalid bulk length")?;
+    if len < 0 {
+        return Ok(Some((None, start)));
+    }
+
+    let end = start + len as usize;
+    if buf.len() < end + 2 {
+        return Ok(None);
+    }
+    if &buf[end..end + 2] != b"\r\n" {
+        return Err("Protocol error: invalid bulk format".to_string());
+    }
+
+    Ok(Some((
+        Some(Bytes::copy_from_slice(&buf[start..end])),
+        end + 2,
+    )))
+}
+
+/// Reads a `\r\n` terminated line starting at `pos`.
+///
+/// Returns the line without the terminator and the position right after it.
+fn read_line(buf: &[u8], pos: usize) -> Option<(&[u8], usize)> {
+    let rest = buf.get(pos..)?;
+    let end = rest.windows(2).position(|w| w == b"\r\n")?;
+    Some((&rest[..end], pos + end + 2))
+}
+
+fn parse_int(line: &[u8]) -> Option<i64> {
+    std::str::from_utf8(line).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resp::Protocol;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_round_trips_encoded_frames() {
+        let frame = Frame::Array(vec![
+            Frame::ok(),
+            Frame::Error("ERR oops".to_string()),
+            Frame::Integer(7),
+            Frame::bulk("a\r\nb"),
+            Frame::Null,
+            Frame::NullArray,
+            Frame::Array(vec![Frame::bulk("")]),
+        ]);
+        let encoded = frame.to_bytes(Protocol::Resp2);
+        assert_eq!(decode(&encoded).unwrap(), Some((frame, encoded.len())));
+    }
+
+    #[test]
+    fn test_decode_round_trips_resp3_frames() {
+        let frame = Frame::Map(vec![
+            (Frame::bulk("double"), Frame::Double(-1.5)),
+            (Frame::bulk("bool"), Frame::Boolean(true)),
+            (
+                Frame::bulk("big"),
+                Frame::BigNumber("12345678901234567890".into()),
+            ),
+            (
+                Frame::bulk("verbatim"),
+                Frame::Verbatim {
+                    format: *b"txt",
+                    text: Bytes::from("some text"),
+                },
+            ),
+            (Frame::bulk("set"), Frame::Set(vec![Frame::Integer(1)])),
+            (Frame::bulk("push"), Frame::Push(vec![Frame::bulk("msg")])),
+            (Frame::bulk("null"), Frame::Null),
+        ]);
+        let encoded = frame.to_bytes(Protocol::Resp3);
+        assert_eq!(decode(&encoded).unwrap(), Some((frame, encoded.len())));
+    }
+}
@@ -0,0 +1,345 @@
+use bytes::{Buf, Bytes, BytesMut};
+
+use crate::config::Config;
+
+/// The longest inline command or length line accepted, like Redis' `PROTO_INLINE_MAX_SIZE`
+const INLINE_MAX_SIZE: usize = 64 * 1024;
+
+/// The most arguments a single command can have
+const MULTIBULK_MAX_LEN: usize = 1024 * 1024;
+
+/// How much free space to make before each socket read
+const READ_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Incrementally decodes client requests out of a connection's read buffer.
+///
+/// Reads are appended to [`CommandDecoder::buffer_mut`], and every complete
+/// request is taken out with [`CommandDecoder::decode`]. A request can span
+/// many reads, and a single read can hold many (pipelined) requests.
+///
+/// Clients send commands as RESP arrays of bulk strings, but like Redis, plain
+/// inline commands (`set x y\r\n`) are accepted too so `telnet` still works.
+pub struct CommandDecoder {
+    buf: BytesMut,
+    /// The parts of the multibulk request being decoded
+    parts: Vec<Bytes>,
+    /// How many parts of the multibulk request are yet to be decoded, if one is in progress
+    remaining_parts: Option<usize>,
+    /// The length of the bulk string being waited for, once its length line was decoded
+    bulk_len: Option<usize>,
+    /// The size of the already decoded parts of the request in progress
+    parts_size: usize,
+    max_bulk_len: usize,
+    max_query_buffer: usize,
+}
+
+impl CommandDecoder {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            buf: BytesMut::with_capacity(READ_CHUNK_SIZE),
+            parts: Vec::new(),
+            remaining_parts: None,
+            bulk_len: None,
+            parts_size: 0,
+            max_bulk_len: config.proto_max_bulk_len,
+            max_query_buffer: config.client_query_buffer_limit,
+        }
+    }
+
+    /// The buffer socket reads should be appended to.
+    pub fn buffer_mut(&mut self) -> &mut BytesMut {
+        // Read payloads of big bulk strings in one go instead of small chunks
+        let wanted = match self.bulk_len {
+            Some(len) => (len + 2).saturating_sub(self.buf.len()),
+            None => 0,
+        };
+        self.buf.reserve(wanted.max(READ_CHUNK_SIZE));
+        &mut self.buf
+    }
+
+    /// Takes the next complete request out of the buffer.
+    ///
+    /// Returns `Ok(None)` if more input must be read first. An empty request is
+    /// returned for blank inline lines and empty arrays, which should be ignored.
+    ///
+    /// Errors are protocol errors, after which the connection should be closed.
+    pub fn decode(&mut self) -> Result<Option<Vec<Bytes>>, String> {
+        let result = self.decode_request();
+
+        if let Ok(None) = result {
+            self.check_query_buffer()?;
+        }
+
+        result
+    }
+
+    /// Errors if more input is buffered than `client-query-buffer-limit`, after
+    /// which the connection should be closed.
+    ///
+    /// [`CommandDecoder::decode`] checks this itself, but input read while a
+    /// command blocks isn't decoded until the command completes.
+    pub fn check_query_buffer(&self) -> Result<(), String> {
+        if self.buf.len() + self.parts_size > self.max_query_buffer {
+            return Err("Protocol error: query buffer limit exceeded".to_string());
+        }
+        Ok(())
+    }
+
+    fn decode_request(&mut self) -> Result<Option<Vec<Bytes>>, String> {
+        if self.remaining_parts.is_none() {
+            match self.buf.first() {
+                None => return Ok(None),
+                Some(b'*') => (),
+                Some(_) => return self.decode_inline(),
+            }
+
+            let Some(line) = self.take_line("Protocol error: too big mbulk count string")? else {
+                return Ok(None);
+            };
+            let len = parse_int(&line[1..])
+                .filter(|len| *len <= MULTIBULK_MAX_LEN as i64)
+                .ok_or("Protocol error: invalid multibulk length")?;
+            if len <= 0 {
+                return Ok(Some(vec![]));
+            }
+
+            self.remaining_parts = Some(len as usize);
+            self.parts = Vec::with_capacity((len as usize).min(1024));
+        }
+
+        while let Some(remaining) = self.remaining_parts.filter(|r| *r > 0) {
+            let len = match self.bulk_len {
+                Some(len) => len,
+                None => {
+                    match self.buf.first() {
+                        None => return Ok(None),
+                        Some(b'$') => (),
+                        Some(&c) => {
+                            return Err(format!(
+                                "Protocol error: expected '$', got '{}'",
+                                c as char
+                            ))
+                        }
+                    }
+
+                    let Some(line) = self.take_line("Protocol error: too big bulk count string")?
+                    else {
+                        return Ok(None);
+                    };
+                    let len = parse_int(&line[1..])
+                        .filter(|len| (0..=self.max_bulk_len as i64).contains(len))
+                        .ok_or("Protocol error: invalid bulk length")?
+                        as usize;
+                    self.bulk_len = Some(len);
+                    len
+                }
+            };
+
+            if self.buf.len() < len + 2 {
+                return Ok(None);
+            }
+            if &self.buf[len..len + 2] != b"\r\n" {
+                return Err("Protocol error: invalid bulk format".to_string());
+            }
+
+            let part = self.buf.split_to(len).freeze();
+            self.buf.advance(2);
+            self.parts_size += part.len();
+            self.parts.push(part);
+            self.bulk_len = None;
+            self.remaining_parts = Some(remaining - 1);
+        }
+
+        self.remaining_parts = None;
+        self.parts_size = 0;
+        Ok(Some(std::mem::take(&mut self.parts)))
+    }
+
+    fn decode_inline(&mut self) -> Result<Option<Vec<Bytes>>, String> {
+        let Some(line) = self.take_line("Protocol error: too big inline request")? else {
+            return Ok(None);
+        };
+
+        let parts = line
+            .split(|b| b.is_ascii_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| line.slice_ref(part))
+            .collect();
+        Ok(Some(parts))
+    }
+
+    /// Takes a `\n` terminated line out of the buffer, without the terminator.
+    ///
+    /// Lines longer than [`INLINE_MAX_SIZE`] are rejected with `error`.
+    fn take_line(&mut self, error: &str) -> Result<Option<Bytes>, String> {
+        let searched = self.buf.len().min(INLINE_MAX_SIZE + 1);
+        match self.buf[..searched].iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                let mut line = self.buf.split_to(end + 1);
+                line.truncate(end);
+                if line.last() == Some(&b'\r') {
+                    line.truncate(end - 1);
+                }
+                Ok(Some(line.freeze()))
+            }
+            None if self.buf.len() <= INLINE_MAX_SIZE => Ok(None),
+            None => Err(error.to_string()),
+        }
+    }
+}
+
+fn parse_int(line: &[u8]) -> Option<i64> {
+    std::str::from_utf8(line).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoder_with(input: &[u8]) -> CommandDecoder {
+        let mut decoder = CommandDecoder::new(&Config::default());
+        decoder.buffer_mut().extend_from_slice(input);
+        decoder
+    }
+
+    #[test]
+    fn test_decode_multibulk_command() {
+        let mut decoder = decoder_with(b"*3\r\n$3\r\nset\r\n$3\r\nkey\r\n$5\r\nvalue\r\n");
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(vec![
+                Bytes::from("set"),
+                Bytes::from("key"),
+                Bytes::from("value")
+            ])
+        );
+        assert_eq!(decoder.decode().unwrap(), None);
+    }
+
+    #[test]
+    fn test_decode_inline_command() {
+        let mut decoder = decoder_with(b"set  key value\r\n\r\n");
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(vec![
+                Bytes::from("set"),
+                Bytes::from("key"),
+                Bytes::from("value")
+            ])
+        );
+        assert_eq!(decoder.decode().unwrap(), Some(vec![]));
+        assert_eq!(decoder.decode().unwrap(), None);
+    }
+
+    #[test]
+    fn test_decode_pipelined_commands() {
+        let mut decoder = decoder_with(b"*1\r\n$4\r\nping\r\n*2\r\n$3\r\nget\r\n$1\r\nx\r\nping\n");
+        assert_eq!(decoder.decode().unwrap(), Some(vec![Bytes::from("ping")]));
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(vec![Bytes::from("get"), Bytes::from("x")])
+        );
+        assert_eq!(decoder.decode().unwrap(), Some(vec![Bytes::from("ping")]));
+        assert_eq!(decoder.decode().unwrap(), None);
+    }
+
+    #[test]
+    fn test_decode_command_split_across_reads() {
+        let input = b"*2\r\n$4\r\necho\r\n$11\r\nhello world\r\n";
+        let mut decoder = CommandDecoder::new(&Config::default());
+
+        for (i, byte) in input.iter().enumerate() {
+            decoder.buffer_mut().extend_from_slice(&[*byte]);
+            let decoded = decoder.decode().unwrap();
+            if i < input.len() - 1 {
+                assert_eq!(decoded, None);
+            } else {
+                assert_eq!(
+                    decoded,
+                    Some(vec![Bytes::from("echo"), Bytes::from("hello world")])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_binary_bulk_strings() {
+        let value = b"\x00\r\n\xff\xc3\x28";
+        let mut input = b"*3\r\n$3\r\nset\r\n$1\r\n\x00\r\n$6\r\n".to_vec();
+        input.extend_from_slice(value);
+        input.extend_from_slice(b"\r\n");
+
+        let mut decoder = decoder_with(&input);
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(vec![
+                Bytes::from("set"),
+                Bytes::from(&b"\x00"[..]),
+                Bytes::from(&value[..])
+            ])
+        );
+    }
+
+    #[test]
+    fn test_decode_protocol_errors() {
+        let cases: [(&[u8], &str); 5] = [
+            (b"*x\r\n", "Protocol error: invalid multibulk length"),
+            (b"*1\r\n:1\r\n", "Protocol error: expected '$', got ':'"),
+            (b"*1\r\n$-1\r\n", "Protocol error: invalid bulk length"),
+            (b"*1\r\n$1\r\nab\r\n", "Protocol error: invalid bulk format"),
+            (b"*99999999\r\n", "Protocol error: invalid multibulk length"),
+        ];
+        for (input, error) in cases {
+            assert_eq!(decoder_with(input).decode().unwrap_err(), error);
+        }
+    }
+
+    #[test]
+    fn test_decode_bulk_bigger_than_max_bulk_len() {
+        let config = Config {
+            proto_max_bulk_len: 4,
+            ..Config::default()
+        };
+        let mut decoder = CommandDecoder::new(&config);
+        decoder.buffer_mut().extend_from_slice(b"*1\r\n$5\r\n");
+        assert_eq!(
+            decoder.decode().unwrap_err(),
+            "Protocol error: invalid bulk length"
+        );
+    }
+
+    #[test]
+    fn test_decode_query_buffer_limit() {
+        let config = Config {
+            client_query_buffer_limit: 7,
+            ..Config::default()
+        };
+        let mut decoder = CommandDecoder::new(&config);
+        decoder
+            .buffer_mut()
+            .extend_from_slice(b"*2\r\n$3\r\nget\r\n$10\r\n01234");
+        assert_eq!(
+            decoder.decode().unwrap_err(),
+            "Protocol error: query buffer limit exceeded"
+        );
+
+        // Complete requests count too, while they wait for a blocked command
+        let mut decoder = CommandDecoder::new(&config);
+        decoder.buffer_mut().extend_from_slice(b"ping\r\n");
+        assert_eq!(decoder.check_query_buffer(), Ok(()));
+        decoder.buffer_mut().extend_from_slice(b"ping\r\n");
+        assert_eq!(
+            decoder.check_query_buffer().unwrap_err(),
+            "Protocol error: query buffer limit exceeded"
+        );
+    }
+
+    #[test]
+    fn test_decode_too_big_inline_request() {
+        let mut decoder = decoder_with(&vec![b'a'; INLINE_MAX_SIZE + 1]);
+        assert_eq!(
+            decoder.decode().unwrap_err(),
+            "Protocol error: too big inline request"
+        );
+    }
+}
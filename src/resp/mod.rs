@@ -0,0 +1,378 @@
+//! The REdis Serialization Protocol (RESP)
+//!
+//! https://redis.io/docs/latest/develop/reference/protocol-spec/
+
+use bytes::Bytes;
+
+/// Requests are decoded by [`CommandDecoder`], so decoding any frame is only
+/// needed to check what's encoded
+#[cfg(test)]
+mod decode;
+pub mod decoder;
+
+pub use decoder::CommandDecoder;
+
+const CRLF: &[u8] = b"\r\n";
+
+/// The protocol version spoken on a connection, negotiated with `HELLO`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Protocol {
+    #[default]
+    Resp2,
+    Resp3,
+}
+
+impl Protocol {
+    pub fn version(&self) -> i64 {
+        match self {
+            Protocol::Resp2 => 2,
+            Protocol::Resp3 => 3,
+        }
+    }
+}
+
+/// A single RESP reply (or request) frame.
+///
+/// Commands always reply with the most specific RESP3 type, and frames are
+/// downgraded to their RESP2 equivalent when encoded for a RESP2 connection.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Frame {
+    /// `+OK\r\n`
+    Simple(String),
+    /// `-ERR message\r\n`
+    Error(String),
+    /// `:1000\r\n`
+    Integer(i64),
+    /// `$5\r\nhello\r\n`
+    Bulk(Bytes),
+    /// `$-1\r\n`, the nil reply of commands returning a bulk string
+    ///
+    /// RESP3: `_\r\n`
+    Null,
+    /// `*-1\r\n`, the nil reply of commands returning an array
+    ///
+    /// RESP3: `_\r\n`
+    NullArray,
+    /// `*2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n`
+    Array(Vec<Frame>),
+    /// RESP3: `%1\r\n+key\r\n:1\r\n`
+    ///
+    /// RESP2: a flat array of keys and values
+    Map(Vec<(Frame, Frame)>),
+    /// RESP3: `~2\r\n+a\r\n+b\r\n`
+    ///
+    /// RESP2: an array
+    Set(Vec<Frame>),
+    /// RESP3: `,3.14\r\n`
+    ///
+    /// RESP2: a bulk string
+    Double(f64),
+    /// RESP3: `#t\r\n`
+    ///
+    /// RESP2: the integer `1` or `0`
+    Boolean(bool),
+    /// RESP3: `(3492890328409238509324850943850943825024385\r\n`
+    ///
+    /// RESP2: a bulk string
+    BigNumber(String),
+    /// RESP3: `=15\r\ntxt:Some string\r\n`
+    ///
+    /// RESP2: a bulk string without the format
+    Verbatim { format: [u8; 3], text: Bytes },
+    /// RESP3: `>2\r\n$7\r\nmessage\r\n$2\r\nhi\r\n`, out-of-band data
+    ///
+    /// RESP2: an array
+    Push(Vec<Frame>),
+}
+
+/// The error codes replies may start with, the generic `ERR` included, so
+/// messages that already start with one aren't given another
+pub const ERROR_CODES: &[&str] = &[
+    "ERR",
+    "WRONGTYPE",
+    "WRONGPASS",
+    "NOAUTH",
+    "NOPERM",
+    "NOPROTO",
+    "NOGROUP",
+    "BUSYGROUP",
+    "BUSYKEY",
+    "OOM",
+    "INVALIDOBJ",
+    "TESTFAILED",
+    "EXECABORT",
+];
+
+impl Frame {
+    pub fn ok() -> Self {
+        Frame::Simple("OK".to_string())
+    }
+
+    pub fn bulk(bytes: impl Into<Bytes>) -> Self {
+        Frame::Bulk(bytes.into())
+    }
+
+    /// Creates an error frame.
+    ///
+    /// Redis errors start with an error code (`ERR`, `WRONGTYPE`, ...), so
+    /// messages that don't start with one of [`ERROR_CODES`] get the generic
+    /// `ERR` code. Messages may start with an uppercase keyword, like
+    /// `COUNT must be > 0`, which isn't a code.
+    pub fn error(message: impl Into<String>) -> Self {
+        let message: String = message.into();
+        let has_code = message
+            .split(' ')
+            .next()
+            .is_some_and(|code| ERROR_CODES.contains(&code));
+
+        // Error frames are line based, so they can't contain line breaks
+        let message = message.replace(['\r', '\n'], " ");
+        if has_code {
+            Frame::Error(message)
+        } else {
+            Frame::Error(format!("ERR {message}"))
+        }
+    }
+
+    /// Serializes the frame into `buf` for a connection speaking `protocol`.
+    pub fn encode(&self, buf: &mut Vec<u8>, protocol: Protocol) {
+        let resp3 = protocol == Protocol::Resp3;
+        match self {
+            Frame::Simple(s) => encode_line(buf, b'+', s.as_bytes()),
+            Frame::Error(e) => encode_line(buf, b'-', e.as_bytes()),
+            Frame::Integer(i) => encode_line(buf, b':', i.to_string().as_bytes()),
+            Frame::Bulk(bytes) => encode_blob(buf, b'$', bytes),
+            Frame::Null | Frame::NullArray if resp3 => buf.extend_from_slice(b"_\r\n"),
+            Frame::Null => buf.extend_from_slice(b"$-1\r\n"),
+            Frame::NullArray => buf.extend_from_slice(b"*-1\r\n"),
+            Frame::Array(frames) => encode_aggregate(buf, b'*', frames, protocol),
+            Frame::Map(pairs) => {
+                let (marker, len) = match resp3 {
+                    true => (b'%', pairs.len()),
+                    false => (b'*', pairs.len() * 2),
+                };
+                encode_line(buf, marker, len.to_string().as_bytes());
+                for (key, value) in pairs {
+                    key.encode(buf, protocol);
+                    value.encode(buf, protocol);
+                }
+            }
+            Frame::Set(frames) if resp3 => encode_aggregate(buf, b'~', frames, protocol),
+            Frame::Push(frames) if resp3 => encode_aggregate(buf, b'>', frames, protocol),
+            Frame::Set(frames) | Frame::Push(frames) => {
+                encode_aggregate(buf, b'*', frames, protocol)
+            }
+            Frame::Double(d) if resp3 => encode_line(buf, b',', format_double(*d).as_bytes()),
+            Frame::Double(d) => encode_blob(buf, b'$', format_double(*d).as_bytes()),
+            Frame::Boolean(b) if resp3 => encode_line(buf, b'#', if *b { b"t" } else { b"f" }),
+            Frame::Boolean(b) => encode_line(buf, b':', if *b { b"1" } else { b"0" }),
+            Frame::BigNumber(n) if resp3 => encode_line(buf, b'(', n.as_bytes()),
+            Frame::BigNumber(n) => encode_blob(buf, b'$', n.as_bytes()),
+            Frame::Verbatim { format, text } if resp3 => {
+                let mut payload = Vec::with_capacity(text.len() + 4);
+                payload.extend_from_slice(format);
+                payload.push(b':');
+                payload.extend_from_slice(text);
+                encode_blob(buf, b'=', &payload);
+            }
+            Frame::Verbatim { text, .. } => encode_blob(buf, b'$', text),
+        }
+    }
+
+    pub fn to_bytes(&self, protocol: Protocol) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode(&mut buf, protocol);
+        buf
+    }
+}
+
+fn encode_line(buf: &mut Vec<u8>, marker: u8, line: &[u8]) {
+    buf.push(marker);
+    buf.extend_from_slice(line);
+    buf.extend_from_slice(CRLF);
+}
+
+fn encode_blob(buf: &mut Vec<u8>, marker: u8, blob: &[u8]) {
+    encode_line(buf, marker, blob.len().to_string().as_bytes());
+    buf.extend_from_slice(blob);
+    buf.extend_from_slice(CRLF);
+}
+
+fn encode_aggregate(buf: &mut Vec<u8>, marker: u8, frames: &[Frame], protocol: Protocol) {
+    encode_line(buf, marker, frames.len().to_string().as_bytes());
+    for frame in frames {
+        frame.encode(buf, protocol);
+    }
+}
+
+/// Formats a double the way Redis replies with it.
+///
+/// Uses the shortest representation that round-trips, switching to exponent
+/// notation for very large or small magnitudes (`1e+300`, `1.5e-10`).
+pub fn format_double(d: f64) -> String {
+    if d.is_nan() {
+        return "nan".to_string();
+    }
+    if d.is_infinite() {
+        return if d > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+
+    let abs = d.abs();
+    if abs != 0.0 && !(1e-5..1e17).contains(&abs) {
+        // Rust prints `1e300` and `1e-10`, Redis prints `1e+300` and `1e-10`
+        let formatted = format!("{d:e}");
+        return match formatted.split_once('e') {
+            Some((mantissa, exp)) if !exp.starts_with('-') => format!("{mantissa}e+{exp}"),
+            _ => formatted,
+        };
+    }
+
+    format!("{d}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_simple_types() {
+        let p = Protocol::Resp2;
+        assert_eq!(Frame::ok().to_bytes(p), b"+OK\r\n");
+        assert_eq!(Frame::Integer(-42).to_bytes(p), b":-42\r\n");
+        assert_eq!(Frame::bulk("hello").to_bytes(p), b"$5\r\nhello\r\n");
+        assert_eq!(Frame::bulk("").to_bytes(p), b"$0\r\n\r\n");
+        assert_eq!(Frame::Null.to_bytes(p), b"$-1\r\n");
+        assert_eq!(Frame::NullArray.to_bytes(p), b"*-1\r\n");
+    }
+
+    #[test]
+    fn test_encode_array() {
+        let frame = Frame::Array(vec![
+            Frame::bulk("foo"),
+            Frame::Integer(1),
+            Frame::Array(vec![]),
+        ]);
+        assert_eq!(
+            frame.to_bytes(Protocol::Resp2),
+            b"*3\r\n$3\r\nfoo\r\n:1\r\n*0\r\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_resp3_types() {
+        let p = Protocol::Resp3;
+        assert_eq!(Frame::Null.to_bytes(p), b"_\r\n");
+        assert_eq!(Frame::NullArray.to_bytes(p), b"_\r\n");
+        assert_eq!(Frame::Double(3.5).to_bytes(p), b",3.5\r\n");
+        assert_eq!(Frame::Double(f64::INFINITY).to_bytes(p), b",inf\r\n");
+        assert_eq!(Frame::Boolean(true).to_bytes(p), b"#t\r\n");
+        assert_eq!(Frame::BigNumber("123".into()).to_bytes(p), b"(123\r\n");
+        assert_eq!(
+            Frame::Verbatim {
+                format: *b"txt",
+                text: Bytes::from("hi")
+            }
+            .to_bytes(p),
+            b"=6\r\ntxt:hi\r\n"
+        );
+        assert_eq!(
+            Frame::Map(vec![(Frame::bulk("a"), Frame::Integer(1))]).to_bytes(p),
+            b"%1\r\n$1\r\na\r\n:1\r\n"
+        );
+        assert_eq!(
+            Frame::Set(vec![Frame::bulk("a")]).to_bytes(p),
+            b"~1\r\n$1\r\na\r\n"
+        );
+        assert_eq!(
+            Frame::Push(vec![Frame::bulk("a")]).to_bytes(p),
+            b">1\r\n$1\r\na\r\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_resp3_types_downgraded_to_resp2() {
+        let p = Protocol::Resp2;
+        assert_eq!(Frame::Double(3.5).to_bytes(p), b"$3\r\n3.5\r\n");
+        assert_eq!(Frame::Boolean(false).to_bytes(p), b":0\r\n");
+        assert_eq!(Frame::BigNumber("123".into()).to_bytes(p), b"$3\r\n123\r\n");
+        assert_eq!(
+            Frame::Verbatim {
+                format: *b"txt",
+                text: Bytes::from("hi")
+            }
+            .to_bytes(p),
+            b"$2\r\nhi\r\n"
+        );
+        assert_eq!(
+            Frame::Map(vec![(Frame::bulk("a"), Frame::Integer(1))]).to_bytes(p),
+            b"*2\r\n$1\r\na\r\n:1\r\n"
+        );
+        assert_eq!(
+            Frame::Set(vec![Frame::bulk("a")]).to_bytes(p),
+            b"*1\r\n$1\r\na\r\n"
+        );
+    }
+
+    #[test]
+    fn test_format_double() {
+        assert_eq!(format_double(1.0), "1");
+        assert_eq!(format_double(-2.5), "-2.5");
+        assert_eq!(format_double(0.1), "0.1");
+        assert_eq!(format_double(1e300), "1e+300");
+        assert_eq!(format_double(1.5e-10), "1.5e-10");
+        assert_eq!(format_double(f64::NEG_INFINITY), "-inf");
+    }
+
+    #[test]
+    fn test_error_gets_generic_code() {
+        assert_eq!(
+            Frame::error("No key provided"),
+            Frame::Error("ERR No key provided".to_string())
+        );
+        assert_eq!(
+            Frame::error("WRONGTYPE Operation against a key holding the wrong kind of value"),
+            Frame::Error(
+                "WRONGTYPE Operation against a key holding the wrong kind of value".to_string()
+            )
+        );
+        assert_eq!(
+            Frame::error("multi\r\nline").to_bytes(Protocol::Resp2),
+            b"-ERR multi  line\r\n"
+        );
+    }
+
+    #[test]
+    fn test_error_keyword_is_not_a_code() {
+        for (message, encoded) in [
+            (
+                "XX and NX options at the same time are not compatible",
+                &b"-ERR XX and NX options at the same time are not compatible\r\n"[..],
+            ),
+            (
+                "LIMIT can't be negative",
+                b"-ERR LIMIT can't be negative\r\n",
+            ),
+            ("RANK can't be zero", b"-ERR RANK can't be zero\r\n"),
+            ("COUNT must be > 0", b"-ERR COUNT must be > 0\r\n"),
+            (
+                "BITOP NOT must be called with a single source key.",
+                b"-ERR BITOP NOT must be called with a single source key.\r\n",
+            ),
+            (
+                "HLL encoding is not sparse",
+                b"-ERR HLL encoding is not sparse\r\n",
+            ),
+            ("ERR syntax error", b"-ERR syntax error\r\n"),
+            (
+                "NOGROUP No such consumer group 'g' for key name 's'",
+                b"-NOGROUP No such consumer group 'g' for key name 's'\r\n",
+            ),
+            (
+                "OOM command not allowed when used memory > 'maxmemory'.",
+                b"-OOM command not allowed when used memory > 'maxmemory'.\r\n",
+            ),
+        ] {
+            assert_eq!(Frame::error(message).to_bytes(Protocol::Resp2), encoded);
+        }
+    }
+}
@@ -0,0 +1,408 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::Duration,
+};
+
+use tokio::sync::oneshot;
+
+use crate::resp::Frame;
+
+use super::{Key, Store, Value};
+
+/// Serves a blocked client from one of its keys.
+///
+/// Returns `None` if the key can't serve the client, which then keeps waiting.
+pub type Serve = Box<dyn Fn(&Store, &Key) -> Option<Result<Frame, String>> + Send + Sync>;
+
+/// A client blocked on keys, like by `BLPOP`.
+pub struct Waiter {
+    keys: Vec<Key>,
+    /// Whether a value is of the type the client waits for
+    wanted: fn(&Value) -> bool,
+    serve: Serve,
+    /// Taken once the client is served, or stops waiting. Held while the client
+    /// is served, so it can't stop waiting halfway and lose what was popped for it
+    reply: Mutex<Option<oneshot::Sender<Result<Frame, String>>>>,
+}
+
+/// The clients blocked on keys, served in the order they blocked.
+#[derive(Default)]
+pub struct BlockedClients {
+    /// The clients blocked on each key, from the first one to block
+    waiters: Mutex<HashMap<Key, VecDeque<Arc<Waiter>>, ahash::RandomState>>,
+    /// Keys created since clients were last served, while clients wait on them,
+    /// in the order they were created
+    ready_keys: Mutex<VecDeque<Key>>,
+    /// How many clients are blocked, so keys aren't signaled when none are
+    blocked: AtomicUsize,
+}
+
+impl BlockedClients {
+    pub fn len(&self) -> usize {
+        self.blocked.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn register(&self, waiter: &Arc<Waiter>) {
+        let mut waiters = lock(&self.waiters);
+        for key in &waiter.keys {
+            waiters
+                .entry(key.clone())
+                .or_default()
+                .push_back(waiter.clone());
+        }
+        self.blocked.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn unregister(&self, waiter: &Arc<Waiter>) {
+        let mut waiters = lock(&self.waiters);
+        for key in &waiter.keys {
+            if let Some(queue) = waiters.get_mut(key) {
+                queue.retain(|w| !Arc::ptr_eq(w, waiter));
+                if queue.is_empty() {
+                    waiters.remove(key);
+                }
+            }
+        }
+        self.blocked.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl Store {
+    /// Serves the client from the first of `keys` that can, blocking until one can
+    /// if none can right away.
+    ///
+    /// Blocked clients are served in the order they blocked, when a key they wait on
+    /// is created. A `timeout` of `None` blocks forever. Returns `None` if the client
+    /// timed out.
+    ///
+    /// If the returned future is dropped, like when the client disconnects, the
+    /// client stops waiting.
+    pub async fn block_on(
+        &self,
+        keys: Vec<Key>,
+        timeout: Option<Duration>,
+        wanted: fn(&Value) -> bool,
+        serve: Serve,
+    ) -> Option<Result<Frame, String>> {
+        let (sender, mut receiver) = oneshot::channel();
+        let mut unique_keys = Vec::with_capacity(keys.len());
+        for key in keys {
+            if !unique_keys.contains(&key) {
+                unique_keys.push(key);
+            }
+        }
+        let waiter = Arc::new(Waiter {
+            keys: unique_keys,
+            wanted,
+            serve,
+            reply: Mutex::new(Some(sender)),
+        });
+
+        // Checking the keys and blocking must not interleave with a push
+        let served = self.atomically(|| {
+            let served = waiter.keys.iter().find_map(|key| (waiter.serve)(self, key));
+            if served.is_none() {
+                self.blocked_clients.register(&waiter);
+            }
+            served
+        });
+        if served.is_some() {
+            return served;
+        }
+
+        /// Stops waiting when dropped, unless the client was served
+        struct Unblock<'a> {
+            store: &'a Store,
+            waiter: Arc<Waiter>,
+        }
+        impl Drop for Unblock<'_> {
+            fn drop(&mut self) {
+                if lock(&self.waiter.reply).take().is_some() {
+                    self.store.blocked_clients.unregister(&self.waiter);
+                }
+            }
+        }
+
+        let unblock = Unblock {
+            store: self,
+            waiter,
+        };
+        let reply = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, &mut receiver).await.ok(),
+            None => Some((&mut receiver).await),
+        };
+        match reply {
+            Some(Ok(reply)) => Some(reply),
+            _ => {
+                drop(unblock);
+                // The client may have been served right as it timed out
+                receiver.try_recv().ok()
+            }
+        }
+    }
+
+    /// Marks a key as able to serve the clients blocked on it, if any.
+    ///
+    /// They are served by [`Store::serve_blocked_clients`].
+    pub fn signal_key_ready(&self, key: &Key) {
+        if self.blocked_clients.is_empty() {
+            return;
+        }
+        if lock(&self.blocked_clients.waiters).contains_key(key) {
+            let mut ready_keys = lock(&self.blocked_clients.ready_keys);
+            if !ready_keys.contains(key) {
+                ready_keys.push_back(key.clone());
+            }
+        }
+    }
+
+    /// Serves the clients blocked on the keys signaled since the last call, in
+    /// the order they blocked, like Redis does after every command.
+    pub fn serve_blocked_clients(&self) {
+        // Runs after every command, so don't lock the keyspace unless a key is ready
+        if self.blocked_clients.is_empty() || lock(&self.blocked_clients.ready_keys).is_empty() {
+            return;
+        }
+
+        self.atomically(|| loop {
+            // Serving a client can signal other keys, like the destination of `BLMOVE`
+            let Some(key) = lock(&self.blocked_clients.ready_keys).pop_front() else {
+                return;
+            };
+            let waiters: Vec<Arc<Waiter>> = match lock(&self.blocked_clients.waiters).get(&key) {
+                Some(queue) => queue.iter().cloned().collect(),
+                None => continue,
+            };
+
+            for waiter in waiters {
+                // Once the key is gone, there's nothing left to serve from it, but
+                // clients waiting for another type are skipped, like Redis does
+                match self.read(&key, waiter.wanted) {
+                    None => break,
+                    Some(false) => continue,
+                    Some(true) => (),
+                }
+                let mut reply = lock(&waiter.reply);
+                if reply.is_none() {
+                    continue;
+                }
+                if let Some(result) = (waiter.serve)(self, &key) {
+                    let sender = reply.take().expect("checked above");
+                    drop(reply);
+                    self.blocked_clients.unregister(&waiter);
+                    // The client may be gone, like Redis, the reply is dropped then
+                    let _ = sender.send(result);
+                }
+            }
+        })
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::types::{list::List, zset::ZSet};
+
+    use super::*;
+
+    fn is_list(value: &Value) -> bool {
+        value.as_list().is_ok()
+    }
+
+    /// Pops the head of the list, replying with the popped element
+    fn pop() -> Serve {
+        Box::new(|store, key| {
+            let element = store.update(key, |value| value.as_list_mut().ok()?.pop_front())?;
+            element.map(|element| Ok(Frame::bulk(element)))
+        })
+    }
+
+    fn push(store: &Store, key: &str, element: &'static str) {
+        push_without_serving(store, key, element);
+        store.serve_blocked_clients();
+    }
+
+    /// Pushes like one of many writes of a single command
+    fn push_without_serving(store: &Store, key: &str, element: &'static str) {
+        store.upsert(
+            &Key::from(key.to_string()),
+            || Value::List(List::new()),
+            |value| value.as_list_mut().unwrap().push_back(element.as_bytes()),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_block_on_serves_right_away() {
+        let store = Store::new();
+        push(&store, "list", "a");
+        let reply = store.block_on(vec![Key::from("list")], None, is_list, pop());
+        assert_eq!(reply.await, Some(Ok(Frame::bulk("a"))));
+        assert!(store.blocked_clients.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_block_on_times_out() {
+        let store = Store::new();
+        let timeout = Some(Duration::from_millis(10));
+        let reply = store.block_on(vec![Key::from("list")], timeout, is_list, pop());
+        assert_eq!(reply.await, None);
+        assert!(store.blocked_clients.is_empty());
+        assert!(lock(&store.blocked_clients.waiters).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_blocked_clients_are_served_in_order() {
+        let store = Store::new();
+        let mut clients = Vec::new();
+        for _ in 0..3 {
+            let client_store = store.clone();
+            clients.push(tokio::spawn(async move {
+                client_store
+                    .block_on(
+                        vec![Key::from("other"), Key::from("list")],
+                        None,
+                        is_list,
+                        pop(),
+                    )
+                    .await
+            }));
+            // Let the client block before the next one
+            while store.blocked_clients.len() < clients.len() {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        push(&store, "list", "a");
+        push(&store, "other", "b");
+        push(&store, "list", "c");
+        let mut replies = Vec::new();
+        for client in clients {
+            replies.push(client.await.unwrap());
+        }
+        assert_eq!(
+            replies,
+            [
+                Some(Ok(Frame::bulk("a"))),
+                Some(Ok(Frame::bulk("b"))),
+                Some(Ok(Frame::bulk("c")))
+            ]
+        );
+        assert!(store.blocked_clients.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_served_in_the_order_they_got_ready() {
+        let store = Store::new();
+        let client = {
+            let store = store.clone();
+            tokio::spawn(async move {
+                store
+                    .block_on(vec![Key::from("a"), Key::from("b")], None, is_list, pop())
+                    .await
+            })
+        };
+        while store.blocked_clients.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        // Both keys get ready in a single command, `b` first
+        push_without_serving(&store, "b", "from b");
+        push_without_serving(&store, "a", "from a");
+        store.serve_blocked_clients();
+        assert_eq!(client.await.unwrap(), Some(Ok(Frame::bulk("from b"))));
+        assert_eq!(
+            store.get(&Key::from("a")),
+            Some(Value::List([Bytes::from("from a")].into_iter().collect()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clients_waiting_for_another_type_are_skipped() {
+        let store = Store::new();
+        let list_client = {
+            let store = store.clone();
+            tokio::spawn(async move {
+                store
+                    .block_on(vec![Key::from("k")], None, is_list, pop())
+                    .await
+            })
+        };
+        while store.blocked_clients.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let zset_client = {
+            let store = store.clone();
+            let pop_min: Serve = Box::new(|store, key| {
+                let popped = store.update(key, |value| value.as_zset_mut().ok()?.pop(false))?;
+                popped.map(|(member, _)| Ok(Frame::bulk(member)))
+            });
+            tokio::spawn(async move {
+                store
+                    .block_on(
+                        vec![Key::from("k")],
+                        None,
+                        |value| value.as_zset().is_ok(),
+                        pop_min,
+                    )
+                    .await
+            })
+        };
+        while store.blocked_clients.len() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        store.upsert(
+            &Key::from("k"),
+            || Value::ZSet(ZSet::new()),
+            |value| value.as_zset_mut().unwrap().insert(Bytes::from("a"), 1.0),
+        );
+        store.serve_blocked_clients();
+        let reply = tokio::time::timeout(Duration::from_secs(1), zset_client).await;
+        assert_eq!(
+            reply.expect("the zset client wasn't served").unwrap(),
+            Some(Ok(Frame::bulk("a")))
+        );
+        assert_eq!(store.blocked_clients.len(), 1);
+        list_client.abort();
+    }
+
+    #[tokio::test]
+    async fn test_dropped_client_stops_waiting() {
+        let store = Store::new();
+        let client = {
+            let store = store.clone();
+            tokio::spawn(async move {
+                store
+                    .block_on(vec![Key::from("list")], None, is_list, pop())
+                    .await
+            })
+        };
+        while store.blocked_clients.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        client.abort();
+        let _ = client.await;
+        assert!(store.blocked_clients.is_empty());
+
+        // Nobody takes the pushed element anymore
+        push(&store, "list", "a");
+        assert_eq!(
+            store.get(&Key::from("list")),
+            Some(Value::List([Bytes::from("a")].into_iter().collect()))
+        );
+    }
+}
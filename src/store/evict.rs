@@ -0,0 +1,244 @@
+use crate::{config::EvictionPolicy, utils::current_epoch_millis};
+
+use super::{stats::Stats, Data, Entry, Key, Store};
+
+/// How many candidates the eviction pool keeps, like Redis' `EVPOOL_SIZE`
+const EVICTION_POOL_SIZE: usize = 16;
+
+/// The best candidates for eviction seen so far, sorted from the worst to the best.
+///
+/// Evicting the best of a few sampled keys is a poor approximation of LRU on its
+/// own, so like Redis, the best candidates of past samples are kept around and
+/// compete with the next samples.
+#[derive(Default)]
+pub struct EvictionPool {
+    /// Pairs of the score of a key, higher is better to evict, and the key
+    candidates: Vec<(u128, Key)>,
+}
+
+impl EvictionPool {
+    fn insert(&mut self, score: u128, key: Key) {
+        if let Some(i) = self.candidates.iter().position(|(_, k)| *k == key) {
+            self.candidates.remove(i);
+        }
+        if self.candidates.len() == EVICTION_POOL_SIZE && score <= self.candidates[0].0 {
+            return;
+        }
+
+        let i = self.candidates.partition_point(|(s, _)| *s < score);
+        self.candidates.insert(i, (score, key));
+        if self.candidates.len() > EVICTION_POOL_SIZE {
+            self.candidates.remove(0);
+        }
+    }
+
+    fn pop(&mut self) -> Option<Key> {
+        self.candidates.pop().map(|(_, key)| key)
+    }
+}
+
+impl Store {
+    /// Evicts keys until the used memory fits in `maxmemory`.
+    ///
+    /// Returns `false` if the memory is still over the limit, because the policy is
+    /// `noeviction` or there's nothing left to evict.
+    pub fn evict_if_needed(&self) -> bool {
+        let maxmemory = self.config.maxmemory;
+        if maxmemory == 0 {
+            return true;
+        }
+
+        while self.used_memory() > maxmemory {
+            if !self.evict_one() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Evicts the best key of a sample, returning `false` if there was none.
+    fn evict_one(&self) -> bool {
+        let policy = self.config.maxmemory_policy;
+        let keys = match policy {
+            EvictionPolicy::NoEviction => return false,
+            _ if policy.is_allkeys() => &self.keys,
+            _ => &self.volatile_keys,
+        };
+        let sample = keys
+            .lock()
+            .unwrap()
+            .sample(self.config.maxmemory_samples, &mut rand::thread_rng());
+
+        if matches!(
+            policy,
+            EvictionPolicy::AllkeysRandom | EvictionPolicy::VolatileRandom
+        ) {
+            return sample.iter().any(|key| self.evict(key));
+        }
+
+        let now = current_epoch_millis();
+        let lfu_decay_time = self.config.lfu_decay_time;
+        let mut pool = self.eviction_pool.lock().unwrap();
+        for key in sample {
+            if let Some(score) = self.map.read(&key, |_, data| {
+                eviction_score(policy, data, now, lfu_decay_time)
+            }) {
+                pool.insert(score, key);
+            }
+        }
+
+        // Candidates from past samples may be gone by now
+        while let Some(key) = pool.pop() {
+            if self.evict(&key) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Deletes a key if the policy allows evicting it.
+    fn evict(&self, key: &Key) -> bool {
+        let _shared = self.shared();
+        match self.map.entry(key.clone()) {
+            Entry::Occupied(entry)
+                if self.config.maxmemory_policy.is_allkeys()
+                    || entry.get().expires_at.is_some() =>
+            {
+                self.remove_entry(entry);
+                Stats::incr(&self.stats.evicted_keys, 1);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// How good a candidate for eviction a key is, higher is better.
+fn eviction_score(policy: EvictionPolicy, data: &Data, now: u128, lfu_decay_time: u32) -> u128 {
+    match policy {
+        EvictionPolicy::AllkeysLru | EvictionPolicy::VolatileLru => {
+            now.saturating_sub(data.last_accessed)
+        }
+        EvictionPolicy::AllkeysLfu | EvictionPolicy::VolatileLfu => {
+            (u8::MAX - data.lfu.frequency(now, lfu_decay_time)) as u128
+        }
+        EvictionPolicy::VolatileTtl => data
+            .expires_at
+            .map_or(0, |expires_at| u128::MAX - expires_at),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::{
+        config::Config,
+        store::{ConcurrentStore, SetOptions, Value},
+    };
+
+    fn store_with(maxmemory: usize, policy: EvictionPolicy) -> ConcurrentStore {
+        Store::with_config(Config {
+            maxmemory,
+            maxmemory_policy: policy,
+            ..Config::default()
+        })
+    }
+
+    fn value() -> Value {
+        Value::Str(Bytes::from(vec![b'x'; 1000]))
+    }
+
+    #[test]
+    fn test_eviction_pool_keeps_the_best_candidates() {
+        let mut pool = EvictionPool::default();
+        for score in 0..40 {
+            pool.insert(score, Key::from(score.to_string()));
+        }
+        pool.insert(39, Key::from("39"));
+
+        assert_eq!(pool.candidates.len(), EVICTION_POOL_SIZE);
+        assert_eq!(pool.pop(), Some(Key::from("39")));
+        assert_eq!(pool.pop(), Some(Key::from("38")));
+    }
+
+    #[test]
+    fn test_noeviction_refuses_to_evict() {
+        let store = store_with(10_000, EvictionPolicy::NoEviction);
+        for i in 0..20 {
+            store.set(Key::from(format!("key:{i}")), value());
+        }
+
+        assert!(!store.evict_if_needed());
+        assert_eq!(store.map.len(), 20);
+    }
+
+    #[test]
+    fn test_allkeys_lru_evicts_the_least_recently_used_keys() {
+        let store = store_with(20_000, EvictionPolicy::AllkeysLru);
+        for i in 0..100 {
+            let key = Key::from(format!("key:{i}"));
+            store.set(key.clone(), value());
+            // Make the first keys the least recently used
+            store.map.update(&key, |_, data| data.last_accessed = i);
+            assert!(store.evict_if_needed());
+        }
+
+        assert!(store.used_memory() <= 20_000);
+        assert!(store.get(&Key::from("key:99")).is_some());
+        assert!(store.get(&Key::from("key:0")).is_none());
+        assert!(Stats::get(&store.stats.evicted_keys) > 0);
+    }
+
+    #[test]
+    fn test_allkeys_lfu_evicts_the_least_frequently_used_keys() {
+        let store = store_with(20_000, EvictionPolicy::AllkeysLfu);
+        let hot = Key::from("hot");
+        store.set(hot.clone(), value());
+        for _ in 0..100 {
+            store.get(&hot);
+        }
+
+        for i in 0..100 {
+            store.set(Key::from(format!("key:{i}")), value());
+            assert!(store.evict_if_needed());
+        }
+
+        assert!(store.lfu_frequency(&hot).unwrap() > 5);
+        assert!(store.get(&hot).is_some());
+    }
+
+    #[test]
+    fn test_volatile_policies_only_evict_volatile_keys() {
+        let store = store_with(10_000, EvictionPolicy::VolatileTtl);
+        let later = current_epoch_millis() + 100_000;
+        for i in 0..20 {
+            let key = Key::from(format!("key:{i}"));
+            let options = SetOptions {
+                expires_at: (i % 2 == 0).then_some(later + i),
+                ..Default::default()
+            };
+            store.set_with(key, value(), options).unwrap();
+        }
+
+        assert!(!store.evict_if_needed());
+        assert_eq!(store.map.len(), 10);
+        assert_eq!(store.volatile_len(), 0);
+    }
+
+    #[test]
+    fn test_used_memory_is_released() {
+        let store = store_with(0, EvictionPolicy::AllkeysRandom);
+        let key = Key::from("key");
+
+        store.set(key.clone(), value());
+        store.set(key.clone(), Value::Str(Bytes::from("small")));
+        assert!(store.used_memory() < 1000);
+
+        store.del(&key);
+        assert_eq!(store.used_memory(), 0);
+        assert!(store.keys.lock().unwrap().is_empty());
+    }
+}
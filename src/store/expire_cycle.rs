@@ -0,0 +1,220 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::{keyset::KeySet, stats::Stats, ConcurrentStore, Key, Store};
+use crate::utils::current_epoch_millis;
+
+/// Keys sampled per loop at the lowest `active-expire-effort`
+const KEYS_PER_LOOP: usize = 20;
+
+/// Share of CPU time the cycle may use at the lowest effort, in percent
+const CYCLE_TIME_PERC: u64 = 25;
+
+/// Share of expired keys in a sample below which the cycle stops, in percent
+const ACCEPTABLE_STALE: usize = 10;
+
+/// Runs [`Store::active_expire_cycle`] `hz` times per second, forever.
+pub async fn run_active_expire(store: ConcurrentStore) {
+    let period = Duration::from_millis(1000 / store.config.hz.max(1) as u64);
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        store.active_expire_cycle();
+    }
+}
+
+impl Store {
+    /// Deletes expired keys that nobody accessed, returning how many were deleted.
+    ///
+    /// Like Redis, random volatile keys are sampled and the expired ones deleted,
+    /// again and again while a sample has too many expired keys, on the assumption
+    /// that the whole keyspace is as stale as the sample. Each cycle is capped to a
+    /// share of the time between two cycles so a mass expiry can't stall clients.
+    /// The hashes with fields to expire are then sampled the same way, within the
+    /// same time cap.
+    ///
+    /// `active-expire-effort` raises the sample size, the time cap, and lowers
+    /// the share of stale keys tolerated.
+    pub fn active_expire_cycle(&self) -> usize {
+        let effort = self.config.active_expire_effort.clamp(1, 10) as usize - 1;
+        let time_perc = CYCLE_TIME_PERC + 2 * effort as u64;
+        let cycle = Cycle {
+            start: Instant::now(),
+            time_limit: Duration::from_micros(
+                time_perc * 1_000_000 / self.config.hz.max(1) as u64 / 100,
+            ),
+            keys_per_loop: KEYS_PER_LOOP + KEYS_PER_LOOP / 4 * effort,
+            acceptable_stale: ACCEPTABLE_STALE - effort,
+        };
+
+        let total_expired = self.sample_and_expire(&cycle, &self.volatile_keys, |key, now| {
+            self.remove_if_expired(key, now)
+        });
+        if cycle.start.elapsed() <= cycle.time_limit {
+            self.sample_and_expire(&cycle, &self.volatile_fields, |key, now| {
+                self.remove_expired_fields_of(key, now)
+            });
+        }
+
+        let elapsed = cycle.start.elapsed().as_micros() as u64;
+        Stats::incr(&self.stats.expire_cycles, 1);
+        Stats::incr(&self.stats.expire_cycle_time_used_us, elapsed);
+        self.stats
+            .expire_cycle_last_time_us
+            .store(elapsed, std::sync::atomic::Ordering::Relaxed);
+
+        total_expired
+    }
+
+    /// Samples keys of `keys` and expires them with `expire`, until a sample is
+    /// fresh enough or the cycle runs out of time.
+    ///
+    /// Returns how many of the sampled keys had expired.
+    fn sample_and_expire(
+        &self,
+        cycle: &Cycle,
+        keys: &Mutex<KeySet>,
+        expire: impl Fn(&Key, u128) -> bool,
+    ) -> usize {
+        let mut rng = rand::thread_rng();
+        let mut total_expired = 0;
+
+        loop {
+            // Only hold the lock while sampling, every removal takes it again
+            let sample = keys.lock().unwrap().sample(cycle.keys_per_loop, &mut rng);
+            if sample.is_empty() {
+                break;
+            }
+
+            let now = current_epoch_millis();
+            let expired = sample.iter().filter(|key| expire(key, now)).count();
+            total_expired += expired;
+
+            if expired * 100 / sample.len() <= cycle.acceptable_stale {
+                break;
+            }
+            if cycle.start.elapsed() > cycle.time_limit {
+                Stats::incr(&self.stats.expire_cycle_time_cap_reached, 1);
+                break;
+            }
+        }
+        total_expired
+    }
+}
+
+/// The bounds of one run of the active expire cycle
+struct Cycle {
+    start: Instant,
+    time_limit: Duration,
+    keys_per_loop: usize,
+    /// Share of expired keys in a sample below which sampling stops, in percent
+    acceptable_stale: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::{
+        store::{ExpireCondition, Value},
+        types::hash::Hash,
+    };
+
+    fn store_with_volatile_keys(count: usize, expires_at: u128) -> ConcurrentStore {
+        let store = Store::new();
+        for i in 0..count {
+            let key = Key::from(format!("key:{i}"));
+            store.set(key.clone(), Value::Str(Bytes::from("value")));
+            let mut entry = store.live_entry(&key).unwrap();
+            store.set_expires_at(&mut entry, Some(expires_at));
+        }
+        store
+    }
+
+    #[test]
+    fn test_active_expire_cycle_deletes_expired_keys() {
+        let store = store_with_volatile_keys(500, 1);
+        store.set(Key::from("persistent"), Value::Str(Bytes::from("value")));
+
+        assert_eq!(store.active_expire_cycle(), 500);
+        assert_eq!(store.map.len(), 1);
+        assert!(store.volatile_keys.lock().unwrap().is_empty());
+        assert_eq!(Stats::get(&store.stats.expired_keys), 500);
+        assert_eq!(Stats::get(&store.stats.expire_cycles), 1);
+    }
+
+    #[test]
+    fn test_active_expire_cycle_keeps_live_keys() {
+        let store = store_with_volatile_keys(100, current_epoch_millis() + 60_000);
+
+        assert_eq!(store.active_expire_cycle(), 0);
+        assert_eq!(store.map.len(), 100);
+        assert_eq!(store.volatile_keys.lock().unwrap().len(), 100);
+    }
+
+    #[test]
+    fn test_active_expire_cycle_deletes_expired_fields() {
+        let store = Store::new();
+        let in_a_minute = current_epoch_millis() + 60_000;
+        for i in 0..10 {
+            let key = Key::from(format!("hash:{i}"));
+            let hash: Hash = [("a", "1"), ("b", "2")].into_iter().collect();
+            store.upsert(
+                &key,
+                || Value::Hash(hash),
+                |value| {
+                    let hash = value.as_hash_mut().unwrap();
+                    hash.set_field_expires_at(b"a", Some(1));
+                    // Half the hashes lose their last field
+                    if i % 2 == 0 {
+                        hash.set_field_expires_at(b"b", Some(1));
+                    } else {
+                        hash.set_field_expires_at(b"b", Some(in_a_minute));
+                    }
+                },
+            );
+        }
+        // Sampled hashes that no longer have fields to expire are dropped
+        store.update(&Key::from("hash:1"), |value| {
+            value
+                .as_hash_mut()
+                .unwrap()
+                .set_field_expires_at(b"b", None)
+        });
+        assert_eq!(store.volatile_fields.lock().unwrap().len(), 10);
+
+        // Fields aren't keys, only the keys with a time to live count
+        assert_eq!(store.active_expire_cycle(), 0);
+        assert_eq!(store.map.len(), 5);
+        assert_eq!(Stats::get(&store.stats.expired_fields), 15);
+        assert_eq!(Stats::get(&store.stats.expired_keys), 0);
+        assert_eq!(store.volatile_fields.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_volatile_keys_follow_expire_changes() {
+        let store = Store::new();
+        let key = Key::from("key");
+        let in_a_minute = current_epoch_millis() + 60_000;
+
+        store.set(key.clone(), Value::Str(Bytes::from("value")));
+        store.expire(&key, in_a_minute, ExpireCondition::default());
+        assert!(store.volatile_keys.lock().unwrap().contains(&key));
+
+        store.persist(&key);
+        assert!(!store.volatile_keys.lock().unwrap().contains(&key));
+
+        store.expire(&key, in_a_minute, ExpireCondition::default());
+        store.set(key.clone(), Value::Str(Bytes::from("value")));
+        assert!(!store.volatile_keys.lock().unwrap().contains(&key));
+
+        store.expire(&key, in_a_minute, ExpireCondition::default());
+        store.del(&key);
+        assert!(store.volatile_keys.lock().unwrap().is_empty());
+    }
+}
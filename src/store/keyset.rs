@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use super::Key;
+
+/// A set of keys that can be sampled at random in constant time.
+///
+/// `scc::HashMap` can't pick random entries, so keys that need to be sampled
+/// (like the ones with a time to live) are tracked here as well. Keys are cheap
+/// to clone, the clones share the buffer of the key in the map.
+#[derive(Default)]
+pub struct KeySet {
+    keys: Vec<Key>,
+    positions: HashMap<Key, usize, ahash::RandomState>,
+}
+
+impl KeySet {
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn contains(&self, key: &Key) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /// Returns `false` if the key was already in the set.
+    pub fn insert(&mut self, key: Key) -> bool {
+        if self.positions.contains_key(&key) {
+            return false;
+        }
+        self.positions.insert(key.clone(), self.keys.len());
+        self.keys.push(key);
+        true
+    }
+
+    /// Returns `false` if the key wasn't in the set.
+    pub fn remove(&mut self, key: &Key) -> bool {
+        let Some(position) = self.positions.remove(key) else {
+            return false;
+        };
+        self.keys.swap_remove(position);
+        if let Some(moved) = self.keys.get(position) {
+            self.positions.insert(moved.clone(), position);
+        }
+        true
+    }
+
+    /// Picks up to `count` distinct keys at random.
+    pub fn sample(&self, count: usize, rng: &mut impl Rng) -> Vec<Key> {
+        if count >= self.keys.len() {
+            return self.keys.clone();
+        }
+        rand::seq::index::sample(rng, self.keys.len(), count)
+            .into_iter()
+            .map(|i| self.keys[i].clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut set = KeySet::default();
+        assert!(set.insert(Key::from("a")));
+        assert!(set.insert(Key::from("b")));
+        assert!(set.insert(Key::from("c")));
+        assert!(!set.insert(Key::from("a")));
+        assert_eq!(set.len(), 3);
+
+        assert!(set.remove(&Key::from("a")));
+        assert!(!set.remove(&Key::from("a")));
+        assert!(set.contains(&Key::from("b")));
+        assert!(set.contains(&Key::from("c")));
+
+        assert!(set.remove(&Key::from("c")));
+        assert!(set.remove(&Key::from("b")));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut set = KeySet::default();
+        let mut rng = rand::thread_rng();
+        assert!(set.sample(5, &mut rng).is_empty());
+
+        for key in ["a", "b", "c", "d"] {
+            set.insert(Key::from(key));
+        }
+        assert_eq!(set.sample(10, &mut rng).len(), 4);
+
+        let mut sample = set.sample(3, &mut rng);
+        sample.sort();
+        sample.dedup();
+        assert_eq!(sample.len(), 3);
+        assert!(sample.iter().all(|key| set.contains(key)));
+    }
+}
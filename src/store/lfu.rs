@@ -0,0 +1,121 @@
+use rand::Rng;
+
+/// The counter of new keys, so they aren't evicted before they get a chance to be accessed
+const LFU_INIT_VAL: u8 = 5;
+
+/// An approximate access frequency, in 3 bytes like Redis' LFU field.
+///
+/// The counter is a logarithmic Morris counter: the higher it is the less likely
+/// an access increments it, so 8 bits are enough for millions of accesses, with
+/// `lfu-log-factor` controlling how fast it saturates. It's decremented once per
+/// `lfu-decay-time` minutes without access, so keys that were popular once don't
+/// stay around forever.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LfuCounter {
+    counter: u8,
+    /// When the counter was last decremented, in minutes since UNIX epoch, wrapping around
+    decremented_at: u16,
+}
+
+impl LfuCounter {
+    pub fn new(now: u128) -> Self {
+        Self {
+            counter: LFU_INIT_VAL,
+            decremented_at: minutes(now),
+        }
+    }
+
+    /// The counter, after the decay since it was last decremented.
+    pub fn frequency(&self, now: u128, decay_time: u32) -> u8 {
+        if decay_time == 0 {
+            return self.counter;
+        }
+        let periods = minutes_elapsed(self.decremented_at, minutes(now)) / decay_time;
+        self.counter
+            .saturating_sub(periods.min(u8::MAX as u32) as u8)
+    }
+
+    /// Records an access, decaying the counter first.
+    pub fn access(&mut self, now: u128, log_factor: u32, decay_time: u32, rng: &mut impl Rng) {
+        let mut counter = self.frequency(now, decay_time);
+        if counter < u8::MAX {
+            let base = counter.saturating_sub(LFU_INIT_VAL) as f64;
+            let p = 1.0 / (base * log_factor as f64 + 1.0);
+            if rng.gen::<f64>() < p {
+                counter += 1;
+            }
+        }
+        self.counter = counter;
+        self.decremented_at = minutes(now);
+    }
+}
+
+/// Minutes since UNIX epoch, in 16 bits like Redis, wrapping around every 45 days
+fn minutes(millis: u128) -> u16 {
+    (millis / 60_000) as u16
+}
+
+fn minutes_elapsed(since: u16, now: u16) -> u32 {
+    now.wrapping_sub(since) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: u128 = 60_000;
+
+    #[test]
+    fn test_new_keys_start_with_a_few_accesses() {
+        assert_eq!(LfuCounter::new(0).frequency(0, 1), LFU_INIT_VAL);
+    }
+
+    #[test]
+    fn test_access_increments_logarithmically() {
+        let mut rng = rand::thread_rng();
+
+        let mut linear = LfuCounter::new(0);
+        for _ in 0..10 {
+            linear.access(0, 0, 1, &mut rng);
+        }
+        assert_eq!(linear.frequency(0, 1), LFU_INIT_VAL + 10);
+
+        let mut logarithmic = LfuCounter::new(0);
+        for _ in 0..1000 {
+            logarithmic.access(0, 10, 1, &mut rng);
+        }
+        let frequency = logarithmic.frequency(0, 1);
+        assert!(
+            frequency > LFU_INIT_VAL + 3 && frequency < 40,
+            "{frequency}"
+        );
+    }
+
+    #[test]
+    fn test_counter_saturates() {
+        let mut rng = rand::thread_rng();
+        let mut counter = LfuCounter::new(0);
+        for _ in 0..300 {
+            counter.access(0, 0, 1, &mut rng);
+        }
+        assert_eq!(counter.frequency(0, 1), u8::MAX);
+    }
+
+    #[test]
+    fn test_counter_decays_over_time() {
+        let counter = LfuCounter::new(0);
+        assert_eq!(counter.frequency(3 * MINUTE, 1), LFU_INIT_VAL - 3);
+        assert_eq!(counter.frequency(3 * MINUTE, 2), LFU_INIT_VAL - 1);
+        assert_eq!(counter.frequency(100 * MINUTE, 1), 0);
+        assert_eq!(counter.frequency(100 * MINUTE, 0), LFU_INIT_VAL);
+    }
+
+    #[test]
+    fn test_decay_survives_wrapping_minutes() {
+        let counter = LfuCounter::new(u16::MAX as u128 * MINUTE);
+        assert_eq!(
+            counter.frequency((u16::MAX as u128 + 2) * MINUTE, 1),
+            LFU_INIT_VAL - 2
+        );
+    }
+}
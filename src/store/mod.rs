@@ -0,0 +1,968 @@
+pub mod blocking;
+pub mod evict;
+pub mod expire_cycle;
+pub mod keyset;
+pub mod lfu;
+pub mod stats;
+pub mod value;
+
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError, RwLock, RwLockReadGuard,
+    },
+};
+
+use bytes::Bytes;
+use scc::{
+    hash_map::{Entry, OccupiedEntry, VacantEntry},
+    HashMap,
+};
+
+use crate::{config::Config, utils::current_epoch_millis};
+
+use self::{
+    blocking::BlockedClients, evict::EvictionPool, keyset::KeySet, lfu::LfuCounter, stats::Stats,
+};
+
+pub use self::value::{Value, WRONGTYPE};
+
+use ahash::AHasher;
+use std::hash::BuildHasher;
+
+// Define a BuildHasher that uses ahash
+pub struct AHashBuilder;
+
+impl BuildHasher for AHashBuilder {
+    type Hasher = AHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        AHasher::default() // Use some fixed seeds
+    }
+}
+
+pub type Key = Bytes;
+
+/// A key-value store.
+///
+/// Methods have names similar to Redis commands, if possible.
+pub struct Store {
+    /// Why chose scc::HashMap with ahash instead of DashMap
+    /// https://github.com/wvwwvwwv/conc-map-bench?tab=readme-ov-file
+    pub map: HashMap<Key, Data, AHashBuilder>,
+    pub config: Config,
+    pub stats: Stats,
+    /// The clients blocked on keys, like by `BLPOP`
+    pub blocked_clients: BlockedClients,
+    /// The keys with a time to live, sampled by the active expire cycle
+    volatile_keys: Mutex<KeySet>,
+    /// The hashes with fields to expire, sampled by the active expire cycle as
+    /// well. Only pruned as they are sampled, so some may no longer have any
+    volatile_fields: Mutex<KeySet>,
+    /// Every key, only tracked when the eviction policy samples all keys
+    keys: Mutex<KeySet>,
+    /// Estimated memory used by keys and values, in bytes
+    used_memory: AtomicUsize,
+    /// The best candidates for eviction seen so far
+    eviction_pool: Mutex<EvictionPool>,
+    /// Held shared by every access to the map, and exclusively by [`Store::atomically`]
+    keyspace_lock: RwLock<()>,
+}
+
+thread_local! {
+    /// The address of the store this thread runs [`Store::atomically`] on, if any
+    static ATOMICALLY: Cell<usize> = const { Cell::new(0) };
+}
+
+pub type ConcurrentStore = Arc<Store>;
+
+impl Store {
+    pub fn new() -> ConcurrentStore {
+        Self::with_config(Config::default())
+    }
+
+    pub fn with_config(config: Config) -> ConcurrentStore {
+        Arc::new(Store {
+            map: HashMap::with_hasher(AHashBuilder),
+            config,
+            stats: Stats::default(),
+            blocked_clients: BlockedClients::default(),
+            volatile_keys: Mutex::new(KeySet::default()),
+            volatile_fields: Mutex::new(KeySet::default()),
+            keys: Mutex::new(KeySet::default()),
+            used_memory: AtomicUsize::new(0),
+            eviction_pool: Mutex::new(EvictionPool::default()),
+            keyspace_lock: RwLock::new(()),
+        })
+    }
+
+    /// Touches a key, updating its last accessed time.
+    ///
+    /// This is useful for implementing LRU cache eviction.
+    pub fn touch(&self, key: &Key) -> usize {
+        let _shared = self.shared();
+        match self.live_entry(key) {
+            Some(mut entry) => {
+                entry.get_mut().mark_accessed(&self.config);
+                1
+            }
+            None => 0,
+        }
+    }
+
+    pub fn touch_many(&self, keys: Vec<Key>) -> usize {
+        keys.into_iter().map(|key| self.touch(&key)).sum()
+    }
+
+    /// Sets a key-value pair in the store.
+    ///
+    /// Like Redis' `SET`, this discards any time to live the key had.
+    pub fn set(&self, key: Key, value: Value) {
+        // Without GET there's no type to check, so it can't fail
+        let _ = self.set_with(key, value, SetOptions::default());
+    }
+
+    /// Sets a key-value pair in the store, like `SET` with options.
+    ///
+    /// The condition is checked and the value and time to live are set under the
+    /// same lock, so concurrent `SET NX`s on a key can't both succeed.
+    ///
+    /// Returns whether the value was set, and the previous value of the key.
+    pub fn set_with(
+        &self,
+        key: Key,
+        value: Value,
+        options: SetOptions,
+    ) -> Result<(bool, Option<Value>), String> {
+        let _shared = self.shared();
+        let now = current_epoch_millis();
+
+        match self.map.entry(key) {
+            Entry::Occupied(mut entry) => {
+                // An expired key is replaced as if it didn't exist
+                let live = !entry.get().is_expired(now);
+                if !live {
+                    Stats::incr(&self.stats.expired_keys, 1);
+                }
+                if options.get && live {
+                    entry.get().value.as_str()?;
+                }
+
+                if (options.nx && live) || (options.xx && !live) {
+                    let previous = live.then(|| entry.get().value.clone());
+                    if !live {
+                        self.remove_entry(entry);
+                    }
+                    return Ok((false, previous));
+                }
+
+                let expires_at = match options.keep_ttl && live {
+                    true => entry.get().expires_at,
+                    false => options.expires_at,
+                };
+                if expires_at.is_some_and(|expires_at| expires_at <= now) {
+                    let previous = self.remove_entry(entry).value;
+                    return Ok((true, live.then_some(previous)));
+                }
+
+                self.set_expires_at(&mut entry, expires_at);
+                let previous = self.replace_value(&mut entry, value);
+                // Replacing a key can serve clients blocked on it, like `ZUNIONSTORE` does
+                self.signal_key_ready(entry.key());
+                let data = entry.get_mut();
+                if live {
+                    data.mark_accessed(&self.config);
+                } else {
+                    // The new key doesn't inherit how the expired one was accessed
+                    data.last_accessed = now;
+                    data.lfu = LfuCounter::new(now);
+                }
+                Ok((true, live.then_some(previous)))
+            }
+            Entry::Vacant(entry) => {
+                if options.xx {
+                    return Ok((false, None));
+                }
+                // Setting a key that expires right away leaves nothing behind
+                if options
+                    .expires_at
+                    .is_some_and(|expires_at| expires_at <= now)
+                {
+                    return Ok((true, None));
+                }
+
+                let mut entry = self.insert_entry(entry, Data::new(value));
+                self.set_expires_at(&mut entry, options.expires_at);
+                self.signal_key_ready(entry.key());
+                Ok((true, None))
+            }
+        }
+    }
+
+    /// Gets the value associated with the key.
+    ///
+    /// Returns `None` if the key does not exist.
+    pub fn get(&self, key: &Key) -> Option<Value> {
+        self.read(key, Value::clone)
+    }
+
+    /// Reads the value of a key in place.
+    ///
+    /// Returns `None` if the key does not exist.
+    pub fn read<R>(&self, key: &Key, f: impl FnOnce(&Value) -> R) -> Option<R> {
+        let _shared = self.shared();
+        let mut entry = self.live_entry(key)?;
+        let data = entry.get_mut();
+        data.mark_accessed(&self.config);
+        Some(f(&data.value))
+    }
+
+    /// Changes the value of a key in place.
+    ///
+    /// Like Redis, a collection left empty deletes the key. Returns `None` if the
+    /// key does not exist.
+    pub fn update<R>(&self, key: &Key, f: impl FnOnce(&mut Value) -> R) -> Option<R> {
+        let _shared = self.shared();
+        let entry = self.live_entry(key)?;
+        Some(self.update_entry(entry, f))
+    }
+
+    /// Changes the value of a key in place, creating it with `create` if it does not exist.
+    ///
+    /// Like Redis, a collection left empty deletes the key, or isn't created at all.
+    /// Creating the key signals the clients blocked on it.
+    pub fn upsert<R>(
+        &self,
+        key: &Key,
+        create: impl FnOnce() -> Value,
+        f: impl FnOnce(&mut Value) -> R,
+    ) -> R {
+        let _shared = self.shared();
+        match self.entry(key) {
+            Entry::Occupied(entry) => self.update_entry(entry, f),
+            Entry::Vacant(entry) => {
+                let mut value = create();
+                let result = f(&mut value);
+                if !value.is_empty() {
+                    self.insert_entry(entry, Data::new(value));
+                    self.signal_key_ready(key);
+                }
+                result
+            }
+        }
+    }
+
+    /// Runs `f` while every other access to the store waits.
+    ///
+    /// Redis runs one command at a time, so commands touching many keys (like
+    /// `LMOVE`) must not interleave with others, which is what this is for. The
+    /// store can be used as usual inside `f`, but `f` must not block.
+    pub fn atomically<R>(&self, f: impl FnOnce() -> R) -> R {
+        let address = self as *const Store as usize;
+        if ATOMICALLY.get() == address {
+            return f();
+        }
+
+        /// Unmarks the thread even if `f` panics
+        struct Unmark;
+        impl Drop for Unmark {
+            fn drop(&mut self) {
+                ATOMICALLY.set(0);
+            }
+        }
+
+        let _exclusive = self
+            .keyspace_lock
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        ATOMICALLY.set(address);
+        let _unmark = Unmark;
+        f()
+    }
+
+    /// Removes the key-value pair from the store.
+    ///
+    /// Returns the value associated with the key, if it exists.
+    pub fn del(&self, key: &Key) -> Option<Value> {
+        let _shared = self.shared();
+        let entry = self.live_entry(key)?;
+        Some(self.remove_entry(entry).value)
+    }
+
+    // TODO: optimize by checking if multiple keys can be deleted at once instead of iterating
+    pub fn del_many(&self, keys: Vec<Key>) -> usize {
+        keys.into_iter().filter_map(|key| self.del(&key)).count()
+    }
+
+    /// Sets the time at which the key expires, in milliseconds since UNIX epoch.
+    ///
+    /// A time in the past deletes the key right away.
+    ///
+    /// Returns `false` if the key does not exist or the condition was not met.
+    pub fn expire(&self, key: &Key, expires_at: u128, condition: ExpireCondition) -> bool {
+        let _shared = self.shared();
+        let Some(mut entry) = self.live_entry(key) else {
+            return false;
+        };
+
+        if !condition.allows(entry.get().expires_at, expires_at) {
+            return false;
+        }
+
+        if expires_at <= current_epoch_millis() {
+            self.remove_entry(entry);
+        } else {
+            self.set_expires_at(&mut entry, Some(expires_at));
+        }
+        true
+    }
+
+    /// Removes the time to live of the key.
+    ///
+    /// Returns `false` if the key does not exist or has no time to live.
+    pub fn persist(&self, key: &Key) -> bool {
+        let _shared = self.shared();
+        match self.live_entry(key) {
+            Some(mut entry) => self.set_expires_at(&mut entry, None).is_some(),
+            None => false,
+        }
+    }
+
+    /// Gets the time at which the key expires, in milliseconds since UNIX epoch.
+    ///
+    /// Returns `None` if the key does not exist, and `Some(None)` if it has no time to live.
+    pub fn expires_at(&self, key: &Key) -> Option<Option<u128>> {
+        let _shared = self.shared();
+        self.live_entry(key).map(|entry| entry.get().expires_at)
+    }
+
+    /// Gets the access frequency of the key, as tracked for LFU eviction.
+    ///
+    /// Returns `None` if the key does not exist. Doesn't count as an access.
+    pub fn lfu_frequency(&self, key: &Key) -> Option<u8> {
+        let _shared = self.shared();
+        let entry = self.live_entry(key)?;
+        Some(
+            entry
+                .get()
+                .lfu
+                .frequency(current_epoch_millis(), self.config.lfu_decay_time),
+        )
+    }
+
+    /// Gets the name of the encoding of the value, as reported by `OBJECT ENCODING`.
+    ///
+    /// Returns `None` if the key does not exist. Doesn't count as an access.
+    pub fn encoding(&self, key: &Key) -> Option<&'static str> {
+        let _shared = self.shared();
+        let entry = self.live_entry(key)?;
+        Some(entry.get().value.encoding())
+    }
+
+    /// Estimated memory used by keys and values, in bytes.
+    pub fn used_memory(&self) -> usize {
+        self.used_memory.load(Ordering::Relaxed)
+    }
+
+    /// How many keys have a time to live.
+    pub fn volatile_len(&self) -> usize {
+        self.volatile_keys.lock().unwrap().len()
+    }
+
+    /// Holds off [`Store::atomically`] while the map is accessed.
+    ///
+    /// Every method accessing the map takes this first, and none of them call each
+    /// other while holding it, as a shared lock can't be safely taken twice.
+    fn shared(&self) -> Option<RwLockReadGuard<'_, ()>> {
+        // Inside `atomically` the store is already held exclusively by this thread
+        if ATOMICALLY.get() == self as *const Store as usize {
+            return None;
+        }
+        Some(
+            self.keyspace_lock
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    /// Gets the entry of a key, treating an expired key as missing.
+    ///
+    /// Expired keys, and the expired fields of hashes, are lazily deleted here,
+    /// when they are accessed.
+    fn entry(&self, key: &Key) -> Entry<'_, Key, Data, AHashBuilder> {
+        loop {
+            let now = current_epoch_millis();
+            match self.map.entry(key.clone()) {
+                Entry::Occupied(entry) if entry.get().is_expired(now) => {
+                    self.remove_entry(entry);
+                    Stats::incr(&self.stats.expired_keys, 1);
+                }
+                Entry::Occupied(entry) if entry.get().has_expired_fields(now) => {
+                    if let Some(entry) = self.remove_expired_fields(entry, now) {
+                        return Entry::Occupied(entry);
+                    }
+                }
+                entry => return entry,
+            }
+        }
+    }
+
+    /// Gets the entry of a key that exists and hasn't expired.
+    fn live_entry(&self, key: &Key) -> Option<OccupiedEntry<'_, Key, Data, AHashBuilder>> {
+        match self.entry(key) {
+            Entry::Occupied(entry) => Some(entry),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    /// Changes the value of an entry in place, see [`Store::update`].
+    fn update_entry<R>(
+        &self,
+        mut entry: OccupiedEntry<'_, Key, Data, AHashBuilder>,
+        f: impl FnOnce(&mut Value) -> R,
+    ) -> R {
+        let data = entry.get_mut();
+        data.mark_accessed(&self.config);
+        let before = data.value.memory_usage();
+        let result = f(&mut data.value);
+        let after = data.value.memory_usage();
+        self.used_memory.fetch_add(after, Ordering::Relaxed);
+        self.used_memory.fetch_sub(before, Ordering::Relaxed);
+
+        if data.value.is_empty() {
+            self.remove_entry(entry);
+        } else if data.value.next_field_expiry().is_some() {
+            self.volatile_fields
+                .lock()
+                .unwrap()
+                .insert(entry.key().clone());
+        }
+        result
+    }
+
+    /// Removes the expired fields of a hash, deleting the key with its last field.
+    ///
+    /// Returns the entry, unless the key was deleted.
+    fn remove_expired_fields<'a>(
+        &self,
+        mut entry: OccupiedEntry<'a, Key, Data, AHashBuilder>,
+        now: u128,
+    ) -> Option<OccupiedEntry<'a, Key, Data, AHashBuilder>> {
+        let Value::Hash(hash) = &mut entry.get_mut().value else {
+            return Some(entry);
+        };
+        let before = hash.memory_usage();
+        let removed = hash.remove_expired_fields(now);
+        let after = hash.memory_usage();
+        self.used_memory.fetch_add(after, Ordering::Relaxed);
+        self.used_memory.fetch_sub(before, Ordering::Relaxed);
+        Stats::incr(&self.stats.expired_fields, removed as u64);
+
+        let (volatile, empty) = (hash.next_field_expiry().is_some(), hash.is_empty());
+        if !volatile {
+            self.volatile_fields.lock().unwrap().remove(entry.key());
+        }
+        if empty {
+            self.remove_entry(entry);
+            return None;
+        }
+        Some(entry)
+    }
+
+    /// Removes the expired fields of the hash, returning whether it had any.
+    fn remove_expired_fields_of(&self, key: &Key, now: u128) -> bool {
+        let _shared = self.shared();
+        match self.map.entry(key.clone()) {
+            Entry::Occupied(entry) if entry.get().has_expired_fields(now) => {
+                self.remove_expired_fields(entry, now);
+                true
+            }
+            Entry::Occupied(entry) if entry.get().value.next_field_expiry().is_some() => false,
+            _ => {
+                // No longer a hash with fields to expire, it mustn't be sampled again
+                self.volatile_fields.lock().unwrap().remove(key);
+                false
+            }
+        }
+    }
+
+    /// Deletes the key if it has expired, returning whether it was deleted.
+    fn remove_if_expired(&self, key: &Key, now: u128) -> bool {
+        let _shared = self.shared();
+        match self.map.entry(key.clone()) {
+            Entry::Occupied(entry) if entry.get().is_expired(now) => {
+                self.remove_entry(entry);
+                Stats::incr(&self.stats.expired_keys, 1);
+                true
+            }
+            Entry::Occupied(_) => false,
+            Entry::Vacant(_) => {
+                // Shouldn't happen, but a key that's gone must not be sampled again
+                self.volatile_keys.lock().unwrap().remove(key);
+                false
+            }
+        }
+    }
+
+    /// Removes an entry from the map.
+    ///
+    /// Every removal must go through here to keep the volatile keys in sync, and
+    /// they are updated while the entry is still locked so concurrent writes to the
+    /// same key can't interleave.
+    fn remove_entry(&self, entry: OccupiedEntry<'_, Key, Data, AHashBuilder>) -> Data {
+        if entry.get().expires_at.is_some() {
+            self.volatile_keys.lock().unwrap().remove(entry.key());
+        }
+        if entry.get().value.next_field_expiry().is_some() {
+            self.volatile_fields.lock().unwrap().remove(entry.key());
+        }
+        if self.tracks_all_keys() {
+            self.keys.lock().unwrap().remove(entry.key());
+        }
+        let (key, data) = entry.remove_entry();
+        self.used_memory
+            .fetch_sub(entry_memory(&key, &data.value), Ordering::Relaxed);
+        data
+    }
+
+    /// Inserts a new key, the counterpart of [`Store::remove_entry`].
+    ///
+    /// The data is inserted without a time to live, set it with [`Store::set_expires_at`].
+    fn insert_entry<'a>(
+        &self,
+        entry: VacantEntry<'a, Key, Data, AHashBuilder>,
+        data: Data,
+    ) -> OccupiedEntry<'a, Key, Data, AHashBuilder> {
+        debug_assert!(data.expires_at.is_none());
+        self.used_memory
+            .fetch_add(entry_memory(entry.key(), &data.value), Ordering::Relaxed);
+        if self.tracks_all_keys() {
+            self.keys.lock().unwrap().insert(entry.key().clone());
+        }
+        if data.value.next_field_expiry().is_some() {
+            self.volatile_fields
+                .lock()
+                .unwrap()
+                .insert(entry.key().clone());
+        }
+        entry.insert_entry(data)
+    }
+
+    /// Replaces the value of an entry, returning the previous one.
+    fn replace_value(
+        &self,
+        entry: &mut OccupiedEntry<'_, Key, Data, AHashBuilder>,
+        value: Value,
+    ) -> Value {
+        // Add before subtracting so the usage never wraps around, even briefly
+        self.used_memory
+            .fetch_add(value.memory_usage(), Ordering::Relaxed);
+        let previous = std::mem::replace(&mut entry.get_mut().value, value);
+        self.used_memory
+            .fetch_sub(previous.memory_usage(), Ordering::Relaxed);
+        previous
+    }
+
+    /// Whether every key is tracked in `keys`, only needed to sample them for eviction
+    fn tracks_all_keys(&self) -> bool {
+        self.config.maxmemory_policy.is_allkeys()
+    }
+
+    /// Sets the expiry of an entry, returning the previous one.
+    ///
+    /// Like [`Store::remove_entry`], every expiry change must go through here.
+    fn set_expires_at(
+        &self,
+        entry: &mut OccupiedEntry<'_, Key, Data, AHashBuilder>,
+        expires_at: Option<u128>,
+    ) -> Option<u128> {
+        let previous = std::mem::replace(&mut entry.get_mut().expires_at, expires_at);
+        match (previous, expires_at) {
+            (None, Some(_)) => {
+                self.volatile_keys
+                    .lock()
+                    .unwrap()
+                    .insert(entry.key().clone());
+            }
+            (Some(_), None) => {
+                self.volatile_keys.lock().unwrap().remove(entry.key());
+            }
+            _ => (),
+        }
+        previous
+    }
+}
+
+/// The conditions of the `EXPIRE` family of commands, any combination of them must hold.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct ExpireCondition {
+    /// Set expiry only when the key has no expiry
+    pub nx: bool,
+    /// Set expiry only when the key has an existing expiry
+    pub xx: bool,
+    /// Set expiry only when the new expiry is greater than the current one
+    pub gt: bool,
+    /// Set expiry only when the new expiry is less than the current one
+    pub lt: bool,
+}
+
+impl ExpireCondition {
+    /// Whether the condition allows replacing the `current` expiry with `expires_at`
+    pub fn allows(&self, current: Option<u128>, expires_at: u128) -> bool {
+        match current {
+            // Without a time to live, it's treated as an infinite one
+            None => !self.xx && !self.gt,
+            Some(current) => {
+                !self.nx && (!self.gt || expires_at > current) && (!self.lt || expires_at < current)
+            }
+        }
+    }
+}
+
+/// The options of `SET`.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct SetOptions {
+    /// Only set the key if it does not already exist
+    pub nx: bool,
+    /// Only set the key if it already exists
+    pub xx: bool,
+    /// When the key expires, in milliseconds since UNIX epoch, or `None` to persist it
+    pub expires_at: Option<u128>,
+    /// Retain the time to live of the key, instead of using `expires_at`
+    pub keep_ttl: bool,
+    /// The previous value is wanted, so it must be a string
+    pub get: bool,
+}
+
+/// The bookkeeping of the map and of [`Data`], on top of the key and value themselves
+const ENTRY_OVERHEAD: usize = std::mem::size_of::<(Key, Data)>();
+
+/// Estimated memory used by an entry of the map, in bytes
+fn entry_memory(key: &Key, value: &Value) -> usize {
+    ENTRY_OVERHEAD + key.len() + value.memory_usage()
+}
+
+#[derive(Clone)]
+pub struct Data {
+    pub value: Value,
+    pub last_accessed: u128, // Milliseconds since UNIX epoch
+    /// Only kept up to date under an LFU eviction policy
+    pub lfu: LfuCounter,
+    pub expires_at: Option<u128>, // Milliseconds since UNIX epoch
+}
+
+impl Data {
+    pub fn new(value: Value) -> Self {
+        let now = current_epoch_millis();
+        Self {
+            value,
+            last_accessed: now,
+            lfu: LfuCounter::new(now),
+            expires_at: None,
+        }
+    }
+
+    pub fn is_expired(&self, now: u128) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Whether the value is a hash with expired fields
+    pub fn has_expired_fields(&self, now: u128) -> bool {
+        self.value
+            .next_field_expiry()
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
+    fn mark_accessed(&mut self, config: &Config) {
+        self.last_accessed = current_epoch_millis();
+        if config.maxmemory_policy.is_lfu() {
+            self.lfu.access(
+                self.last_accessed,
+                config.lfu_log_factor,
+                config.lfu_decay_time,
+                &mut rand::thread_rng(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{hash::Hash, list::List};
+
+    use super::*;
+
+    #[test]
+    fn test_set_and_get_round_trip_binary_data() {
+        let store = Store::new();
+        let key = Key::from(&b"\x00key\xff"[..]);
+        let value = Bytes::from(&b"\x00\x01\xfe\xff\r\ninvalid \xc3\x28 utf-8"[..]);
+
+        store.set(key.clone(), Value::Str(value.clone()));
+
+        assert_eq!(store.get(&key), Some(Value::Str(value)));
+        assert_eq!(store.get(&Key::from(&b"\x00key"[..])), None);
+    }
+
+    #[test]
+    fn test_del_many() {
+        let store = Store::new();
+        store.set(Key::from("a"), Value::Str(Bytes::from("1")));
+        store.set(Key::from("b"), Value::Str(Bytes::from("2")));
+
+        let deleted = store.del_many(vec![Key::from("a"), Key::from("b"), Key::from("c")]);
+
+        assert_eq!(deleted, 2);
+        assert_eq!(store.get(&Key::from("a")), None);
+    }
+
+    #[test]
+    fn test_set_with_conditions() {
+        let store = Store::new();
+        let key = Key::from("key");
+        let value = |v: &'static str| Value::Str(Bytes::from(v));
+        let nx = SetOptions {
+            nx: true,
+            ..Default::default()
+        };
+        let xx = SetOptions {
+            xx: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            store.set_with(key.clone(), value("1"), xx),
+            Ok((false, None))
+        );
+        assert_eq!(store.get(&key), None);
+        assert_eq!(
+            store.set_with(key.clone(), value("1"), nx),
+            Ok((true, None))
+        );
+        assert_eq!(
+            store.set_with(key.clone(), value("2"), nx),
+            Ok((false, Some(value("1"))))
+        );
+        assert_eq!(
+            store.set_with(key.clone(), value("3"), xx),
+            Ok((true, Some(value("1"))))
+        );
+        assert_eq!(store.get(&key), Some(value("3")));
+    }
+
+    #[test]
+    fn test_set_with_expiry() {
+        let store = Store::new();
+        let key = Key::from("key");
+        let value = Value::Str(Bytes::from("value"));
+        let later = current_epoch_millis() + 100_000;
+        let expiring = SetOptions {
+            expires_at: Some(later),
+            ..Default::default()
+        };
+        let keep_ttl = SetOptions {
+            keep_ttl: true,
+            ..Default::default()
+        };
+
+        store
+            .set_with(key.clone(), value.clone(), expiring)
+            .unwrap();
+        assert_eq!(store.expires_at(&key), Some(Some(later)));
+        store
+            .set_with(key.clone(), value.clone(), keep_ttl)
+            .unwrap();
+        assert_eq!(store.expires_at(&key), Some(Some(later)));
+        store.set(key.clone(), value.clone());
+        assert_eq!(store.expires_at(&key), Some(None));
+
+        let expired = SetOptions {
+            expires_at: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            store.set_with(key.clone(), value.clone(), expired),
+            Ok((true, Some(value)))
+        );
+        assert!(!store.map.contains(&key));
+        assert!(store.volatile_keys.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_with_get_checks_the_type_first() {
+        let store = Store::new();
+        let key = Key::from("key");
+        store.upsert(
+            &key,
+            || Value::List(List::new()),
+            |value| value.as_list_mut().unwrap().push_back(b"element"),
+        );
+        let get = SetOptions {
+            get: true,
+            ..Default::default()
+        };
+
+        let result = store.set_with(key.clone(), Value::Str(Bytes::from("value")), get);
+        assert_eq!(result, Err(WRONGTYPE.to_string()));
+        assert!(store.read(&key, |value| value.as_list().is_ok()).unwrap());
+    }
+
+    #[test]
+    fn test_upsert_and_update_delete_empty_collections() {
+        let store = Store::new();
+        let key = Key::from("list");
+        let new_list = || Value::List(List::new());
+
+        // Nothing pushed, nothing created
+        store.upsert(&key, new_list, |_| ());
+        assert!(!store.map.contains(&key));
+
+        store.upsert(&key, new_list, |value| {
+            value.as_list_mut().unwrap().push_back(b"element")
+        });
+        let used_memory = store.used_memory();
+        assert!(used_memory > 0);
+
+        let popped = store.update(&key, |value| value.as_list_mut().unwrap().pop_back());
+        assert_eq!(popped, Some(Some(Bytes::from("element"))));
+        assert!(!store.map.contains(&key));
+        assert_eq!(store.used_memory(), 0);
+        assert_eq!(store.update(&key, |_| ()), None);
+    }
+
+    #[test]
+    fn test_atomically_is_reentrant_and_excludes_others() {
+        let store = Store::new();
+        let key = Key::from("counter");
+        store.set(key.clone(), Value::Str(Bytes::from("0")));
+
+        let writer = {
+            let store = store.clone();
+            let key = key.clone();
+            std::thread::spawn(move || {
+                store.atomically(|| {
+                    store.set(key.clone(), Value::Str(Bytes::from("1")));
+                    std::thread::sleep(std::time::Duration::from_millis(50));
+                    store.atomically(|| store.set(key, Value::Str(Bytes::from("2"))));
+                })
+            })
+        };
+        std::thread::sleep(std::time::Duration::from_millis(10));
+
+        // Either before the transaction or after it, never in between
+        let value = store.get(&key).unwrap();
+        writer.join().unwrap();
+        assert!(value == Value::Str(Bytes::from("0")) || value == Value::Str(Bytes::from("2")));
+        assert_eq!(store.get(&key), Some(Value::Str(Bytes::from("2"))));
+    }
+
+    #[test]
+    fn test_expired_keys_are_deleted_lazily() {
+        let store = Store::new();
+        let key = Key::from("session");
+        store.set(key.clone(), Value::Str(Bytes::from("data")));
+        let mut entry = store.live_entry(&key).unwrap();
+        store.set_expires_at(&mut entry, Some(1));
+        drop(entry);
+
+        assert_eq!(store.touch(&key), 0);
+        assert!(!store.map.contains(&key));
+        assert!(store.volatile_keys.lock().unwrap().is_empty());
+        assert_eq!(Stats::get(&store.stats.expired_keys), 1);
+    }
+
+    #[test]
+    fn test_expired_fields_are_deleted_lazily() {
+        let store = Store::new();
+        let key = Key::from("hash");
+        let hash: Hash = [("a", "1"), ("b", "2")].into_iter().collect();
+        store.set(key.clone(), Value::Hash(hash));
+        store.update(&key, |value| {
+            value
+                .as_hash_mut()
+                .unwrap()
+                .set_field_expires_at(b"a", Some(1))
+        });
+
+        let fields = store.read(&key, |value| value.as_hash().unwrap().len());
+        assert_eq!(fields, Some(1));
+        assert_eq!(Stats::get(&store.stats.expired_fields), 1);
+
+        // The last field to expire takes the key with it
+        store.update(&key, |value| {
+            value
+                .as_hash_mut()
+                .unwrap()
+                .set_field_expires_at(b"b", Some(1))
+        });
+        assert_eq!(store.get(&key), None);
+        assert!(!store.map.contains(&key));
+        assert_eq!(store.used_memory(), 0);
+        assert!(store.volatile_fields.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_expire_in_the_past_deletes_the_key() {
+        let store = Store::new();
+        let key = Key::from("session");
+        store.set(key.clone(), Value::Str(Bytes::from("data")));
+
+        assert!(store.expire(&key, 1, ExpireCondition::default()));
+        assert_eq!(store.get(&key), None);
+        assert!(!store.expire(&key, 1, ExpireCondition::default()));
+    }
+
+    #[test]
+    fn test_expire_conditions() {
+        let store = Store::new();
+        let key = Key::from("session");
+        let later = current_epoch_millis() + 100_000;
+        store.set(key.clone(), Value::Str(Bytes::from("data")));
+
+        let xx = ExpireCondition {
+            xx: true,
+            ..Default::default()
+        };
+        let nx = ExpireCondition {
+            nx: true,
+            ..Default::default()
+        };
+        let gt = ExpireCondition {
+            gt: true,
+            ..Default::default()
+        };
+        let lt = ExpireCondition {
+            lt: true,
+            ..Default::default()
+        };
+
+        assert!(!store.expire(&key, later, xx));
+        assert!(!store.expire(&key, later, gt));
+        assert!(store.expire(&key, later, nx));
+        assert!(!store.expire(&key, later, nx));
+        assert!(!store.expire(&key, later - 1, gt));
+        assert!(store.expire(&key, later + 1, gt));
+        assert!(!store.expire(&key, later + 2, lt));
+        assert!(store.expire(&key, later, lt));
+        assert_eq!(store.expires_at(&key), Some(Some(later)));
+    }
+
+    #[test]
+    fn test_persist_and_set_discard_the_time_to_live() {
+        let store = Store::new();
+        let key = Key::from("session");
+        let later = current_epoch_millis() + 100_000;
+        store.set(key.clone(), Value::Str(Bytes::from("data")));
+
+        assert!(!store.persist(&key));
+        store.expire(&key, later, ExpireCondition::default());
+        assert!(store.persist(&key));
+        assert_eq!(store.expires_at(&key), Some(None));
+
+        store.expire(&key, later, ExpireCondition::default());
+        store.set(key.clone(), Value::Str(Bytes::from("new data")));
+        assert_eq!(store.expires_at(&key), Some(None));
+        assert_eq!(store.expires_at(&Key::from("missing")), None);
+    }
+}
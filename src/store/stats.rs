@@ -0,0 +1,30 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Server wide counters, reported by `INFO stats`.
+#[derive(Default)]
+pub struct Stats {
+    /// Keys deleted because they expired, either lazily or by the active expire cycle
+    pub expired_keys: AtomicU64,
+    /// Hash fields deleted because they expired, either lazily or by the active expire cycle
+    pub expired_fields: AtomicU64,
+    /// Times the active expire cycle ran
+    pub expire_cycles: AtomicU64,
+    /// Times the active expire cycle stopped early because it ran out of time
+    pub expire_cycle_time_cap_reached: AtomicU64,
+    /// Time spent in the active expire cycle, in microseconds
+    pub expire_cycle_time_used_us: AtomicU64,
+    /// Duration of the last active expire cycle, in microseconds
+    pub expire_cycle_last_time_us: AtomicU64,
+    /// Keys deleted to fit in `maxmemory`
+    pub evicted_keys: AtomicU64,
+}
+
+impl Stats {
+    pub fn incr(counter: &AtomicU64, by: u64) {
+        counter.fetch_add(by, Ordering::Relaxed);
+    }
+
+    pub fn get(counter: &AtomicU64) -> u64 {
+        counter.load(Ordering::Relaxed)
+    }
+}
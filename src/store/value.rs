@@ -0,0 +1,257 @@
+use bytes::{Bytes, BytesMut};
+
+use crate::{
+    parser::utils::ParseError,
+    resp::Frame,
+    types::{
+        hash::Hash,
+        list::List,
+        set::Set,
+        stream::{Stream, StreamEntry},
+        zset::ZSet,
+    },
+};
+
+/// The longest string Redis embeds with its object, reported as `embstr`
+const EMBSTR_MAX_LEN: usize = 44;
+
+/// The longest an integer gets once formatted, `-9223372036854775808`
+const MAX_INT_LEN: usize = 20;
+
+/// The error of commands run against a key of another type
+pub const WRONGTYPE: &str = "WRONGTYPE Operation against a key holding the wrong kind of value";
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Str(Bytes),
+    /// A string holding an integer, stored as one, like Redis' `int` encoding
+    Int(i64),
+    List(List),
+    Hash(Hash),
+    Set(Set),
+    ZSet(ZSet),
+    Stream(Stream),
+}
+
+impl Value {
+    /// Estimated memory used by the value, in bytes
+    pub fn memory_usage(&self) -> usize {
+        match self {
+            Value::Str(bytes) => bytes.len(),
+            Value::Int(_) => std::mem::size_of::<i64>(),
+            Value::List(list) => list.memory_usage(),
+            Value::Hash(hash) => hash.memory_usage(),
+            Value::Set(set) => set.memory_usage(),
+            Value::ZSet(zset) => zset.memory_usage(),
+            Value::Stream(stream) => stream.memory_usage(),
+        }
+    }
+
+    /// Whether the value is an empty collection, which Redis never keeps around.
+    ///
+    /// Empty strings are values like any other, and so are empty streams, which
+    /// remember the last ID they gave out.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Value::Str(_) | Value::Int(_) | Value::Stream(_) => false,
+            Value::List(list) => list.is_empty(),
+            Value::Hash(hash) => hash.is_empty(),
+            Value::Set(set) => set.is_empty(),
+            Value::ZSet(zset) => zset.is_empty(),
+        }
+    }
+
+    /// When the next field of a hash expires, if it has fields with a time to live
+    pub fn next_field_expiry(&self) -> Option<u128> {
+        match self {
+            Value::Hash(hash) => hash.next_field_expiry(),
+            _ => None,
+        }
+    }
+
+    /// A string value, encoded as an integer if it reads back the same as one.
+    ///
+    /// Like Redis, strings with a sign, spaces or leading zeros that would be
+    /// lost are kept as they are.
+    pub fn string(bytes: Bytes) -> Self {
+        match canonical_int(&bytes) {
+            Some(int) => Value::Int(int),
+            None => Value::Str(bytes),
+        }
+    }
+
+    /// The name of the encoding, as reported by `OBJECT ENCODING`
+    pub fn encoding(&self) -> &'static str {
+        match self {
+            Value::Str(bytes) if bytes.len() <= EMBSTR_MAX_LEN => "embstr",
+            Value::Str(_) => "raw",
+            Value::Int(_) => "int",
+            Value::List(list) => list.encoding(),
+            Value::Hash(hash) => hash.encoding(),
+            Value::Set(set) => set.encoding(),
+            Value::ZSet(zset) => zset.encoding(),
+            Value::Stream(_) => "stream",
+        }
+    }
+
+    /// The bytes of a string, integers being formatted back
+    pub fn as_str(&self) -> Result<Bytes, String> {
+        match self {
+            Value::Str(bytes) => Ok(bytes.clone()),
+            Value::Int(int) => Ok(Bytes::from(int.to_string())),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    /// Changes a string in place, integers being formatted first.
+    ///
+    /// The buffer is reused when nothing else holds on to it, so growing a string
+    /// bit by bit doesn't copy it every time.
+    pub fn str_mut<R>(&mut self, f: impl FnOnce(&mut BytesMut) -> R) -> Result<R, String> {
+        let bytes = match self {
+            Value::Str(bytes) => std::mem::take(bytes),
+            Value::Int(int) => Bytes::from(int.to_string()),
+            _ => return Err(WRONGTYPE.to_string()),
+        };
+        let mut buf = BytesMut::from(bytes);
+        let result = f(&mut buf);
+        *self = Value::Str(buf.freeze());
+        Ok(result)
+    }
+
+    /// The integer held by a string, the way `INCR` and friends read it
+    pub fn as_int(&self) -> Result<i64, String> {
+        match self {
+            Value::Int(int) => Ok(*int),
+            Value::Str(bytes) => canonical_int(bytes).ok_or(ParseError::NotAnInteger.to_string()),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_list(&self) -> Result<&List, String> {
+        match self {
+            Value::List(list) => Ok(list),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_list_mut(&mut self) -> Result<&mut List, String> {
+        match self {
+            Value::List(list) => Ok(list),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_hash(&self) -> Result<&Hash, String> {
+        match self {
+            Value::Hash(hash) => Ok(hash),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_hash_mut(&mut self) -> Result<&mut Hash, String> {
+        match self {
+            Value::Hash(hash) => Ok(hash),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_set(&self) -> Result<&Set, String> {
+        match self {
+            Value::Set(set) => Ok(set),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_set_mut(&mut self) -> Result<&mut Set, String> {
+        match self {
+            Value::Set(set) => Ok(set),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_zset(&self) -> Result<&ZSet, String> {
+        match self {
+            Value::ZSet(zset) => Ok(zset),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_zset_mut(&mut self) -> Result<&mut ZSet, String> {
+        match self {
+            Value::ZSet(zset) => Ok(zset),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_stream(&self) -> Result<&Stream, String> {
+        match self {
+            Value::Stream(stream) => Ok(stream),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+
+    pub fn as_stream_mut(&mut self) -> Result<&mut Stream, String> {
+        match self {
+            Value::Stream(stream) => Ok(stream),
+            _ => Err(WRONGTYPE.to_string()),
+        }
+    }
+}
+
+impl From<Value> for Frame {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Str(s) => Frame::bulk(s),
+            Value::Int(int) => Frame::bulk(int.to_string()),
+            Value::List(list) => Frame::Array(
+                list.iter()
+                    .map(|value| Frame::bulk(Bytes::copy_from_slice(value)))
+                    .collect(),
+            ),
+            Value::Hash(hash) => Frame::Map(
+                hash.iter()
+                    .map(|(field, value)| {
+                        (
+                            Frame::bulk(Bytes::copy_from_slice(field)),
+                            Frame::bulk(Bytes::copy_from_slice(value)),
+                        )
+                    })
+                    .collect(),
+            ),
+            Value::Set(set) => Frame::Set(set.iter().map(Frame::bulk).collect()),
+            Value::ZSet(zset) => Frame::Array(
+                zset.iter()
+                    .flat_map(|(member, score)| [Frame::bulk(member.clone()), Frame::Double(score)])
+                    .collect(),
+            ),
+            Value::Stream(stream) => Frame::Array(stream.iter().map(Frame::from).collect()),
+        }
+    }
+}
+
+/// The integer a string reads as, if formatting it back gives the same string,
+/// like Redis' `string2ll`
+fn canonical_int(bytes: &[u8]) -> Option<i64> {
+    if bytes.len() > MAX_INT_LEN {
+        return None;
+    }
+    let int: i64 = std::str::from_utf8(bytes).ok()?.parse().ok()?;
+    (int.to_string().as_bytes() == bytes).then_some(int)
+}
+
+/// `[id, [field, value, ...]]`, like every command replying with stream entries
+impl From<StreamEntry> for Frame {
+    fn from(entry: StreamEntry) -> Self {
+        Frame::Array(vec![
+            Frame::bulk(entry.id.to_string()),
+            Frame::Array(
+                entry
+                    .fields
+                    .into_iter()
+                    .flat_map(|(field, value)| [Frame::bulk(field), Frame::bulk(value)])
+                    .collect(),
+            ),
+        ])
+    }
+}
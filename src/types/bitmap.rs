@@ -0,0 +1,238 @@
+//! Bit level access to strings, which Redis treats as bitmaps.
+//!
+//! Bits are numbered from the most significant bit of the first byte, and the
+//! bits past the end of a string read as zeros.
+
+use bytes::BytesMut;
+
+/// The bit at `offset`
+pub fn get_bit(bytes: &[u8], offset: usize) -> bool {
+    bytes
+        .get(offset / 8)
+        .is_some_and(|byte| byte & (0x80 >> (offset % 8)) != 0)
+}
+
+/// Sets the bit at `offset`, growing the string with zeros to reach it.
+///
+/// Returns the previous bit.
+pub fn set_bit(buf: &mut BytesMut, offset: usize, bit: bool) -> bool {
+    let index = offset / 8;
+    if buf.len() <= index {
+        buf.resize(index + 1, 0);
+    }
+    let mask = 0x80 >> (offset % 8);
+    let previous = buf[index] & mask != 0;
+    if bit {
+        buf[index] |= mask;
+    } else {
+        buf[index] &= !mask;
+    }
+    previous
+}
+
+/// Resolves the range of `BITCOUNT` and `BITPOS` to the bits it covers, inclusive.
+///
+/// Like Redis, negative indexes count from the end, and the range is clamped to
+/// the string. `end` defaults to the end of the string, and both are counted in
+/// bits rather than bytes if `bit`. Returns `None` if the range is empty.
+pub fn resolve_bit_range(
+    start: i64,
+    end: Option<i64>,
+    len: usize,
+    bit: bool,
+) -> Option<(usize, usize)> {
+    let total = if bit { len * 8 } else { len } as i64;
+    let end = end.unwrap_or(total - 1);
+    if start < 0 && end < 0 && start > end {
+        return None;
+    }
+    let start = if start < 0 { total + start } else { start }.max(0);
+    let end = if end < 0 { total + end } else { end }
+        .max(0)
+        .min(total - 1);
+    if start > end {
+        return None;
+    }
+
+    let (start, end) = (start as usize, end as usize);
+    Some(match bit {
+        true => (start, end),
+        false => (start * 8, end * 8 + 7),
+    })
+}
+
+/// How many bits are set from `start` to `end`, inclusive
+pub fn count(bytes: &[u8], start: usize, end: usize) -> usize {
+    let (first, last) = (start / 8, end / 8);
+    if first == last {
+        return (start..=end).filter(|&bit| get_bit(bytes, bit)).count();
+    }
+
+    // Only the bytes at either end may be partly in the range
+    let head = (start..(first + 1) * 8)
+        .filter(|&bit| get_bit(bytes, bit))
+        .count();
+    let tail = (last * 8..=end).filter(|&bit| get_bit(bytes, bit)).count();
+    let middle: u32 = bytes[first + 1..last]
+        .iter()
+        .map(|byte| byte.count_ones())
+        .sum();
+    head + middle as usize + tail
+}
+
+/// The position of the first bit set to `bit` from `start` to `end`, inclusive
+pub fn position(bytes: &[u8], bit: bool, start: usize, end: usize) -> Option<usize> {
+    // Whole bytes without the bit are skipped at once
+    let skip = if bit { 0x00 } else { 0xff };
+    let mut offset = start;
+    while offset <= end {
+        if offset.is_multiple_of(8) && offset + 7 <= end && bytes.get(offset / 8) == Some(&skip) {
+            offset += 8;
+            continue;
+        }
+        if get_bit(bytes, offset) == bit {
+            return Some(offset);
+        }
+        offset += 1;
+    }
+    None
+}
+
+/// What `BITFIELD` does with values that don't fit in their field
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Overflow {
+    /// Wrap around, like integer arithmetic does
+    #[default]
+    Wrap,
+    /// Saturate to the minimum or maximum value
+    Sat,
+    /// Leave the field as it is
+    Fail,
+}
+
+/// The type of a `BITFIELD` field, a signed or unsigned integer of up to 64 bits
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FieldType {
+    pub signed: bool,
+    /// From 1 to 64 if signed, or 63 if unsigned
+    pub bits: u32,
+}
+
+impl FieldType {
+    /// Parses a type like `i16` or `u8`
+    pub fn parse(part: &[u8]) -> Result<Self, String> {
+        let invalid = || {
+            "Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is."
+                .to_string()
+        };
+        let (signed, max) = match part.first() {
+            Some(b'i' | b'I') => (true, 64),
+            Some(b'u' | b'U') => (false, 63),
+            _ => return Err(invalid()),
+        };
+        let bits = std::str::from_utf8(&part[1..])
+            .ok()
+            .and_then(|bits| bits.parse().ok())
+            .filter(|bits| (1..=max).contains(bits))
+            .ok_or_else(invalid)?;
+        Ok(Self { signed, bits })
+    }
+
+    fn min(&self) -> i128 {
+        match self.signed {
+            true => -(1 << (self.bits - 1)),
+            false => 0,
+        }
+    }
+
+    fn max(&self) -> i128 {
+        match self.signed {
+            true => (1 << (self.bits - 1)) - 1,
+            false => (1 << self.bits) - 1,
+        }
+    }
+
+    /// Reads the field at `offset`, in bits
+    pub fn get(&self, bytes: &[u8], offset: usize) -> i64 {
+        let mut value: u64 = 0;
+        for bit in offset..offset + self.bits as usize {
+            value = (value << 1) | get_bit(bytes, bit) as u64;
+        }
+        if self.signed && self.bits < 64 && value >> (self.bits - 1) == 1 {
+            // Sign extended
+            value |= u64::MAX << self.bits;
+        }
+        value as i64
+    }
+
+    /// Writes the field at `offset`, in bits, growing the string to fit it
+    pub fn set(&self, buf: &mut BytesMut, offset: usize, value: i64) {
+        for i in 0..self.bits {
+            let bit = (value as u64 >> (self.bits - 1 - i)) & 1 == 1;
+            set_bit(buf, offset + i as usize, bit);
+        }
+    }
+
+    /// Fits a value in the field, or `None` if it doesn't fit and overflowing fails
+    pub fn fit(&self, value: i128, overflow: Overflow) -> Option<i64> {
+        let (min, max) = (self.min(), self.max());
+        if (min..=max).contains(&value) {
+            return Some(value as i64);
+        }
+        match overflow {
+            Overflow::Wrap => Some(((value - min).rem_euclid(1 << self.bits) + min) as i64),
+            Overflow::Sat => Some(value.clamp(min, max) as i64),
+            Overflow::Fail => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits() {
+        let mut buf = BytesMut::new();
+        assert!(!set_bit(&mut buf, 7, true));
+        assert!(set_bit(&mut buf, 7, true));
+        set_bit(&mut buf, 17, true);
+        assert_eq!(&buf[..], [0x01, 0x00, 0x40]);
+        assert!(get_bit(&buf, 17) && !get_bit(&buf, 18) && !get_bit(&buf, 100));
+
+        assert_eq!(count(&buf, 0, 23), 2);
+        assert_eq!(count(&buf, 8, 17), 1);
+        assert_eq!(position(&buf, true, 8, 23), Some(17));
+        assert_eq!(position(&buf, false, 7, 7), None);
+
+        assert_eq!(resolve_bit_range(0, None, 3, false), Some((0, 23)));
+        assert_eq!(resolve_bit_range(-2, Some(-1), 3, false), Some((8, 23)));
+        assert_eq!(resolve_bit_range(5, Some(100), 3, true), Some((5, 23)));
+        assert_eq!(resolve_bit_range(2, Some(1), 3, false), None);
+        assert_eq!(resolve_bit_range(-100, Some(-200), 3, false), None);
+        assert_eq!(resolve_bit_range(0, None, 0, false), None);
+    }
+
+    #[test]
+    fn test_fields() {
+        let i8 = FieldType::parse(b"i8").unwrap();
+        let u4 = FieldType::parse(b"u4").unwrap();
+        assert!(FieldType::parse(b"u64").is_err());
+        assert!(FieldType::parse(b"i0").is_err());
+        assert!(FieldType::parse(b"x8").is_err());
+
+        let mut buf = BytesMut::new();
+        i8.set(&mut buf, 4, -2);
+        assert_eq!(&buf[..], [0x0f, 0xe0]);
+        assert_eq!(i8.get(&buf, 4), -2);
+        assert_eq!(u4.get(&buf, 4), 15);
+        assert_eq!(FieldType::parse(b"i64").unwrap().get(&buf, 100), 0);
+
+        assert_eq!(i8.fit(128, Overflow::Wrap), Some(-128));
+        assert_eq!(i8.fit(-129, Overflow::Wrap), Some(127));
+        assert_eq!(i8.fit(300, Overflow::Sat), Some(127));
+        assert_eq!(u4.fit(-1, Overflow::Sat), Some(0));
+        assert_eq!(u4.fit(17, Overflow::Wrap), Some(1));
+        assert_eq!(u4.fit(16, Overflow::Fail), None);
+    }
+}
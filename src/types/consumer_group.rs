@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use bytes::Bytes;
+
+use super::stream::StreamId;
+
+/// Memory used by a pending entry, counted twice as both the group and its consumer index it
+const PENDING_ENTRY_OVERHEAD: usize =
+    std::mem::size_of::<(StreamId, PendingEntry)>() + std::mem::size_of::<StreamId>() + 16;
+
+/// Memory used by a consumer besides its name and pending entries, in bytes
+const CONSUMER_OVERHEAD: usize = std::mem::size_of::<(Bytes, Consumer)>() + 16;
+
+/// An entry delivered to a consumer of a group, but not acknowledged yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingEntry {
+    pub consumer: Bytes,
+    /// When the entry was last delivered, in milliseconds since UNIX epoch
+    pub delivered_at: u128,
+    /// How many times the entry was delivered
+    pub deliveries: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Consumer {
+    /// When the consumer last read or claimed entries, in milliseconds since UNIX epoch
+    pub seen_at: u128,
+    /// When the consumer was last delivered entries, if ever
+    pub active_at: Option<u128>,
+    /// The IDs of the entries pending for the consumer
+    pending: BTreeSet<StreamId>,
+}
+
+impl Consumer {
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+/// A group of consumers sharing the entries of a stream, like Redis' consumer groups.
+///
+/// Entries are delivered to one consumer of the group, which owns them in the
+/// pending entries list until it acknowledges them, or another consumer claims them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsumerGroup {
+    /// The ID of the last entry delivered to the group, the next ones being after it
+    pub last_id: StreamId,
+    /// How many entries the group read, `None` if it can't be told, like when the
+    /// group was moved to an arbitrary ID
+    pub entries_read: Option<u64>,
+    /// The pending entries list, of every consumer
+    pending: BTreeMap<StreamId, PendingEntry>,
+    consumers: BTreeMap<Bytes, Consumer>,
+    /// The size of the consumer names, including in pending entries
+    bytes: usize,
+}
+
+impl ConsumerGroup {
+    pub fn new(last_id: StreamId, entries_read: Option<u64>) -> Self {
+        Self {
+            last_id,
+            entries_read,
+            pending: BTreeMap::new(),
+            consumers: BTreeMap::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Estimated memory used by the group, in bytes
+    pub fn memory_usage(&self) -> usize {
+        self.bytes
+            + self.pending.len() * PENDING_ENTRY_OVERHEAD
+            + self.consumers.len() * CONSUMER_OVERHEAD
+    }
+
+    pub fn pending(&self) -> &BTreeMap<StreamId, PendingEntry> {
+        &self.pending
+    }
+
+    pub fn consumers(&self) -> &BTreeMap<Bytes, Consumer> {
+        &self.consumers
+    }
+
+    pub fn consumer(&self, name: &[u8]) -> Option<&Consumer> {
+        self.consumers.get(name)
+    }
+
+    /// The IDs pending for a consumer, from `start`
+    pub fn consumer_pending(
+        &self,
+        name: &[u8],
+        start: StreamId,
+    ) -> impl Iterator<Item = StreamId> + '_ {
+        self.consumers
+            .get(name)
+            .into_iter()
+            .flat_map(move |consumer| consumer.pending.range(start..).copied())
+    }
+
+    /// Creates a consumer, returning `false` if it already exists.
+    pub fn create_consumer(&mut self, name: &Bytes, now: u128) -> bool {
+        if self.consumers.contains_key(name) {
+            return false;
+        }
+        self.bytes += name.len();
+        let consumer = Consumer {
+            seen_at: now,
+            ..Default::default()
+        };
+        self.consumers.insert(name.clone(), consumer);
+        true
+    }
+
+    /// Marks a consumer as seen, creating it if it doesn't exist yet.
+    pub fn touch_consumer(&mut self, name: &Bytes, now: u128) -> &mut Consumer {
+        self.create_consumer(name, now);
+        let consumer = self.consumers.get_mut(name).expect("created above");
+        consumer.seen_at = now;
+        consumer
+    }
+
+    /// Deletes a consumer along with the entries pending for it.
+    ///
+    /// Returns how many entries were pending, or `None` if there's no such consumer.
+    pub fn delete_consumer(&mut self, name: &[u8]) -> Option<usize> {
+        let consumer = self.consumers.remove(name)?;
+        self.bytes -= name.len();
+        for id in &consumer.pending {
+            self.pending.remove(id);
+            self.bytes -= name.len();
+        }
+        Some(consumer.pending.len())
+    }
+
+    /// Delivers an entry to a consumer, which takes it over if it was pending for
+    /// another one.
+    ///
+    /// A new delivery resets the delivery count, unlike delivering a pending entry
+    /// again with [`ConsumerGroup::redeliver`].
+    pub fn deliver(&mut self, id: StreamId, consumer: &Bytes, now: u128) {
+        self.assign(id, consumer, now);
+        let entry = self.pending.get_mut(&id).expect("assigned above");
+        entry.delivered_at = now;
+        entry.deliveries = 1;
+    }
+
+    /// Delivers a pending entry again, to the consumer it's pending for.
+    pub fn redeliver(&mut self, id: StreamId, now: u128) {
+        if let Some(entry) = self.pending.get_mut(&id) {
+            entry.delivered_at = now;
+            entry.deliveries += 1;
+        }
+    }
+
+    /// Makes a consumer own an entry, adding it to the pending entries list if
+    /// it's not there yet. The consumer must exist.
+    pub fn assign(&mut self, id: StreamId, consumer: &Bytes, now: u128) {
+        let previous = self.pending.get(&id).map(|entry| entry.consumer.clone());
+        if previous.as_ref() == Some(consumer) {
+            return;
+        }
+        if let Some(previous) = previous {
+            if let Some(owner) = self.consumers.get_mut(&previous) {
+                owner.pending.remove(&id);
+            }
+            self.bytes -= previous.len();
+        }
+
+        self.bytes += consumer.len();
+        let entry = self.pending.entry(id).or_insert_with(|| PendingEntry {
+            consumer: consumer.clone(),
+            delivered_at: now,
+            deliveries: 0,
+        });
+        entry.consumer = consumer.clone();
+        self.consumers
+            .get_mut(consumer)
+            .expect("entries are assigned to existing consumers")
+            .pending
+            .insert(id);
+    }
+
+    /// Acknowledges an entry, returning `false` if it wasn't pending.
+    pub fn ack(&mut self, id: StreamId) -> bool {
+        let Some(entry) = self.pending.remove(&id) else {
+            return false;
+        };
+        self.bytes -= entry.consumer.len();
+        if let Some(consumer) = self.consumers.get_mut(&entry.consumer) {
+            consumer.pending.remove(&id);
+        }
+        true
+    }
+
+    pub fn pending_entry_mut(&mut self, id: StreamId) -> Option<&mut PendingEntry> {
+        self.pending.get_mut(&id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_entries_follow_their_consumer() {
+        let mut group = ConsumerGroup::new(StreamId::MIN, Some(0));
+        let (alice, bob) = (Bytes::from("alice"), Bytes::from("bob"));
+        group.create_consumer(&alice, 1);
+        group.create_consumer(&bob, 1);
+        for seq in 1..=3 {
+            group.deliver(StreamId::new(1, seq), &alice, 10);
+        }
+        group.redeliver(StreamId::new(1, 2), 20);
+
+        // Claiming keeps the delivery count, delivering anew resets it
+        group.assign(StreamId::new(1, 2), &bob, 30);
+        let entry = &group.pending()[&StreamId::new(1, 2)];
+        assert_eq!((&entry.consumer[..], entry.deliveries), (&b"bob"[..], 2));
+        group.deliver(StreamId::new(1, 2), &alice, 40);
+        assert_eq!(group.pending()[&StreamId::new(1, 2)].deliveries, 1);
+        group.assign(StreamId::new(1, 3), &bob, 50);
+
+        assert!(group.ack(StreamId::new(1, 1)));
+        assert!(!group.ack(StreamId::new(1, 1)));
+        let pending: Vec<_> = group.consumer_pending(b"alice", StreamId::MIN).collect();
+        assert_eq!(pending, [StreamId::new(1, 2)]);
+        assert_eq!(group.consumer(b"bob").unwrap().pending_len(), 1);
+
+        assert_eq!(group.delete_consumer(b"bob"), Some(1));
+        assert_eq!(group.delete_consumer(b"bob"), None);
+        assert_eq!(group.pending().len(), 1);
+        assert_eq!(group.delete_consumer(b"alice"), Some(1));
+        assert!(group.pending().is_empty());
+        assert_eq!(group.memory_usage(), 0);
+    }
+}
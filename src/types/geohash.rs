@@ -0,0 +1,373 @@
+//! Geohashes, which geospatial indexes store their points as, like Redis does.
+//!
+//! A point is encoded by halving the ranges of longitude and latitude 26 times
+//! each, and interleaving the resulting bits, latitude first. The 52 bits fit a
+//! sorted set score exactly, and points that are close share a prefix, so the
+//! points in an area can be found in a few ranges of scores.
+
+/// The most times the ranges are halved, for 52 bits
+const MAX_STEP: u8 = 26;
+const LONGITUDE: (f64, f64) = (-180.0, 180.0);
+/// Like Web Mercator, the poles can't be indexed
+const LATITUDE: (f64, f64) = (-85.05112878, 85.05112878);
+/// `GEOHASH` encodes the full range of latitudes, like the standard geohash does
+const STANDARD_LATITUDE: (f64, f64) = (-90.0, 90.0);
+const EARTH_RADIUS: f64 = 6372797.560856;
+const MERCATOR_MAX: f64 = 20037726.37;
+const ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Longitude and latitude, in degrees
+pub type Point = (f64, f64);
+
+/// Errors unless the point can be indexed.
+pub fn check_point((longitude, latitude): Point) -> Result<(), String> {
+    if !(LONGITUDE.0..=LONGITUDE.1).contains(&longitude)
+        || !(LATITUDE.0..=LATITUDE.1).contains(&latitude)
+    {
+        return Err(format!(
+            "invalid longitude,latitude pair {longitude:.6},{latitude:.6}"
+        ));
+    }
+    Ok(())
+}
+
+/// The score a point is indexed by, which must be valid.
+pub fn encode(point: Point) -> u64 {
+    GeoHash::encode(point, LATITUDE, MAX_STEP).bits
+}
+
+/// The point at the center of the area a score covers.
+pub fn decode(score: u64) -> Point {
+    let area = GeoHash::new(score, MAX_STEP).area(LATITUDE);
+    (
+        ((area.longitude.0 + area.longitude.1) / 2.0).clamp(LONGITUDE.0, LONGITUDE.1),
+        ((area.latitude.0 + area.latitude.1) / 2.0).clamp(LATITUDE.0, LATITUDE.1),
+    )
+}
+
+/// The standard 11 character geohash of a score, as `GEOHASH` replies.
+pub fn to_standard(score: u64) -> String {
+    let bits = GeoHash::encode(decode(score), STANDARD_LATITUDE, MAX_STEP).bits;
+    (0..11)
+        .map(|i| {
+            // Only 52 bits are known, so the last character is taken as zero, like Redis does
+            let index = match i {
+                10 => 0,
+                _ => (bits >> (52 - (i + 1) * 5)) & 0x1f,
+            };
+            ALPHABET[index as usize] as char
+        })
+        .collect()
+}
+
+/// The distance between two points, in meters, by the haversine formula.
+pub fn distance(from: Point, to: Point) -> f64 {
+    let v = ((to.0.to_radians() - from.0.to_radians()) / 2.0).sin();
+    // Along a meridian the distance is cheaper to work out
+    if v == 0.0 {
+        return latitude_distance(from.1, to.1);
+    }
+    let (from_lat, to_lat) = (from.1.to_radians(), to.1.to_radians());
+    let u = ((to_lat - from_lat) / 2.0).sin();
+    let a = u * u + from_lat.cos() * to_lat.cos() * v * v;
+    2.0 * EARTH_RADIUS * a.sqrt().asin()
+}
+
+fn latitude_distance(from: f64, to: f64) -> f64 {
+    EARTH_RADIUS * (to.to_radians() - from.to_radians()).abs()
+}
+
+/// The area a search covers, in meters
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Shape {
+    Radius(f64),
+    Box { width: f64, height: f64 },
+}
+
+impl Shape {
+    /// The distance of a point from the center, if it's within the shape.
+    pub fn distance_if_within(&self, center: Point, point: Point) -> Option<f64> {
+        match *self {
+            Shape::Radius(radius) => Some(distance(center, point)).filter(|&d| d <= radius),
+            Shape::Box { width, height } => {
+                if latitude_distance(point.1, center.1) > height / 2.0
+                    || distance((point.0, point.1), (center.0, point.1)) > width / 2.0
+                {
+                    return None;
+                }
+                Some(distance(center, point))
+            }
+        }
+    }
+
+    /// The ranges of scores, each from inclusive to exclusive, of the areas that
+    /// cover the shape around `center`.
+    ///
+    /// Like Redis, these are the area of a geohash about the size of the shape,
+    /// and its eight neighbors, leaving out the ones the shape doesn't reach.
+    pub fn score_ranges(&self, center: Point) -> Vec<(u64, u64)> {
+        let (min_lon, min_lat, max_lon, max_lat) = self.bounding_box(center);
+        let radius = match *self {
+            Shape::Radius(radius) => radius,
+            Shape::Box { width, height } => (width / 2.0).hypot(height / 2.0),
+        };
+
+        let mut step = estimate_step(radius, center.1);
+        let mut hash = GeoHash::encode(center, LATITUDE, step);
+        let neighbors = hash.neighbors();
+        // Near the edge of its area, the neighbors may not reach far enough
+        let [north, south, east, west] = [0, 1, 2, 3].map(|i| neighbors[i].area(LATITUDE));
+        if step > 1
+            && (north.latitude.1 < max_lat
+                || south.latitude.0 > min_lat
+                || east.longitude.1 < max_lon
+                || west.longitude.0 > min_lon)
+        {
+            step -= 1;
+            hash = GeoHash::encode(center, LATITUDE, step);
+        }
+
+        let area = hash.area(LATITUDE);
+        let [north, south, east, west, north_east, north_west, south_east, south_west] =
+            hash.neighbors().map(Some);
+        let mut areas = [
+            Some(hash),
+            north,
+            south,
+            east,
+            west,
+            north_east,
+            north_west,
+            south_east,
+            south_west,
+        ];
+        if step >= 2 {
+            let mut excluded = Vec::new();
+            if area.latitude.0 < min_lat {
+                excluded.extend([2, 7, 8]);
+            }
+            if area.latitude.1 > max_lat {
+                excluded.extend([1, 5, 6]);
+            }
+            if area.longitude.0 < min_lon {
+                excluded.extend([4, 8, 6]);
+            }
+            if area.longitude.1 > max_lon {
+                excluded.extend([3, 7, 5]);
+            }
+            for i in excluded {
+                areas[i] = None;
+            }
+        }
+
+        let mut ranges: Vec<(u64, u64)> = Vec::new();
+        let mut last = None;
+        for hash in areas.into_iter().flatten() {
+            // With huge shapes, neighbors can wrap around to the same area
+            if last == Some(hash) {
+                continue;
+            }
+            last = Some(hash);
+            let shift = 52 - hash.step as u32 * 2;
+            ranges.push((hash.bits << shift, (hash.bits + 1) << shift));
+        }
+        ranges
+    }
+
+    /// The least and greatest longitude and latitude of the shape around `center`
+    fn bounding_box(&self, (longitude, latitude): Point) -> (f64, f64, f64, f64) {
+        let (width, height) = match *self {
+            Shape::Radius(radius) => (radius, radius),
+            Shape::Box { width, height } => (width / 2.0, height / 2.0),
+        };
+        let lat_delta = (height / EARTH_RADIUS).to_degrees();
+        let lon_delta =
+            |latitude: f64| (width / EARTH_RADIUS / latitude.to_radians().cos()).to_degrees();
+        // Meridians get closer towards the pole, which is the other way in the south
+        let lon_delta = match latitude < 0.0 {
+            true => lon_delta(latitude - lat_delta),
+            false => lon_delta(latitude + lat_delta),
+        };
+        (
+            longitude - lon_delta,
+            latitude - lat_delta,
+            longitude + lon_delta,
+            latitude + lat_delta,
+        )
+    }
+}
+
+/// How many times to halve the ranges for areas about the size of a radius.
+fn estimate_step(mut radius: f64, latitude: f64) -> u8 {
+    if radius == 0.0 {
+        return MAX_STEP;
+    }
+    let mut step: i32 = 1;
+    while radius < MERCATOR_MAX {
+        radius *= 2.0;
+        step += 1;
+    }
+    // So the radius is included in most cases
+    step -= 2;
+    // Areas are narrower towards the poles
+    if latitude.abs() > 66.0 {
+        step -= 1;
+        if latitude.abs() > 80.0 {
+            step -= 1;
+        }
+    }
+    step.clamp(1, MAX_STEP as i32) as u8
+}
+
+/// The ranges of an area, from the least to the greatest
+struct Area {
+    longitude: (f64, f64),
+    latitude: (f64, f64),
+}
+
+/// A geohash of `step * 2` bits
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct GeoHash {
+    bits: u64,
+    step: u8,
+}
+
+impl GeoHash {
+    fn new(bits: u64, step: u8) -> Self {
+        Self { bits, step }
+    }
+
+    fn encode((longitude, latitude): Point, latitudes: (f64, f64), step: u8) -> Self {
+        let cells = (1u64 << step) as f64;
+        let lat_offset = (latitude - latitudes.0) / (latitudes.1 - latitudes.0) * cells;
+        let lon_offset = (longitude - LONGITUDE.0) / (LONGITUDE.1 - LONGITUDE.0) * cells;
+        Self::new(
+            spread(lat_offset as u32) | spread(lon_offset as u32) << 1,
+            step,
+        )
+    }
+
+    fn area(&self, latitudes: (f64, f64)) -> Area {
+        let cells = (1u64 << self.step) as f64;
+        let (lat, lon) = (squash(self.bits) as f64, squash(self.bits >> 1) as f64);
+        let lat_scale = latitudes.1 - latitudes.0;
+        let lon_scale = LONGITUDE.1 - LONGITUDE.0;
+        Area {
+            longitude: (
+                LONGITUDE.0 + (lon / cells) * lon_scale,
+                LONGITUDE.0 + ((lon + 1.0) / cells) * lon_scale,
+            ),
+            latitude: (
+                latitudes.0 + (lat / cells) * lat_scale,
+                latitudes.0 + ((lat + 1.0) / cells) * lat_scale,
+            ),
+        }
+    }
+
+    /// North, south, east, west, north east, north west, south east and south west
+    fn neighbors(&self) -> [GeoHash; 8] {
+        let moved = |x, y| self.move_by(x, y);
+        [
+            moved(0, 1),
+            moved(0, -1),
+            moved(1, 0),
+            moved(-1, 0),
+            moved(1, 1),
+            moved(-1, 1),
+            moved(1, -1),
+            moved(-1, -1),
+        ]
+    }
+
+    /// Moves to the adjacent area east or west by `x`, and north or south by `y`,
+    /// wrapping around the ranges
+    fn move_by(&self, x: i8, y: i8) -> GeoHash {
+        const ODD: u64 = 0xaaaaaaaaaaaaaaaa;
+        const EVEN: u64 = 0x5555555555555555;
+        let shift = 64 - self.step as u32 * 2;
+        // Adding one to the bits of either coordinate, with the other bits set to
+        // carry over them, or subtracting one, with them cleared to borrow over them
+        let step = |bits: u64, others: u64, d: i8| match d {
+            0 => bits,
+            1.. => bits.wrapping_add((others >> shift) + 1),
+            _ => (bits | others >> shift).wrapping_sub((others >> shift) + 1),
+        };
+        let lon = step(self.bits & ODD, EVEN, x) & (ODD >> shift);
+        let lat = step(self.bits & EVEN, ODD, y) & (EVEN >> shift);
+        GeoHash::new(lon | lat, self.step)
+    }
+}
+
+/// Spreads the bits of `value` to the even bits
+fn spread(value: u32) -> u64 {
+    let mut x = value as u64;
+    x = (x | x << 16) & 0x0000ffff0000ffff;
+    x = (x | x << 8) & 0x00ff00ff00ff00ff;
+    x = (x | x << 4) & 0x0f0f0f0f0f0f0f0f;
+    x = (x | x << 2) & 0x3333333333333333;
+    (x | x << 1) & 0x5555555555555555
+}
+
+/// Gathers the even bits of `value`, undoing [`spread`]
+fn squash(value: u64) -> u32 {
+    let mut x = value & 0x5555555555555555;
+    x = (x | x >> 1) & 0x3333333333333333;
+    x = (x | x >> 2) & 0x0f0f0f0f0f0f0f0f;
+    x = (x | x >> 4) & 0x00ff00ff00ff00ff;
+    x = (x | x >> 8) & 0x0000ffff0000ffff;
+    (x | x >> 16) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PALERMO: Point = (13.361389, 38.115556);
+    const CATANIA: Point = (15.087269, 37.502669);
+
+    #[test]
+    fn test_geohash() {
+        assert_eq!(encode(PALERMO), 3479099956230698);
+        assert_eq!(encode(CATANIA), 3479447370796909);
+        let (longitude, latitude) = decode(encode(PALERMO));
+        assert!((longitude - 13.361389).abs() < 1e-5 && (latitude - 38.115556).abs() < 1e-5);
+        assert_eq!(to_standard(encode(PALERMO)), "sqc8b49rny0");
+        assert_eq!(to_standard(encode(CATANIA)), "sqdtr74hyu0");
+        let indexed = |point| decode(encode(point));
+        assert_eq!(
+            format!("{:.4}", distance(indexed(PALERMO), indexed(CATANIA))),
+            "166274.1516"
+        );
+
+        assert!(check_point((180.0, 85.0)).is_ok());
+        assert_eq!(
+            check_point((0.0, 86.0)),
+            Err("invalid longitude,latitude pair 0.000000,86.000000".to_string())
+        );
+
+        let hash = GeoHash::encode((0.0, 0.0), LATITUDE, 2);
+        assert_eq!(hash.move_by(1, 0).move_by(-1, 0), hash);
+        assert_eq!(hash.move_by(0, 1).move_by(0, -1), hash);
+    }
+
+    #[test]
+    fn test_score_ranges() {
+        let score = encode(PALERMO);
+        for shape in [
+            Shape::Radius(200_000.0),
+            Shape::Box {
+                width: 400_000.0,
+                height: 400_000.0,
+            },
+        ] {
+            let ranges = shape.score_ranges(CATANIA);
+            assert!(!ranges.is_empty() && ranges.len() <= 9);
+            assert!(ranges.iter().any(|&(min, max)| (min..max).contains(&score)));
+            assert!(shape.distance_if_within(CATANIA, PALERMO).is_some());
+        }
+        assert_eq!(
+            Shape::Radius(100_000.0).distance_if_within(CATANIA, PALERMO),
+            None
+        );
+    }
+}
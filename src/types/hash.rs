@@ -0,0 +1,406 @@
+use std::collections::{BTreeSet, HashMap};
+
+use bytes::Bytes;
+
+use super::listpack::{ListPack, ListPackLimits};
+
+/// Memory used by a field of a hash table besides its field and value, in bytes
+const TABLE_ENTRY_OVERHEAD: usize = std::mem::size_of::<(Bytes, Bytes)>() + 8;
+
+/// Memory used by the time to live of a field besides the field itself, in bytes
+const EXPIRY_OVERHEAD: usize =
+    std::mem::size_of::<(Bytes, u128)>() + 8 + std::mem::size_of::<(u128, Bytes)>();
+
+/// A map of fields to values, encoded like Redis hashes.
+///
+/// Small hashes are kept in a [`ListPack`] of alternating fields and values,
+/// which is compact but takes a scan to find a field. Once a hash grows past
+/// its [`ListPackLimits`], it's converted to a hash table for good.
+///
+/// Fields can have a time to live of their own, kept aside from the fields so
+/// hashes without any don't pay for them.
+#[derive(Debug, Clone)]
+pub struct Hash {
+    encoding: Encoding,
+    expiries: Option<Box<FieldExpiries>>,
+}
+
+#[derive(Debug, Clone)]
+enum Encoding {
+    ListPack(ListPack),
+    Table {
+        map: HashMap<Bytes, Bytes, ahash::RandomState>,
+        /// The size of every field and value, kept up to date to report memory usage cheaply
+        bytes: usize,
+    },
+}
+
+/// When the fields with a time to live expire, in milliseconds since UNIX epoch
+#[derive(Debug, Clone, Default)]
+struct FieldExpiries {
+    by_field: HashMap<Bytes, u128, ahash::RandomState>,
+    /// The same expiries ordered by time, to find the fields to expire first
+    by_time: BTreeSet<(u128, Bytes)>,
+    /// The size of every field, which both indexes share
+    bytes: usize,
+}
+
+impl Default for Hash {
+    fn default() -> Self {
+        Self {
+            encoding: Encoding::ListPack(ListPack::new()),
+            expiries: None,
+        }
+    }
+}
+
+impl Hash {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.encoding {
+            Encoding::ListPack(listpack) => listpack.len() / 2,
+            Encoding::Table { map, .. } => map.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The name of the encoding, as reported by `OBJECT ENCODING`.
+    ///
+    /// Like Redis, a listpack with fields to expire is a `listpackex`.
+    pub fn encoding(&self) -> &'static str {
+        match &self.encoding {
+            Encoding::ListPack(_) if self.expiries.is_some() => "listpackex",
+            Encoding::ListPack(_) => "listpack",
+            Encoding::Table { .. } => "hashtable",
+        }
+    }
+
+    /// Estimated memory used by the hash, in bytes
+    pub fn memory_usage(&self) -> usize {
+        let expiries = self.expiries.as_ref().map_or(0, |expiries| {
+            expiries.bytes + expiries.by_field.len() * EXPIRY_OVERHEAD
+        });
+        expiries
+            + match &self.encoding {
+                Encoding::ListPack(listpack) => listpack.bytes(),
+                Encoding::Table { map, bytes } => bytes + map.capacity() * TABLE_ENTRY_OVERHEAD,
+            }
+    }
+
+    pub fn get(&self, field: &[u8]) -> Option<&[u8]> {
+        match &self.encoding {
+            Encoding::ListPack(listpack) => {
+                pairs(listpack).find_map(|(f, value)| (f == field).then_some(value))
+            }
+            Encoding::Table { map, .. } => map.get(field).map(|value| &value[..]),
+        }
+    }
+
+    pub fn contains(&self, field: &[u8]) -> bool {
+        self.get(field).is_some()
+    }
+
+    /// Sets the value of a field, converting the hash to a hash table if it
+    /// outgrows `limits`.
+    ///
+    /// Like Redis' `HSET`, overwriting a field discards its time to live.
+    /// Returns `true` if the field is new.
+    pub fn insert(&mut self, field: &[u8], value: &[u8], limits: ListPackLimits) -> bool {
+        self.set_field_expires_at(field, None);
+        if let Encoding::ListPack(listpack) = &mut self.encoding {
+            let index = pairs(listpack).position(|(f, _)| f == field);
+            let fits = field.len() <= limits.max_value
+                && value.len() <= limits.max_value
+                && (index.is_some() || listpack.len() / 2 < limits.max_entries);
+            if fits {
+                match index {
+                    Some(index) => listpack.replace(index * 2 + 1, value),
+                    None => {
+                        listpack.push_back(field);
+                        listpack.push_back(value);
+                    }
+                }
+                return index.is_none();
+            }
+            self.convert_to_table();
+        }
+
+        let Encoding::Table { map, bytes } = &mut self.encoding else {
+            unreachable!("the hash was just converted to a table");
+        };
+        *bytes += value.len();
+        match map.insert(Bytes::copy_from_slice(field), Bytes::copy_from_slice(value)) {
+            Some(previous) => {
+                *bytes -= previous.len();
+                false
+            }
+            None => {
+                *bytes += field.len();
+                true
+            }
+        }
+    }
+
+    /// Sets the value of a field like [`Hash::insert`], but keeps its time to
+    /// live, like `HINCRBY` does.
+    pub fn insert_keeping_ttl(&mut self, field: &[u8], value: &[u8], limits: ListPackLimits) {
+        let expires_at = self.field_expires_at(field).flatten();
+        self.insert(field, value, limits);
+        self.set_field_expires_at(field, expires_at);
+    }
+
+    /// Returns `true` if the field existed.
+    pub fn remove(&mut self, field: &[u8]) -> bool {
+        self.set_field_expires_at(field, None);
+        match &mut self.encoding {
+            Encoding::ListPack(listpack) => {
+                let index = pairs(listpack).position(|(f, _)| f == field);
+                if let Some(index) = index {
+                    listpack.remove_range(index * 2, 2);
+                }
+                index.is_some()
+            }
+            Encoding::Table { map, bytes } => match map.remove(field) {
+                Some(value) => {
+                    *bytes -= field.len() + value.len();
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Iterates over the fields and their values.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (&[u8], &[u8])> + '_> {
+        match &self.encoding {
+            Encoding::ListPack(listpack) => Box::new(pairs(listpack)),
+            Encoding::Table { map, .. } => {
+                Box::new(map.iter().map(|(field, value)| (&field[..], &value[..])))
+            }
+        }
+    }
+
+    /// Gets the time at which a field expires, in milliseconds since UNIX epoch.
+    ///
+    /// Returns `None` if the field does not exist, and `Some(None)` if it has no time to live.
+    pub fn field_expires_at(&self, field: &[u8]) -> Option<Option<u128>> {
+        if !self.contains(field) {
+            return None;
+        }
+        Some(
+            self.expiries
+                .as_ref()
+                .and_then(|expiries| expiries.by_field.get(field).copied()),
+        )
+    }
+
+    /// Sets the time at which a field expires, or removes its time to live with `None`.
+    ///
+    /// The field isn't removed even if the time is in the past, that's up to
+    /// [`Hash::remove_expired_fields`]. Returns the previous expiry.
+    pub fn set_field_expires_at(&mut self, field: &[u8], expires_at: Option<u128>) -> Option<u128> {
+        if expires_at.is_none() && self.expiries.is_none() {
+            return None;
+        }
+        let expiries = self.expiries.get_or_insert_with(Default::default);
+        let previous = expiries.by_field.remove(field);
+        if let Some(previous) = previous {
+            let (_, field) = expiries
+                .by_time
+                .take(&(previous, Bytes::copy_from_slice(field)))
+                .expect("both indexes hold the same fields");
+            expiries.bytes -= field.len();
+        }
+        match expires_at {
+            Some(expires_at) => {
+                let field = Bytes::copy_from_slice(field);
+                expiries.bytes += field.len();
+                expiries.by_field.insert(field.clone(), expires_at);
+                expiries.by_time.insert((expires_at, field));
+            }
+            None if expiries.by_field.is_empty() => self.expiries = None,
+            None => (),
+        }
+        previous
+    }
+
+    /// When the next field expires, if any has a time to live.
+    pub fn next_field_expiry(&self) -> Option<u128> {
+        let (expires_at, _) = self.expiries.as_ref()?.by_time.first()?;
+        Some(*expires_at)
+    }
+
+    /// Removes the fields that expired by `now`, returning how many were removed.
+    pub fn remove_expired_fields(&mut self, now: u128) -> usize {
+        let mut removed = 0;
+        while let Some(expires_at) = self.next_field_expiry() {
+            if expires_at > now {
+                break;
+            }
+            let expiries = self.expiries.as_ref().expect("a field expires");
+            let (_, field) = expiries.by_time.first().expect("a field expires").clone();
+            self.remove(&field);
+            removed += 1;
+        }
+        removed
+    }
+
+    /// Whether the hash is still a listpack, which is small enough to be scanned in one go
+    pub fn is_listpack(&self) -> bool {
+        matches!(self.encoding, Encoding::ListPack(_))
+    }
+
+    fn convert_to_table(&mut self) {
+        let Encoding::ListPack(listpack) = &self.encoding else {
+            return;
+        };
+        let mut map = HashMap::with_capacity_and_hasher(listpack.len() / 2, Default::default());
+        let mut bytes = 0;
+        for (field, value) in pairs(listpack) {
+            bytes += field.len() + value.len();
+            map.insert(Bytes::copy_from_slice(field), Bytes::copy_from_slice(value));
+        }
+        self.encoding = Encoding::Table { map, bytes };
+    }
+}
+
+impl PartialEq for Hash {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(field, value)| other.get(field) == Some(value))
+    }
+}
+
+impl<F: AsRef<[u8]>, V: AsRef<[u8]>> FromIterator<(F, V)> for Hash {
+    /// Collects into a listpack regardless of size, meant for tests and small hashes
+    fn from_iter<I: IntoIterator<Item = (F, V)>>(iter: I) -> Self {
+        let limits = ListPackLimits {
+            max_entries: usize::MAX,
+            max_value: usize::MAX,
+        };
+        let mut hash = Hash::new();
+        for (field, value) in iter {
+            hash.insert(field.as_ref(), value.as_ref(), limits);
+        }
+        hash
+    }
+}
+
+/// The fields and values of a listpack encoded hash
+fn pairs(listpack: &ListPack) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut entries = listpack.iter();
+    std::iter::from_fn(move || Some((entries.next()?, entries.next()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: ListPackLimits = ListPackLimits {
+        max_entries: 4,
+        max_value: 8,
+    };
+
+    #[test]
+    fn test_insert_get_and_remove() {
+        let mut hash = Hash::new();
+        assert!(hash.insert(b"a", b"1", LIMITS));
+        assert!(hash.insert(b"b", b"2", LIMITS));
+        assert!(!hash.insert(b"a", b"3", LIMITS));
+
+        assert_eq!(hash.len(), 2);
+        assert_eq!(hash.get(b"a"), Some(&b"3"[..]));
+        assert_eq!(hash.get(b"c"), None);
+        assert!(hash.remove(b"a"));
+        assert!(!hash.remove(b"a"));
+        assert_eq!(hash.iter().collect::<Vec<_>>(), [(&b"b"[..], &b"2"[..])]);
+        assert_eq!(hash.encoding(), "listpack");
+    }
+
+    #[test]
+    fn test_converts_to_a_table_past_the_limits() {
+        let mut hash = Hash::new();
+        for field in ["a", "b", "c", "d"] {
+            hash.insert(field.as_bytes(), b"value", LIMITS);
+        }
+        assert_eq!(hash.encoding(), "listpack");
+        hash.insert(b"e", b"value", LIMITS);
+        assert_eq!(hash.encoding(), "hashtable");
+        assert_eq!(hash.len(), 5);
+        assert_eq!(hash.get(b"a"), Some(&b"value"[..]));
+
+        // Tables are never converted back
+        for field in ["a", "b", "c", "d"] {
+            hash.remove(field.as_bytes());
+        }
+        assert_eq!(hash.encoding(), "hashtable");
+
+        let mut hash = Hash::new();
+        hash.insert(b"a", b"a value too long", LIMITS);
+        assert_eq!(hash.encoding(), "hashtable");
+    }
+
+    #[test]
+    fn test_memory_usage_is_kept_up_to_date() {
+        let mut hash = Hash::new();
+        hash.insert(b"a long field", b"1", LIMITS);
+        let Encoding::Table { map, bytes } = &hash.encoding else {
+            panic!("Expected a table");
+        };
+        assert_eq!(*bytes, map.iter().map(|(f, v)| f.len() + v.len()).sum());
+
+        hash.insert(b"a long field", b"22", LIMITS);
+        hash.insert(b"b", b"333", LIMITS);
+        hash.remove(b"b");
+        let Encoding::Table { bytes, .. } = &hash.encoding else {
+            panic!("Expected a table");
+        };
+        assert_eq!(*bytes, 14);
+    }
+
+    #[test]
+    fn test_fields_expire() {
+        let mut hash: Hash = [("a", "1"), ("b", "2"), ("c", "3")].into_iter().collect();
+        let bare = hash.memory_usage();
+        assert_eq!(hash.set_field_expires_at(b"a", Some(20)), None);
+        assert_eq!(hash.set_field_expires_at(b"b", Some(10)), None);
+        assert_eq!(hash.set_field_expires_at(b"a", Some(30)), Some(20));
+        assert_eq!(hash.encoding(), "listpackex");
+        assert_eq!(hash.field_expires_at(b"a"), Some(Some(30)));
+        assert_eq!(hash.field_expires_at(b"c"), Some(None));
+        assert_eq!(hash.field_expires_at(b"d"), None);
+        assert_eq!(hash.next_field_expiry(), Some(10));
+
+        // Overwriting a field discards its time to live, unless asked to keep it
+        hash.insert_keeping_ttl(b"b", b"4", LIMITS);
+        assert_eq!(hash.field_expires_at(b"b"), Some(Some(10)));
+        hash.insert(b"b", b"5", LIMITS);
+        assert_eq!(hash.field_expires_at(b"b"), Some(None));
+        hash.set_field_expires_at(b"b", Some(10));
+
+        assert_eq!(hash.remove_expired_fields(9), 0);
+        assert_eq!(hash.remove_expired_fields(30), 2);
+        assert_eq!(hash.iter().collect::<Vec<_>>(), [(&b"c"[..], &b"3"[..])]);
+        assert_eq!(hash.next_field_expiry(), None);
+        assert_eq!(hash.encoding(), "listpack");
+        assert!(hash.memory_usage() < bare);
+    }
+
+    #[test]
+    fn test_equality_ignores_the_encoding() {
+        let listpack: Hash = [("a", "1"), ("b", "2")].into_iter().collect();
+        let mut table = Hash::new();
+        table.insert(b"b", b"2", LIMITS);
+        table.insert(b"a", b"1", LIMITS);
+        table.convert_to_table();
+        assert_eq!(listpack, table);
+    }
+}
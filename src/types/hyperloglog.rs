@@ -0,0 +1,510 @@
+//! HyperLogLogs, laid out like Redis' so they can be moved between the two as strings.
+//!
+//! A HyperLogLog is a string starting with a 16 byte header:
+//!
+//! ```text
+//! +------+---+-----+----------+
+//! | HYLL | E | N/U | Cardin.  |
+//! +------+---+-----+----------+
+//! ```
+//!
+//! The magic `HYLL`, the encoding `E`, three unused bytes, and the cached
+//! cardinality as a little endian integer, whose most significant bit is set
+//! when the cache is stale. The 16384 registers of 6 bits follow, either
+//! packed one after the other (the dense encoding), or run length encoded with
+//! three opcodes (the sparse encoding):
+//!
+//! - `ZERO` (`00xxxxxx`) -- `xxxxxx + 1` registers set to 0
+//! - `XZERO` (`01xxxxxx yyyyyyyy`) -- `xxxxxxyyyyyyyy + 1` registers set to 0
+//! - `VAL` (`1vvvvvxx`) -- `xx + 1` registers set to `vvvvv + 1`
+//!
+//! HyperLogLogs start sparse, and turn dense for good once a register is over
+//! what `VAL` can hold, or the sparse encoding grows past `hll-sparse-max-bytes`.
+
+use std::fmt;
+
+use bytes::Bytes;
+use rand::Rng;
+
+const MAGIC: &[u8] = b"HYLL";
+const HEADER_LEN: usize = 16;
+/// Bits of the hash used to pick a register
+const P: u32 = 14;
+pub const REGISTERS: usize = 1 << P;
+/// Bits of the hash used to count leading zeros
+const Q: u32 = 64 - P;
+const REGISTER_BITS: usize = 6;
+const REGISTER_MAX: u8 = (1 << REGISTER_BITS) - 1;
+const DENSE_LEN: usize = HEADER_LEN + (REGISTERS * REGISTER_BITS).div_ceil(8);
+const ALPHA_INF: f64 = 0.721_347_520_444_481_7;
+
+const ZERO_MAX_LEN: usize = 64;
+const XZERO_MAX_LEN: usize = REGISTERS;
+const VAL_MAX_VALUE: u8 = 32;
+const VAL_MAX_LEN: usize = 4;
+
+pub const INVALID: &str = "WRONGTYPE Key is not a valid HyperLogLog string value.";
+pub const CORRUPTED: &str = "INVALIDOBJ Corrupted HLL object detected";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Encoding {
+    Dense = 0,
+    Sparse = 1,
+}
+
+#[derive(Debug, Clone)]
+pub struct HyperLogLog {
+    /// One byte per register, rather than packed
+    registers: Vec<u8>,
+    encoding: Encoding,
+    /// As laid out in the header
+    cardinality: [u8; 8],
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self {
+            registers: vec![0; REGISTERS],
+            encoding: Encoding::Sparse,
+            cardinality: [0; 8],
+        }
+    }
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes a HyperLogLog from its string.
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        let (encoding, cardinality) = parse_header(bytes)?;
+        let body = &bytes[HEADER_LEN..];
+        let registers = match encoding {
+            Encoding::Dense => (0..REGISTERS).map(|i| dense_get(body, i)).collect(),
+            Encoding::Sparse => {
+                let mut registers = Vec::with_capacity(REGISTERS);
+                for op in sparse_ops(bytes)? {
+                    let (value, len) = match op {
+                        SparseOp::Zero(len) | SparseOp::XZero(len) => (0, len),
+                        SparseOp::Val(value, len) => (value, len),
+                    };
+                    registers.resize(registers.len() + len, value);
+                }
+                registers
+            }
+        };
+        Ok(Self {
+            registers,
+            encoding,
+            cardinality,
+        })
+    }
+
+    /// The cached cardinality of a HyperLogLog string, without decoding it,
+    /// or `None` if the cache is stale.
+    pub fn cached_count(bytes: &[u8]) -> Result<Option<u64>, String> {
+        let (_, cardinality) = parse_header(bytes)?;
+        Ok((cardinality[7] & 0x80 == 0).then(|| u64::from_le_bytes(cardinality)))
+    }
+
+    /// Caches the cardinality in a HyperLogLog string, which must be valid.
+    pub fn cache_count(buf: &mut [u8], count: u64) {
+        buf[8..HEADER_LEN].copy_from_slice(&count.to_le_bytes());
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    /// Returns `true` if a register changed, and so the approximated cardinality may have.
+    pub fn add(&mut self, element: &[u8]) -> bool {
+        let (index, count) = hash(element);
+        if self.registers[index] >= count {
+            return false;
+        }
+        self.registers[index] = count;
+        self.invalidate();
+        true
+    }
+
+    /// Keeps the greater of each register, approximating the union of both.
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        for (register, &value) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(value);
+        }
+        self.invalidate();
+    }
+
+    pub fn to_dense(&mut self) {
+        self.encoding = Encoding::Dense;
+    }
+
+    fn invalidate(&mut self) {
+        self.cardinality[7] |= 0x80;
+    }
+
+    /// The approximated cardinality, cached until a register changes.
+    pub fn count(&mut self) -> u64 {
+        if self.cardinality[7] & 0x80 == 0 {
+            return u64::from_le_bytes(self.cardinality);
+        }
+        let count = estimate(&self.registers);
+        self.cardinality = count.to_le_bytes();
+        count
+    }
+
+    /// Encodes the HyperLogLog, turning it dense if it no longer fits the sparse
+    /// encoding in `sparse_max_bytes`.
+    pub fn to_bytes(&mut self, sparse_max_bytes: usize) -> Bytes {
+        if self.encoding == Encoding::Sparse {
+            match self.encode_sparse() {
+                Some(bytes) if bytes.len() <= sparse_max_bytes => return Bytes::from(bytes),
+                _ => self.encoding = Encoding::Dense,
+            }
+        }
+
+        let mut bytes = self.header();
+        bytes.resize(DENSE_LEN, 0);
+        for (i, &value) in self.registers.iter().enumerate() {
+            dense_set(&mut bytes[HEADER_LEN..], i, value);
+        }
+        Bytes::from(bytes)
+    }
+
+    fn header(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(DENSE_LEN);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&[self.encoding as u8, 0, 0, 0]);
+        header.extend_from_slice(&self.cardinality);
+        header
+    }
+
+    /// Returns `None` if a register is over what the sparse encoding can hold.
+    fn encode_sparse(&self) -> Option<Vec<u8>> {
+        let mut bytes = self.header();
+        let mut registers = &self.registers[..];
+        while let Some(&value) = registers.first() {
+            let run = registers
+                .iter()
+                .take_while(|&&other| other == value)
+                .count();
+            registers = &registers[run..];
+
+            let mut left = run;
+            while left > 0 {
+                let op = match value {
+                    0 if left > ZERO_MAX_LEN => SparseOp::XZero(left.min(XZERO_MAX_LEN)),
+                    0 => SparseOp::Zero(left),
+                    1..=VAL_MAX_VALUE => SparseOp::Val(value, left.min(VAL_MAX_LEN)),
+                    _ => return None,
+                };
+                left -= op.len();
+                op.encode(&mut bytes);
+            }
+        }
+        Some(bytes)
+    }
+}
+
+fn parse_header(bytes: &[u8]) -> Result<(Encoding, [u8; 8]), String> {
+    if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+        return Err(INVALID.to_string());
+    }
+    let encoding = match bytes[4] {
+        0 if bytes.len() == DENSE_LEN => Encoding::Dense,
+        1 => Encoding::Sparse,
+        _ => return Err(INVALID.to_string()),
+    };
+    let cardinality = bytes[8..HEADER_LEN].try_into().expect("checked above");
+    Ok((encoding, cardinality))
+}
+
+/// The register an element is counted in, and the position of the first set bit
+/// in the rest of its hash, which the register keeps the greatest of.
+fn hash(element: &[u8]) -> (usize, u8) {
+    let hash = murmur_hash_64a(element, 0xadc83b19);
+    let index = hash as usize & (REGISTERS - 1);
+    // The bit past the end ensures the count stops at `Q + 1`
+    let rest = (hash >> P) | (1 << Q);
+    (index, rest.trailing_zeros() as u8 + 1)
+}
+
+/// Austin Appleby's MurmurHash64A, like Redis uses for HyperLogLogs.
+fn murmur_hash_64a(bytes: &[u8], seed: u64) -> u64 {
+    const M: u64 = 0xc6a4a7935bd1e995;
+    const R: u32 = 47;
+
+    let mut h = seed ^ (bytes.len() as u64).wrapping_mul(M);
+    let chunks = bytes.chunks_exact(8);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        let mut k = u64::from_le_bytes(chunk.try_into().expect("chunks of 8"));
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+    if !tail.is_empty() {
+        for (i, &byte) in tail.iter().enumerate() {
+            h ^= (byte as u64) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
+
+/// Otmar Ertl's improved estimator, like Redis uses, from a histogram of the registers.
+fn estimate(registers: &[u8]) -> u64 {
+    // Registers can't be over `Q + 1` when added to, but a string set by hand
+    // can hold up to `REGISTER_MAX`, which the estimate leaves out like Redis
+    let mut histogram = [0u32; REGISTER_MAX as usize + 1];
+    for &value in registers {
+        histogram[value as usize] += 1;
+    }
+
+    let m = REGISTERS as f64;
+    let mut z = m * tau((m - histogram[Q as usize + 1] as f64) / m);
+    for &count in histogram[1..=Q as usize].iter().rev() {
+        z += count as f64;
+        z *= 0.5;
+    }
+    z += m * sigma(histogram[0] as f64 / m);
+    (ALPHA_INF * m * m / z).round() as u64
+}
+
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+    let mut y = 1.0;
+    let mut z = x;
+    loop {
+        x *= x;
+        let previous = z;
+        z += x * y;
+        y += y;
+        if z == previous {
+            return z;
+        }
+    }
+}
+
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+    let mut y = 1.0;
+    let mut z = 1.0 - x;
+    loop {
+        x = x.sqrt();
+        let previous = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if z == previous {
+            return z / 3.0;
+        }
+    }
+}
+
+/// Registers straddle bytes, with their least significant bits first.
+fn dense_get(body: &[u8], index: usize) -> u8 {
+    let byte = index * REGISTER_BITS / 8;
+    let shift = index * REGISTER_BITS % 8;
+    let low = body[byte] >> shift;
+    let high = match shift > 8 - REGISTER_BITS {
+        true => body[byte + 1] << (8 - shift),
+        false => 0,
+    };
+    (low | high) & REGISTER_MAX
+}
+
+fn dense_set(body: &mut [u8], index: usize, value: u8) {
+    let byte = index * REGISTER_BITS / 8;
+    let shift = index * REGISTER_BITS % 8;
+    body[byte] &= !(REGISTER_MAX << shift);
+    body[byte] |= value << shift;
+    if shift > 8 - REGISTER_BITS {
+        body[byte + 1] &= !(REGISTER_MAX >> (8 - shift));
+        body[byte + 1] |= value >> (8 - shift);
+    }
+}
+
+/// An opcode of the sparse encoding, with the number of registers it covers
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SparseOp {
+    Zero(usize),
+    XZero(usize),
+    Val(u8, usize),
+}
+
+impl SparseOp {
+    fn len(&self) -> usize {
+        match *self {
+            SparseOp::Zero(len) | SparseOp::XZero(len) | SparseOp::Val(_, len) => len,
+        }
+    }
+
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match *self {
+            SparseOp::Zero(len) => bytes.push((len - 1) as u8),
+            SparseOp::XZero(len) => {
+                bytes.extend_from_slice(&(0x4000 | (len - 1) as u16).to_be_bytes())
+            }
+            SparseOp::Val(value, len) => bytes.push(0x80 | (value - 1) << 2 | (len - 1) as u8),
+        }
+    }
+}
+
+/// Like `PFDEBUG DECODE` shows them
+impl fmt::Display for SparseOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SparseOp::Zero(len) => write!(f, "Z:{len}"),
+            SparseOp::XZero(len) => write!(f, "XZ:{len}"),
+            SparseOp::Val(value, len) => write!(f, "v:{value},{len}"),
+        }
+    }
+}
+
+/// The opcodes of a sparse HyperLogLog string, which must cover every register.
+pub fn sparse_ops(bytes: &[u8]) -> Result<Vec<SparseOp>, String> {
+    if parse_header(bytes)?.0 != Encoding::Sparse {
+        return Err("HLL encoding is not sparse".to_string());
+    }
+
+    let mut ops = Vec::new();
+    let mut covered = 0;
+    let mut body = bytes[HEADER_LEN..].iter();
+    while let Some(&byte) = body.next() {
+        let op = match byte >> 6 {
+            0b00 => SparseOp::Zero((byte & 0x3f) as usize + 1),
+            0b01 => {
+                let next = *body.next().ok_or(CORRUPTED)?;
+                SparseOp::XZero((((byte & 0x3f) as usize) << 8 | next as usize) + 1)
+            }
+            _ => SparseOp::Val(((byte >> 2) & 0x1f) + 1, (byte & 0x03) as usize + 1),
+        };
+        covered += op.len();
+        ops.push(op);
+    }
+    if covered != REGISTERS {
+        return Err(CORRUPTED.to_string());
+    }
+    Ok(ops)
+}
+
+/// Checks the dense registers and the error of the estimate, like `PFSELFTEST`.
+pub fn self_test() -> Result<(), String> {
+    let mut rng = rand::thread_rng();
+    let mut body = vec![0; DENSE_LEN - HEADER_LEN];
+    let values: Vec<u8> = (0..REGISTERS)
+        .map(|_| rng.gen_range(0..=REGISTER_MAX))
+        .collect();
+    for (i, &value) in values.iter().enumerate() {
+        dense_set(&mut body, i, value);
+    }
+    if let Some(i) = (0..REGISTERS).find(|&i| dense_get(&body, i) != values[i]) {
+        return Err(format!("TESTFAILED Register error at {i}"));
+    }
+
+    // The sparse and dense encodings must count the same, within the standard error
+    let max_error = 5.0 * 1.04 / (REGISTERS as f64).sqrt();
+    let mut hll = HyperLogLog::new();
+    let seed: u64 = rng.gen();
+    for added in 1..=100_000u64 {
+        hll.add(&(seed ^ added).to_le_bytes());
+        if !added.is_power_of_two() && added % 10_000 != 0 {
+            continue;
+        }
+        let count = hll.count();
+        let dense = HyperLogLog::parse(&hll.clone().to_bytes(0))?.count();
+        let sparse = HyperLogLog::parse(&hll.clone().to_bytes(usize::MAX))?.count();
+        if count != dense || count != sparse {
+            return Err(format!(
+                "TESTFAILED sparse and dense counts differ: {sparse} vs {dense}"
+            ));
+        }
+        let error = (count as f64 - added as f64).abs() / added as f64;
+        if error > max_error {
+            return Err(format!(
+                "TESTFAILED Too big error. card:{added} abserr:{error}"
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyperloglog_encodings() {
+        let mut hll = HyperLogLog::new();
+        assert_eq!(&hll.to_bytes(3000)[HEADER_LEN..], [0x7f, 0xff]);
+        assert_eq!(hll.count(), 0);
+
+        for i in 0..1000 {
+            hll.add(format!("element:{i}").as_bytes());
+        }
+        assert!(!hll.add(b"element:0"));
+        let count = hll.count();
+        assert!((950..=1050).contains(&count), "{count}");
+
+        let sparse = hll.to_bytes(3000);
+        assert_eq!(hll.encoding(), Encoding::Sparse);
+        assert_eq!(HyperLogLog::cached_count(&sparse), Ok(Some(count)));
+        let ops = sparse_ops(&sparse).unwrap();
+        assert_eq!(ops.iter().map(SparseOp::len).sum::<usize>(), REGISTERS);
+
+        let dense = hll.to_bytes(100);
+        assert_eq!(hll.encoding(), Encoding::Dense);
+        assert_eq!(dense.len(), DENSE_LEN);
+        for bytes in [&sparse, &dense] {
+            let mut decoded = HyperLogLog::parse(bytes).unwrap();
+            assert_eq!(decoded.registers(), hll.registers());
+            decoded.invalidate();
+            assert_eq!(decoded.count(), count);
+        }
+
+        assert_eq!(HyperLogLog::parse(b"HYLL").err(), Some(INVALID.to_string()));
+        assert_eq!(
+            HyperLogLog::parse(&sparse[..sparse.len() - 1]).err(),
+            Some(CORRUPTED.to_string())
+        );
+    }
+
+    #[test]
+    fn test_dense_registers() {
+        let mut body = vec![0; DENSE_LEN - HEADER_LEN];
+        for i in 0..REGISTERS {
+            dense_set(&mut body, i, (i % 64) as u8);
+        }
+        for i in 0..REGISTERS {
+            assert_eq!(dense_get(&body, i), (i % 64) as u8);
+        }
+    }
+
+    #[test]
+    fn test_dense_register_over_q() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[Encoding::Dense as u8, 0, 0, 0]);
+        // A stale cardinality, so that it's estimated
+        bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0x80]);
+        bytes.resize(DENSE_LEN, 0);
+        dense_set(&mut bytes[HEADER_LEN..], 0, REGISTER_MAX);
+
+        let mut hll = HyperLogLog::parse(&bytes).unwrap();
+        assert_eq!(hll.registers()[0], REGISTER_MAX);
+        assert_eq!(hll.count(), 1);
+    }
+}
@@ -0,0 +1,169 @@
+/// A sorted set of integers, laid out like Redis' intset.
+///
+/// The integers are kept in a sorted array of the narrowest width that fits all
+/// of them, so a set of small integers takes 2 bytes per element. Adding an
+/// integer that doesn't fit upgrades the whole array to a wider width, which is
+/// never undone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntSet {
+    contents: Contents,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Contents {
+    I16(Vec<i16>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+}
+
+impl Default for IntSet {
+    fn default() -> Self {
+        Self {
+            contents: Contents::I16(Vec::new()),
+        }
+    }
+}
+
+impl IntSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.contents {
+            Contents::I16(values) => values.len(),
+            Contents::I32(values) => values.len(),
+            Contents::I64(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The size of the integers, in bytes
+    pub fn bytes(&self) -> usize {
+        match &self.contents {
+            Contents::I16(values) => values.len() * 2,
+            Contents::I32(values) => values.len() * 4,
+            Contents::I64(values) => values.len() * 8,
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.search(value).is_some_and(|found| found.is_ok())
+    }
+
+    /// Returns `true` if the value is new.
+    pub fn insert(&mut self, value: i64) -> bool {
+        let fits = match &self.contents {
+            Contents::I16(_) => i16::try_from(value).is_ok(),
+            Contents::I32(_) => i32::try_from(value).is_ok(),
+            Contents::I64(_) => true,
+        };
+        if !fits {
+            // A value too wide for the others is either the smallest or the largest
+            self.upgrade(value);
+            return true;
+        }
+
+        let Some(Err(index)) = self.search(value) else {
+            return false;
+        };
+        match &mut self.contents {
+            Contents::I16(values) => values.insert(index, value as i16),
+            Contents::I32(values) => values.insert(index, value as i32),
+            Contents::I64(values) => values.insert(index, value),
+        }
+        true
+    }
+
+    /// Returns `true` if the value was in the set.
+    pub fn remove(&mut self, value: i64) -> bool {
+        let Some(Ok(index)) = self.search(value) else {
+            return false;
+        };
+        match &mut self.contents {
+            Contents::I16(values) => _ = values.remove(index),
+            Contents::I32(values) => _ = values.remove(index),
+            Contents::I64(values) => _ = values.remove(index),
+        }
+        true
+    }
+
+    pub fn get(&self, index: usize) -> Option<i64> {
+        match &self.contents {
+            Contents::I16(values) => values.get(index).map(|v| *v as i64),
+            Contents::I32(values) => values.get(index).map(|v| *v as i64),
+            Contents::I64(values) => values.get(index).copied(),
+        }
+    }
+
+    /// Iterates over the integers, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        (0..self.len()).map(|index| self.get(index).expect("index in bounds"))
+    }
+
+    /// Looks the value up like `binary_search`, or returns `None` if it's too wide
+    /// to be in the set at all.
+    fn search(&self, value: i64) -> Option<Result<usize, usize>> {
+        Some(match &self.contents {
+            Contents::I16(values) => values.binary_search(&i16::try_from(value).ok()?),
+            Contents::I32(values) => values.binary_search(&i32::try_from(value).ok()?),
+            Contents::I64(values) => values.binary_search(&value),
+        })
+    }
+
+    /// Widens the integers to fit `value`, and adds it at either end
+    fn upgrade(&mut self, value: i64) {
+        let mut values: Vec<i64> = self.iter().collect();
+        match value < 0 {
+            true => values.insert(0, value),
+            false => values.push(value),
+        }
+        self.contents = match i32::try_from(value) {
+            Ok(_) => Contents::I32(values.into_iter().map(|v| v as i32).collect()),
+            Err(_) => Contents::I64(values),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_keeps_integers_sorted() {
+        let mut set = IntSet::new();
+        for value in [5, -3, 10, 5, 0] {
+            set.insert(value);
+        }
+        assert_eq!(set.iter().collect::<Vec<_>>(), [-3, 0, 5, 10]);
+        assert_eq!(set.bytes(), 8);
+        assert!(set.contains(10));
+        assert!(!set.contains(11));
+        assert!(!set.contains(i64::MAX));
+
+        assert!(set.remove(0));
+        assert!(!set.remove(0));
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_insert_upgrades_the_width() {
+        let mut set = IntSet::new();
+        set.insert(1);
+        assert!(set.insert(70_000));
+        assert!(matches!(set.contents, Contents::I32(_)));
+        assert!(set.insert(-5_000_000_000));
+        assert!(matches!(set.contents, Contents::I64(_)));
+        assert!(!set.insert(70_000));
+
+        assert_eq!(set.iter().collect::<Vec<_>>(), [-5_000_000_000, 1, 70_000]);
+        assert_eq!(set.bytes(), 24);
+
+        // Removing the wide integers doesn't narrow the set back
+        set.remove(-5_000_000_000);
+        assert!(matches!(set.contents, Contents::I64(_)));
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::VecDeque;
+
+use bytes::Bytes;
+
+use super::listpack::ListPack;
+
+/// The most bytes a node holds before a new one is started, like Redis'
+/// default `list-max-listpack-size -2`
+const NODE_MAX_BYTES: usize = 8 * 1024;
+
+/// Either end of a list, `LEFT` being the head and `RIGHT` the tail
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ListEnd {
+    Left,
+    Right,
+}
+
+/// A list of byte strings, laid out like Redis' quicklist.
+///
+/// Elements are packed into [`ListPack`] nodes of up to [`NODE_MAX_BYTES`], which
+/// keeps the per element overhead low, while pushing and popping at either end
+/// stays O(1) and only the nodes on the way are walked to reach an index.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct List {
+    /// Never holds an empty node
+    nodes: VecDeque<ListPack>,
+    len: usize,
+    /// The encoded size of every node, kept up to date to report memory usage cheaply
+    bytes: usize,
+}
+
+impl List {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The name of the encoding, as reported by `OBJECT ENCODING`.
+    ///
+    /// Like Redis, a list that fits in a single node is a `listpack`.
+    pub fn encoding(&self) -> &'static str {
+        match self.nodes.len() {
+            0 | 1 => "listpack",
+            _ => "quicklist",
+        }
+    }
+
+    /// Estimated memory used by the list, in bytes
+    pub fn memory_usage(&self) -> usize {
+        self.bytes + self.nodes.len() * std::mem::size_of::<ListPack>()
+    }
+
+    pub fn push_back(&mut self, value: &[u8]) {
+        let fits =
+            |node: &ListPack| node.bytes() + ListPack::entry_size(value.len()) <= NODE_MAX_BYTES;
+        match self.nodes.back_mut() {
+            Some(node) if fits(node) => node.push_back(value),
+            _ => {
+                let mut node = ListPack::new();
+                node.push_back(value);
+                self.nodes.push_back(node);
+            }
+        }
+        self.len += 1;
+        self.bytes += ListPack::entry_size(value.len());
+    }
+
+    pub fn push_front(&mut self, value: &[u8]) {
+        let fits =
+            |node: &ListPack| node.bytes() + ListPack::entry_size(value.len()) <= NODE_MAX_BYTES;
+        match self.nodes.front_mut() {
+            Some(node) if fits(node) => node.push_front(value),
+            _ => {
+                let mut node = ListPack::new();
+                node.push_back(value);
+                self.nodes.push_front(node);
+            }
+        }
+        self.len += 1;
+        self.bytes += ListPack::entry_size(value.len());
+    }
+
+    pub fn pop_front(&mut self) -> Option<Bytes> {
+        let node = self.nodes.front_mut()?;
+        let value = node.pop_front()?;
+        if node.is_empty() {
+            self.nodes.pop_front();
+        }
+        self.removed(&value);
+        Some(value)
+    }
+
+    pub fn pop_back(&mut self) -> Option<Bytes> {
+        let node = self.nodes.back_mut()?;
+        let value = node.pop_back()?;
+        if node.is_empty() {
+            self.nodes.pop_back();
+        }
+        self.removed(&value);
+        Some(value)
+    }
+
+    pub fn push(&mut self, end: ListEnd, value: &[u8]) {
+        match end {
+            ListEnd::Left => self.push_front(value),
+            ListEnd::Right => self.push_back(value),
+        }
+    }
+
+    pub fn pop(&mut self, end: ListEnd) -> Option<Bytes> {
+        match end {
+            ListEnd::Left => self.pop_front(),
+            ListEnd::Right => self.pop_back(),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let (node, index) = self.locate(index)?;
+        self.nodes[node].get(index)
+    }
+
+    /// Replaces the element at `index`, returning `false` if it's out of range.
+    pub fn set(&mut self, index: usize, value: &[u8]) -> bool {
+        let Some((node, index)) = self.locate(index) else {
+            return false;
+        };
+        self.update_node(node, |node| node.replace(index, value));
+        true
+    }
+
+    /// Inserts the value before `index`, or at the end if `index` is the length.
+    pub fn insert(&mut self, index: usize, value: &[u8]) {
+        if index == self.len {
+            return self.push_back(value);
+        }
+        let (node, index) = self.locate(index).expect("index out of bounds");
+        self.update_node(node, |node| node.insert(index, value));
+        self.len += 1;
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Bytes> {
+        let (node, index) = self.locate(index)?;
+        let value = self.update_node(node, |node| node.remove(index))?;
+        if self.nodes[node].is_empty() {
+            self.nodes.remove(node);
+        }
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Removes up to `limit` elements equal to `value`, from the tail if `from_back`.
+    ///
+    /// Returns how many elements were removed.
+    pub fn remove_matching(&mut self, value: &[u8], limit: usize, from_back: bool) -> usize {
+        let mut removed = 0;
+        let nodes: Vec<usize> = match from_back {
+            true => (0..self.nodes.len()).rev().collect(),
+            false => (0..self.nodes.len()).collect(),
+        };
+        for node in nodes {
+            if removed == limit {
+                break;
+            }
+            removed += self.update_node(node, |node| {
+                node.remove_matching(value, limit - removed, from_back)
+            });
+        }
+        self.nodes.retain(|node| !node.is_empty());
+        self.len -= removed;
+        removed
+    }
+
+    /// Keeps only the elements in `start..end`.
+    pub fn trim(&mut self, start: usize, end: usize) {
+        let end = end.min(self.len);
+        if start >= end {
+            *self = List::new();
+            return;
+        }
+
+        let mut to_drop = self.len - end;
+        while to_drop > 0 {
+            let node = self.nodes.back_mut().unwrap();
+            if node.len() <= to_drop {
+                to_drop -= node.len();
+                self.bytes -= node.bytes();
+                self.nodes.pop_back();
+            } else {
+                let keep = node.len() - to_drop;
+                self.update_node(self.nodes.len() - 1, |node| {
+                    node.remove_range(keep, usize::MAX)
+                });
+                to_drop = 0;
+            }
+        }
+
+        let mut to_drop = start;
+        while to_drop > 0 {
+            let node = self.nodes.front_mut().unwrap();
+            if node.len() <= to_drop {
+                to_drop -= node.len();
+                self.bytes -= node.bytes();
+                self.nodes.pop_front();
+            } else {
+                self.update_node(0, |node| node.remove_range(0, to_drop));
+                to_drop = 0;
+            }
+        }
+
+        self.len = end - start;
+    }
+
+    /// Iterates over the elements in `start..end`.
+    pub fn range(&self, start: usize, end: usize) -> impl Iterator<Item = &[u8]> {
+        let end = end.min(self.len);
+        let (node, index) = self.locate(start).unwrap_or((self.nodes.len(), 0));
+        self.nodes
+            .range(node..)
+            .flat_map(ListPack::iter)
+            .skip(index)
+            .take(end.saturating_sub(start))
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &[u8]> {
+        self.nodes.iter().flat_map(ListPack::iter)
+    }
+
+    /// Finds the node holding `index`, walking from the nearest end.
+    ///
+    /// Returns the index of the node and the index of the element in it.
+    fn locate(&self, index: usize) -> Option<(usize, usize)> {
+        if index >= self.len {
+            return None;
+        }
+
+        if index < self.len / 2 {
+            let mut index = index;
+            for (i, node) in self.nodes.iter().enumerate() {
+                if index < node.len() {
+                    return Some((i, index));
+                }
+                index -= node.len();
+            }
+        } else {
+            let mut from_back = self.len - index;
+            for (i, node) in self.nodes.iter().enumerate().rev() {
+                if from_back <= node.len() {
+                    return Some((i, node.len() - from_back));
+                }
+                from_back -= node.len();
+            }
+        }
+        unreachable!("the length of the list is out of sync with its nodes")
+    }
+
+    /// Changes a node, keeping the size of the list up to date and the node within bounds.
+    fn update_node<R>(&mut self, node: usize, f: impl FnOnce(&mut ListPack) -> R) -> R {
+        let before = self.nodes[node].bytes();
+        let result = f(&mut self.nodes[node]);
+        let after = self.nodes[node].bytes();
+        self.bytes = self.bytes + after - before;
+
+        if after > NODE_MAX_BYTES && self.nodes[node].len() > 1 {
+            let half = self.nodes[node].len() / 2;
+            let tail = self.nodes[node].split_off(half);
+            self.nodes.insert(node + 1, tail);
+        }
+        result
+    }
+
+    fn removed(&mut self, value: &[u8]) {
+        self.len -= 1;
+        self.bytes -= ListPack::entry_size(value.len());
+    }
+}
+
+impl<T: AsRef<[u8]>> FromIterator<T> for List {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        for value in iter {
+            list.push_back(value.as_ref());
+        }
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(list: &List) -> Vec<String> {
+        list.iter()
+            .map(|value| String::from_utf8_lossy(value).to_string())
+            .collect()
+    }
+
+    /// A list spanning many nodes, with the elements `0..len`
+    fn numbers(len: usize) -> List {
+        (0..len).map(|i| format!("{i:0>500}")).collect()
+    }
+
+    fn number(i: usize) -> Vec<u8> {
+        format!("{i:0>500}").into_bytes()
+    }
+
+    #[test]
+    fn test_elements_are_split_across_nodes() {
+        let list = numbers(100);
+        assert!(list.nodes.len() > 5);
+        assert!(list.nodes.iter().all(|node| node.bytes() <= NODE_MAX_BYTES));
+        assert_eq!(list.len(), 100);
+        for i in [0, 1, 15, 16, 50, 98, 99] {
+            assert_eq!(list.get(i), Some(&number(i)[..]));
+        }
+        assert_eq!(list.get(100), None);
+    }
+
+    #[test]
+    fn test_push_and_pop() {
+        let mut list = List::new();
+        list.push_back(b"b");
+        list.push_front(b"a");
+        list.push_back(b"c");
+
+        assert_eq!(values(&list), ["a", "b", "c"]);
+        assert_eq!(list.pop_front(), Some(Bytes::from("a")));
+        assert_eq!(list.pop_back(), Some(Bytes::from("c")));
+        assert_eq!(list.pop_back(), Some(Bytes::from("b")));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty());
+        assert_eq!(list.memory_usage(), 0);
+    }
+
+    #[test]
+    fn test_insert_set_and_remove_keep_nodes_small() {
+        let mut list = numbers(40);
+        for _ in 0..40 {
+            list.insert(20, &number(1000));
+        }
+        list.set(0, &number(2000));
+        assert!(list.nodes.iter().all(|node| node.bytes() <= NODE_MAX_BYTES));
+        assert_eq!(list.len(), 80);
+        assert_eq!(list.get(0), Some(&number(2000)[..]));
+        assert_eq!(list.get(20), Some(&number(1000)[..]));
+        assert_eq!(list.get(60), Some(&number(20)[..]));
+
+        assert_eq!(list.remove(60), Some(Bytes::from(number(20))));
+        assert_eq!(list.len(), 79);
+        assert_eq!(list.remove_matching(&number(1000), 10, true), 10);
+        assert_eq!(list.remove_matching(&number(1000), usize::MAX, false), 30);
+        assert_eq!(list.len(), 39);
+        assert_eq!(list.get(20), Some(&number(21)[..]));
+    }
+
+    #[test]
+    fn test_remove_drops_empty_nodes() {
+        let mut list = numbers(40);
+        assert!(list.nodes.len() > 1);
+        for i in 0..39 {
+            assert_eq!(list.remove(0), Some(Bytes::from(number(i))));
+            assert!(list.nodes.iter().all(|node| !node.is_empty()));
+        }
+        assert_eq!(list.pop_front(), Some(Bytes::from(number(39))));
+        assert!(list.is_empty());
+        assert!(list.nodes.is_empty());
+        assert_eq!(list.memory_usage(), 0);
+    }
+
+    #[test]
+    fn test_trim_and_range() {
+        let mut list = numbers(100);
+        list.trim(10, 95);
+        assert_eq!(list.len(), 85);
+        assert_eq!(list.get(0), Some(&number(10)[..]));
+        assert_eq!(list.get(84), Some(&number(94)[..]));
+
+        let range: Vec<&[u8]> = list.range(40, 43).collect();
+        assert_eq!(range, [&number(50)[..], &number(51)[..], &number(52)[..]]);
+        assert_eq!(list.range(80, 200).count(), 5);
+
+        let bytes: usize = list.nodes.iter().map(ListPack::bytes).sum();
+        assert_eq!(list.bytes, bytes);
+
+        list.trim(5, 5);
+        assert!(list.is_empty());
+    }
+}
@@ -0,0 +1,412 @@
+use bytes::Bytes;
+
+/// The most a collection can hold before it's converted from a [`ListPack`] to a
+/// faster encoding, like Redis' `*-max-listpack-entries` and `*-max-listpack-value`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListPackLimits {
+    /// The most elements
+    pub max_entries: usize,
+    /// The longest element, in bytes
+    pub max_value: usize,
+}
+
+/// A compact list of byte strings in a single allocation, like Redis' listpack.
+///
+/// Every entry is laid out as `<len><bytes><backlen>`, where `len` is the length
+/// of `bytes` as a varint, and `backlen` is the size of `<len><bytes>` as a varint
+/// written backwards, so entries can be walked from either end. Small collections
+/// are kept in one of these to save the overhead of an allocation per element.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListPack {
+    buf: Vec<u8>,
+    len: usize,
+}
+
+impl ListPack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The size of the encoded entries, in bytes
+    pub fn bytes(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The size an entry of `len` bytes takes once encoded
+    pub fn entry_size(len: usize) -> usize {
+        let size = varint_len(len) + len;
+        size + varint_len(size)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        if index >= self.len {
+            return None;
+        }
+        let (value, _) = self.entry_at(self.offset_of(index));
+        Some(value)
+    }
+
+    pub fn push_back(&mut self, value: &[u8]) {
+        encode_entry(&mut self.buf, value);
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: &[u8]) {
+        self.insert(0, value);
+    }
+
+    /// Inserts the value before `index`, or at the end if `index` is the length.
+    pub fn insert(&mut self, index: usize, value: &[u8]) {
+        assert!(index <= self.len, "index out of bounds");
+        let offset = self.offset_of(index);
+        let mut entry = Vec::with_capacity(Self::entry_size(value.len()));
+        encode_entry(&mut entry, value);
+        self.buf.splice(offset..offset, entry);
+        self.len += 1;
+    }
+
+    pub fn replace(&mut self, index: usize, value: &[u8]) {
+        assert!(index < self.len, "index out of bounds");
+        let offset = self.offset_of(index);
+        let (_, next) = self.entry_at(offset);
+        let mut entry = Vec::with_capacity(Self::entry_size(value.len()));
+        encode_entry(&mut entry, value);
+        self.buf.splice(offset..next, entry);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Bytes> {
+        if index >= self.len {
+            return None;
+        }
+        let offset = self.offset_of(index);
+        let (value, next) = self.entry_at(offset);
+        let value = Bytes::copy_from_slice(value);
+        self.buf.drain(offset..next);
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Removes `count` entries starting at `index`.
+    pub fn remove_range(&mut self, index: usize, count: usize) {
+        let count = count.min(self.len.saturating_sub(index));
+        if count == 0 {
+            return;
+        }
+        let start = self.offset_of(index);
+        let end = (0..count).fold(start, |offset, _| self.entry_at(offset).1);
+        self.buf.drain(start..end);
+        self.len -= count;
+    }
+
+    pub fn pop_front(&mut self) -> Option<Bytes> {
+        self.remove(0)
+    }
+
+    pub fn pop_back(&mut self) -> Option<Bytes> {
+        let end = self.buf.len();
+        if end == 0 {
+            return None;
+        }
+        let start = self.prev_offset(end);
+        let (value, _) = self.entry_at(start);
+        let value = Bytes::copy_from_slice(value);
+        self.buf.truncate(start);
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Removes up to `limit` entries equal to `value`, from the back if `from_back`.
+    ///
+    /// Returns how many entries were removed.
+    pub fn remove_matching(&mut self, value: &[u8], limit: usize, from_back: bool) -> usize {
+        let mut removed = 0;
+
+        if from_back {
+            let mut end = self.buf.len();
+            while end > 0 && removed < limit {
+                let start = self.prev_offset(end);
+                if self.entry_at(start).0 == value {
+                    self.buf.drain(start..end);
+                    removed += 1;
+                }
+                end = start;
+            }
+        } else {
+            let mut offset = 0;
+            while offset < self.buf.len() && removed < limit {
+                let (entry, next) = self.entry_at(offset);
+                if entry == value {
+                    self.buf.drain(offset..next);
+                    removed += 1;
+                } else {
+                    offset = next;
+                }
+            }
+        }
+
+        self.len -= removed;
+        removed
+    }
+
+    /// Splits the list in two at `index`, returning the entries from `index` on.
+    pub fn split_off(&mut self, index: usize) -> ListPack {
+        assert!(index <= self.len, "index out of bounds");
+        let offset = self.offset_of(index);
+        let tail = ListPack {
+            buf: self.buf.split_off(offset),
+            len: self.len - index,
+        };
+        self.len = index;
+        tail
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            buf: &self.buf,
+            front: 0,
+            back: self.buf.len(),
+            remaining: self.len,
+        }
+    }
+
+    /// The offset of the entry at `index`, walking from the nearest end.
+    fn offset_of(&self, index: usize) -> usize {
+        if index <= self.len / 2 {
+            (0..index).fold(0, |offset, _| self.entry_at(offset).1)
+        } else {
+            (index..self.len).fold(self.buf.len(), |end, _| self.prev_offset(end))
+        }
+    }
+
+    /// Decodes the entry starting at `offset`, returning its value and the offset of the next entry.
+    fn entry_at(&self, offset: usize) -> (&[u8], usize) {
+        entry_at(&self.buf, offset)
+    }
+
+    /// The offset of the entry ending at `end`.
+    fn prev_offset(&self, end: usize) -> usize {
+        prev_offset(&self.buf, end)
+    }
+}
+
+impl<'a> IntoIterator for &'a ListPack {
+    type Item = &'a [u8];
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: AsRef<[u8]>> FromIterator<T> for ListPack {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = ListPack::new();
+        for value in iter {
+            list.push_back(value.as_ref());
+        }
+        list
+    }
+}
+
+pub struct Iter<'a> {
+    buf: &'a [u8],
+    front: usize,
+    back: usize,
+    remaining: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (value, next) = entry_at(self.buf, self.front);
+        self.front = next;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let start = prev_offset(self.buf, self.back);
+        let (value, _) = entry_at(self.buf, start);
+        self.back = start;
+        self.remaining -= 1;
+        Some(value)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+fn varint_len(mut n: usize) -> usize {
+    let mut len = 1;
+    while n >= 0x80 {
+        n >>= 7;
+        len += 1;
+    }
+    len
+}
+
+fn encode_entry(buf: &mut Vec<u8>, value: &[u8]) {
+    let mut len = value.len();
+    while len >= 0x80 {
+        buf.push((len & 0x7f) as u8 | 0x80);
+        len >>= 7;
+    }
+    buf.push(len as u8);
+    buf.extend_from_slice(value);
+
+    // The backlen is read from the right, so its last byte holds the lowest bits,
+    // and every byte but the leftmost one says there's more to the left
+    let size = varint_len(value.len()) + value.len();
+    let groups = varint_len(size);
+    for group in (0..groups).rev() {
+        let byte = ((size >> (7 * group)) & 0x7f) as u8;
+        buf.push(if group + 1 < groups {
+            byte | 0x80
+        } else {
+            byte
+        });
+    }
+}
+
+fn entry_at(buf: &[u8], offset: usize) -> (&[u8], usize) {
+    let mut len = 0;
+    let mut shift = 0;
+    let mut i = offset;
+    loop {
+        let byte = buf[i];
+        len |= ((byte & 0x7f) as usize) << shift;
+        i += 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+
+    let value = &buf[i..i + len];
+    let size = i - offset + len;
+    (value, offset + size + varint_len(size))
+}
+
+fn prev_offset(buf: &[u8], end: usize) -> usize {
+    let mut i = end - 1;
+    let mut size = (buf[i] & 0x7f) as usize;
+    let mut shift = 7;
+    while buf[i] & 0x80 != 0 {
+        i -= 1;
+        size |= ((buf[i] & 0x7f) as usize) << shift;
+        shift += 7;
+    }
+    i - size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(list: &ListPack) -> Vec<Vec<u8>> {
+        list.iter().map(<[u8]>::to_vec).collect()
+    }
+
+    #[test]
+    fn test_push_and_pop_from_both_ends() {
+        let mut list = ListPack::new();
+        list.push_back(b"b");
+        list.push_front(b"a");
+        list.push_back(b"c");
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(
+            values(&list),
+            vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]
+        );
+        assert_eq!(list.pop_back(), Some(Bytes::from("c")));
+        assert_eq!(list.pop_front(), Some(Bytes::from("a")));
+        assert_eq!(list.pop_front(), Some(Bytes::from("b")));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.bytes(), 0);
+    }
+
+    #[test]
+    fn test_entries_of_every_size_round_trip() {
+        // Sizes around the varint boundaries of both the length and the backlen
+        let sizes = [
+            0, 1, 125, 126, 127, 128, 129, 16_380, 16_383, 16_384, 70_000,
+        ];
+        let entries: Vec<Vec<u8>> = sizes.iter().map(|&size| vec![b'x'; size]).collect();
+        let list: ListPack = entries.iter().collect();
+
+        assert_eq!(values(&list), entries);
+        let reversed: Vec<Vec<u8>> = list.iter().rev().map(<[u8]>::to_vec).collect();
+        assert_eq!(reversed, entries.iter().rev().cloned().collect::<Vec<_>>());
+        for (size, entry) in sizes.iter().zip(&entries) {
+            assert_eq!(ListPack::entry_size(*size), {
+                let mut buf = Vec::new();
+                encode_entry(&mut buf, entry);
+                buf.len()
+            });
+        }
+    }
+
+    #[test]
+    fn test_insert_replace_and_remove() {
+        let mut list: ListPack = ["a", "b", "c", "d"].into_iter().collect();
+        list.insert(2, b"x");
+        list.replace(0, b"long value");
+        assert_eq!(list.remove(3), Some(Bytes::from("c")));
+        assert_eq!(list.get(3), Some(&b"d"[..]));
+        assert_eq!(list.get(4), None);
+        assert_eq!(
+            values(&list),
+            vec![
+                b"long value".to_vec(),
+                b"b".to_vec(),
+                b"x".to_vec(),
+                b"d".to_vec()
+            ]
+        );
+
+        list.remove_range(1, 2);
+        assert_eq!(values(&list), vec![b"long value".to_vec(), b"d".to_vec()]);
+    }
+
+    #[test]
+    fn test_remove_matching() {
+        let mut list: ListPack = ["a", "b", "a", "c", "a"].into_iter().collect();
+        assert_eq!(list.remove_matching(b"a", 1, true), 1);
+        assert_eq!(
+            values(&list),
+            vec![b"a".to_vec(), b"b".to_vec(), b"a".to_vec(), b"c".to_vec()]
+        );
+        assert_eq!(list.remove_matching(b"a", usize::MAX, false), 2);
+        assert_eq!(values(&list), vec![b"b".to_vec(), b"c".to_vec()]);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut list: ListPack = ["a", "b", "c"].into_iter().collect();
+        let tail = list.split_off(1);
+        assert_eq!(values(&list), vec![b"a".to_vec()]);
+        assert_eq!(values(&tail), vec![b"b".to_vec(), b"c".to_vec()]);
+    }
+}
@@ -0,0 +1,13 @@
+pub mod bitmap;
+pub mod consumer_group;
+pub mod geohash;
+pub mod hash;
+pub mod hyperloglog;
+pub mod intset;
+pub mod list;
+pub mod listpack;
+pub mod scan;
+pub mod set;
+pub mod skiplist;
+pub mod stream;
+pub mod zset;
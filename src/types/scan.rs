@@ -0,0 +1,96 @@
+/// The hasher cursors are computed with, seeded the same across restarts so
+/// cursors stay meaningful
+const CURSOR_HASHER: ahash::RandomState = ahash::RandomState::with_seeds(
+    0x243f_6a88_85a3_08d3,
+    0x1319_8a2e_0370_7344,
+    0xa409_3822_299f_31d0,
+    0x082e_fa98_ec4e_6c89,
+);
+
+/// The position of an element in the order collections are scanned in
+pub fn cursor_hash(element: &[u8]) -> u64 {
+    CURSOR_HASHER.hash_one(element)
+}
+
+/// Picks the elements from `cursor` on in scan order, up to about `count` of them.
+///
+/// Elements are ordered by their [`cursor_hash`], so unlike an index, a cursor
+/// keeps its place when the collection changes between calls. Like Redis, an
+/// element present during the whole scan is returned at least once.
+///
+/// Returns the next cursor, `0` once the scan is complete.
+pub fn scan<T>(
+    elements: impl Iterator<Item = (u64, T)>,
+    cursor: u64,
+    count: usize,
+) -> (u64, Vec<T>) {
+    let mut elements: Vec<(u64, T)> = elements.filter(|(hash, _)| *hash >= cursor).collect();
+    if elements.len() <= count {
+        return (
+            0,
+            elements.into_iter().map(|(_, element)| element).collect(),
+        );
+    }
+
+    elements.sort_unstable_by_key(|(hash, _)| *hash);
+    // Elements with the same hash are returned together, so none is skipped
+    let last_hash = elements[count.max(1) - 1].0;
+    let end = elements.partition_point(|(hash, _)| *hash <= last_hash);
+    let next_cursor = if end == elements.len() {
+        0
+    } else {
+        last_hash + 1
+    };
+    elements.truncate(end);
+    (
+        next_cursor,
+        elements.into_iter().map(|(_, element)| element).collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_returns_everything_once() {
+        let elements: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let mut cursor = 0;
+        let mut scanned = Vec::new();
+        loop {
+            let hashed = elements.iter().map(|e| (cursor_hash(e.as_bytes()), e));
+            let (next_cursor, batch) = scan(hashed, cursor, 7);
+            assert!(batch.len() <= 7);
+            scanned.extend(batch);
+            cursor = next_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
+        scanned.sort();
+        scanned.dedup();
+        assert_eq!(scanned.len(), 100);
+    }
+
+    #[test]
+    fn test_scan_keeps_its_place_when_elements_are_added() {
+        let mut elements: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+        let hashed = |elements: &[String]| {
+            elements
+                .iter()
+                .map(|e| (cursor_hash(e.as_bytes()), e.clone()))
+                .collect::<Vec<_>>()
+        };
+        let (cursor, first) = scan(hashed(&elements).into_iter(), 0, 10);
+
+        elements.extend((20..1000).map(|i| i.to_string()));
+        let mut cursor = cursor;
+        let mut scanned = first;
+        while cursor != 0 {
+            let (next_cursor, batch) = scan(hashed(&elements).into_iter(), cursor, 10);
+            scanned.extend(batch);
+            cursor = next_cursor;
+        }
+        assert!((0..20).all(|i| scanned.contains(&i.to_string())));
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::HashSet;
+
+use bytes::Bytes;
+use rand::{seq::IteratorRandom, Rng};
+
+use super::intset::IntSet;
+
+/// Memory used by a member of a hash set besides the member itself, in bytes
+const TABLE_ENTRY_OVERHEAD: usize = std::mem::size_of::<Bytes>() + 8;
+
+/// A set of byte strings, encoded like Redis sets.
+///
+/// Small sets of integers are kept in an [`IntSet`], which is compact and
+/// sorted. Once a set holds anything else, or grows past its limit, it's
+/// converted to a hash set for good.
+#[derive(Debug, Clone)]
+pub struct Set {
+    encoding: Encoding,
+}
+
+#[derive(Debug, Clone)]
+enum Encoding {
+    IntSet(IntSet),
+    Table {
+        set: HashSet<Bytes, ahash::RandomState>,
+        /// The size of every member, kept up to date to report memory usage cheaply
+        bytes: usize,
+    },
+}
+
+impl Default for Set {
+    fn default() -> Self {
+        Self {
+            encoding: Encoding::IntSet(IntSet::new()),
+        }
+    }
+}
+
+impl Set {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.encoding {
+            Encoding::IntSet(intset) => intset.len(),
+            Encoding::Table { set, .. } => set.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The name of the encoding, as reported by `OBJECT ENCODING`
+    pub fn encoding(&self) -> &'static str {
+        match &self.encoding {
+            Encoding::IntSet(_) => "intset",
+            Encoding::Table { .. } => "hashtable",
+        }
+    }
+
+    /// Estimated memory used by the set, in bytes
+    pub fn memory_usage(&self) -> usize {
+        match &self.encoding {
+            Encoding::IntSet(intset) => intset.bytes(),
+            Encoding::Table { set, bytes } => bytes + set.capacity() * TABLE_ENTRY_OVERHEAD,
+        }
+    }
+
+    pub fn contains(&self, member: &[u8]) -> bool {
+        match &self.encoding {
+            Encoding::IntSet(intset) => as_int(member).is_some_and(|value| intset.contains(value)),
+            Encoding::Table { set, .. } => set.contains(member),
+        }
+    }
+
+    /// Adds a member, converting the set to a hash set if it isn't an integer or
+    /// the set would hold more than `max_intset_entries`.
+    ///
+    /// Returns `true` if the member is new.
+    pub fn insert(&mut self, member: &[u8], max_intset_entries: usize) -> bool {
+        if let Encoding::IntSet(intset) = &mut self.encoding {
+            if let Some(value) = as_int(member) {
+                if intset.contains(value) {
+                    return false;
+                }
+                if intset.len() < max_intset_entries {
+                    return intset.insert(value);
+                }
+            }
+            self.convert_to_table();
+        }
+
+        let Encoding::Table { set, bytes } = &mut self.encoding else {
+            unreachable!("the set was just converted to a table");
+        };
+        let added = set.insert(Bytes::copy_from_slice(member));
+        if added {
+            *bytes += member.len();
+        }
+        added
+    }
+
+    /// Returns `true` if the member was in the set.
+    pub fn remove(&mut self, member: &[u8]) -> bool {
+        match &mut self.encoding {
+            Encoding::IntSet(intset) => as_int(member).is_some_and(|value| intset.remove(value)),
+            Encoding::Table { set, bytes } => {
+                let removed = set.remove(member);
+                if removed {
+                    *bytes -= member.len();
+                }
+                removed
+            }
+        }
+    }
+
+    /// Removes a random member.
+    pub fn pop(&mut self, rng: &mut impl Rng) -> Option<Bytes> {
+        let member = self.random(rng)?;
+        self.remove(&member);
+        Some(member)
+    }
+
+    /// Picks a random member.
+    pub fn random(&self, rng: &mut impl Rng) -> Option<Bytes> {
+        match &self.encoding {
+            Encoding::IntSet(intset) if intset.is_empty() => None,
+            Encoding::IntSet(intset) => {
+                let value = intset.get(rng.gen_range(0..intset.len()))?;
+                Some(Bytes::from(value.to_string()))
+            }
+            Encoding::Table { set, .. } => set.iter().choose(rng).cloned(),
+        }
+    }
+
+    /// Iterates over the members, integers being formatted as they were added.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = Bytes> + '_> {
+        match &self.encoding {
+            Encoding::IntSet(intset) => {
+                Box::new(intset.iter().map(|value| Bytes::from(value.to_string())))
+            }
+            Encoding::Table { set, .. } => Box::new(set.iter().cloned()),
+        }
+    }
+
+    /// Whether the set is still an intset, which is small enough to be scanned in one go
+    pub fn is_intset(&self) -> bool {
+        matches!(self.encoding, Encoding::IntSet(_))
+    }
+
+    fn convert_to_table(&mut self) {
+        let Encoding::IntSet(intset) = &self.encoding else {
+            return;
+        };
+        let mut set = HashSet::with_capacity_and_hasher(intset.len(), Default::default());
+        let mut bytes = 0;
+        for value in intset.iter() {
+            let member = Bytes::from(value.to_string());
+            bytes += member.len();
+            set.insert(member);
+        }
+        self.encoding = Encoding::Table { set, bytes };
+    }
+}
+
+impl PartialEq for Set {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|member| other.contains(&member))
+    }
+}
+
+impl<T: AsRef<[u8]>> FromIterator<T> for Set {
+    /// Collects into an intset if every member is an integer, regardless of size
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Set::new();
+        for member in iter {
+            set.insert(member.as_ref(), usize::MAX);
+        }
+        set
+    }
+}
+
+/// The integer a member stands for, if it's written the way Redis would write it,
+/// so the member can be formatted back exactly as it was
+fn as_int(member: &[u8]) -> Option<i64> {
+    if member.len() > 20 {
+        return None;
+    }
+    let value: i64 = std::str::from_utf8(member).ok()?.parse().ok()?;
+    (value.to_string().as_bytes() == member).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers_stay_in_an_intset() {
+        let mut set = Set::new();
+        assert!(set.insert(b"3", 4));
+        assert!(set.insert(b"-1", 4));
+        assert!(!set.insert(b"3", 4));
+        assert_eq!(set.encoding(), "intset");
+        assert!(set.contains(b"-1"));
+        assert!(!set.contains(b"+3"));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [Bytes::from("-1"), Bytes::from("3")]
+        );
+
+        assert!(set.remove(b"3"));
+        assert!(!set.remove(b"a"));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_converts_to_a_table() {
+        // Integers written differently than Redis would aren't integers
+        let mut set = Set::new();
+        set.insert(b"1", 4);
+        set.insert(b"01", 4);
+        assert_eq!(set.encoding(), "hashtable");
+        assert!(set.contains(b"1") && set.contains(b"01"));
+
+        let mut set: Set = ["1", "2", "3", "4"].into_iter().collect();
+        assert!(!set.insert(b"4", 4));
+        assert_eq!(set.encoding(), "intset");
+        assert!(set.insert(b"5", 4));
+        assert_eq!(set.encoding(), "hashtable");
+        assert_eq!(set.len(), 5);
+        assert!(set.memory_usage() >= 5);
+    }
+
+    #[test]
+    fn test_pop_and_random() {
+        let mut rng = rand::thread_rng();
+        let mut set: Set = ["a", "b"].into_iter().collect();
+        let member = set.random(&mut rng).unwrap();
+        assert!(set.contains(&member));
+
+        let popped = [set.pop(&mut rng).unwrap(), set.pop(&mut rng).unwrap()];
+        assert!(popped.contains(&Bytes::from("a")) && popped.contains(&Bytes::from("b")));
+        assert_eq!(set.pop(&mut rng), None);
+        assert_eq!(Set::new().random(&mut rng), None);
+    }
+
+    #[test]
+    fn test_equality_ignores_the_encoding() {
+        let intset: Set = ["1", "2"].into_iter().collect();
+        let mut table: Set = ["2", "1"].into_iter().collect();
+        table.convert_to_table();
+        assert_eq!(intset, table);
+    }
+}
@@ -0,0 +1,423 @@
+use bytes::Bytes;
+use rand::Rng;
+
+/// The most levels a node can have, enough for 2^64 elements with `P = 1/4`
+const MAX_LEVEL: usize = 32;
+
+/// The chance of a node reaching the next level
+const P: f64 = 0.25;
+
+/// The node every level starts from, which holds no element
+const HEAD: usize = 0;
+
+/// Members ordered by score, then lexicographically, like Redis' zskiplist.
+///
+/// Every link of a level skips over a number of nodes, its span, which makes
+/// looking up the rank of an element, or the element at a rank, O(log n) like
+/// looking up an element. Nodes are kept in an arena and linked by index, and
+/// the slots of removed nodes are reused.
+///
+/// The members must be unique, which is up to the caller.
+#[derive(Debug, Clone)]
+pub struct SkipList {
+    nodes: Vec<Node>,
+    /// Slots of removed nodes, reused by the next inserts
+    free: Vec<usize>,
+    tail: Option<usize>,
+    len: usize,
+    /// How many levels are in use
+    level: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    member: Bytes,
+    score: f64,
+    backward: Option<usize>,
+    levels: Vec<Level>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Level {
+    forward: Option<usize>,
+    /// How many nodes the link moves forward by
+    span: usize,
+}
+
+impl Default for SkipList {
+    fn default() -> Self {
+        let head = Node {
+            member: Bytes::new(),
+            score: 0.0,
+            backward: None,
+            levels: vec![
+                Level {
+                    forward: None,
+                    span: 0
+                };
+                MAX_LEVEL
+            ],
+        };
+        Self {
+            nodes: vec![head],
+            free: Vec::new(),
+            tail: None,
+            len: 0,
+            level: 1,
+        }
+    }
+}
+
+impl SkipList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Memory used by the nodes besides their members, in bytes
+    pub fn overhead(&self) -> usize {
+        self.nodes.capacity() * std::mem::size_of::<Node>()
+            + self
+                .nodes
+                .iter()
+                .map(|node| node.levels.capacity() * std::mem::size_of::<Level>())
+                .sum::<usize>()
+    }
+
+    /// Adds a member, which must not be in the list already.
+    pub fn insert(&mut self, score: f64, member: Bytes) {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut rank = [0; MAX_LEVEL];
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            rank[i] = if i == self.level - 1 { 0 } else { rank[i + 1] };
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !self.nodes[next].is_before(score, &member) {
+                    break;
+                }
+                rank[i] += self.nodes[x].levels[i].span;
+                x = next;
+            }
+            update[i] = x;
+        }
+
+        let level = random_level();
+        if level > self.level {
+            for i in self.level..level {
+                rank[i] = 0;
+                update[i] = HEAD;
+                self.nodes[HEAD].levels[i].span = self.len;
+            }
+            self.level = level;
+        }
+
+        let node = Node {
+            member,
+            score,
+            backward: (update[0] != HEAD).then_some(update[0]),
+            levels: vec![
+                Level {
+                    forward: None,
+                    span: 0
+                };
+                level
+            ],
+        };
+        let new = match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot] = node;
+                slot
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        for i in 0..level {
+            let previous = self.nodes[update[i]].levels[i];
+            self.nodes[new].levels[i] = Level {
+                forward: previous.forward,
+                span: previous.span - (rank[0] - rank[i]),
+            };
+            self.nodes[update[i]].levels[i] = Level {
+                forward: Some(new),
+                span: rank[0] - rank[i] + 1,
+            };
+        }
+        for (i, &previous) in update.iter().enumerate().take(self.level).skip(level) {
+            self.nodes[previous].levels[i].span += 1;
+        }
+
+        match self.nodes[new].levels[0].forward {
+            Some(next) => self.nodes[next].backward = Some(new),
+            None => self.tail = Some(new),
+        }
+        self.len += 1;
+    }
+
+    /// Removes a member with its score, returning `false` if it wasn't found.
+    pub fn remove(&mut self, score: f64, member: &[u8]) -> bool {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !self.nodes[next].is_before(score, member) {
+                    break;
+                }
+                x = next;
+            }
+            update[i] = x;
+        }
+
+        match self.nodes[x].levels[0].forward {
+            Some(node) if self.nodes[node].score == score && self.nodes[node].member == member => {
+                self.remove_node(node, &update);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes the members at the ranks `start..=end`, returning them.
+    pub fn remove_ranks(&mut self, start: usize, end: usize) -> Vec<Bytes> {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut traversed = 0;
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if traversed + self.nodes[x].levels[i].span > start {
+                    break;
+                }
+                traversed += self.nodes[x].levels[i].span;
+                x = next;
+            }
+            update[i] = x;
+        }
+
+        let mut removed = Vec::new();
+        let mut next = self.nodes[x].levels[0].forward;
+        let mut rank = start;
+        while let Some(node) = next {
+            if rank > end {
+                break;
+            }
+            next = self.nodes[node].levels[0].forward;
+            removed.push(self.remove_node(node, &update));
+            rank += 1;
+        }
+        removed
+    }
+
+    /// The rank of the first member for which `before` is `false`, which must
+    /// be `true` for the first members and `false` for the rest.
+    ///
+    /// Equals the length of the list if it's `true` for every member.
+    pub fn rank_where(&self, before: impl Fn(f64, &[u8]) -> bool) -> usize {
+        let mut rank = 0;
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if !before(self.nodes[next].score, &self.nodes[next].member) {
+                    break;
+                }
+                rank += self.nodes[x].levels[i].span;
+                x = next;
+            }
+        }
+        rank
+    }
+
+    /// The rank of a member with its score.
+    pub fn rank(&self, score: f64, member: &[u8]) -> Option<usize> {
+        let rank = self.rank_where(|s, m| s < score || (s == score && m < member));
+        self.get(rank)
+            .filter(|(m, s)| *s == score && &m[..] == member)
+            .map(|_| rank)
+    }
+
+    /// The member at a rank, with its score.
+    pub fn get(&self, rank: usize) -> Option<(&Bytes, f64)> {
+        let node = self.node_at(rank)?;
+        Some((&self.nodes[node].member, self.nodes[node].score))
+    }
+
+    /// Iterates from the member at `rank`, towards the tail or the head if `reverse`.
+    pub fn iter_from(
+        &self,
+        rank: usize,
+        reverse: bool,
+    ) -> impl Iterator<Item = (&Bytes, f64)> + '_ {
+        let mut next = self.node_at(rank);
+        std::iter::from_fn(move || {
+            let node = &self.nodes[next?];
+            next = match reverse {
+                true => node.backward,
+                false => node.levels[0].forward,
+            };
+            Some((&node.member, node.score))
+        })
+    }
+
+    /// Iterates over the members, from the lowest score.
+    pub fn iter(&self) -> impl Iterator<Item = (&Bytes, f64)> + '_ {
+        self.iter_from(0, false)
+    }
+
+    fn node_at(&self, rank: usize) -> Option<usize> {
+        if rank >= self.len {
+            return None;
+        }
+        // Ranks start at 1 here, the head being 0
+        let target = rank + 1;
+        let mut traversed = 0;
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            while let Some(next) = self.nodes[x].levels[i].forward {
+                if traversed + self.nodes[x].levels[i].span > target {
+                    break;
+                }
+                traversed += self.nodes[x].levels[i].span;
+                x = next;
+            }
+            if traversed == target {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    /// Unlinks a node, given the last node before it on every level
+    fn remove_node(&mut self, node: usize, update: &[usize; MAX_LEVEL]) -> Bytes {
+        for (i, &previous) in update.iter().enumerate().take(self.level) {
+            if self.nodes[previous].levels[i].forward == Some(node) {
+                let removed = self.nodes[node].levels[i];
+                let previous = &mut self.nodes[previous].levels[i];
+                previous.span += removed.span;
+                previous.span -= 1;
+                previous.forward = removed.forward;
+            } else {
+                self.nodes[previous].levels[i].span -= 1;
+            }
+        }
+        match self.nodes[node].levels[0].forward {
+            Some(next) => self.nodes[next].backward = self.nodes[node].backward,
+            None => self.tail = self.nodes[node].backward,
+        }
+        while self.level > 1 && self.nodes[HEAD].levels[self.level - 1].forward.is_none() {
+            self.level -= 1;
+        }
+        self.len -= 1;
+
+        self.free.push(node);
+        self.nodes[node].levels = Vec::new();
+        std::mem::take(&mut self.nodes[node].member)
+    }
+}
+
+impl Node {
+    /// Whether the node comes before the member with the score
+    fn is_before(&self, score: f64, member: &[u8]) -> bool {
+        self.score < score || (self.score == score && &self.member[..] < member)
+    }
+}
+
+fn random_level() -> usize {
+    let mut rng = rand::thread_rng();
+    let mut level = 1;
+    while level < MAX_LEVEL && rng.gen_bool(P) {
+        level += 1;
+    }
+    level
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members(list: &SkipList) -> Vec<String> {
+        list.iter()
+            .map(|(member, _)| String::from_utf8_lossy(member).to_string())
+            .collect()
+    }
+
+    /// A list with the members `0..len` scored by their index
+    fn numbers(len: usize) -> SkipList {
+        let mut list = SkipList::new();
+        // Inserted out of order to exercise the links
+        for i in (0..len).rev() {
+            list.insert(i as f64, Bytes::from(format!("{i:04}")));
+        }
+        list
+    }
+
+    #[test]
+    fn test_members_are_ordered_by_score_then_member() {
+        let mut list = SkipList::new();
+        list.insert(2.0, Bytes::from("b"));
+        list.insert(1.0, Bytes::from("z"));
+        list.insert(2.0, Bytes::from("a"));
+        list.insert(f64::NEG_INFINITY, Bytes::from("c"));
+        assert_eq!(members(&list), ["c", "z", "a", "b"]);
+        assert_eq!(list.rank(2.0, b"a"), Some(2));
+        assert_eq!(list.rank(2.0, b"x"), None);
+        assert_eq!(list.get(3), Some((&Bytes::from("b"), 2.0)));
+
+        assert!(list.remove(1.0, b"z"));
+        assert!(!list.remove(1.0, b"z"));
+        assert!(!list.remove(3.0, b"a"));
+        assert_eq!(members(&list), ["c", "a", "b"]);
+        assert_eq!(list.rank(2.0, b"b"), Some(2));
+    }
+
+    #[test]
+    fn test_ranks_of_a_long_list() {
+        let mut list = numbers(1000);
+        for i in [0, 1, 499, 998, 999] {
+            assert_eq!(list.rank(i as f64, format!("{i:04}").as_bytes()), Some(i));
+            assert_eq!(list.get(i).unwrap().1, i as f64);
+        }
+        assert_eq!(list.get(1000), None);
+        assert_eq!(list.rank_where(|score, _| score < 250.5), 251);
+
+        for i in (0..1000).step_by(2) {
+            assert!(list.remove(i as f64, format!("{i:04}").as_bytes()));
+        }
+        assert_eq!(list.len(), 500);
+        assert_eq!(list.get(10).unwrap().1, 21.0);
+        assert_eq!(list.rank(21.0, b"0021"), Some(10));
+
+        // Slots are reused
+        let slots = list.nodes.len();
+        list.insert(0.0, Bytes::from("0000"));
+        assert_eq!(list.nodes.len(), slots);
+        assert_eq!(list.rank(21.0, b"0021"), Some(11));
+    }
+
+    #[test]
+    fn test_remove_ranks_and_iterate_from_a_rank() {
+        let mut list = numbers(100);
+        let removed = list.remove_ranks(10, 19);
+        assert_eq!(removed.len(), 10);
+        assert_eq!(removed[0], "0010");
+        assert_eq!(list.len(), 90);
+        assert_eq!(list.get(10).unwrap().1, 20.0);
+
+        let forward: Vec<f64> = list.iter_from(8, false).take(3).map(|(_, s)| s).collect();
+        assert_eq!(forward, [8.0, 9.0, 20.0]);
+        let backward: Vec<f64> = list.iter_from(10, true).take(3).map(|(_, s)| s).collect();
+        assert_eq!(backward, [20.0, 9.0, 8.0]);
+
+        list.remove_ranks(0, usize::MAX);
+        assert!(list.is_empty());
+        assert_eq!(list.tail, None);
+        assert_eq!(list.level, 1);
+    }
+}
@@ -0,0 +1,756 @@
+use std::{collections::BTreeMap, fmt};
+
+use bytes::Bytes;
+
+use crate::utils::current_epoch_millis;
+
+use super::{consumer_group::ConsumerGroup, listpack::ListPack};
+
+/// The most entries a node holds before a new one is started, like Redis'
+/// default `stream-node-max-entries`
+const NODE_MAX_ENTRIES: usize = 100;
+
+/// The most bytes a node holds before a new one is started, like Redis'
+/// default `stream-node-max-bytes`
+const NODE_MAX_BYTES: usize = 4096;
+
+/// The most entries an approximate trim drops unless given a `LIMIT`, like Redis'
+pub const APPROX_TRIM_LIMIT: usize = 100 * NODE_MAX_ENTRIES;
+
+/// Memory used by a node besides its entries, in bytes
+const NODE_OVERHEAD: usize = std::mem::size_of::<(StreamId, Node)>() + 16;
+
+/// Flags every entry of a node, as deleted entries are only flagged until the whole node goes
+const LIVE: &[u8] = b"0";
+const DELETED: &[u8] = b"1";
+
+/// The ID of a stream entry, `<ms>-<seq>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct StreamId {
+    /// Milliseconds since UNIX epoch, unless the ID was given explicitly
+    pub ms: u64,
+    /// Tells apart the entries added in the same millisecond
+    pub seq: u64,
+}
+
+impl StreamId {
+    pub const MIN: StreamId = StreamId::new(0, 0);
+    pub const MAX: StreamId = StreamId::new(u64::MAX, u64::MAX);
+
+    pub const fn new(ms: u64, seq: u64) -> Self {
+        Self { ms, seq }
+    }
+
+    /// The smallest ID greater than this one, if any
+    pub fn next(self) -> Option<Self> {
+        match self.seq.checked_add(1) {
+            Some(seq) => Some(Self::new(self.ms, seq)),
+            None => Some(Self::new(self.ms.checked_add(1)?, 0)),
+        }
+    }
+
+    /// The greatest ID smaller than this one, if any
+    pub fn prev(self) -> Option<Self> {
+        match self.seq.checked_sub(1) {
+            Some(seq) => Some(Self::new(self.ms, seq)),
+            None => Some(Self::new(self.ms.checked_sub(1)?, u64::MAX)),
+        }
+    }
+
+    /// Parses `<ms>-<seq>`, or `<ms>` alone with `missing_seq` as its sequence number.
+    pub fn parse(part: &[u8], missing_seq: u64) -> Result<Self, String> {
+        let invalid = || "Invalid stream ID specified as stream command argument".to_string();
+        let part = std::str::from_utf8(part).map_err(|_| invalid())?;
+        let number = |part: &str| {
+            // Unlike `u64::from_str`, Redis rejects a leading `+`
+            match part.starts_with('+') {
+                true => Err(invalid()),
+                false => part.parse::<u64>().map_err(|_| invalid()),
+            }
+        };
+
+        match part.split_once('-') {
+            Some((ms, seq)) => Ok(Self::new(number(ms)?, number(seq)?)),
+            None => Ok(Self::new(number(part)?, missing_seq)),
+        }
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+/// The ID asked of `XADD`, which can leave all or part of it for the stream to pick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NewId {
+    /// `*` -- The current time, or right after the last entry if the clock is behind
+    Auto,
+    /// `<ms>-*` -- The next sequence number of the given time
+    AutoSeq(u64),
+    /// `<ms>-<seq>`, or `<ms>` alone for `<ms>-0`
+    Explicit(StreamId),
+}
+
+impl NewId {
+    pub fn parse(part: &[u8]) -> Result<Self, String> {
+        if part == b"*" {
+            return Ok(NewId::Auto);
+        }
+        if let Some(ms) = part.strip_suffix(b"-*") {
+            return Ok(NewId::AutoSeq(StreamId::parse(ms, 0)?.ms));
+        }
+        match StreamId::parse(part, 0)? {
+            StreamId::MIN => Err("The ID specified in XADD must be greater than 0-0".to_string()),
+            id => Ok(NewId::Explicit(id)),
+        }
+    }
+}
+
+/// An entry of a stream, and its fields and values in the order they were added
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEntry {
+    pub id: StreamId,
+    pub fields: Vec<(Bytes, Bytes)>,
+}
+
+/// What to trim a stream down to, with `XTRIM` or `XADD`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrimStrategy {
+    /// `MAXLEN` -- Keep at most this many entries
+    MaxLen(usize),
+    /// `MINID` -- Drop the entries with a smaller ID
+    MinId(StreamId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trim {
+    pub strategy: TrimStrategy,
+    /// With `~`, only whole nodes are dropped, which can leave a few more entries than asked
+    pub approx: bool,
+    /// The most entries to drop, `0` meaning no limit
+    pub limit: usize,
+}
+
+/// An append only log of entries, laid out like Redis streams.
+///
+/// Entries are packed into [`ListPack`] nodes of up to [`NODE_MAX_ENTRIES`] and
+/// [`NODE_MAX_BYTES`], keyed by the ID of their first entry in an ordered map
+/// standing in for Redis' radix tree. Every entry stores its ID relative to the
+/// node's, so a node is a single allocation no matter how many entries it holds,
+/// and a range starts with a lookup of its node rather than a scan.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stream {
+    nodes: BTreeMap<StreamId, Node>,
+    /// Live entries, not counting the ones flagged as deleted
+    len: usize,
+    /// The ID of the last entry ever added, which new IDs must be greater than
+    last_id: StreamId,
+    /// The encoded size of every node, kept up to date to report memory usage cheaply
+    bytes: usize,
+    /// How many entries were ever added, which tells how far behind consumer groups are
+    entries_added: u64,
+    /// The greatest ID deleted with `XDEL`, past which the count of entries read
+    /// by consumer groups can't be kept up
+    max_deleted_id: StreamId,
+    groups: BTreeMap<Bytes, ConsumerGroup>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Node {
+    /// Every entry as `<flag> <ms delta> <seq delta> <field count> [<field> <value> ...]`,
+    /// with the deltas from the ID the node is keyed by
+    entries: ListPack,
+    /// Entries, counting the ones flagged as deleted
+    count: usize,
+    /// Entries not flagged as deleted, the node being dropped once there are none
+    live: usize,
+}
+
+/// An entry as decoded from a node
+struct NodeEntry<'a> {
+    /// The index of the entry's flag in the node's list pack
+    index: usize,
+    deleted: bool,
+    id: StreamId,
+    fields: Vec<(&'a [u8], &'a [u8])>,
+}
+
+impl NodeEntry<'_> {
+    fn to_entry(&self) -> StreamEntry {
+        StreamEntry {
+            id: self.id,
+            fields: self
+                .fields
+                .iter()
+                .map(|(field, value)| {
+                    (Bytes::copy_from_slice(field), Bytes::copy_from_slice(value))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Node {
+    fn push(&mut self, master: StreamId, id: StreamId, fields: &[(Bytes, Bytes)]) {
+        self.entries.push_back(LIVE);
+        self.entries
+            .push_back((id.ms - master.ms).to_string().as_bytes());
+        self.entries
+            .push_back(id.seq.wrapping_sub(master.seq).to_string().as_bytes());
+        self.entries.push_back(fields.len().to_string().as_bytes());
+        for (field, value) in fields {
+            self.entries.push_back(field);
+            self.entries.push_back(value);
+        }
+        self.count += 1;
+        self.live += 1;
+    }
+
+    /// Decodes every entry, the deleted ones included
+    fn decode(&self, master: StreamId) -> Vec<NodeEntry<'_>> {
+        let mut values = self.entries.iter();
+        let mut next = || values.next().expect("stream entries are complete");
+        let int = |value: &[u8]| -> u64 {
+            std::str::from_utf8(value)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .expect("stream entries hold valid integers")
+        };
+
+        let mut entries = Vec::with_capacity(self.count);
+        let mut index = 0;
+        for _ in 0..self.count {
+            let deleted = next() == DELETED;
+            let ms = master.ms + int(next());
+            let seq = master.seq.wrapping_add(int(next()));
+            let len = int(next()) as usize;
+            let fields = (0..len).map(|_| (next(), next())).collect();
+            entries.push(NodeEntry {
+                index,
+                deleted,
+                id: StreamId::new(ms, seq),
+                fields,
+            });
+            index += 4 + len * 2;
+        }
+        entries
+    }
+
+    /// Flags the entry whose flag is at `index` as deleted
+    fn delete(&mut self, index: usize) {
+        self.entries.replace(index, DELETED);
+        self.live -= 1;
+    }
+}
+
+impl Stream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The ID of the last entry ever added, even if it was since deleted
+    pub fn last_id(&self) -> StreamId {
+        self.last_id
+    }
+
+    pub fn entries_added(&self) -> u64 {
+        self.entries_added
+    }
+
+    pub fn max_deleted_id(&self) -> StreamId {
+        self.max_deleted_id
+    }
+
+    /// How many nodes the entries are packed into
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Estimated memory used by the stream, in bytes
+    pub fn memory_usage(&self) -> usize {
+        let groups: usize = self
+            .groups
+            .iter()
+            .map(|(name, group)| name.len() + group.memory_usage())
+            .sum();
+        self.bytes + self.nodes.len() * NODE_OVERHEAD + groups
+    }
+
+    pub fn first_entry(&self) -> Option<StreamEntry> {
+        self.range(StreamId::MIN, StreamId::MAX, false, Some(1))
+            .pop()
+    }
+
+    pub fn last_entry(&self) -> Option<StreamEntry> {
+        self.range(StreamId::MIN, StreamId::MAX, true, Some(1))
+            .pop()
+    }
+
+    pub fn get(&self, id: StreamId) -> Option<StreamEntry> {
+        self.range(id, id, false, Some(1)).pop()
+    }
+
+    /// Picks the ID of the next entry, which must be greater than the last one.
+    pub fn next_id(&self, id: NewId) -> Result<StreamId, String> {
+        const EXHAUSTED: &str =
+            "The stream has exhausted the last possible ID, unable to add more items";
+        const TOO_SMALL: &str =
+            "The ID specified in XADD is equal or smaller than the target stream top item";
+
+        match id {
+            NewId::Auto => {
+                let now = current_epoch_millis() as u64;
+                if now > self.last_id.ms {
+                    Ok(StreamId::new(now, 0))
+                } else {
+                    self.last_id.next().ok_or(EXHAUSTED.to_string())
+                }
+            }
+            NewId::AutoSeq(ms) if ms == self.last_id.ms => {
+                let seq = self.last_id.seq.checked_add(1).ok_or(TOO_SMALL)?;
+                Ok(StreamId::new(ms, seq))
+            }
+            NewId::AutoSeq(ms) if ms > self.last_id.ms => Ok(StreamId::new(ms, 0)),
+            NewId::Explicit(id) if id > self.last_id => Ok(id),
+            _ => Err(TOO_SMALL.to_string()),
+        }
+    }
+
+    /// Appends an entry, whose ID must be greater than the [`Stream::last_id`].
+    pub fn add(&mut self, id: StreamId, fields: &[(Bytes, Bytes)]) {
+        debug_assert!(self.len == 0 || id > self.last_id, "stream IDs must grow");
+        let size: usize = fields
+            .iter()
+            .map(|(field, value)| {
+                ListPack::entry_size(field.len()) + ListPack::entry_size(value.len())
+            })
+            .sum();
+
+        match self.nodes.last_entry() {
+            Some(mut node)
+                if node.get().count < NODE_MAX_ENTRIES
+                    && node.get().entries.bytes() + size <= NODE_MAX_BYTES =>
+            {
+                let master = *node.key();
+                let node = node.get_mut();
+                self.bytes -= node.entries.bytes();
+                node.push(master, id, fields);
+                self.bytes += node.entries.bytes();
+            }
+            _ => {
+                let mut node = Node::default();
+                node.push(id, id, fields);
+                self.bytes += node.entries.bytes();
+                self.nodes.insert(id, node);
+            }
+        }
+        self.len += 1;
+        self.last_id = id;
+        self.entries_added += 1;
+    }
+
+    /// The entries from `start` to `end`, both included, from the end if `rev`.
+    pub fn range(
+        &self,
+        start: StreamId,
+        end: StreamId,
+        rev: bool,
+        count: Option<usize>,
+    ) -> Vec<StreamEntry> {
+        let count = count.unwrap_or(usize::MAX);
+        if start > end || count == 0 {
+            return Vec::new();
+        }
+        // The node holding `start` is keyed by the closest ID before it
+        let first = match self.nodes.range(..=start).next_back() {
+            Some((&master, _)) => master,
+            None => start,
+        };
+        let mut nodes = self.nodes.range(first..=end);
+
+        let mut entries = Vec::new();
+        while entries.len() < count {
+            let next = if rev { nodes.next_back() } else { nodes.next() };
+            let Some((&master, node)) = next else {
+                break;
+            };
+            let decoded = node.decode(master);
+            let matching = decoded
+                .iter()
+                .filter(|entry| !entry.deleted && (start..=end).contains(&entry.id))
+                .map(NodeEntry::to_entry);
+            let wanted = count - entries.len();
+            match rev {
+                true => entries.extend(matching.rev().take(wanted)),
+                false => entries.extend(matching.take(wanted)),
+            }
+        }
+        entries
+    }
+
+    /// Deletes an entry, returning `false` if there's none with this ID.
+    pub fn delete(&mut self, id: StreamId) -> bool {
+        let Some((&master, node)) = self.nodes.range_mut(..=id).next_back() else {
+            return false;
+        };
+        let index = node
+            .decode(master)
+            .into_iter()
+            .find(|entry| !entry.deleted && entry.id == id)
+            .map(|entry| entry.index);
+        let Some(index) = index else {
+            return false;
+        };
+
+        node.delete(index);
+        if node.live == 0 {
+            self.bytes -= node.entries.bytes();
+            self.nodes.remove(&master);
+        }
+        self.len -= 1;
+        self.max_deleted_id = self.max_deleted_id.max(id);
+        true
+    }
+
+    /// Drops the first entries, returning how many were dropped.
+    pub fn trim(&mut self, trim: Trim) -> usize {
+        let mut trimmed = 0;
+        while let Some(mut first) = self.nodes.first_entry() {
+            let master = *first.key();
+            let node = first.get_mut();
+            let whole = match trim.strategy {
+                TrimStrategy::MaxLen(max) => self.len - node.live >= max,
+                TrimStrategy::MinId(min) => node
+                    .decode(master)
+                    .last()
+                    .is_some_and(|entry| entry.id < min),
+            };
+
+            if whole {
+                if trim.limit != 0 && trimmed + node.live > trim.limit {
+                    break;
+                }
+                trimmed += node.live;
+                self.len -= node.live;
+                self.bytes -= node.entries.bytes();
+                first.remove();
+                continue;
+            }
+            if trim.approx {
+                break;
+            }
+
+            // Only part of the first node goes, whose entries are flagged one by one
+            let indexes: Vec<_> = node
+                .decode(master)
+                .into_iter()
+                .filter(|entry| !entry.deleted)
+                .map(|entry| (entry.index, entry.id))
+                .collect();
+            for (index, id) in indexes {
+                let drop = match trim.strategy {
+                    TrimStrategy::MaxLen(max) => self.len > max,
+                    TrimStrategy::MinId(min) => id < min,
+                };
+                if !drop {
+                    break;
+                }
+                node.delete(index);
+                self.len -= 1;
+                trimmed += 1;
+            }
+            if node.live == 0 {
+                self.bytes -= node.entries.bytes();
+                first.remove();
+            }
+            break;
+        }
+        trimmed
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = StreamEntry> + '_ {
+        self.nodes.iter().flat_map(|(&master, node)| {
+            node.decode(master)
+                .into_iter()
+                .filter(|entry| !entry.deleted)
+                .map(|entry| entry.to_entry())
+                .collect::<Vec<_>>()
+        })
+    }
+
+    pub fn groups(&self) -> &BTreeMap<Bytes, ConsumerGroup> {
+        &self.groups
+    }
+
+    pub fn group(&self, name: &[u8]) -> Option<&ConsumerGroup> {
+        self.groups.get(name)
+    }
+
+    pub fn group_mut(&mut self, name: &[u8]) -> Option<&mut ConsumerGroup> {
+        self.groups.get_mut(name)
+    }
+
+    /// Creates a consumer group, returning `false` if one already has this name.
+    pub fn create_group(&mut self, name: Bytes, group: ConsumerGroup) -> bool {
+        match self.groups.entry(name) {
+            std::collections::btree_map::Entry::Occupied(_) => false,
+            std::collections::btree_map::Entry::Vacant(entry) => {
+                entry.insert(group);
+                true
+            }
+        }
+    }
+
+    pub fn destroy_group(&mut self, name: &[u8]) -> bool {
+        self.groups.remove(name).is_some()
+    }
+
+    /// Whether an entry from `start` on was deleted, which throws off counting
+    /// entries from the first one ever added
+    fn has_tombstones_from(&self, start: StreamId) -> bool {
+        self.len > 0 && self.max_deleted_id != StreamId::MIN && start <= self.max_deleted_id
+    }
+
+    /// How many entries were added up to `id`, counting from the first one ever
+    /// added, if it can be told.
+    pub fn entries_added_until(&self, id: StreamId) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+        if id >= self.last_id {
+            // Nothing can be read past the last entry
+            return (id == self.last_id || self.len == 0).then_some(self.entries_added);
+        }
+
+        let first_id = self.first_entry().map(|entry| entry.id)?;
+        // Only when no entry was deleted in between can they be counted
+        if self.max_deleted_id == StreamId::MIN || self.max_deleted_id < first_id {
+            let before_first = self.entries_added - self.len as u64;
+            if id < first_id {
+                return Some(before_first);
+            } else if id == first_id {
+                return Some(before_first + 1);
+            }
+        }
+        None
+    }
+
+    /// How many entries a group has yet to read, if it can be told
+    pub fn lag(&self, group: &ConsumerGroup) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+        let entries_read = match group.entries_read {
+            Some(read) if !self.has_tombstones_from(group.last_id) => Some(read),
+            _ => self.entries_added_until(group.last_id),
+        };
+        entries_read.map(|read| self.entries_added.saturating_sub(read))
+    }
+
+    /// Delivers up to `count` entries the group hasn't read yet to a consumer,
+    /// adding them to the pending entries list unless `no_ack`.
+    ///
+    /// Returns `None` if there's no such group.
+    pub fn read_group(
+        &mut self,
+        group: &[u8],
+        consumer: &Bytes,
+        count: Option<usize>,
+        no_ack: bool,
+        now: u128,
+    ) -> Option<Vec<StreamEntry>> {
+        let last_id = self.groups.get(group)?.last_id;
+        let entries = match last_id.next() {
+            Some(start) => self.range(start, StreamId::MAX, false, count),
+            None => Vec::new(),
+        };
+        // Like Redis, the count of entries read is kept up when it can be, and
+        // estimated again otherwise. Estimates only ever work out for the first
+        // entry or the last one, so they aren't worked out for the others.
+        let counts: Vec<_> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let tombstones = self.has_tombstones_from(entry.id);
+                let estimate = match tombstones || i == 0 || entry.id == self.last_id {
+                    true => self.entries_added_until(entry.id),
+                    false => None,
+                };
+                (tombstones, estimate)
+            })
+            .collect();
+
+        let group = self.groups.get_mut(group).expect("checked above");
+        group.touch_consumer(consumer, now);
+        for (entry, (tombstones, estimate)) in entries.iter().zip(counts) {
+            group.entries_read = match group.entries_read {
+                Some(read) if !tombstones => Some(read + 1),
+                _ => estimate,
+            };
+            group.last_id = entry.id;
+            if !no_ack {
+                group.deliver(entry.id, consumer, now);
+            }
+        }
+        if !entries.is_empty() {
+            group.touch_consumer(consumer, now).active_at = Some(now);
+        }
+        Some(entries)
+    }
+
+    /// Delivers again up to `count` entries pending for a consumer, from `start`.
+    ///
+    /// Entries deleted since they were delivered are `None`. Returns `None` if
+    /// there's no such group.
+    pub fn read_group_pending(
+        &mut self,
+        group: &[u8],
+        consumer: &Bytes,
+        start: StreamId,
+        count: Option<usize>,
+        now: u128,
+    ) -> Option<Vec<(StreamId, Option<StreamEntry>)>> {
+        let ids: Vec<_> = self
+            .groups
+            .get(group)?
+            .consumer_pending(consumer, start)
+            .take(count.unwrap_or(usize::MAX))
+            .collect();
+        let entries: Vec<_> = ids.into_iter().map(|id| (id, self.get(id))).collect();
+
+        let group = self.groups.get_mut(group).expect("checked above");
+        group.touch_consumer(consumer, now);
+        for (id, entry) in &entries {
+            if entry.is_some() {
+                group.redeliver(*id, now);
+            }
+        }
+        Some(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(value: &str) -> Vec<(Bytes, Bytes)> {
+        vec![(
+            Bytes::from("field"),
+            Bytes::copy_from_slice(value.as_bytes()),
+        )]
+    }
+
+    fn ids(entries: &[StreamEntry]) -> Vec<String> {
+        entries.iter().map(|entry| entry.id.to_string()).collect()
+    }
+
+    fn stream_of(len: u64) -> Stream {
+        let mut stream = Stream::new();
+        for seq in 1..=len {
+            stream.add(StreamId::new(1, seq), &fields(&seq.to_string()));
+        }
+        stream
+    }
+
+    #[test]
+    fn test_next_id() {
+        let mut stream = Stream::new();
+        assert_eq!(stream.next_id(NewId::AutoSeq(0)), Ok(StreamId::new(0, 1)));
+        stream.add(StreamId::new(5, 3), &fields("a"));
+
+        assert_eq!(stream.next_id(NewId::AutoSeq(5)), Ok(StreamId::new(5, 4)));
+        assert_eq!(stream.next_id(NewId::AutoSeq(6)), Ok(StreamId::new(6, 0)));
+        assert!(stream.next_id(NewId::AutoSeq(4)).is_err());
+        assert!(stream
+            .next_id(NewId::Explicit(StreamId::new(5, 3)))
+            .is_err());
+        assert!(stream.next_id(NewId::Auto).unwrap() > StreamId::new(5, 3));
+
+        stream.add(StreamId::new(u64::MAX, u64::MAX), &fields("b"));
+        assert_eq!(
+            stream.next_id(NewId::Auto),
+            Err(
+                "The stream has exhausted the last possible ID, unable to add more items"
+                    .to_string()
+            )
+        );
+
+        assert_eq!(
+            StreamId::parse(b"12", u64::MAX),
+            Ok(StreamId::new(12, u64::MAX))
+        );
+        assert!(StreamId::parse(b"1-2-3", 0).is_err());
+        assert!(StreamId::parse(b"+1-2", 0).is_err());
+        assert!(NewId::parse(b"0-0").is_err());
+        assert_eq!(NewId::parse(b"7-*"), Ok(NewId::AutoSeq(7)));
+    }
+
+    #[test]
+    fn test_range_across_nodes() {
+        let mut stream = stream_of(250);
+        assert_eq!(stream.nodes.len(), 3);
+        assert_eq!(stream.iter().count(), 250);
+
+        let range = stream.range(StreamId::new(1, 98), StreamId::new(1, 102), false, None);
+        assert_eq!(ids(&range), ["1-98", "1-99", "1-100", "1-101", "1-102"]);
+        assert_eq!(range[0].fields, fields("98"));
+
+        let range = stream.range(StreamId::MIN, StreamId::MAX, true, Some(3));
+        assert_eq!(ids(&range), ["1-250", "1-249", "1-248"]);
+
+        assert!(stream.delete(StreamId::new(1, 99)));
+        assert!(!stream.delete(StreamId::new(1, 99)));
+        assert!(!stream.delete(StreamId::new(2, 0)));
+        assert_eq!(stream.len(), 249);
+        let range = stream.range(StreamId::new(1, 101), StreamId::new(1, 97), false, None);
+        assert!(range.is_empty());
+        let range = stream.range(StreamId::new(1, 97), StreamId::new(1, 101), true, None);
+        assert_eq!(ids(&range), ["1-101", "1-100", "1-98", "1-97"]);
+    }
+
+    #[test]
+    fn test_trim() {
+        let mut stream = stream_of(250);
+        let trim = |strategy, approx| Trim {
+            strategy,
+            approx,
+            limit: 0,
+        };
+
+        // Approximately, only the nodes that can go as a whole do
+        assert_eq!(stream.trim(trim(TrimStrategy::MaxLen(120), true)), 100);
+        assert_eq!(stream.len(), 150);
+        assert_eq!(stream.trim(trim(TrimStrategy::MaxLen(120), false)), 30);
+        assert_eq!(
+            ids(&stream.range(StreamId::MIN, StreamId::MAX, false, Some(1))),
+            ["1-131"]
+        );
+
+        assert_eq!(
+            stream.trim(trim(TrimStrategy::MinId(StreamId::new(1, 240)), false)),
+            109
+        );
+        assert_eq!(stream.len(), 11);
+        assert_eq!(stream.nodes.len(), 1);
+
+        let limited = Trim {
+            limit: 5,
+            ..trim(TrimStrategy::MaxLen(0), true)
+        };
+        assert_eq!(stream.trim(limited), 0);
+        assert_eq!(stream.trim(trim(TrimStrategy::MaxLen(0), false)), 11);
+        assert!(stream.is_empty());
+        assert_eq!(stream.memory_usage(), 0);
+        assert_eq!(stream.last_id(), StreamId::new(1, 250));
+    }
+}
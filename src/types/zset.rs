@@ -0,0 +1,313 @@
+use std::{collections::HashMap, ops::Range};
+
+use bytes::Bytes;
+
+use crate::parser::utils::parse_float;
+
+use super::skiplist::SkipList;
+
+/// Memory used by a member of the index besides the member itself, in bytes
+const INDEX_ENTRY_OVERHEAD: usize = std::mem::size_of::<(Bytes, f64)>() + 8;
+
+/// A set of members ordered by score, encoded like Redis' skiplist sorted sets.
+///
+/// The members are ordered in a [`SkipList`], for ranges and ranks, and indexed
+/// by a hash table, to look their score up in O(1).
+#[derive(Debug, Clone, Default)]
+pub struct ZSet {
+    scores: HashMap<Bytes, f64, ahash::RandomState>,
+    list: SkipList,
+    /// The size of every member, kept up to date to report memory usage cheaply
+    bytes: usize,
+}
+
+/// A bound of a score range, like `1.5`, `(1.5` or `-inf`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreBound {
+    pub score: f64,
+    pub exclusive: bool,
+}
+
+/// A bound of a lexicographical range, like `[a`, `(a`, `-` or `+`
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexBound {
+    /// `-`, before every member
+    Min,
+    /// `+`, after every member
+    Max,
+    Inclusive(Bytes),
+    Exclusive(Bytes),
+}
+
+impl ZSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// The name of the encoding, as reported by `OBJECT ENCODING`
+    pub fn encoding(&self) -> &'static str {
+        "skiplist"
+    }
+
+    /// Estimated memory used by the sorted set, in bytes.
+    ///
+    /// Members are shared by the index and the list, so they're only counted once.
+    pub fn memory_usage(&self) -> usize {
+        self.bytes + self.scores.capacity() * INDEX_ENTRY_OVERHEAD + self.list.overhead()
+    }
+
+    pub fn score(&self, member: &[u8]) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    /// Adds a member or changes its score, returning the previous score.
+    pub fn insert(&mut self, member: Bytes, score: f64) -> Option<f64> {
+        debug_assert!(!score.is_nan());
+        let previous = self.scores.insert(member.clone(), score);
+        match previous {
+            Some(previous) if previous == score => return Some(previous),
+            Some(previous) => _ = self.list.remove(previous, &member),
+            None => self.bytes += member.len(),
+        }
+        self.list.insert(score, member);
+        previous
+    }
+
+    /// Removes a member, returning its score.
+    pub fn remove(&mut self, member: &[u8]) -> Option<f64> {
+        let score = self.scores.remove(member)?;
+        self.list.remove(score, member);
+        self.bytes -= member.len();
+        Some(score)
+    }
+
+    /// The rank of a member, from the lowest score, or from the highest if `reverse`.
+    pub fn rank(&self, member: &[u8], reverse: bool) -> Option<usize> {
+        let rank = self.list.rank(self.score(member)?, member)?;
+        Some(match reverse {
+            true => self.len() - 1 - rank,
+            false => rank,
+        })
+    }
+
+    /// The member at a rank from the lowest score, with its score.
+    pub fn get(&self, rank: usize) -> Option<(&Bytes, f64)> {
+        self.list.get(rank)
+    }
+
+    /// Iterates from the member at `rank` from the lowest score, towards the
+    /// highest score, or the lowest if `reverse`.
+    pub fn iter_from(
+        &self,
+        rank: usize,
+        reverse: bool,
+    ) -> impl Iterator<Item = (&Bytes, f64)> + '_ {
+        self.list.iter_from(rank, reverse)
+    }
+
+    /// Iterates over the members, from the lowest score.
+    pub fn iter(&self) -> impl Iterator<Item = (&Bytes, f64)> + '_ {
+        self.list.iter()
+    }
+
+    /// The ranks of the members with a score between `min` and `max`.
+    pub fn score_ranks(&self, min: ScoreBound, max: ScoreBound) -> Range<usize> {
+        let start = self.list.rank_where(|score, _| min.starts_after(score));
+        let end = self.list.rank_where(|score, _| !max.ends_before(score));
+        start..end.max(start)
+    }
+
+    /// The ranks of the members between `min` and `max`, which only makes sense
+    /// when every member has the same score.
+    pub fn lex_ranks(&self, min: &LexBound, max: &LexBound) -> Range<usize> {
+        let start = self.list.rank_where(|_, member| min.starts_after(member));
+        let end = self.list.rank_where(|_, member| !max.ends_before(member));
+        start..end.max(start)
+    }
+
+    /// Removes the members at `ranks`, returning how many were removed.
+    pub fn remove_ranks(&mut self, ranks: Range<usize>) -> usize {
+        if ranks.is_empty() {
+            return 0;
+        }
+        let removed = self.list.remove_ranks(ranks.start, ranks.end - 1);
+        for member in &removed {
+            self.scores.remove(member);
+            self.bytes -= member.len();
+        }
+        removed.len()
+    }
+
+    /// Removes the member with the lowest score, or the highest if `max`.
+    pub fn pop(&mut self, max: bool) -> Option<(Bytes, f64)> {
+        let rank = match max {
+            true => self.len().checked_sub(1)?,
+            false => 0,
+        };
+        let (member, score) = self.list.get(rank)?;
+        let member = member.clone();
+        self.remove(&member);
+        Some((member, score))
+    }
+}
+
+impl PartialEq for ZSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.scores == other.scores
+    }
+}
+
+impl<T: AsRef<[u8]>> FromIterator<(T, f64)> for ZSet {
+    fn from_iter<I: IntoIterator<Item = (T, f64)>>(iter: I) -> Self {
+        let mut zset = ZSet::new();
+        for (member, score) in iter {
+            zset.insert(Bytes::copy_from_slice(member.as_ref()), score);
+        }
+        zset
+    }
+}
+
+impl ScoreBound {
+    /// Parses a bound of `ZRANGEBYSCORE` and friends.
+    pub fn parse(part: &[u8]) -> Result<Self, String> {
+        let (exclusive, score) = match part.strip_prefix(b"(") {
+            Some(score) => (true, score),
+            None => (false, part),
+        };
+        let score = parse_float(score).map_err(|_| "min or max is not a float")?;
+        Ok(Self { score, exclusive })
+    }
+
+    /// Whether a score is below the range this bound starts
+    fn starts_after(&self, score: f64) -> bool {
+        score < self.score || (self.exclusive && score == self.score)
+    }
+
+    /// Whether a score is past the range this bound ends
+    fn ends_before(&self, score: f64) -> bool {
+        score > self.score || (self.exclusive && score == self.score)
+    }
+}
+
+impl LexBound {
+    /// Parses a bound of `ZRANGEBYLEX` and friends.
+    pub fn parse(part: &[u8]) -> Result<Self, String> {
+        match part {
+            b"-" => Ok(LexBound::Min),
+            b"+" => Ok(LexBound::Max),
+            [b'[', member @ ..] => Ok(LexBound::Inclusive(Bytes::copy_from_slice(member))),
+            [b'(', member @ ..] => Ok(LexBound::Exclusive(Bytes::copy_from_slice(member))),
+            _ => Err("min or max not valid string range item".to_string()),
+        }
+    }
+
+    /// Whether a member is before the range this bound starts
+    fn starts_after(&self, member: &[u8]) -> bool {
+        match self {
+            LexBound::Min => false,
+            LexBound::Max => true,
+            LexBound::Inclusive(bound) => member < &bound[..],
+            LexBound::Exclusive(bound) => member <= &bound[..],
+        }
+    }
+
+    /// Whether a member is past the range this bound ends
+    fn ends_before(&self, member: &[u8]) -> bool {
+        match self {
+            LexBound::Min => true,
+            LexBound::Max => false,
+            LexBound::Inclusive(bound) => member > &bound[..],
+            LexBound::Exclusive(bound) => member >= &bound[..],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members(zset: &ZSet, ranks: Range<usize>) -> Vec<&[u8]> {
+        ranks.map(|rank| &zset.get(rank).unwrap().0[..]).collect()
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut zset = ZSet::new();
+        assert_eq!(zset.insert(Bytes::from("a"), 2.0), None);
+        assert_eq!(zset.insert(Bytes::from("b"), 1.0), None);
+        assert_eq!(zset.insert(Bytes::from("a"), 0.5), Some(2.0));
+        assert_eq!(zset.insert(Bytes::from("a"), 0.5), Some(0.5));
+        assert_eq!(zset.len(), 2);
+        assert_eq!(zset.score(b"a"), Some(0.5));
+        assert_eq!(zset.rank(b"a", false), Some(0));
+        assert_eq!(zset.rank(b"a", true), Some(1));
+        assert_eq!(zset.rank(b"c", false), None);
+
+        assert_eq!(zset.remove(b"a"), Some(0.5));
+        assert_eq!(zset.remove(b"a"), None);
+        assert_eq!(zset.pop(true), Some((Bytes::from("b"), 1.0)));
+        assert_eq!(zset.pop(false), None);
+        assert!(zset.is_empty());
+        assert_eq!(zset.bytes, 0);
+    }
+
+    #[test]
+    fn test_score_ranges() {
+        let zset: ZSet = [("a", 1.0), ("b", 2.0), ("c", 2.0), ("d", 3.0)]
+            .into_iter()
+            .collect();
+        let ranks = |min: &str, max: &str| {
+            zset.score_ranks(
+                ScoreBound::parse(min.as_bytes()).unwrap(),
+                ScoreBound::parse(max.as_bytes()).unwrap(),
+            )
+        };
+        assert_eq!(ranks("-inf", "+inf"), 0..4);
+        assert_eq!(ranks("2", "2"), 1..3);
+        assert_eq!(ranks("(1", "(3"), 1..3);
+        assert_eq!(ranks("(2", "3"), 3..4);
+        assert_eq!(ranks("3", "1"), 3..3);
+        assert_eq!(ranks("4", "+inf"), 4..4);
+        assert_eq!(
+            ScoreBound::parse(b"(nan"),
+            Err("min or max is not a float".to_string())
+        );
+        assert_eq!(
+            ScoreBound::parse(b"1e400"),
+            Err("min or max is not a float".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lex_ranges() {
+        let mut zset: ZSet = ["a", "b", "c", "d"].map(|m| (m, 0.0)).into_iter().collect();
+        let ranks = |zset: &ZSet, min: &str, max: &str| {
+            zset.lex_ranks(
+                &LexBound::parse(min.as_bytes()).unwrap(),
+                &LexBound::parse(max.as_bytes()).unwrap(),
+            )
+        };
+        assert_eq!(ranks(&zset, "-", "+"), 0..4);
+        assert_eq!(ranks(&zset, "[b", "(d"), 1..3);
+        assert_eq!(ranks(&zset, "(a", "[aa"), 1..1);
+        assert_eq!(ranks(&zset, "+", "-"), 4..4);
+        assert_eq!(
+            LexBound::parse(b"a"),
+            Err("min or max not valid string range item".to_string())
+        );
+
+        let range = ranks(&zset, "[b", "[c");
+        assert_eq!(members(&zset, range.clone()), [b"b", b"c"]);
+        assert_eq!(zset.remove_ranks(range), 2);
+        assert_eq!(members(&zset, 0..2), [b"a", b"d"]);
+        assert_eq!(zset.score(b"b"), None);
+    }
+}
@@ -0,0 +1,197 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Milliseconds since UNIX epoch
+pub fn current_epoch_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis()
+}
+
+/// Resolves a Redis index, where negative ones count from the end.
+///
+/// Returns `None` if it's out of range.
+pub fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index } else { index };
+    (0..len as i64).contains(&index).then_some(index as usize)
+}
+
+/// Resolves an inclusive Redis range, where negative indexes count from the end,
+/// to the exclusive range it covers, clamped to the length.
+///
+/// Returns `None` if the range is empty.
+pub fn resolve_range(start: i64, stop: i64, len: usize) -> Option<(usize, usize)> {
+    let len = len as i64;
+    let start = if start < 0 { len + start } else { start }.max(0);
+    let stop = if stop < 0 { len + stop } else { stop }.min(len - 1);
+    (start <= stop).then(|| (start as usize, stop as usize + 1))
+}
+
+/// Matches a string against a glob-style pattern, like Redis' `stringmatchlen`.
+///
+/// Supports `*`, `?`, `[abc]`, `[^abc]`, `[a-z]`, and `\` to escape any of them.
+pub fn glob_match(pattern: &[u8], string: &[u8]) -> bool {
+    let (mut p, mut s) = (0, 0);
+    // Where to resume from when what follows the last `*` doesn't match
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while s < string.len() {
+        let matched = match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, s));
+                p += 1;
+                continue;
+            }
+            Some(b'?') => Some(p + 1),
+            Some(b'[') => match_class(pattern, p, string[s]),
+            Some(b'\\') if p + 1 < pattern.len() => (pattern[p + 1] == string[s]).then_some(p + 2),
+            Some(&c) => (c == string[s]).then_some(p + 1),
+            None => None,
+        };
+
+        match (matched, backtrack) {
+            (Some(next), _) => {
+                p = next;
+                s += 1;
+            }
+            // Let the last `*` match one more character, and try again
+            (None, Some((star, star_s))) => {
+                backtrack = Some((star, star_s + 1));
+                p = star + 1;
+                s = star_s + 1;
+            }
+            (None, None) => return false,
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Matches a character against the `[...]` class starting at `start`, returning
+/// where the pattern continues if it matches.
+fn match_class(pattern: &[u8], start: usize, c: u8) -> Option<usize> {
+    let mut p = start + 1;
+    let negate = pattern.get(p) == Some(&b'^');
+    if negate {
+        p += 1;
+    }
+
+    let mut matched = false;
+    loop {
+        match pattern.get(p) {
+            // Like Redis, an unterminated class ends with the pattern
+            None => {
+                p -= 1;
+                break;
+            }
+            Some(b']') => break,
+            Some(b'\\') if p + 1 < pattern.len() => {
+                matched |= pattern[p + 1] == c;
+                p += 2;
+            }
+            Some(&low) if pattern.get(p + 1) == Some(&b'-') && p + 2 < pattern.len() => {
+                let high = pattern[p + 2];
+                let (low, high) = (low.min(high), low.max(high));
+                matched |= (low..=high).contains(&c);
+                p += 3;
+            }
+            Some(&class_char) => {
+                matched |= class_char == c;
+                p += 1;
+            }
+        }
+    }
+
+    (matched != negate).then_some(p + 1)
+}
+
+/// Formats the result of `INCRBYFLOAT` and `HINCRBYFLOAT` like Redis does,
+/// without an exponent and with trailing zeros trimmed.
+///
+/// Redis prints its long doubles with 17 decimals, which are all reliable. A
+/// double only has 15 reliable significant digits, so it's rounded to those,
+/// or else `0.1 + 0.2` would print as `0.30000000000000004` rather than `0.3`.
+pub fn format_human_float(value: f64) -> String {
+    let magnitude = match value == 0.0 {
+        true => 0,
+        false => value.abs().log10().floor() as i32,
+    };
+    let decimals = (f64::DIGITS as i32 - 1 - magnitude).clamp(0, 17) as usize;
+    let formatted = format!("{value:.decimals$}");
+    let formatted = match formatted.contains('.') {
+        true => formatted.trim_end_matches('0').trim_end_matches('.'),
+        false => &formatted,
+    };
+    match formatted {
+        "-0" => "0".to_string(),
+        formatted => formatted.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_human_float() {
+        assert_eq!(format_human_float(0.2 + 0.1), "0.3");
+        assert_eq!(format_human_float(10.5 + 0.1), "10.6");
+        assert_eq!(format_human_float(5000.0), "5000");
+        assert_eq!(format_human_float(-2.5), "-2.5");
+        assert_eq!(format_human_float(0.0), "0");
+        assert_eq!(format_human_float(1e20), "100000000000000000000");
+        assert_eq!(format_human_float(1.5e-10), "0.00000000015");
+        assert_eq!(format_human_float(1e-20), "0");
+        assert_eq!(format_human_float(-1e-20), "0");
+    }
+
+    #[test]
+    fn test_resolve_index() {
+        assert_eq!(resolve_index(0, 3), Some(0));
+        assert_eq!(resolve_index(-1, 3), Some(2));
+        assert_eq!(resolve_index(-3, 3), Some(0));
+        assert_eq!(resolve_index(3, 3), None);
+        assert_eq!(resolve_index(-4, 3), None);
+        assert_eq!(resolve_index(0, 0), None);
+    }
+
+    #[test]
+    fn test_resolve_range() {
+        assert_eq!(resolve_range(0, -1, 5), Some((0, 5)));
+        assert_eq!(resolve_range(-100, 100, 5), Some((0, 5)));
+        assert_eq!(resolve_range(1, 2, 5), Some((1, 3)));
+        assert_eq!(resolve_range(-2, -1, 5), Some((3, 5)));
+        assert_eq!(resolve_range(3, 1, 5), None);
+        assert_eq!(resolve_range(5, 10, 5), None);
+        assert_eq!(resolve_range(0, -1, 0), None);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        let cases: [(&str, &str, bool); 16] = [
+            ("*", "anything", true),
+            ("*", "", true),
+            ("h?llo", "hello", true),
+            ("h?llo", "hllo", false),
+            ("h*llo", "heeeello", true),
+            ("h*llo", "hello world", false),
+            ("h[ae]llo", "hallo", true),
+            ("h[ae]llo", "hillo", false),
+            ("h[^e]llo", "hallo", true),
+            ("h[^e]llo", "hello", false),
+            ("h[a-b]llo", "hbllo", true),
+            ("h[b-a]llo", "hallo", true),
+            ("h\\*llo", "h*llo", true),
+            ("h\\*llo", "hello", false),
+            ("*a*b*c", "xxaxxbxxbxc", true),
+            ("user:*:name", "user:1:email", false),
+        ];
+        for (pattern, string, expected) in cases {
+            assert_eq!(
+                glob_match(pattern.as_bytes(), string.as_bytes()),
+                expected,
+                "{pattern} {string}"
+            );
+        }
+    }
+}